@@ -0,0 +1,150 @@
+//! Criterion comparison of DLOG proofs with k256's ECDSA and BIP-340 Schnorr signatures
+//!
+//! Run with `cargo bench --bench comparison`. Proving and verifying a proof for sid "bench"
+//! are measured next to signing and verifying the same 5-byte message, on the same machine
+//! and in the same run. Afterwards a Markdown table of the mean times, their ratio to ECDSA
+//! and the encoded sizes is printed and written to `target/criterion/comparison/report.md`.
+//! An operation left out with a filter is reported from its latest earlier run, if any.
+
+use std::fmt::Write as _;
+use std::hint::black_box;
+use std::path::PathBuf;
+use std::{env, fs};
+
+use criterion::{criterion_group, Criterion};
+use elliptic_curve::Field;
+use k256::ecdsa::signature::{Signer, Verifier};
+use k256::{ecdsa, schnorr, ProjectivePoint, Scalar};
+use rand_core::OsRng;
+use schnorr_dlog_zkp::{DLogProof, ProverContext, VerifierContext};
+
+const MESSAGE: &[u8] = b"bench";
+
+/// A row of the report: scheme, the ids of its two operations, proof or signature size and
+/// public key size
+const SCHEMES: [(&str, &str, &str, usize, usize); 4] = [
+    ("ECDSA", "ecdsa_sign", "ecdsa_verify", 64, 33),
+    ("BIP-340 Schnorr", "schnorr_sign", "schnorr_verify", 64, 32),
+    (
+        "DLOG proof",
+        "dlog_prove",
+        "dlog_verify",
+        DLogProof::ENCODED_LEN,
+        33,
+    ),
+    (
+        "DLOG proof, precomputed",
+        "dlog_prove_precomputed",
+        "dlog_verify_precomputed",
+        DLogProof::ENCODED_LEN,
+        33,
+    ),
+];
+
+fn comparison(c: &mut Criterion) {
+    let mut group = c.benchmark_group("comparison");
+
+    let ecdsa_key = ecdsa::SigningKey::random(&mut OsRng);
+    let ecdsa_signature: ecdsa::Signature = ecdsa_key.sign(MESSAGE);
+    group.bench_function("ecdsa_sign", |b| {
+        b.iter(|| -> ecdsa::Signature { ecdsa_key.sign(black_box(MESSAGE)) })
+    });
+    group.bench_function("ecdsa_verify", |b| {
+        b.iter(|| {
+            ecdsa_key
+                .verifying_key()
+                .verify(black_box(MESSAGE), &ecdsa_signature)
+                .unwrap()
+        })
+    });
+
+    let schnorr_key = schnorr::SigningKey::random(&mut OsRng);
+    let schnorr_signature: schnorr::Signature = schnorr_key.sign(MESSAGE);
+    group.bench_function("schnorr_sign", |b| {
+        b.iter(|| -> schnorr::Signature { schnorr_key.sign(black_box(MESSAGE)) })
+    });
+    group.bench_function("schnorr_verify", |b| {
+        b.iter(|| {
+            schnorr_key
+                .verifying_key()
+                .verify(black_box(MESSAGE), &schnorr_signature)
+                .unwrap()
+        })
+    });
+
+    let g = ProjectivePoint::GENERATOR;
+    let x = Scalar::random(&mut OsRng);
+    let y = g * x;
+    let proof = DLogProof::prove("bench", 1, &x, y, g).unwrap();
+    group.bench_function("dlog_prove", |b| {
+        b.iter(|| DLogProof::prove(black_box("bench"), 1, &x, y, g).unwrap())
+    });
+    group.bench_function("dlog_verify", |b| {
+        b.iter(|| assert!(black_box(&proof).verify("bench", 1, y, g).unwrap()))
+    });
+    let prover = ProverContext::new(g);
+    let verifier = VerifierContext::new(y, g);
+    group.bench_function("dlog_prove_precomputed", |b| {
+        b.iter(|| prover.prove(black_box("bench"), 1, &x, y).unwrap())
+    });
+    group.bench_function("dlog_verify_precomputed", |b| {
+        b.iter(|| assert!(verifier.verify(black_box(&proof), "bench", 1).unwrap()))
+    });
+    group.finish();
+}
+
+/// Where criterion keeps its results, following its own lookup
+fn criterion_home() -> PathBuf {
+    if let Some(home) = env::var_os("CRITERION_HOME") {
+        return home.into();
+    }
+    let target = env::var_os("CARGO_TARGET_DIR").unwrap_or_else(|| "target".into());
+    PathBuf::from(target).join("criterion")
+}
+
+/// Mean time of an operation in nanoseconds, from its latest run
+fn mean_ns(id: &str) -> Option<f64> {
+    let path = criterion_home()
+        .join("comparison")
+        .join(id)
+        .join("new/estimates.json");
+    let estimates: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    estimates["mean"]["point_estimate"].as_f64()
+}
+
+fn report() {
+    let (Some(ecdsa_sign), Some(ecdsa_verify)) = (mean_ns("ecdsa_sign"), mean_ns("ecdsa_verify"))
+    else {
+        return;
+    };
+    let cell = |mean: Option<f64>, baseline: f64| match mean {
+        Some(mean) => format!("{:.1} µs ({:.2}x)", mean / 1000.0, mean / baseline),
+        None => "-".to_string(),
+    };
+    let mut table = String::from(
+        "| Scheme | Prove / sign | Verify | Proof / signature | Public key |\n\
+         |--------|--------------|--------|-------------------|------------|\n",
+    );
+    for (scheme, sign, verify, size, key_size) in SCHEMES {
+        let _ = writeln!(
+            table,
+            "| {scheme} | {} | {} | {size} bytes | {key_size} bytes |",
+            cell(mean_ns(sign), ecdsa_sign),
+            cell(mean_ns(verify), ecdsa_verify),
+        );
+    }
+    println!("\nMean times, relative to ECDSA:\n\n{table}");
+    let path = criterion_home().join("comparison/report.md");
+    if let Err(e) = fs::write(&path, &table) {
+        eprintln!("Failed to write {}: {e}", path.display());
+    }
+}
+
+criterion_group!(benches, comparison);
+
+fn main() {
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+    report();
+}
@@ -0,0 +1,169 @@
+//! Criterion benchmarks of proving, verification and proof (de)serialization
+//!
+//! Run with `cargo bench`, or e.g. `cargo bench -- verify` for one group. Criterion keeps the
+//! previous results in `target/criterion` and reports the change against them, so a run
+//! before and after a change shows its effect.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use elliptic_curve::Field;
+use k256::{ProjectivePoint, Scalar};
+use rand_core::OsRng;
+use schnorr_dlog_zkp::{AndProof, DLogProof, Prover, ProverContext, SecretScalar, VerifierContext};
+
+const BATCH_SIZES: [usize; 4] = [1, 16, 64, 256];
+
+fn statement() -> (Scalar, ProjectivePoint) {
+    let x = Scalar::random(&mut OsRng);
+    (x, ProjectivePoint::GENERATOR * x)
+}
+
+fn prove(c: &mut Criterion) {
+    let g = ProjectivePoint::GENERATOR;
+    let (x, y) = statement();
+    let mut group = c.benchmark_group("prove");
+    group.bench_function("default", |b| {
+        b.iter(|| DLogProof::prove(black_box("bench"), 1, &x, y, g).unwrap())
+    });
+    let blinded = Prover::new().blinded(true);
+    group.bench_function("blinded", |b| {
+        b.iter(|| blinded.prove(black_box("bench"), 1, &x, y, g).unwrap())
+    });
+    let hardened = Prover::hardened();
+    group.bench_function("hardened", |b| {
+        b.iter(|| hardened.prove(black_box("bench"), 1, &x, y, g).unwrap())
+    });
+    let context = ProverContext::new(g);
+    group.bench_function("precomputed", |b| {
+        b.iter(|| context.prove(black_box("bench"), 1, &x, y).unwrap())
+    });
+    group.finish();
+}
+
+fn verify(c: &mut Criterion) {
+    let g = ProjectivePoint::GENERATOR;
+    let (x, y) = statement();
+    let proof = DLogProof::prove("bench", 1, &x, y, g).unwrap();
+    let mut group = c.benchmark_group("verify");
+    group.bench_function("default", |b| {
+        b.iter(|| assert!(black_box(&proof).verify("bench", 1, y, g).unwrap()))
+    });
+    let context = VerifierContext::new(y, g);
+    group.bench_function("precomputed", |b| {
+        b.iter(|| assert!(context.verify(black_box(&proof), "bench", 1).unwrap()))
+    });
+    group.finish();
+}
+
+fn verify_batch(c: &mut Criterion) {
+    let g = ProjectivePoint::GENERATOR;
+    let max = BATCH_SIZES[BATCH_SIZES.len() - 1];
+    let proofs: Vec<_> = (0..max as u32)
+        .map(|pid| {
+            let (x, y) = statement();
+            (pid, y, DLogProof::prove("bench", pid, &x, y, g).unwrap())
+        })
+        .collect();
+    let items: Vec<_> = proofs
+        .iter()
+        .map(|(pid, y, proof)| ("bench".into(), (*pid).into(), *y, proof))
+        .collect();
+
+    let mut group = c.benchmark_group("verify_batch");
+    for size in BATCH_SIZES {
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size),
+            &items[..size],
+            |b, items| b.iter(|| assert!(DLogProof::verify_batch(black_box(items), g).unwrap())),
+        );
+    }
+    group.finish();
+}
+
+fn prove_batch(c: &mut Criterion) {
+    let g = ProjectivePoint::GENERATOR;
+    let max = BATCH_SIZES[BATCH_SIZES.len() - 1];
+    let keys: Vec<_> = (0..max)
+        .map(|_| SecretScalar::new(Scalar::random(&mut OsRng)))
+        .collect();
+    let items: Vec<_> = (0..max as u32)
+        .zip(&keys)
+        .map(|(pid, x)| ("bench".into(), pid.into(), x, g * x.expose_secret()))
+        .collect();
+
+    let mut group = c.benchmark_group("prove_batch");
+    for size in BATCH_SIZES {
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size),
+            &items[..size],
+            |b, items| b.iter(|| DLogProof::prove_batch(black_box(items), g)),
+        );
+    }
+    group.finish();
+}
+
+fn and_proof(c: &mut Criterion) {
+    let g = ProjectivePoint::GENERATOR;
+    let max = BATCH_SIZES[BATCH_SIZES.len() - 1];
+    let xs: Vec<_> = (0..max).map(|_| Scalar::random(&mut OsRng)).collect();
+    let ys: Vec<_> = xs.iter().map(|x| g * x).collect();
+
+    let mut group = c.benchmark_group("and_proof");
+    for size in BATCH_SIZES {
+        let (xs, ys) = (&xs[..size], &ys[..size]);
+        let proof = AndProof::prove("bench", 1, xs, ys, g).unwrap();
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::new("prove", size), &size, |b, _| {
+            b.iter(|| AndProof::prove("bench", 1, black_box(xs), ys, g).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("verify", size), &size, |b, _| {
+            b.iter(|| assert!(black_box(&proof).verify("bench", 1, ys, g).unwrap()))
+        });
+    }
+    group.finish();
+}
+
+fn serialization(c: &mut Criterion) {
+    let g = ProjectivePoint::GENERATOR;
+    let (x, y) = statement();
+    let proof = DLogProof::prove("bench", 1, &x, y, g).unwrap();
+    let json = serde_json::to_string(&proof).unwrap();
+    let bytes = proof.to_bytes();
+
+    let mut group = c.benchmark_group("serialization");
+    group.bench_function("json_encode", |b| {
+        b.iter(|| serde_json::to_string(black_box(&proof)).unwrap())
+    });
+    group.bench_function("json_decode", |b| {
+        b.iter(|| serde_json::from_str::<DLogProof>(black_box(&json)).unwrap())
+    });
+    group.bench_function("binary_encode", |b| b.iter(|| black_box(&proof).to_bytes()));
+    group.bench_function("binary_decode", |b| {
+        b.iter(|| DLogProof::from_bytes(black_box(&bytes)).unwrap())
+    });
+    group.bench_function("binary_decode_and_verify", |b| {
+        b.iter_batched(
+            || bytes,
+            |bytes| {
+                let proof = DLogProof::from_bytes(&bytes).unwrap();
+                assert!(proof.verify("bench", 1, y, g).unwrap());
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    prove,
+    verify,
+    verify_batch,
+    prove_batch,
+    and_proof,
+    serialization
+);
+criterion_main!(benches);
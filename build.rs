@@ -0,0 +1,11 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    // The gRPC service is generated from proto/dlog.proto with the protoc binary vendored by
+    // protoc-bin-vendored, so no protobuf toolchain needs to be installed
+    #[cfg(feature = "grpc")]
+    {
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc");
+        std::env::set_var("PROTOC", protoc);
+        tonic_build::compile_protos("proto/dlog.proto").expect("compiling proto/dlog.proto");
+    }
+}
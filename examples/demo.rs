@@ -0,0 +1,472 @@
+use elliptic_curve::sec1::ToEncodedPoint;
+use elliptic_curve::Field;
+use k256::{ProjectivePoint, Scalar};
+use rand_core::OsRng;
+use schnorr_dlog_zkp::base58check::{
+    public_key_from_base58check, public_key_to_base58check, PROOF_VERSION, PUBLIC_KEY_VERSION,
+};
+use schnorr_dlog_zkp::bloom::ProofBloomFilter;
+use schnorr_dlog_zkp::canonical_json::to_canonical_json;
+use schnorr_dlog_zkp::data_integrity::{add_proof, did_key_url, verify_proof, ProofOptions};
+use schnorr_dlog_zkp::decimal::{
+    point_from_decimal, point_to_decimal, scalar_from_decimal, scalar_to_decimal,
+};
+use schnorr_dlog_zkp::generators::{nums_generator, PEDERSEN_H};
+use schnorr_dlog_zkp::homomorphic::{Statement, Witness};
+use schnorr_dlog_zkp::inverse_proof::InverseProof;
+use schnorr_dlog_zkp::multiformats::{
+    public_key_from_multibase, public_key_to_multibase, Multibase,
+};
+use schnorr_dlog_zkp::product_proof::{PedersenOpening, ProductProof};
+use schnorr_dlog_zkp::serialization::{Prefixed, ProofEncoding, ScalarHex, Uncompressed};
+use schnorr_dlog_zkp::streaming::StreamingProver;
+use schnorr_dlog_zkp::truncated::TruncatedProof;
+use schnorr_dlog_zkp::{
+    DLogGroup, DLogProof, ProofBuilder, ProofId, Prover, UnknownFieldPolicy, Verifier,
+};
+use serde::Serialize;
+use serde_with::de::DeserializeAsWrap;
+use serde_with::ser::SerializeAsWrap;
+use std::time::Instant;
+
+/// `{"t": "0x04…", "s": "0x…"}`: the commitment as uncompressed SEC1 hex, both with a `0x` prefix
+type UncompressedProof = ProofEncoding<Prefixed<Uncompressed>, Prefixed<ScalarHex>>;
+
+/// Discrete Logarithm Zero-Knowledge Proof System
+///
+/// It implements a non-interactive Schnorr zero-knowledge proof system
+/// for discrete logarithms on the secp256k1 curve
+///
+/// The system proves knowledge of a secret value x (the discrete logarithm)
+/// satisfying y = x * G, where:
+/// - g is the generator point (base point)
+/// - y is the public point
+/// - x is the secret scalar
+///
+/// With `--json` only the basic proof is run, and its inputs, encodings, timings and verdict
+/// are printed as one JSON document (see `JsonReport`) for scripts to consume.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    if std::env::args().skip(1).any(|arg| arg == "--json") {
+        let report = JsonReport::run("sid", 1)?;
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    // Base point of secp256k1 curve
+    let base_point = ProjectivePoint::GENERATOR;
+
+    // Session identifier for domain separation in the proof (any string value)
+    let sid = "sid";
+
+    // Participant ID, make proofs distinct when using the same session ID
+    // Just an additional input to the hash function
+    let pid = 1;
+
+    // Generate a random secret scalar (private key) using OS random number generator
+    let x = Scalar::random(&mut OsRng);
+    println!("Random secret: 0X{}", hex::encode_upper(x.to_bytes()));
+
+    // Calculate the public point y = x * G where G is the base point
+    let y = base_point * x;
+    let public_key = public_key_to_multibase(&y, Multibase::Base58Btc);
+    println!("Public key (multibase): {}", public_key);
+    assert_eq!(public_key_from_multibase(&public_key), Ok(y));
+    let public_key = public_key_to_base58check(&y, PUBLIC_KEY_VERSION);
+    println!("Public key (Base58Check): {}", public_key);
+    assert_eq!(
+        public_key_from_base58check(&public_key, PUBLIC_KEY_VERSION),
+        Ok(y)
+    );
+
+    // Generate the zero-knowledge proof that we know x such that y = x * G
+    // (timings are measured by `dlog bench`)
+    let (proof, prove_stats) = Prover::new()
+        .prove_with_stats(sid, pid, &x, y, base_point)
+        .map_err(|e| format!("Proof generation failed: {:?}", e))?;
+    println!(
+        "Proof computation: {} scalar multiplications, {} bytes hashed",
+        prove_stats.scalar_muls, prove_stats.hash_bytes
+    );
+
+    // Verify the proof without knowing the secret x
+    let result = Verifier::new()
+        .verify(&proof, sid, pid, y, base_point)
+        .map_err(|e| format!("Verification failed: {:?}", e))?;
+    if result {
+        println!("✅ DLOG proof is correct");
+    } else {
+        println!("❌ DLOG proof is not correct");
+    }
+
+    // Same statement proven under an application domain, with blinded scalar operations
+    // (side-channel hardening) and verified before being returned (fault-attack countermeasure)
+    // The challenge is also keyed with HMAC, so only the holder of the key can verify the proof
+    let app_domain = b"schnorr-dlog-zkp/demo";
+    let verifier_key = b"demo verifier key";
+    let blinded_proof = Prover::new()
+        .domain(app_domain)
+        .hmac_key(verifier_key)
+        .blinded(true)
+        .verify_after_prove(true)
+        .prove(sid, pid, &x, y, base_point)?;
+    let app_verifier = Verifier::new().domain(app_domain).hmac_key(verifier_key);
+    if app_verifier.verify(&blinded_proof, sid, pid, y, base_point)? {
+        println!("✅ Blinded DLOG proof is correct");
+    } else {
+        println!("❌ Blinded DLOG proof is not correct");
+    }
+
+    // Builder form, which won't compile without sid and pid; the proof is also bound to a
+    // request payload (additional authenticated data) that the verifier must supply
+    let request = b"register key for account 42";
+    let bound_proof = ProofBuilder::new()
+        .sid(sid)
+        .pid(pid)
+        .aad(request)
+        .prover(Prover::new().verify_after_prove(true))
+        .base_point(base_point)
+        .prove(x)?;
+    if Verifier::new()
+        .aad(request)
+        .verify(&bound_proof, sid, pid, y, base_point)?
+    {
+        println!("✅ Request-bound DLOG proof is correct");
+    } else {
+        println!("❌ Request-bound DLOG proof is not correct");
+    }
+
+    // 128-bit challenges for constrained verifiers, repeated twice for 256-bit soundness
+    let truncated_proof = TruncatedProof::<128>::prove(sid, pid, &x, y, base_point, 2)?;
+    if truncated_proof.verify(sid, pid, y, base_point, 2)? {
+        println!(
+            "✅ Truncated-challenge proof is correct ({}, {}-bit soundness)",
+            truncated_proof.ciphersuite(),
+            truncated_proof.soundness_bits()
+        );
+    } else {
+        println!("❌ Truncated-challenge proof is not correct");
+    }
+
+    prove_derived_statement(sid, pid, x, y, base_point)?;
+    prove_committed_product(sid, pid, base_point)?;
+    prove_inverse(sid, pid, x, y, base_point)?;
+    attest_key_ownership(x, y)?;
+
+    println!("Proof ID: {}", proof.id());
+    detect_duplicate_proofs(&[&proof, &blinded_proof, &proof])?;
+    prove_jsonl_stream(sid)?;
+
+    print_proof_in_multiple_formats(&proof)
+}
+
+/// The `--json` output: a fresh key pair's public key, a proof for it in the JSON and the
+/// 65-byte hex encoding, how long proving and verifying took, and whether the proof verified
+#[derive(Serialize)]
+struct JsonReport {
+    sid: String,
+    pid: u32,
+    /// Compressed SEC1 public key y = x * G, as hex
+    public_key: String,
+    proof: ProofEncodings,
+    timings_us: JsonTimings,
+    valid: bool,
+}
+
+#[derive(Serialize)]
+struct ProofEncodings {
+    /// `{"t": "<hex>", "s": "<hex>"}`
+    json: DLogProof,
+    /// `DLogProof::to_bytes` as hex
+    hex: String,
+}
+
+/// Wall-clock times of one run, in microseconds
+#[derive(Serialize)]
+struct JsonTimings {
+    prove: f64,
+    verify: f64,
+}
+
+impl JsonReport {
+    fn run(sid: &str, pid: u32) -> Result<Self, Box<dyn std::error::Error>> {
+        let base_point = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = base_point * x;
+        let micros = |start: Instant| start.elapsed().as_secs_f64() * 1e6;
+
+        let start = Instant::now();
+        let proof = DLogProof::prove(sid, pid, &x, y, base_point)?;
+        let prove = micros(start);
+        let start = Instant::now();
+        let valid = proof.verify(sid, pid, y, base_point)?;
+        let verify = micros(start);
+
+        Ok(JsonReport {
+            sid: sid.to_string(),
+            pid,
+            public_key: hex::encode(y.to_encoded_point(true)),
+            proof: ProofEncodings {
+                hex: hex::encode(proof.to_bytes()),
+                json: proof,
+            },
+            timings_us: JsonTimings { prove, verify },
+            valid,
+        })
+    }
+}
+
+/// Aggregates the key with a second one and applies a public tweak, then proves knowledge
+/// of the combined secret: (x + x2 + Δ) for the statement y + y2 + Δ * G
+fn prove_derived_statement(
+    sid: &str,
+    pid: u32,
+    x: Scalar,
+    y: ProjectivePoint,
+    base_point: ProjectivePoint,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let second = Witness::new(Scalar::random(&mut OsRng));
+    let witness = Witness::aggregate(&[Witness::new(x), second.clone()]);
+    let statement = Statement::aggregate(&[
+        Statement::new(y, base_point),
+        Statement::from_witness(&second, base_point),
+    ])?;
+
+    let delta = Scalar::random(&mut OsRng);
+    let (witness, statement) = (witness.tweak(&delta), statement.tweak(&delta));
+
+    let proof = witness.prove(sid, pid, &statement)?;
+    if statement.verify(&proof, sid, pid)? {
+        println!("✅ Aggregated and tweaked statement proof is correct");
+    } else {
+        println!("❌ Aggregated and tweaked statement proof is not correct");
+    }
+    Ok(())
+}
+
+/// Commits to random a, b and a * b and proves the multiplicative relation between them
+fn prove_committed_product(
+    sid: &str,
+    pid: u32,
+    base_point: ProjectivePoint,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Second generator derived by hashing to the curve, so nobody knows log_G(H)
+    let h = nums_generator(PEDERSEN_H)?;
+
+    let a = PedersenOpening::new(Scalar::random(&mut OsRng), Scalar::random(&mut OsRng));
+    let b = PedersenOpening::new(Scalar::random(&mut OsRng), Scalar::random(&mut OsRng));
+    let ab = PedersenOpening::new(a.value * b.value, Scalar::random(&mut OsRng));
+
+    let proof = ProductProof::prove(sid, pid, &a, &b, &ab, base_point, h)?;
+    let commitments = [
+        a.commit(base_point, h),
+        b.commit(base_point, h),
+        ab.commit(base_point, h),
+    ];
+    if proof.verify(sid, pid, &commitments, base_point, h)? {
+        println!("✅ Committed product proof is correct");
+    } else {
+        println!("❌ Committed product proof is not correct");
+    }
+    Ok(())
+}
+
+/// Publishes z = x⁻¹ * G next to y = x * G and proves the two are consistent
+fn prove_inverse(
+    sid: &str,
+    pid: u32,
+    x: Scalar,
+    y: ProjectivePoint,
+    base_point: ProjectivePoint,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let x_inv = Option::<Scalar>::from(x.invert()).ok_or("Secret has no inverse")?;
+    let z = base_point * x_inv;
+
+    let proof = InverseProof::prove(sid, pid, &x, y, z, base_point)?;
+    if proof.verify(sid, pid, y, z, base_point)? {
+        println!("✅ Inverse relation proof is correct");
+    } else {
+        println!("❌ Inverse relation proof is not correct");
+    }
+    Ok(())
+}
+
+/// Feeds proof IDs through a Bloom filter, as a verifier pipeline would, flagging repeats
+fn detect_duplicate_proofs(proofs: &[&DLogProof]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut seen = ProofBloomFilter::new(1_000, 0.001)?;
+    assert!(seen.is_empty());
+    for proof in proofs {
+        let id = proof.id();
+        if seen.insert(&id) {
+            println!("⚠️ Proof {} was probably seen before", id);
+        }
+    }
+
+    // The filter survives a restart of the pipeline through its byte encoding
+    let restored = ProofBloomFilter::from_bytes(&seen.to_bytes())?;
+    assert!(restored.contains(&proofs[0].id()));
+    println!(
+        "Bloom filter: {} distinct proofs, estimated false-positive rate {:.2e}",
+        restored.len(),
+        restored.estimated_false_positive_rate()
+    );
+    Ok(())
+}
+
+/// Streams a small JSONL batch of secrets through the parallel prover, one result line each
+fn prove_jsonl_stream(sid: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let input: String = (1..=4)
+        .map(|pid| {
+            let secret = hex::encode(Scalar::random(&mut OsRng).to_bytes());
+            format!(r#"{{"sid":"{}","pid":{},"secret":"{}"}}"#, sid, pid, secret) + "\n"
+        })
+        .collect();
+
+    let mut output = Vec::new();
+    let summary = StreamingProver::new(Prover::new())
+        .workers(2)
+        .batch_size(2)
+        .run(input.as_bytes(), &mut output)?;
+    println!(
+        "Streaming prover: {} proved, {} failed, {} bytes of JSONL written",
+        summary.proved,
+        summary.failed,
+        output.len()
+    );
+    Ok(())
+}
+
+/// Embeds a key-ownership proof in a verifiable credential as a Data Integrity proof
+fn attest_key_ownership(x: Scalar, y: ProjectivePoint) -> Result<(), Box<dyn std::error::Error>> {
+    let credential = serde_json::json!({
+        "@context": ["https://www.w3.org/ns/credentials/v2"],
+        "type": ["VerifiableCredential"],
+        "issuer": did_key_url(&y),
+        "credentialSubject": {"id": did_key_url(&y)}
+    });
+    let options = ProofOptions {
+        verification_method: did_key_url(&y),
+        proof_purpose: "assertionMethod".to_string(),
+        created: None,
+    };
+
+    let secured = add_proof(&credential, &options, &x)?;
+    println!(
+        "Credential proof: {}",
+        to_canonical_json(&secured["proof"])?
+    );
+    if verify_proof(&secured, y)? {
+        println!("✅ Verifiable credential proof is correct");
+    } else {
+        println!("❌ Verifiable credential proof is not correct");
+    }
+    Ok(())
+}
+
+fn print_proof_in_multiple_formats(proof: &DLogProof) -> Result<(), Box<dyn std::error::Error>> {
+    println!("Printing proof...");
+
+    println!("-----Original-----");
+    // t:  ProjectivePoint where each x, y & z coordinate (some values on curve) is in `Montgomery Form`
+    // (some_value × 2^256 mod p), split in 5 chunks of 52 bits, (where p is a large prime used in the curve)
+    // Used internally for efficient calculations (working with big number directly vs small chunks)
+    // Why 52 bits - Working with 52 bits leaves room for calculations without overflow for 64-bit numbers
+    // Why 5? Since we need to handle 256-bit numbers (52 bits × 5 = 260 bits, still give us enough space (260 > 256))
+    // `magnitude`: tells how big the number has grown (e.g., magnitude: 5 means 5 times)
+    // `normalized` indicates if the number is in final expected format
+    // s: A regular big number
+    println!("{:?}", proof);
+
+    println!("-----Affine-----");
+    // Standard (x,y) coordinates after normalizing the z-coordinate (computing x/z, y/z)
+    // Easier to read, but less efficient for calculations
+    let affine = proof.t().to_affine();
+    println!("t: {:?}", affine);
+
+    println!("-----HEX-----");
+    // Normalized (uncompressed) coordinates in hexadecimal format
+    let (t_x, t_y) = proof.t().coordinates()?;
+    println!("t.x: 0x{}", hex::encode(t_x));
+    println!("t.y: 0x{}", hex::encode(t_y));
+    let s_bytes = proof.s().to_bytes();
+    println!("s: 0x{}", hex::encode(s_bytes));
+
+    println!("-----Decimal-----");
+    // Base-10 integers, as exchanged with Python/Sage tooling
+    let (t_x, t_y) = point_to_decimal(&proof.t())?;
+    let s_decimal = scalar_to_decimal(&proof.s());
+    println!("t.x: {}", t_x);
+    println!("t.y: {}", t_y);
+    println!("s: {}", s_decimal);
+    assert_eq!(point_from_decimal(&t_x, &t_y), Ok(proof.t()));
+    assert_eq!(scalar_from_decimal(&s_decimal), Ok(proof.s()));
+
+    println!("-----Multiformats-----");
+    // Self-describing encodings: the leading character identifies the base (z = base58btc, f = hex)
+    let multibase = proof.to_multibase(Multibase::Base58Btc);
+    println!("Multibase (base58btc): {}", multibase);
+    println!(
+        "Multibase (base16): {}",
+        proof.to_multibase(Multibase::Base16)
+    );
+    assert_eq!(DLogProof::from_multibase(&multibase).as_ref(), Ok(proof));
+    // Proof ID wrapped as a SHA2-256 multihash, as used for IPFS/IPLD content addressing
+    let multihash = proof.id().to_multihash();
+    println!(
+        "Proof ID multihash: {}",
+        Multibase::Base58Btc.encode(&multihash)
+    );
+    assert_eq!(ProofId::from_multihash(&multihash), Ok(proof.id()));
+
+    println!("-----Base58Check-----");
+    // Version byte + payload + 4-byte double-SHA256 checksum, as used by Bitcoin tooling
+    let base58check = proof.to_base58check(PROOF_VERSION);
+    println!("Base58Check: {}", base58check);
+    assert_eq!(
+        DLogProof::from_base58check(&base58check, PROOF_VERSION).as_ref(),
+        Ok(proof)
+    );
+
+    in_json(proof)
+}
+
+fn in_json(proof: &DLogProof) -> Result<(), Box<dyn std::error::Error>> {
+    println!("-----JSON-----");
+    // Compressed format - Uses prefix (02=even y, 03=odd y) + x-coordinate
+    let json = serde_json::to_string(&proof)?;
+    println!("Compressed JSON (standard): {}", json);
+
+    // Canonical form (RFC 8785), byte-stable so it can be hashed or signed across languages
+    let canonical = to_canonical_json(proof)?;
+    println!("Canonical JSON (RFC 8785): {}", canonical);
+
+    // Uncompressed format - 0x04 prefix + x- and y-coordinate, as expected by EVM contracts
+    // and many JavaScript libraries, through the crate's `serde_with` adapters
+    let uncompressed_proof = SerializeAsWrap::<DLogProof, UncompressedProof>::new(proof);
+    println!("Uncompressed JSON (with both coordinates):");
+    let uncompressed = serde_json::to_string(&uncompressed_proof)?;
+    println!("serde_json::to_string: {}", uncompressed);
+    println!(
+        "serde_json::to_string_pretty: {}",
+        serde_json::to_string_pretty(&uncompressed_proof)?
+    );
+    let parsed: DeserializeAsWrap<DLogProof, UncompressedProof> =
+        serde_json::from_str(&uncompressed)?;
+    assert_eq!(parsed.into_inner(), *proof);
+
+    // Will utilise Serde `deserialize_with` attributes (if any)
+    let (parsed_proof, _) = DLogProof::from_json(&json, UnknownFieldPolicy::Strict)?;
+    println!("Parsed proof from JSON: \n{:?}", parsed_proof);
+
+    assert_eq!(
+        parsed_proof, *proof,
+        "❌ Parsed proof doesn't match original"
+    );
+    println!("✅ DLog proof recovered successfully!");
+
+    // Proofs from newer provers may carry extra metadata, which a tolerant parse skips
+    let extended_json = json.replacen('{', r#"{"version":2,"#, 1);
+    let (_, ignored): (DLogProof, _) =
+        DLogProof::from_json(&extended_json, UnknownFieldPolicy::Tolerant)?;
+    println!("Ignored unknown fields: {:?}", ignored);
+    Ok(())
+}
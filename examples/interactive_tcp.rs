@@ -0,0 +1,43 @@
+//! A device proves knowledge of its key to a provisioning service over TCP
+//!
+//! `cargo run --example interactive_tcp --features transport`
+
+use elliptic_curve::Field;
+use k256::{ProjectivePoint, Scalar};
+use rand_core::OsRng;
+use schnorr_dlog_zkp::transport::{InteractiveSession, StreamTransport};
+use schnorr_dlog_zkp::{Prover, Verifier};
+use tokio::net::{TcpListener, TcpStream};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let g = ProjectivePoint::GENERATOR;
+    let x = Scalar::random(&mut OsRng);
+    let y = g * x;
+    let session = InteractiveSession::new("provisioning-42", 1);
+
+    // The service knows the device's public key and checks one connection
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let address = listener.local_addr()?;
+    let service = tokio::spawn({
+        let session = session.clone();
+        async move {
+            let (stream, peer) = listener.accept().await?;
+            let mut transport = StreamTransport::new(stream);
+            let accepted = session
+                .verify(&mut transport, &Verifier::new(), y, g)
+                .await?;
+            println!("Service: device at {peer} accepted: {accepted}");
+            Ok::<_, Box<dyn std::error::Error + Send + Sync>>(())
+        }
+    });
+
+    let mut transport = StreamTransport::new(TcpStream::connect(address).await?);
+    let accepted = session
+        .prove(&mut transport, &Prover::new(), &x, y, g)
+        .await?;
+    println!("Device: proof accepted: {accepted}");
+
+    service.await?.map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -0,0 +1,26 @@
+//! Feeds arbitrary strings to the hex point and scalar deserializers and checks that what
+//! they accept survives a round trip
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use schnorr_dlog_zkp::serialization::{
+    deserialize_point_hex, deserialize_scalar_hex, serialize_point_hex, serialize_scalar_hex,
+};
+use serde_json::value::Serializer;
+use serde_json::Value;
+
+fuzz_target!(|text: &str| {
+    let value = || Value::String(text.to_string());
+    if let Ok(point) = deserialize_point_hex(value()) {
+        let encoded = serialize_point_hex(&point, Serializer).unwrap();
+        assert_eq!(deserialize_point_hex(encoded).unwrap(), point);
+    }
+    if let Ok(scalar) = deserialize_scalar_hex(value()) {
+        let encoded = serialize_scalar_hex(&scalar, Serializer).unwrap();
+        // Scalars have one encoding up to the letter case and `0x` prefix
+        let digits = text.trim_start_matches("0x").trim_start_matches("0X");
+        assert_eq!(encoded, Value::String(digits.to_lowercase()));
+        assert_eq!(deserialize_scalar_hex(encoded).unwrap(), scalar);
+    }
+});
@@ -0,0 +1,23 @@
+//! Decodes arbitrary bytes with `DLogProof::from_bytes` and verifies whatever decodes
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use schnorr_dlog_zkp::generators::nums_generator;
+use schnorr_dlog_zkp::k256::ProjectivePoint;
+use schnorr_dlog_zkp::DLogProof;
+
+fuzz_target!(|input: (&str, u32, &[u8])| {
+    let (sid, pid, bytes) = input;
+    let Ok(proof) = DLogProof::from_bytes(bytes) else {
+        return;
+    };
+    // The encoding is canonical: anything accepted encodes back to the same bytes
+    assert_eq!(proof.to_bytes()[..], *bytes);
+    // Nobody knows the discrete logarithm of a NUMS point, so no proof may verify for it
+    let y = nums_generator("fuzz/y").unwrap();
+    assert_ne!(
+        proof.verify(sid, pid, y, ProjectivePoint::GENERATOR),
+        Ok(true)
+    );
+});
@@ -0,0 +1,31 @@
+//! Parses arbitrary text as a JSON proof, with serde and `DLogProof::from_json`, and verifies
+//! whatever parses
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use schnorr_dlog_zkp::generators::nums_generator;
+use schnorr_dlog_zkp::k256::ProjectivePoint;
+use schnorr_dlog_zkp::{DLogProof, UnknownFieldPolicy};
+
+fuzz_target!(|json: &str| {
+    let strict = DLogProof::from_json(json, UnknownFieldPolicy::Strict);
+    let tolerant = DLogProof::from_json(json, UnknownFieldPolicy::Tolerant);
+    if let Ok((proof, ignored)) = &strict {
+        assert!(ignored.is_empty());
+        assert_eq!(tolerant.as_ref().map(|(proof, _)| proof), Ok(proof));
+    }
+    let serde = serde_json::from_str::<DLogProof>(json);
+    let Some(proof) = serde.ok().or(tolerant.ok().map(|(proof, _)| proof)) else {
+        return;
+    };
+
+    let json = serde_json::to_string(&proof).unwrap();
+    assert_eq!(serde_json::from_str::<DLogProof>(&json).unwrap(), proof);
+    // Nobody knows the discrete logarithm of a NUMS point, so no proof may verify for it
+    let y = nums_generator("fuzz/y").unwrap();
+    assert_ne!(
+        proof.verify("sid", 1, y, ProjectivePoint::GENERATOR),
+        Ok(true)
+    );
+});
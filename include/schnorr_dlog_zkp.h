@@ -0,0 +1,115 @@
+#ifndef SCHNORR_DLOG_ZKP_H
+#define SCHNORR_DLOG_ZKP_H
+
+/* Generated by cbindgen from src/ffi.rs; don't edit by hand. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+// Length of the encoding written by `dlog_proof_serialize`
+#define DLOG_PROOF_LEN 65
+
+// Length of the big-endian secret key taken by `dlog_prove`
+#define DLOG_SECRET_KEY_LEN 32
+
+// Result of an FFI call
+//
+// The numbering predates `DLogProofError::code` and is kept for the ABI. Errors of the
+// Rust API map to statuses by their code:
+//
+// | `DLogProofError::code` | Status |
+// |---|---|
+// | 1 (`InvalidPoint`) | `InvalidPoint` |
+// | 2 (`InvalidScalar`) | `InvalidScalar` |
+// | 4 (`Serialization`) | `InvalidEncoding` |
+// | 7 (`IdentityPoint`) | `IdentityPoint` |
+// | any other | `Internal` |
+typedef enum DlogStatus {
+  // Success; for `dlog_verify`, the proof is valid
+  DLOG_STATUS_OK = 0,
+  // `dlog_verify` only: the proof is well-formed but doesn't verify
+  DLOG_STATUS_INVALID_PROOF = 1,
+  // A required pointer is null
+  DLOG_STATUS_NULL_POINTER = 2,
+  // The output buffer is smaller than the length written to `written`
+  DLOG_STATUS_BUFFER_TOO_SMALL = 3,
+  // A point (public key or commitment) is malformed or not on the curve
+  DLOG_STATUS_INVALID_POINT = 4,
+  // A scalar (secret key or response) is malformed, zero where it must not be, or not
+  // below the group order
+  DLOG_STATUS_INVALID_SCALAR = 5,
+  // An encoding has the wrong length or format
+  DLOG_STATUS_INVALID_ENCODING = 6,
+  // The public key is the identity point
+  DLOG_STATUS_IDENTITY_POINT = 7,
+  // Any other error
+  DLOG_STATUS_INTERNAL = 8,
+} DlogStatus;
+
+// A proof owned by the caller, released with `dlog_proof_free`
+typedef struct DlogProofHandle DlogProofHandle;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Proves knowledge of a secret key for the session and participant
+//
+// On success `*proof_out` holds a new proof, to be released with `dlog_proof_free`.
+//
+// # Safety
+// `sid` must point to `sid_len` readable bytes (or be null with `sid_len` 0), `secret_key`
+// to `DLOG_SECRET_KEY_LEN` readable bytes, and `proof_out` must be valid for a write.
+enum DlogStatus dlog_prove(const uint8_t *sid,
+                           size_t sid_len,
+                           uint32_t pid,
+                           const uint8_t *secret_key,
+                           struct DlogProofHandle **proof_out);
+
+// Verifies a proof against a SEC1 public key, returning `Ok` if it is valid and
+// `InvalidProof` if not
+//
+// # Safety
+// `proof` must be a live handle, `sid` must point to `sid_len` readable bytes (or be null
+// with `sid_len` 0) and `public_key` to `public_key_len` readable bytes.
+enum DlogStatus dlog_verify(const struct DlogProofHandle *proof,
+                            const uint8_t *sid,
+                            size_t sid_len,
+                            uint32_t pid,
+                            const uint8_t *public_key,
+                            size_t public_key_len);
+
+// Writes the `DLOG_PROOF_LEN`-byte encoding of a proof into `out`
+//
+// `*written` receives the encoding's length, also when `out_len` is too small and
+// `BufferTooSmall` is returned.
+//
+// # Safety
+// `proof` must be a live handle, `out` must point to `out_len` writable bytes and `written`
+// must be valid for a write.
+enum DlogStatus dlog_proof_serialize(const struct DlogProofHandle *proof,
+                                     uint8_t *out,
+                                     size_t out_len,
+                                     size_t *written);
+
+// Decodes a proof from the encoding of `dlog_proof_serialize`
+//
+// On success `*proof_out` holds a new proof, to be released with `dlog_proof_free`.
+//
+// # Safety
+// `bytes` must point to `len` readable bytes and `proof_out` must be valid for a write.
+enum DlogStatus dlog_proof_deserialize(const uint8_t *bytes,
+                                       size_t len,
+                                       struct DlogProofHandle **proof_out);
+
+// Releases a proof; null is ignored
+//
+// # Safety
+// `proof` must be null or a handle from this library that hasn't been freed yet.
+void dlog_proof_free(struct DlogProofHandle *proof);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SCHNORR_DLOG_ZKP_H */
@@ -0,0 +1,193 @@
+use elliptic_curve::subtle::{Choice, ConstantTimeEq};
+use elliptic_curve::Field;
+use k256::ProjectivePoint;
+use rand_core::{CryptoRngCore, OsRng};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use zeroize::Zeroizing;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::secret::SecretScalar;
+use crate::serialization::{PointHex, ScalarHex};
+use crate::session::SessionId;
+use crate::sigma::{DLogStatement, SchnorrDLog, SigmaProof};
+
+/// A `DLogProof` locked to an adaptor point T = t * G, completable only with t
+///
+/// The prover commits to R = r * G but derives the challenge for R + T, the commitment of
+/// the completed proof: c = H(G, y, R + T) and s' = r + c * x. Anyone can check s' * G =
+/// R + c * y without knowing t; the holder of t completes it to the ordinary proof
+/// (R + T, s' + t), and whoever sees both learns t = s - s'. This is the building block of
+/// scriptless-script constructions such as atomic swaps:
+/// * `r` - The prover's commitment R = r * G, without the adaptor point
+/// * `s` - The pre-response s' = r + c * x
+#[serde_as]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct AdaptorProof<G: DLogGroup = ProjectivePoint> {
+    #[serde_as(as = "PointHex")]
+    r: G,
+    #[serde_as(as = "ScalarHex")]
+    s: G::Scalar,
+}
+
+impl<G: DLogGroup> AdaptorProof<G> {
+    /// The commitment R = r * G
+    pub fn r(&self) -> G {
+        self.r
+    }
+
+    /// The pre-response s' = r + c * x
+    pub fn s(&self) -> G::Scalar {
+        self.s
+    }
+
+    /// Creates a proof of knowledge of `x` for y = x * G, locked to `adaptor_point`
+    ///
+    /// # Arguments
+    /// * `sid` - Session identifier used for domain separation, a string or bytes (see `SessionId`)
+    /// * `pid` - Participant ID for uniqueness to distinguish different proofs
+    /// * `x` - The secret scalar
+    /// * `y` - The public point, must satisfy y = x * G
+    /// * `base_point` - Base point of the group
+    /// * `adaptor_point` - The point T = t * G whose discrete log completes the proof
+    ///
+    /// # Returns
+    /// * `Ok(AdaptorProof)` - The locked proof
+    /// * `Err(DLogProofError)` - `IdentityPoint` for a degenerate statement or adaptor point,
+    ///   or if the challenge can't be derived
+    pub fn prove<'s>(
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: G,
+        base_point: G,
+        adaptor_point: G,
+    ) -> Result<Self, DLogProofError> {
+        Self::prove_with_rng(&mut OsRng, sid, pid, x, y, base_point, adaptor_point)
+    }
+
+    /// Creates a locked proof like `prove`, drawing the nonce from `rng`
+    pub fn prove_with_rng<'s>(
+        rng: &mut impl CryptoRngCore,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: G,
+        base_point: G,
+        adaptor_point: G,
+    ) -> Result<Self, DLogProofError> {
+        DLogProof::check_statement(y, base_point)?;
+        DLogProof::check_statement(adaptor_point, base_point)?;
+        let x = x.into();
+
+        let nonce = Zeroizing::new(G::Scalar::random(rng));
+        let r = base_point * *nonce;
+        let c = Self::challenge(sid.into(), pid, y, base_point, r + adaptor_point)?;
+        Ok(AdaptorProof {
+            r,
+            s: *nonce + c * x.expose_secret(),
+        })
+    }
+
+    /// Verifies that completing the proof with the discrete log of `adaptor_point` yields a
+    /// valid `DLogProof` for y
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Validity of the locked proof
+    /// * `Err(DLogProofError)` - `IdentityPoint` for a degenerate statement or adaptor point,
+    ///   or if the challenge can't be derived
+    pub fn verify<'s>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        y: G,
+        base_point: G,
+        adaptor_point: G,
+    ) -> Result<bool, DLogProofError> {
+        DLogProof::check_statement(y, base_point)?;
+        DLogProof::check_statement(adaptor_point, base_point)?;
+        let c = Self::challenge(sid.into(), pid, y, base_point, self.r + adaptor_point)?;
+        Ok((base_point * self.s).ct_eq(&(self.r + y * c)).into())
+    }
+
+    /// Completes the proof with the adaptor secret t, giving the `DLogProof` (R + T, s' + t)
+    pub fn complete(&self, t: impl Into<SecretScalar<G::Scalar>>, base_point: G) -> DLogProof<G> {
+        let t = t.into();
+        let adaptor_point = base_point * t.expose_secret();
+        DLogProof::new(self.r + adaptor_point, self.s + t.expose_secret())
+    }
+
+    /// Recovers the adaptor secret t = s - s' from the completed proof
+    ///
+    /// Check that `proof` verifies before trusting the result; for an unrelated proof the
+    /// returned scalar is meaningless.
+    pub fn extract(&self, proof: &DLogProof<G>) -> G::Scalar {
+        proof.s() - self.s
+    }
+
+    /// The `DLogProof` challenge for the completed commitment R + T
+    fn challenge(
+        sid: SessionId<'_>,
+        pid: u32,
+        y: G,
+        base_point: G,
+        t: G,
+    ) -> Result<G::Scalar, DLogProofError> {
+        SigmaProof::<SchnorrDLog<G>>::challenge(
+            sid,
+            pid.into(),
+            &DLogStatement { base_point, y },
+            &t,
+        )
+    }
+}
+
+impl<G: DLogGroup> ConstantTimeEq for AdaptorProof<G> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.r.ct_eq(&other.r) & self.s.ct_eq(&other.s)
+    }
+}
+
+impl<G: DLogGroup> PartialEq for AdaptorProof<G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<G: DLogGroup> Eq for AdaptorProof<G> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::Scalar;
+
+    #[test]
+    fn completion_reveals_the_adaptor_secret() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let t = Scalar::random(&mut OsRng);
+        let adaptor_point = g * t;
+
+        let locked = AdaptorProof::prove("sid", 1, &x, y, g, adaptor_point).unwrap();
+        assert!(locked.verify("sid", 1, y, g, adaptor_point).unwrap());
+        assert!(!locked.verify("sid", 1, y, g, adaptor_point + g).unwrap());
+        assert!(!locked.verify("sid", 2, y, g, adaptor_point).unwrap());
+
+        // The locked proof alone doesn't pass as a DLogProof
+        assert!(!DLogProof::new(locked.r(), locked.s())
+            .verify("sid", 1, y, g)
+            .unwrap());
+
+        let proof = locked.complete(&t, g);
+        assert!(proof.verify("sid", 1, y, g).unwrap());
+        assert_eq!(locked.extract(&proof), t);
+
+        let json = serde_json::to_string(&locked).unwrap();
+        let decoded: AdaptorProof = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, locked);
+    }
+}
@@ -0,0 +1,396 @@
+//! Anchoring proof digests on an EVM chain (`anchor` feature)
+//!
+//! `AnchorClient` submits the `ProofId` of a proof to an anchoring contract, which records the
+//! timestamp of the block that first saw it, and later reads that timestamp back: immutable,
+//! externally timestamped evidence that the proof existed by then. `ANCHOR_CONTRACT` is the
+//! Solidity source of the contract; deploy it once and pass its address to the client. Only
+//! the 32-byte digest goes on chain, never the proof or its statement.
+//!
+//! The client talks JSON-RPC to any Ethereum node through alloy. Submitting needs a funded
+//! secp256k1 key, which signs the transactions locally; reading the anchoring status doesn't.
+
+use std::time::Duration;
+
+use alloy::network::EthereumWallet;
+use alloy::primitives::{Address, B256, U256};
+use alloy::providers::{DynProvider, Provider, ProviderBuilder};
+use alloy::signers::local::PrivateKeySigner;
+use alloy::sol;
+use alloy::transports::http::reqwest::Url;
+use elliptic_curve::PrimeField;
+use k256::ProjectivePoint;
+
+use crate::dlog_proof::{DLogProof, ProofId};
+use crate::error::DLogProofError;
+use crate::keys::SecretKey;
+use crate::participant::ParticipantId;
+use crate::session::SessionId;
+use crate::verifier::Verifier;
+
+/// Solidity source of the anchoring contract `AnchorClient` talks to
+pub const ANCHOR_CONTRACT: &str = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// Records when proof digests (schnorr-dlog-zkp `ProofId`s) were first anchored
+contract ProofAnchor {
+    mapping(bytes32 => uint256) public anchoredAt;
+
+    event Anchored(bytes32 indexed digest, uint256 timestamp);
+
+    /// Records the current block's timestamp for `digest`, unless it is already anchored
+    function anchor(bytes32 digest) external {
+        if (anchoredAt[digest] == 0) {
+            anchoredAt[digest] = block.timestamp;
+            emit Anchored(digest, block.timestamp);
+        }
+    }
+}
+"#;
+
+sol! {
+    #[sol(rpc)]
+    interface ProofAnchor {
+        function anchor(bytes32 digest) external;
+        function anchoredAt(bytes32 digest) external view returns (uint256);
+    }
+}
+
+/// The transaction that anchored a proof
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnchorReceipt {
+    /// Hash of the anchoring transaction
+    pub transaction_hash: [u8; 32],
+    /// Number of the block that included it
+    pub block_number: Option<u64>,
+}
+
+/// Client for a deployed `ANCHOR_CONTRACT`
+///
+/// ```no_run
+/// # use schnorr_dlog_zkp::{DLogProof, DLogProofError, SecretKey};
+/// use schnorr_dlog_zkp::anchor::AnchorClient;
+///
+/// # async fn run(proof: DLogProof, key: SecretKey, contract: [u8; 20]) -> Result<(), DLogProofError> {
+/// let client = AnchorClient::with_signer("https://rpc.example.org", contract, &key)?;
+/// client.anchor(&proof).await?;
+/// let anchored_at = client.anchored_at(&proof.id()).await?;
+/// # let _ = anchored_at;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AnchorClient {
+    contract: ProofAnchor::ProofAnchorInstance<DynProvider>,
+    timeout: Duration,
+}
+
+impl AnchorClient {
+    /// Waiting for an anchoring transaction to be included times out after 2 minutes by
+    /// default
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(120);
+
+    /// Creates a read-only client for the contract at `contract` on the node at `rpc_url`,
+    /// which can check the anchoring status but not anchor
+    ///
+    /// # Returns
+    /// * `Ok(AnchorClient)` - The client
+    /// * `Err(DLogProofError)` - `Io` if `rpc_url` isn't a valid URL
+    pub fn new(rpc_url: &str, contract: [u8; 20]) -> Result<Self, DLogProofError> {
+        let provider = ProviderBuilder::new().connect_http(parse_url(rpc_url)?);
+        Ok(Self::from_provider(provider.erased(), contract))
+    }
+
+    /// Creates a client that anchors with transactions signed by `key`, whose account pays
+    /// for them
+    ///
+    /// # Returns
+    /// * `Ok(AnchorClient)` - The client
+    /// * `Err(DLogProofError)` - `Io` if `rpc_url` isn't a valid URL
+    pub fn with_signer(
+        rpc_url: &str,
+        contract: [u8; 20],
+        key: &SecretKey,
+    ) -> Result<Self, DLogProofError> {
+        let signer = PrivateKeySigner::from_slice(&key.expose_secret().to_repr())
+            .map_err(|_| DLogProofError::InvalidScalar)?;
+        let provider = ProviderBuilder::new()
+            .wallet(EthereumWallet::from(signer))
+            .connect_http(parse_url(rpc_url)?);
+        Ok(Self::from_provider(provider.erased(), contract))
+    }
+
+    fn from_provider(provider: DynProvider, contract: [u8; 20]) -> Self {
+        AnchorClient {
+            contract: ProofAnchor::new(Address::from(contract), provider),
+            timeout: Self::DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Sets how long `anchor` waits for its transaction to be included
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Anchors the `ProofId` of `proof` and waits for the transaction to be included
+    ///
+    /// Anchoring a proof again succeeds but keeps the first timestamp.
+    ///
+    /// # Returns
+    /// * `Ok(AnchorReceipt)` - The included transaction
+    /// * `Err(DLogProofError)` - `Io` if the node can't be reached, rejects or reverts the
+    ///   transaction (e.g. a read-only client or an unfunded account), or it isn't included
+    ///   within the timeout
+    pub async fn anchor(&self, proof: &DLogProof) -> Result<AnchorReceipt, DLogProofError> {
+        let receipt = self
+            .contract
+            .anchor(B256::from(proof.id().0))
+            .send()
+            .await
+            .map_err(|e| rpc_error("Failed to send the anchoring transaction", e))?
+            .with_timeout(Some(self.timeout))
+            .get_receipt()
+            .await
+            .map_err(|e| rpc_error("Anchoring transaction not included", e))?;
+        if !receipt.status() {
+            return Err(DLogProofError::Io(format!(
+                "Anchoring transaction {} reverted",
+                receipt.transaction_hash
+            )));
+        }
+        Ok(AnchorReceipt {
+            transaction_hash: receipt.transaction_hash.0,
+            block_number: receipt.block_number,
+        })
+    }
+
+    /// Returns the timestamp of the block that anchored the proof with `id`
+    ///
+    /// # Returns
+    /// * `Ok(Some(u64))` - Seconds since the Unix epoch when the proof was first anchored
+    /// * `Ok(None)` - If the proof was never anchored
+    /// * `Err(DLogProofError)` - `Io` if the node can't be reached or the call fails
+    pub async fn anchored_at(&self, id: &ProofId) -> Result<Option<u64>, DLogProofError> {
+        let timestamp = self
+            .contract
+            .anchoredAt(B256::from(id.0))
+            .call()
+            .await
+            .map_err(|e| rpc_error("Failed to read the anchoring status", e))?;
+        if timestamp == U256::ZERO {
+            return Ok(None);
+        }
+        u64::try_from(timestamp)
+            .map(Some)
+            .map_err(|_| DLogProofError::Io(format!("Invalid anchoring timestamp {timestamp}")))
+    }
+
+    /// Verifies `proof` with `verifier` and reads when it was anchored
+    ///
+    /// The anchoring status is only read for a valid proof.
+    ///
+    /// # Returns
+    /// * `Ok((bool, Option<u64>))` - Validity of the proof, and the timestamp of its anchoring
+    ///   if it is valid and anchored
+    /// * `Err(DLogProofError)` - Any error of `Verifier::verify` or `anchored_at`
+    pub async fn verify_anchored<'s, 'p>(
+        &self,
+        verifier: &Verifier,
+        proof: &DLogProof,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        y: impl Into<ProjectivePoint>,
+        base_point: ProjectivePoint,
+    ) -> Result<(bool, Option<u64>), DLogProofError> {
+        if !verifier.verify(proof, sid, pid, y, base_point)? {
+            return Ok((false, None));
+        }
+        Ok((true, self.anchored_at(&proof.id()).await?))
+    }
+}
+
+fn parse_url(rpc_url: &str) -> Result<Url, DLogProofError> {
+    rpc_url
+        .parse()
+        .map_err(|e| DLogProofError::Io(format!("Invalid RPC URL {rpc_url}: {e}")))
+}
+
+fn rpc_error(context: &str, error: impl std::fmt::Display) -> DLogProofError {
+    DLogProofError::Io(format!("{context}: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::consensus::{Transaction, TxEnvelope};
+    use alloy::eips::Decodable2718;
+    use alloy::primitives::keccak256;
+    use alloy::sol_types::SolCall;
+    use elliptic_curve::Field;
+    use k256::Scalar;
+    use rand_core::OsRng;
+    use serde_json::{json, Value};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    const CONTRACT: [u8; 20] = [0xaa; 20];
+
+    /// Methods and parameters of the JSON-RPC calls a test node received
+    type Calls = Arc<Mutex<Vec<(String, Value)>>>;
+
+    /// Answers JSON-RPC requests with `answer(method, params)` until the test ends, and
+    /// collects the calls
+    fn serve(answer: fn(&str, &Value) -> Value) -> (String, Calls) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&calls);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut reader = BufReader::new(stream.unwrap());
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                let request: Value = serde_json::from_slice(&body).unwrap();
+                let method = request["method"].as_str().unwrap().to_string();
+                let result = answer(&method, &request["params"]);
+                seen.lock()
+                    .unwrap()
+                    .push((method, request["params"].clone()));
+                let response =
+                    json!({"jsonrpc": "2.0", "id": request["id"], "result": result}).to_string();
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+                     content-length: {}\r\nconnection: close\r\n\r\n{response}",
+                    response.len()
+                )
+                .unwrap();
+            }
+        });
+        (url, calls)
+    }
+
+    fn proof() -> (ProjectivePoint, DLogProof) {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        (y, DLogProof::prove("s", 1, &x, y, g).unwrap())
+    }
+
+    #[tokio::test]
+    async fn anchoring_status_is_read_from_the_contract() {
+        // Anchored at 0x65 if the digest starts with an even byte
+        let (url, calls) = serve(|method, params| match method {
+            "eth_call" => {
+                let data = params[0]["input"].as_str().unwrap();
+                let first = u8::from_str_radix(&data[10..12], 16).unwrap();
+                let timestamp = if first % 2 == 0 { 0x65 } else { 0 };
+                json!(format!("0x{timestamp:064x}"))
+            }
+            _ => Value::Null,
+        });
+        let client = AnchorClient::new(&url, CONTRACT).unwrap();
+        let g = ProjectivePoint::GENERATOR;
+        let (y, proof) = proof();
+        let expected = (proof.id().0[0] % 2 == 0).then_some(0x65);
+        assert_eq!(client.anchored_at(&proof.id()).await, Ok(expected));
+        assert_eq!(
+            client
+                .verify_anchored(&Verifier::new(), &proof, "s", 1, y, g)
+                .await,
+            Ok((true, expected))
+        );
+        // Invalid proofs are rejected without asking the node
+        assert_eq!(
+            client
+                .verify_anchored(&Verifier::new(), &proof, "s", 2, y, g)
+                .await,
+            Ok((false, None))
+        );
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 2);
+        let call = &calls[0].1[0];
+        assert_eq!(call["to"], format!("0x{}", hex::encode(CONTRACT)));
+        let input = hex::decode(&call["input"].as_str().unwrap()[2..]).unwrap();
+        assert_eq!(
+            input,
+            ProofAnchor::anchoredAtCall::new((B256::from(proof.id().0),)).abi_encode()
+        );
+    }
+
+    #[tokio::test]
+    async fn proofs_are_anchored_with_signed_transactions() {
+        let (url, calls) = serve(|method, params| match method {
+            "eth_chainId" => json!("0x1"),
+            "eth_blockNumber" | "eth_getTransactionCount" => json!("0x1"),
+            "eth_estimateGas" => json!("0xc350"),
+            "eth_gasPrice" | "eth_maxPriorityFeePerGas" => json!("0x3b9aca00"),
+            "eth_feeHistory" => json!({
+                "oldestBlock": "0x1",
+                "baseFeePerGas": ["0x3b9aca00", "0x3b9aca00"],
+                "gasUsedRatio": [0.5],
+                "reward": [["0x3b9aca00"]],
+            }),
+            "eth_sendRawTransaction" => {
+                let raw = hex::decode(&params[0].as_str().unwrap()[2..]).unwrap();
+                json!(keccak256(raw))
+            }
+            "eth_getTransactionReceipt" => json!({
+                "transactionHash": params[0],
+                "transactionIndex": "0x0",
+                "blockHash": format!("0x{}", "11".repeat(32)),
+                "blockNumber": "0x2a",
+                "from": format!("0x{}", "22".repeat(20)),
+                "to": format!("0x{}", "aa".repeat(20)),
+                "cumulativeGasUsed": "0xc350",
+                "gasUsed": "0xc350",
+                "effectiveGasPrice": "0x3b9aca00",
+                "contractAddress": null,
+                "logs": [],
+                "logsBloom": format!("0x{}", "00".repeat(256)),
+                "status": "0x1",
+                "type": "0x2",
+            }),
+            _ => Value::Null,
+        });
+        let key = SecretKey::generate(&mut OsRng);
+        let client = AnchorClient::with_signer(&url, CONTRACT, &key).unwrap();
+        let (_, proof) = proof();
+        let receipt = client.anchor(&proof).await.unwrap();
+        assert_eq!(receipt.block_number, Some(42));
+
+        let calls = calls.lock().unwrap();
+        let raw = calls
+            .iter()
+            .find(|(method, _)| method == "eth_sendRawTransaction")
+            .map(|(_, params)| hex::decode(&params[0].as_str().unwrap()[2..]).unwrap())
+            .unwrap();
+        assert_eq!(receipt.transaction_hash, keccak256(&raw).0);
+        let transaction = TxEnvelope::decode_2718(&mut raw.as_slice()).unwrap();
+        assert_eq!(transaction.to(), Some(Address::from(CONTRACT)));
+        assert_eq!(
+            transaction.input().as_ref(),
+            ProofAnchor::anchorCall::new((B256::from(proof.id().0),)).abi_encode()
+        );
+        let signer = PrivateKeySigner::from_slice(&key.expose_secret().to_repr()).unwrap();
+        let sender = transaction
+            .signature()
+            .recover_address_from_prehash(&transaction.signature_hash())
+            .unwrap();
+        assert_eq!(sender, signer.address());
+    }
+}
@@ -0,0 +1,419 @@
+//! Verification of memory-mapped binary proof archives (`mmap` feature)
+//!
+//! An archive is a file of binary `ProofBundle`s one after the other, each as written by
+//! `ProofBundle::write_to`: a header with the version, sid and entry count, then fixed-size
+//! entries `pid (4) || public (33) || proof (65)`. Archives are built by writing bundles of
+//! any size in sequence, so neither writing nor verifying needs the whole archive in memory.
+//!
+//! `ArchiveVerifier` maps the file and walks it chunk by chunk: the entries of a chunk are
+//! decoded straight from the mapping, checked with one batch verification (bisected to the
+//! invalid proofs if it fails) and dropped before the next chunk. Working memory is bounded
+//! by the chunk size, and the page cache is told the mapping is read sequentially, so
+//! archives far larger than RAM verify. With the `parallel` feature one chunk per rayon
+//! thread is checked at a time.
+
+use std::fmt;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use k256::ProjectivePoint;
+use memmap2::Mmap;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::batch::BatchItem;
+use crate::bundle::BUNDLE_VERSION;
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::verifier::Verifier;
+
+/// Length of one archive entry: pid, compressed public key and proof
+const ENTRY_LEN: usize = 4 + 33 + DLogProof::ENCODED_LEN;
+
+/// How far `ArchiveVerifier` has got, passed to its progress callback after every chunk
+/// (and every empty bundle)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ArchiveProgress {
+    /// Bytes of the archive verified so far
+    pub bytes_done: u64,
+    /// Size of the archive in bytes
+    pub bytes_total: u64,
+    /// Proofs verified so far
+    pub proofs: u64,
+    /// Invalid proofs among them
+    pub invalid: u64,
+}
+
+/// Result of verifying an archive
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchiveSummary {
+    /// Number of bundles in the archive
+    pub bundles: u64,
+    /// Number of proofs in the archive
+    pub proofs: u64,
+    /// Positions of the invalid proofs, counting all entries of the archive from 0, in order
+    pub invalid: Vec<u64>,
+}
+
+impl ArchiveSummary {
+    /// Whether every proof in the archive is valid
+    pub fn all_valid(&self) -> bool {
+        self.invalid.is_empty()
+    }
+}
+
+type ProgressCallback = Arc<dyn Fn(&ArchiveProgress) + Send + Sync>;
+
+/// Verifies binary proof archives with bounded memory (see the module documentation)
+///
+/// ```no_run
+/// # use schnorr_dlog_zkp::DLogProofError;
+/// use schnorr_dlog_zkp::archive::ArchiveVerifier;
+/// use schnorr_dlog_zkp::k256::ProjectivePoint;
+///
+/// # fn run() -> Result<(), DLogProofError> {
+/// let summary = ArchiveVerifier::new()
+///     .progress(|p| eprintln!("{} of {} bytes", p.bytes_done, p.bytes_total))
+///     .verify_file("proofs.bin", ProjectivePoint::GENERATOR)?;
+/// println!("{} proofs, {} invalid", summary.proofs, summary.invalid.len());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Participant IDs aren't checked for duplicates across an archive, which would take memory
+/// growing with it; `ProofBundle::verify_all` does that for a single bundle.
+#[derive(Clone)]
+pub struct ArchiveVerifier {
+    verifier: Verifier,
+    chunk_size: usize,
+    progress: Option<ProgressCallback>,
+}
+
+impl ArchiveVerifier {
+    /// Proofs per chunk by default, about 400 KiB of the archive
+    pub const DEFAULT_CHUNK_SIZE: usize = 4096;
+
+    /// Creates an archive verifier with the default `Verifier` configuration
+    pub fn new() -> Self {
+        ArchiveVerifier {
+            verifier: Verifier::new(),
+            chunk_size: Self::DEFAULT_CHUNK_SIZE,
+            progress: None,
+        }
+    }
+
+    /// Verifies the proofs with the transcript options of `verifier`
+    pub fn verifier(mut self, verifier: Verifier) -> Self {
+        self.verifier = verifier;
+        self
+    }
+
+    /// Sets how many proofs are decoded and batch-verified at once (at least 1)
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Calls `progress` after every chunk, from the thread running the verification
+    pub fn progress(mut self, progress: impl Fn(&ArchiveProgress) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// Memory-maps the archive at `path` and verifies every proof in it
+    ///
+    /// The file must not be modified or truncated while it is verified; like any reader of a
+    /// mapped file, the process would see the changes or be killed on access.
+    ///
+    /// # Returns
+    /// * `Ok(ArchiveSummary)` - The number of bundles and proofs, and which proofs are invalid
+    /// * `Err(DLogProofError)` - `Io` if the file can't be opened or mapped, or any error of
+    ///   `verify_bytes`
+    pub fn verify_file(
+        &self,
+        path: impl AsRef<Path>,
+        base_point: ProjectivePoint,
+    ) -> Result<ArchiveSummary, DLogProofError> {
+        let path = path.as_ref();
+        let io_error = |e: std::io::Error| {
+            DLogProofError::Io(format!("Failed to map {}: {}", path.display(), e))
+        };
+        let file = File::open(path).map_err(io_error)?;
+        // SAFETY: the mapping is read-only and lives only for this call; the caller keeps
+        // the file unchanged meanwhile, as documented above
+        let map = unsafe { Mmap::map(&file) }.map_err(io_error)?;
+        #[cfg(unix)]
+        map.advise(memmap2::Advice::Sequential).map_err(io_error)?;
+        self.verify_bytes(&map, base_point)
+    }
+
+    /// Verifies every proof of an archive held in memory or mapped by the caller
+    ///
+    /// A malformed public key or proof makes that entry invalid; the archive structure itself
+    /// must be intact.
+    ///
+    /// # Returns
+    /// * `Ok(ArchiveSummary)` - The number of bundles and proofs, and which proofs are invalid
+    /// * `Err(DLogProofError)` - `Serialization` for a truncated archive, a bundle of another
+    ///   version or a sid that isn't UTF-8, or any error of `Verifier::find_invalid`
+    pub fn verify_bytes(
+        &self,
+        archive: &[u8],
+        base_point: ProjectivePoint,
+    ) -> Result<ArchiveSummary, DLogProofError> {
+        let mut summary = ArchiveSummary::default();
+        let mut rest = archive;
+        while !rest.is_empty() {
+            let version = u32::from_be_bytes(take_array(&mut rest)?);
+            if version != BUNDLE_VERSION {
+                return Err(DLogProofError::Serialization(format!(
+                    "Unsupported bundle version {version}, expected {BUNDLE_VERSION}"
+                )));
+            }
+            let sid_len = u32::from_be_bytes(take_array(&mut rest)?);
+            let sid = std::str::from_utf8(take(&mut rest, sid_len as usize)?).map_err(|_| {
+                DLogProofError::Serialization("Bundle sid is not UTF-8".to_string())
+            })?;
+            let count = u32::from_be_bytes(take_array(&mut rest)?);
+            let entries = take(&mut rest, (count as usize).saturating_mul(ENTRY_LEN))?;
+            summary.bundles += 1;
+
+            let mut bytes_done = archive.len() - rest.len() - entries.len();
+            for window in entries.chunks(self.window_len()) {
+                let invalid = self.verify_window(sid, window, base_point)?;
+                summary.invalid.extend(
+                    invalid
+                        .into_iter()
+                        .map(|index| summary.proofs + index as u64),
+                );
+                summary.proofs += (window.len() / ENTRY_LEN) as u64;
+                bytes_done += window.len();
+                self.report(bytes_done, archive.len(), &summary);
+            }
+            if entries.is_empty() {
+                self.report(bytes_done, archive.len(), &summary);
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Passes the progress so far to the callback, if any
+    fn report(&self, bytes_done: usize, bytes_total: usize, summary: &ArchiveSummary) {
+        if let Some(progress) = &self.progress {
+            progress(&ArchiveProgress {
+                bytes_done: bytes_done as u64,
+                bytes_total: bytes_total as u64,
+                proofs: summary.proofs,
+                invalid: summary.invalid.len() as u64,
+            });
+        }
+    }
+
+    /// Bytes of the entries checked at once: one chunk per thread
+    fn window_len(&self) -> usize {
+        #[cfg(feature = "parallel")]
+        let chunks = rayon::current_num_threads();
+        #[cfg(not(feature = "parallel"))]
+        let chunks = 1;
+        self.chunk_size * chunks * ENTRY_LEN
+    }
+
+    /// Indices of the invalid entries in `window`
+    fn verify_window(
+        &self,
+        sid: &str,
+        window: &[u8],
+        base_point: ProjectivePoint,
+    ) -> Result<Vec<usize>, DLogProofError> {
+        #[cfg(feature = "parallel")]
+        {
+            let chunk_len = self.chunk_size * ENTRY_LEN;
+            let per_chunk = window
+                .par_chunks(chunk_len)
+                .enumerate()
+                .map(|(chunk_index, chunk)| {
+                    let offset = chunk_index * self.chunk_size;
+                    self.verify_chunk(sid, chunk, base_point)
+                        .map(|invalid| invalid.into_iter().map(|index| offset + index).collect())
+                })
+                .collect::<Result<Vec<Vec<usize>>, _>>()?;
+            Ok(per_chunk.concat())
+        }
+        #[cfg(not(feature = "parallel"))]
+        self.verify_chunk(sid, window, base_point)
+    }
+
+    /// Indices of the invalid entries in `chunk`, malformed ones included
+    fn verify_chunk(
+        &self,
+        sid: &str,
+        chunk: &[u8],
+        base_point: ProjectivePoint,
+    ) -> Result<Vec<usize>, DLogProofError> {
+        let mut malformed = Vec::new();
+        let mut decoded = Vec::with_capacity(chunk.len() / ENTRY_LEN);
+        for (index, entry) in chunk.chunks_exact(ENTRY_LEN).enumerate() {
+            let (pid, entry) = entry.split_at(4);
+            let (public, proof) = entry.split_at(33);
+            let pid = u32::from_be_bytes([pid[0], pid[1], pid[2], pid[3]]);
+            match (
+                ProjectivePoint::decode_point(public),
+                DLogProof::from_bytes(proof),
+            ) {
+                (Some(y), Ok(proof)) => decoded.push((index, pid, y, proof)),
+                _ => malformed.push(index),
+            }
+        }
+
+        let items: Vec<BatchItem<ProjectivePoint>> = decoded
+            .iter()
+            .map(|(_, pid, y, proof)| (sid.into(), (*pid).into(), *y, proof))
+            .collect();
+        let mut invalid: Vec<usize> = self
+            .verifier
+            .find_invalid(&items, base_point)?
+            .into_iter()
+            .map(|item| decoded[item].0)
+            .chain(malformed)
+            .collect();
+        invalid.sort_unstable();
+        Ok(invalid)
+    }
+}
+
+impl Default for ArchiveVerifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ArchiveVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArchiveVerifier")
+            .field("verifier", &self.verifier)
+            .field("chunk_size", &self.chunk_size)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+fn take<'a>(rest: &mut &'a [u8], len: usize) -> Result<&'a [u8], DLogProofError> {
+    if rest.len() < len {
+        return Err(DLogProofError::Serialization(
+            "Truncated proof archive".to_string(),
+        ));
+    }
+    let (head, tail) = rest.split_at(len);
+    *rest = tail;
+    Ok(head)
+}
+
+fn take_array<const N: usize>(rest: &mut &[u8]) -> Result<[u8; N], DLogProofError> {
+    let (head, tail) = rest
+        .split_first_chunk::<N>()
+        .ok_or_else(|| DLogProofError::Serialization("Truncated proof archive".to_string()))?;
+    *rest = tail;
+    Ok(*head)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::ProofBundle;
+    use elliptic_curve::Field;
+    use k256::Scalar;
+    use rand_core::OsRng;
+    use std::sync::Mutex;
+
+    fn bundle(sid: &str, count: u32) -> ProofBundle {
+        let g = ProjectivePoint::GENERATOR;
+        let mut bundle = ProofBundle::new(sid);
+        for pid in 1..=count {
+            let x = Scalar::random(&mut OsRng);
+            let y = g * x;
+            bundle.push(pid, y, DLogProof::prove(sid, pid, &x, y, g).unwrap());
+        }
+        bundle
+    }
+
+    fn archive(bundles: &[ProofBundle]) -> Vec<u8> {
+        let mut archive = Vec::new();
+        for bundle in bundles {
+            bundle.write_to(&mut archive).unwrap();
+        }
+        archive
+    }
+
+    #[test]
+    fn archives_are_verified_chunk_by_chunk() {
+        let g = ProjectivePoint::GENERATOR;
+        let mut second = bundle("second", 5);
+        second.entries[3].pid = 9;
+        let mut bytes = archive(&[bundle("first", 4), second, bundle("empty", 0)]);
+        // Corrupt the proof of the second entry of the first bundle
+        let second_proof = 12 + "first".len() + ENTRY_LEN + 4 + 33;
+        bytes[second_proof] = 0x05;
+
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&progress);
+        let summary = ArchiveVerifier::new()
+            .chunk_size(2)
+            .progress(move |p| seen.lock().unwrap().push(*p))
+            .verify_bytes(&bytes, g)
+            .unwrap();
+        assert_eq!(
+            summary,
+            ArchiveSummary {
+                bundles: 3,
+                proofs: 9,
+                invalid: vec![1, 7],
+            }
+        );
+        assert!(!summary.all_valid());
+
+        let progress = progress.lock().unwrap();
+        let last = progress.last().unwrap();
+        assert_eq!(last.bytes_done, last.bytes_total);
+        assert_eq!((last.proofs, last.invalid), (9, 2));
+        assert!(progress
+            .windows(2)
+            .all(|pair| pair[0].bytes_done < pair[1].bytes_done));
+    }
+
+    #[test]
+    fn archive_files_are_mapped() {
+        let g = ProjectivePoint::GENERATOR;
+        let path = std::env::temp_dir().join(format!("archive-{}.bin", std::process::id()));
+        std::fs::write(&path, archive(&[bundle("s", 3), bundle("t", 2)])).unwrap();
+        let summary = ArchiveVerifier::default().verify_file(&path, g);
+        std::fs::remove_file(&path).unwrap();
+        let summary = summary.unwrap();
+        assert_eq!((summary.bundles, summary.proofs), (2, 5));
+        assert!(summary.all_valid());
+
+        assert!(matches!(
+            ArchiveVerifier::new().verify_file(&path, g),
+            Err(DLogProofError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn broken_archives_are_errors() {
+        let g = ProjectivePoint::GENERATOR;
+        let bytes = archive(&[bundle("s", 2)]);
+        let truncated = ArchiveVerifier::new().verify_bytes(&bytes[..bytes.len() - 1], g);
+        assert!(matches!(truncated, Err(DLogProofError::Serialization(_))));
+
+        let mut other_version = bytes.clone();
+        other_version[3] = 2;
+        let result = ArchiveVerifier::new().verify_bytes(&other_version, g);
+        assert!(matches!(result, Err(DLogProofError::Serialization(_))));
+
+        assert_eq!(
+            ArchiveVerifier::new().verify_bytes(&[], g),
+            Ok(ArchiveSummary::default())
+        );
+    }
+}
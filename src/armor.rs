@@ -0,0 +1,110 @@
+use elliptic_curve::group::GroupEncoding;
+use k256::ProjectivePoint;
+use pem::Pem;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::decode_canonical;
+
+/// PEM label of a proof, holding its fixed binary form `t || s`
+pub const PROOF_LABEL: &str = "SCHNORR DLOG PROOF";
+/// PEM label of a public point, holding its compressed SEC1 encoding
+pub const PUBLIC_KEY_LABEL: &str = "SCHNORR DLOG PUBLIC KEY";
+
+/// Returns the contents of the first PEM block with the given label in `input`
+///
+/// Blocks with other labels (certificates, other keys) are skipped, so the input can be a
+/// whole bundle. Line length, CRLF line endings and surrounding text don't matter.
+fn find_block(input: &str, label: &str) -> Result<Vec<u8>, DLogProofError> {
+    let blocks = pem::parse_many(input)
+        .map_err(|e| DLogProofError::Serialization(format!("Invalid PEM: {e}")))?;
+    blocks
+        .into_iter()
+        .find(|block| block.tag() == label)
+        .map(Pem::into_contents)
+        .ok_or_else(|| DLogProofError::Serialization(format!("No PEM block labeled '{label}'")))
+}
+
+/// Encodes a public point as a `SCHNORR DLOG PUBLIC KEY` PEM block
+pub fn public_key_to_pem(point: &ProjectivePoint) -> String {
+    pem::encode(&Pem::new(PUBLIC_KEY_LABEL, point.to_bytes().to_vec()))
+}
+
+/// Decodes the first `SCHNORR DLOG PUBLIC KEY` block in `input`
+pub fn public_key_from_pem(input: &str) -> Result<ProjectivePoint, DLogProofError> {
+    let bytes: [u8; 33] = find_block(input, PUBLIC_KEY_LABEL)?
+        .try_into()
+        .map_err(|_| DLogProofError::InvalidPoint)?;
+    decode_canonical::<ProjectivePoint>(&bytes.into()).ok_or(DLogProofError::InvalidPoint)
+}
+
+impl DLogProof {
+    /// Encodes the proof as a `SCHNORR DLOG PROOF` PEM block
+    pub fn to_pem(&self) -> String {
+        pem::encode(&Pem::new(PROOF_LABEL, self.to_bytes().to_vec()))
+    }
+
+    /// Decodes the first `SCHNORR DLOG PROOF` block in `input`, validating it like `from_bytes`
+    pub fn from_pem(input: &str) -> Result<Self, DLogProofError> {
+        DLogProof::from_bytes(&find_block(input, PROOF_LABEL)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::Field;
+    use k256::Scalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn proofs_and_keys_round_trip() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let proof = DLogProof::prove("sid", 1, &x, g * x, g).unwrap();
+
+        let pem = proof.to_pem();
+        assert!(pem.starts_with("-----BEGIN SCHNORR DLOG PROOF-----"));
+        assert_eq!(DLogProof::from_pem(&pem).unwrap(), proof);
+
+        let key_pem = public_key_to_pem(&(g * x));
+        assert!(key_pem.starts_with("-----BEGIN SCHNORR DLOG PUBLIC KEY-----"));
+        assert_eq!(public_key_from_pem(&key_pem).unwrap(), g * x);
+
+        // The labels keep the two apart
+        assert!(DLogProof::from_pem(&key_pem).is_err());
+        assert!(public_key_from_pem(&pem).is_err());
+    }
+
+    #[test]
+    fn parsing_tolerates_wrapping_and_other_blocks() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let proof = DLogProof::prove("sid", 1, &x, g * x, g).unwrap();
+
+        // Rewrap the base64 body to 20 columns with CRLF line endings
+        let body: String = proof
+            .to_pem()
+            .lines()
+            .filter(|line| !line.starts_with("-----"))
+            .collect();
+        let wrapped: Vec<&str> = body
+            .as_bytes()
+            .chunks(20)
+            .map(|chunk| std::str::from_utf8(chunk).unwrap())
+            .collect();
+        let bundle = format!(
+            "{}\r\n-----BEGIN SCHNORR DLOG PROOF-----\r\n{}\r\n-----END SCHNORR DLOG PROOF-----\r\n",
+            public_key_to_pem(&(g * x)),
+            wrapped.join("\r\n")
+        );
+        assert_eq!(DLogProof::from_pem(&bundle).unwrap(), proof);
+        assert_eq!(public_key_from_pem(&bundle).unwrap(), g * x);
+
+        let error = DLogProof::from_pem(&public_key_to_pem(&g)).unwrap_err();
+        assert_eq!(
+            error,
+            DLogProofError::Serialization("No PEM block labeled 'SCHNORR DLOG PROOF'".into())
+        );
+    }
+}
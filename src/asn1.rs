@@ -0,0 +1,125 @@
+use der::asn1::{OctetStringRef, UintRef};
+use der::{Decode, Encode, Sequence};
+use elliptic_curve::PrimeField;
+use k256::{FieldBytes, Scalar};
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+
+/// ASN.1 structure of a DER-encoded proof (`der` feature)
+///
+/// ```text
+/// DLogProof ::= SEQUENCE {
+///     t  OCTET STRING,  -- commitment, compressed SEC1 (33 bytes)
+///     s  INTEGER        -- response, unsigned
+/// }
+/// ```
+///
+/// DER requires the minimal encoding of `s`, so a padded or negative INTEGER is rejected on
+/// decoding and every proof has exactly one encoding.
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+pub struct Asn1DlogProof<'a> {
+    /// Commitment t in compressed SEC1 format
+    pub t: OctetStringRef<'a>,
+    /// Response s as a big-endian unsigned integer
+    pub s: UintRef<'a>,
+}
+
+fn der_error(e: der::Error) -> DLogProofError {
+    DLogProofError::Serialization(format!("Invalid DER proof: {e}"))
+}
+
+impl DLogProof {
+    /// Encodes the proof in DER as an `Asn1DlogProof` SEQUENCE
+    pub fn to_der(&self) -> Result<Vec<u8>, DLogProofError> {
+        let bytes = self.to_bytes();
+        let (t, s) = bytes.split_at(33);
+        let asn1 = Asn1DlogProof {
+            t: OctetStringRef::new(t).map_err(der_error)?,
+            s: UintRef::new(s).map_err(der_error)?,
+        };
+        asn1.to_der().map_err(der_error)
+    }
+
+    /// Decodes a DER `Asn1DlogProof`, rejecting non-minimal encodings, trailing data, invalid
+    /// points and scalars that aren't below the group order
+    pub fn from_der(bytes: &[u8]) -> Result<Self, DLogProofError> {
+        let asn1 = Asn1DlogProof::from_der(bytes).map_err(der_error)?;
+        let t = asn1.t.as_bytes();
+        if t.len() != 33 {
+            return Err(DLogProofError::InvalidPoint);
+        }
+        let s = asn1.s.as_bytes();
+        let mut repr = FieldBytes::default();
+        let offset = repr
+            .len()
+            .checked_sub(s.len())
+            .ok_or(DLogProofError::InvalidScalar)?;
+        repr[offset..].copy_from_slice(s);
+        let s =
+            Option::<Scalar>::from(Scalar::from_repr(repr)).ok_or(DLogProofError::InvalidScalar)?;
+
+        let mut fixed = [0u8; Self::ENCODED_LEN];
+        fixed[..33].copy_from_slice(t);
+        fixed[33..].copy_from_slice(&s.to_repr());
+        Self::from_bytes(&fixed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::Field;
+    use k256::ProjectivePoint;
+    use rand_core::OsRng;
+
+    const GENERATOR: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+    #[test]
+    fn der_encoding_is_minimal() {
+        // SEQUENCE (38 bytes) { OCTET STRING (33 bytes) G, INTEGER 1 }
+        let proof = DLogProof::new(ProjectivePoint::GENERATOR, Scalar::ONE);
+        let der = proof.to_der().unwrap();
+        assert_eq!(hex::encode(&der), format!("30260421{GENERATOR}020101"));
+        assert_eq!(DLogProof::from_der(&der).unwrap(), proof);
+
+        let x = Scalar::random(&mut OsRng);
+        let g = ProjectivePoint::GENERATOR;
+        let proof = DLogProof::prove("sid", 1, &x, g * x, g).unwrap();
+        assert_eq!(
+            DLogProof::from_der(&proof.to_der().unwrap()).unwrap(),
+            proof
+        );
+    }
+
+    #[test]
+    fn non_canonical_der_is_rejected() {
+        let reject = |der: String| DLogProof::from_der(&hex::decode(der).unwrap()).unwrap_err();
+
+        // INTEGER 1 padded with a zero byte
+        assert!(matches!(
+            reject(format!("30270421{GENERATOR}02020001")),
+            DLogProofError::Serialization(_)
+        ));
+        // Negative INTEGER
+        assert!(matches!(
+            reject(format!("30260421{GENERATOR}020181")),
+            DLogProofError::Serialization(_)
+        ));
+        // Trailing data
+        assert!(matches!(
+            reject(format!("30260421{GENERATOR}02010100")),
+            DLogProofError::Serialization(_)
+        ));
+        // Uncompressed-length point
+        assert_eq!(
+            reject(format!("30070402{}020101", &GENERATOR[..4])),
+            DLogProofError::InvalidPoint
+        );
+        // s = 2^256 - 1 is above the group order
+        assert_eq!(
+            reject(format!("30460421{GENERATOR}022100{}", "ff".repeat(32))),
+            DLogProofError::InvalidScalar
+        );
+    }
+}
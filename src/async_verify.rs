@@ -0,0 +1,266 @@
+//! Asynchronous streaming verification (`async` feature)
+//!
+//! `verify_stream` consumes any `Stream` of proofs and yields a verdict per proof, in input
+//! order, as soon as its batch is verified. Proofs that are already available are gathered into
+//! batches of up to `max_batch` and checked with one multi-scalar multiplication
+//! (`DLogProof::find_invalid`); a slow source gives smaller batches rather than stalling, so
+//! memory stays bounded by `max_batch` however long the stream runs.
+//!
+//! `read_proofs` and `verify_reader` do the same for a byte stream of length-prefixed proofs,
+//! such as a socket or a message queue consumer. Each frame is
+//!
+//! ```text
+//! length (4, big-endian) || pid (4, big-endian) || y (33, compressed SEC1)
+//!     || proof (65, `DLogProof::to_bytes`) || sid (UTF-8, the rest of the frame)
+//! ```
+//!
+//! The readers take `futures_io::AsyncRead`; tokio readers are adapted with
+//! `tokio_util::compat`. Batches are verified on the task polling the stream, so with a
+//! multi-threaded runtime run it where blocking for a batch is acceptable.
+
+use std::convert::Infallible;
+use std::io;
+
+use futures_util::io::{AsyncRead, AsyncReadExt};
+use futures_util::stream::{self, Stream, StreamExt};
+use k256::ProjectivePoint;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+
+/// Longest frame `read_proofs` accepts
+pub const MAX_FRAME_LEN: usize = 64 * 1024;
+
+/// Length of a frame without the sid
+const FRAME_HEADER_LEN: usize = 4 + 33 + DLogProof::ENCODED_LEN;
+
+/// A proof with the statement it is verified against
+#[derive(Debug)]
+pub struct StreamedProof<G: DLogGroup = ProjectivePoint> {
+    pub sid: String,
+    pub pid: u32,
+    pub y: G,
+    pub proof: DLogProof<G>,
+}
+
+/// The outcome of verifying a `StreamedProof`, which is handed back with it
+#[derive(Debug)]
+pub struct Verdict<G: DLogGroup = ProjectivePoint> {
+    pub proof: StreamedProof<G>,
+    /// `Ok(true)` for a valid proof, as returned by `DLogProof::verify`
+    pub result: Result<bool, DLogProofError>,
+}
+
+/// Verifies a stream of proofs in batches of up to `max_batch`, yielding verdicts in order
+pub fn verify_stream<G, S>(proofs: S, max_batch: usize) -> impl Stream<Item = Verdict<G>>
+where
+    G: DLogGroup,
+    S: Stream<Item = StreamedProof<G>>,
+{
+    verify_results(proofs.map(Ok::<_, Infallible>), max_batch).map(|verdict| match verdict {
+        Ok(verdict) => verdict,
+        Err(never) => match never {},
+    })
+}
+
+/// Reads length-prefixed secp256k1 proofs until the end of `reader`
+///
+/// A malformed frame yields an `io::ErrorKind::InvalidData` error wrapping the
+/// `DLogProofError` and reading continues with the next frame. A read error, a frame longer
+/// than `MAX_FRAME_LEN` or a truncated last frame yields an error and ends the stream.
+pub fn read_proofs<R: AsyncRead + Unpin>(
+    reader: R,
+) -> impl Stream<Item = io::Result<StreamedProof>> {
+    stream::unfold(Some(reader), |reader| async move {
+        let mut reader = reader?;
+        match read_frame(&mut reader).await {
+            Ok(None) => None,
+            Ok(Some(frame)) => {
+                let proof =
+                    decode_frame(&frame).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+                Some((proof, Some(reader)))
+            }
+            Err(e) => Some((Err(e), None)),
+        }
+    })
+}
+
+/// Reads and verifies length-prefixed proofs, see `read_proofs` and `verify_stream`
+///
+/// Errors of `read_proofs` are passed through in their place among the verdicts.
+pub fn verify_reader<R: AsyncRead + Unpin>(
+    reader: R,
+    max_batch: usize,
+) -> impl Stream<Item = io::Result<Verdict>> {
+    verify_results(read_proofs(reader), max_batch)
+}
+
+/// Verifies the `Ok` proofs of a stream in batches, passing errors through in order
+fn verify_results<G, E, S>(proofs: S, max_batch: usize) -> impl Stream<Item = Result<Verdict<G>, E>>
+where
+    G: DLogGroup,
+    S: Stream<Item = Result<StreamedProof<G>, E>>,
+{
+    proofs
+        .ready_chunks(max_batch.max(1))
+        .map(|chunk| stream::iter(verify_chunk(chunk)))
+        .flatten()
+}
+
+fn verify_chunk<G: DLogGroup, E>(
+    chunk: Vec<Result<StreamedProof<G>, E>>,
+) -> Vec<Result<Verdict<G>, E>> {
+    let items: Vec<_> = chunk
+        .iter()
+        .filter_map(|proof| proof.as_ref().ok())
+        .map(|proof| {
+            (
+                proof.sid.as_str().into(),
+                proof.pid.into(),
+                proof.y,
+                &proof.proof,
+            )
+        })
+        .collect();
+    // A batch error (e.g. an identity y) can't be pinned on one proof, so each is then
+    // verified alone to attribute it
+    let invalid = DLogProof::find_invalid(&items, G::generator()).ok();
+    drop(items);
+
+    let mut position = 0;
+    chunk
+        .into_iter()
+        .map(|proof| {
+            let proof = proof?;
+            let result = match &invalid {
+                Some(invalid) => Ok(!invalid.contains(&position)),
+                None => proof
+                    .proof
+                    .verify(&proof.sid, proof.pid, proof.y, G::generator()),
+            };
+            position += 1;
+            Ok(Verdict { proof, result })
+        })
+        .collect()
+}
+
+/// Reads the next frame, or `None` at a clean end of the input
+async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<Option<Vec<u8>>> {
+    let mut len = [0u8; 4];
+    let mut filled = 0;
+    while filled < len.len() {
+        match reader.read(&mut len[filled..]).await? {
+            0 if filled == 0 => return Ok(None),
+            0 => return Err(io::ErrorKind::UnexpectedEof.into()),
+            n => filled += n,
+        }
+    }
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame of {len} bytes exceeds {MAX_FRAME_LEN}"),
+        ));
+    }
+    let mut frame = vec![0u8; len];
+    reader.read_exact(&mut frame).await?;
+    Ok(Some(frame))
+}
+
+fn decode_frame(frame: &[u8]) -> Result<StreamedProof, DLogProofError> {
+    let too_short = || {
+        DLogProofError::Serialization(format!(
+            "Frame of {} bytes is shorter than {FRAME_HEADER_LEN}",
+            frame.len()
+        ))
+    };
+    if frame.len() < FRAME_HEADER_LEN {
+        return Err(too_short());
+    }
+    let (pid, rest) = frame.split_first_chunk::<4>().ok_or_else(too_short)?;
+    let (y, rest) = rest.split_at(33);
+    let (proof, sid) = rest.split_at(DLogProof::ENCODED_LEN);
+    Ok(StreamedProof {
+        sid: String::from_utf8(sid.to_vec())
+            .map_err(|_| DLogProofError::Serialization("Frame sid is not UTF-8".to_string()))?,
+        pid: u32::from_be_bytes(*pid),
+        y: ProjectivePoint::decode_point(y).ok_or(DLogProofError::InvalidPoint)?,
+        proof: DLogProof::from_bytes(proof)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::Field;
+    use futures_util::io::Cursor;
+    use futures_util::FutureExt;
+    use k256::Scalar;
+    use rand_core::OsRng;
+
+    fn streamed(pid: u32) -> StreamedProof {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        StreamedProof {
+            sid: "queue".to_string(),
+            pid,
+            y: g * x,
+            proof: DLogProof::prove("queue", pid, &x, g * x, g).unwrap(),
+        }
+    }
+
+    fn frame(proof: &StreamedProof) -> Vec<u8> {
+        let mut payload = proof.pid.to_be_bytes().to_vec();
+        payload.extend_from_slice(&proof.y.encode_point());
+        payload.extend_from_slice(&proof.proof.to_bytes());
+        payload.extend_from_slice(proof.sid.as_bytes());
+        let mut frame = (payload.len() as u32).to_be_bytes().to_vec();
+        frame.extend_from_slice(&payload);
+        frame
+    }
+
+    #[test]
+    fn verdicts_follow_input_order_across_batches() {
+        let mut proofs: Vec<_> = (1..=10).map(streamed).collect();
+        proofs[3].pid = 99;
+        proofs[7].y = ProjectivePoint::IDENTITY;
+
+        let verdicts: Vec<_> = verify_stream(stream::iter(proofs), 4)
+            .collect::<Vec<_>>()
+            .now_or_never()
+            .unwrap();
+        let results: Vec<_> = verdicts.iter().map(|v| v.result.clone()).collect();
+        let mut expected = vec![Ok(true); 10];
+        expected[3] = Ok(false);
+        expected[7] = Err(DLogProofError::IdentityPoint);
+        assert_eq!(results, expected);
+        assert_eq!(verdicts[9].proof.pid, 10);
+    }
+
+    #[test]
+    fn readers_verify_frames_and_skip_malformed_ones() {
+        let mut bytes = frame(&streamed(1));
+        bytes.extend_from_slice(&[0, 0, 0, 3, 1, 2, 3]);
+        bytes.extend(frame(&streamed(2)));
+        // Truncated last frame
+        bytes.extend_from_slice(&[0, 0, 1]);
+
+        let verdicts: Vec<_> = verify_reader(Cursor::new(bytes), 16)
+            .collect::<Vec<_>>()
+            .now_or_never()
+            .unwrap();
+        assert_eq!(verdicts.len(), 4);
+        assert_eq!(verdicts[0].as_ref().unwrap().result, Ok(true));
+        assert_eq!(
+            verdicts[1].as_ref().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert_eq!(verdicts[2].as_ref().unwrap().proof.pid, 2);
+        assert_eq!(verdicts[2].as_ref().unwrap().result, Ok(true));
+        assert_eq!(
+            verdicts[3].as_ref().unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use blake2::Blake2b512;
+use elliptic_curve::PrimeField;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+use sha3::Sha3_256;
+
+use crate::dlog_proof::{
+    ChallengeHash, ChallengeMode, ChallengeParams, DLogProof, TranscriptVersion,
+};
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::participant::ParticipantId;
+use crate::session::SessionId;
+
+/// One labelled input of a Fiat-Shamir transcript
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TranscriptEntry {
+    /// `dom` (domain separation tag), `sid`, `pid`, `pts` (number of points, transcript v2
+    /// only), `pt` (a point: G, y and t in this order) or `aad`
+    pub label: String,
+    /// The bytes hashed for the input, as hex; in transcript v2 they start with the label and
+    /// the length prefix, and the `aad` entry always starts with its length
+    pub data: String,
+}
+
+/// Record of exactly what went into a challenge, for audits
+///
+/// Made by `Prover::prove_with_transcript` and `Verifier::verify_with_transcript`. Hashing
+/// the concatenated entries with `hash` gives the challenge, which `replay` checks along with
+/// the proof. Only the standard challenge mode without an HMAC key is recorded: a keyed
+/// challenge can't be checked without the key, and the other modes hash differently.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TranscriptLog {
+    /// Name of the challenge hash (see `ChallengeHash::name`)
+    pub hash: String,
+    /// `1`, `2` or `3` (see `TranscriptVersion`)
+    pub transcript: u8,
+    pub entries: Vec<TranscriptEntry>,
+    /// The challenge c, as hex
+    pub challenge: String,
+}
+
+/// Collects the chunks a transcript is absorbed in
+#[derive(Default)]
+struct Recorder(Vec<Vec<u8>>);
+
+impl hmac::digest::Update for Recorder {
+    fn update(&mut self, data: &[u8]) {
+        self.0.push(data.to_vec());
+    }
+}
+
+impl TranscriptLog {
+    /// Records the transcript of the challenge for `points` under `params`
+    pub(crate) fn record<G: DLogGroup>(
+        params: &ChallengeParams,
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        points: &[G],
+    ) -> Result<Self, DLogProofError> {
+        if params.mode != ChallengeMode::Standard || params.hmac_key.is_some() {
+            return Err(DLogProofError::UnsupportedChallengeMode);
+        }
+        let mut recorder = Recorder::default();
+        let domain = DLogProof::<G>::domain(params);
+        DLogProof::<G>::absorb_transcript(&mut recorder, params.version, &domain, sid, pid, points);
+        DLogProof::<G>::absorb_aad(&mut recorder, params);
+        let (c, _) = DLogProof::challenge(params, sid, pid, points)?;
+
+        // Every input is one chunk in v1 and three (label, length, bytes) from v2 on; the
+        // additional authenticated data is two (length, bytes) after them
+        let (fields, width) = if params.version.framed() {
+            (4 + points.len(), 3)
+        } else {
+            (3 + points.len(), 1)
+        };
+        let mut chunks = recorder.0.into_iter();
+        let mut entries = Vec::with_capacity(fields + 1);
+        for index in 0..fields {
+            let parts: Vec<Vec<u8>> = chunks.by_ref().take(width).collect();
+            let label = if params.version.framed() {
+                parts
+                    .first()
+                    .and_then(|label| core::str::from_utf8(label).ok())
+                    .ok_or(DLogProofError::ChallengeDerivation)?
+            } else {
+                ["dom", "sid", "pid"].get(index).unwrap_or(&"pt")
+            };
+            entries.push(TranscriptEntry {
+                label: label.to_string(),
+                data: hex::encode(parts.concat()),
+            });
+        }
+        let aad: Vec<Vec<u8>> = chunks.collect();
+        if !aad.is_empty() {
+            entries.push(TranscriptEntry {
+                label: "aad".to_string(),
+                data: hex::encode(aad.concat()),
+            });
+        }
+
+        Ok(TranscriptLog {
+            hash: params.hash.name().to_string(),
+            transcript: match params.version {
+                TranscriptVersion::V1 => 1,
+                TranscriptVersion::V2 => 2,
+                TranscriptVersion::V3 => 3,
+            },
+            entries,
+            challenge: hex::encode(c.to_repr()),
+        })
+    }
+
+    /// Recomputes the challenge from the entries and checks it against the recorded one and
+    /// the proof
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If hashing the entries gives the recorded challenge, the `pt` entries
+    ///   are G, y and the proof's t, and the proof verifies with that challenge
+    /// * `Ok(false)` - If any of these doesn't hold, e.g. for an edited entry
+    /// * `Err(DLogProofError)` - `Serialization` for an unknown hash or version or malformed
+    ///   hex, or `IdentityPoint` for a degenerate statement
+    pub fn replay<G: DLogGroup>(
+        &self,
+        proof: &DLogProof<G>,
+        y: G,
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        DLogProof::check_statement(y, base_point)?;
+        let hash = ChallengeHash::from_name(&self.hash)
+            .ok_or_else(|| DLogProofError::Serialization(format!("Unknown hash {}", self.hash)))?;
+        let version = match self.transcript {
+            1 => TranscriptVersion::V1,
+            2 => TranscriptVersion::V2,
+            3 => TranscriptVersion::V3,
+            version => {
+                return Err(DLogProofError::Serialization(format!(
+                    "Unknown transcript version {version}"
+                )))
+            }
+        };
+
+        let mut transcript = Vec::new();
+        let mut points = Vec::new();
+        for entry in &self.entries {
+            let data = decode_hex(&entry.data)?;
+            transcript.extend_from_slice(&data);
+            if entry.label == "pt" {
+                points.push(data);
+            }
+        }
+        let c = G::challenge_from_hash(&version.challenge_hash(hash.output_len(), |counter| {
+            digest(hash, &[&transcript[..], counter].concat())
+        }))?;
+
+        let expected: Vec<Vec<u8>> = [base_point, y, proof.t]
+            .iter()
+            .map(|point| point_entry(version, point))
+            .collect();
+        let challenge_matches = decode_hex(&self.challenge)? == c.to_repr().as_ref();
+        let equation = G::lincomb(&[(base_point, proof.s), (y, -c)]).ct_eq(&proof.t);
+        Ok(challenge_matches && points == expected && bool::from(equation))
+    }
+}
+
+/// The bytes `DLogProof::absorb_transcript` hashes for a point
+fn point_entry<G: DLogGroup>(version: TranscriptVersion, point: &G) -> Vec<u8> {
+    let encoded = point.to_bytes();
+    let encoded = encoded.as_ref();
+    if !version.framed() {
+        return encoded.to_vec();
+    }
+    let mut entry = b"pt".to_vec();
+    entry.extend_from_slice(&(encoded.len() as u64).to_be_bytes());
+    entry.extend_from_slice(encoded);
+    entry
+}
+
+pub(crate) fn digest(hash: ChallengeHash, data: &[u8]) -> Vec<u8> {
+    match hash {
+        ChallengeHash::Sha256 => Sha256::digest(data).to_vec(),
+        ChallengeHash::Sha512 => Sha512::digest(data).to_vec(),
+        ChallengeHash::Sha3_256 => Sha3_256::digest(data).to_vec(),
+        ChallengeHash::Blake2b512 => Blake2b512::digest(data).to_vec(),
+    }
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>, DLogProofError> {
+    hex::decode(value).map_err(|e| DLogProofError::Serialization(format!("Invalid hex: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prover::Prover;
+    use crate::verifier::Verifier;
+    use elliptic_curve::Field;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_core::OsRng;
+
+    #[test]
+    fn logs_replay_against_their_proof() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let (proof, log) = Prover::new()
+            .prove_with_transcript("sid", 7, &x, y, g)
+            .unwrap();
+        let labels: Vec<&str> = log.entries.iter().map(|e| e.label.as_str()).collect();
+        assert_eq!(labels, ["dom", "sid", "pid", "pt", "pt", "pt"]);
+        assert_eq!(log.entries[1].data, hex::encode("sid"));
+        assert_eq!(log.entries[2].data, hex::encode(7u32.to_le_bytes()));
+        assert!(log.replay(&proof, y, g).unwrap());
+
+        // The verifier records the same transcript
+        let (valid, verified) = Verifier::new()
+            .verify_with_transcript(&proof, "sid", 7, y, g)
+            .unwrap();
+        assert!(valid);
+        assert_eq!(verified, log);
+
+        let json = serde_json::to_string(&log).unwrap();
+        let log: TranscriptLog = serde_json::from_str(&json).unwrap();
+        assert!(log.replay(&proof, y, g).unwrap());
+
+        let mut edited = log.clone();
+        edited.entries[1].data = hex::encode("other");
+        assert!(!edited.replay(&proof, y, g).unwrap());
+        let other = DLogProof::prove("sid", 7, &x, y, g).unwrap();
+        assert!(!log.replay(&other, y, g).unwrap());
+        assert!(!log.replay(&proof, y.double(), g).unwrap());
+    }
+
+    #[test]
+    fn v2_transcripts_keep_their_field_labels() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let prover = Prover::new()
+            .hash(ChallengeHash::Blake2b512)
+            .transcript_version(TranscriptVersion::V2)
+            .aad(b"context");
+        let (proof, log) = prover.prove_with_transcript("sid", 1, &x, y, g).unwrap();
+        let aad = [&7u32.to_be_bytes()[..], b"context"].concat();
+        assert_eq!(log.entries[7].data, hex::encode(aad));
+        let labels: Vec<&str> = log.entries.iter().map(|e| e.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            ["dom", "sid", "pid", "pts", "pt", "pt", "pt", "aad"]
+        );
+        assert_eq!(log.hash, "BLAKE2b-512");
+        assert!(log.replay(&proof, y, g).unwrap());
+
+        // Same labels in v3, whose widened SHA-256 challenge replays too
+        let prover = Prover::new().transcript_version(TranscriptVersion::V3);
+        let (proof, log) = prover.prove_with_transcript("sid", 1, &x, y, g).unwrap();
+        assert_eq!(log.transcript, 3);
+        assert_eq!(log.entries[0].label, "dom");
+        assert!(log.replay(&proof, y, g).unwrap());
+
+        let bip340 = Prover::new().challenge_mode(ChallengeMode::Bip340Tagged);
+        assert_eq!(
+            bip340
+                .prove_with_transcript("sid", 1, &x, y, g)
+                .unwrap_err(),
+            DLogProofError::UnsupportedChallengeMode
+        );
+    }
+}
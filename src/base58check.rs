@@ -0,0 +1,67 @@
+use elliptic_curve::group::GroupEncoding;
+use k256::ProjectivePoint;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::decode_canonical;
+
+/// Default version byte for public keys
+pub const PUBLIC_KEY_VERSION: u8 = 0x1c;
+/// Default version byte for proofs
+pub const PROOF_VERSION: u8 = 0x2a;
+
+/// Encodes `version || payload || checksum` with the Bitcoin base58 alphabet,
+/// where checksum is the first 4 bytes of SHA256(SHA256(version || payload))
+fn encode(version: u8, payload: &[u8]) -> String {
+    bs58::encode(payload)
+        .with_check_version(version)
+        .into_string()
+}
+
+/// Decodes a Base58Check string, verifying the checksum and the version byte
+fn decode(encoded: &str, version: u8) -> Result<Vec<u8>, DLogProofError> {
+    let mut bytes = bs58::decode(encoded)
+        .with_check(Some(version))
+        .into_vec()
+        .map_err(|e| {
+            DLogProofError::Serialization(match e {
+                bs58::decode::Error::InvalidChecksum { .. } => {
+                    "Invalid Base58Check checksum".to_string()
+                }
+                bs58::decode::Error::InvalidVersion { ver, expected_ver } => format!(
+                    "Unexpected Base58Check version byte 0x{ver:02x}, expected 0x{expected_ver:02x}"
+                ),
+                _ => "Invalid base58 encoding".to_string(),
+            })
+        })?;
+    // Strip the version byte, the checksum has already been removed
+    bytes.remove(0);
+    Ok(bytes)
+}
+
+/// Encodes a public point (compressed SEC1) as Base58Check with the given version byte
+pub fn public_key_to_base58check(point: &ProjectivePoint, version: u8) -> String {
+    encode(version, &point.to_bytes())
+}
+
+/// Decodes a public point from Base58Check, requiring the given version byte
+pub fn public_key_from_base58check(
+    encoded: &str,
+    version: u8,
+) -> Result<ProjectivePoint, DLogProofError> {
+    let bytes = decode(encoded, version)?;
+    let bytes: [u8; 33] = bytes.try_into().map_err(|_| DLogProofError::InvalidPoint)?;
+    decode_canonical::<ProjectivePoint>(&bytes.into()).ok_or(DLogProofError::InvalidPoint)
+}
+
+impl DLogProof {
+    /// Encodes the proof's fixed binary form (`t || s`) as Base58Check with the given version byte
+    pub fn to_base58check(&self, version: u8) -> String {
+        encode(version, &self.to_bytes())
+    }
+
+    /// Decodes a proof from Base58Check, requiring the given version byte
+    pub fn from_base58check(encoded: &str, version: u8) -> Result<Self, DLogProofError> {
+        DLogProof::from_bytes(&decode(encoded, version)?)
+    }
+}
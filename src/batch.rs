@@ -0,0 +1,429 @@
+use elliptic_curve::Field;
+use rand_core::OsRng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::dlog_proof::{ChallengeParams, DLogProof};
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::participant::ParticipantId;
+use crate::session::SessionId;
+use crate::verifier::Timing;
+
+/// One statement of a batch: session ID, participant ID, public key y and its proof
+pub type BatchItem<'a, G> = (SessionId<'a>, ParticipantId<'a>, G, &'a DLogProof<G>);
+
+/// Engine for the multi-scalar multiplications of batch verification
+///
+/// A batch of n proofs is checked with one multi-scalar multiplication of 2n + 1 terms (see
+/// `DLogProof::verify_batch`), which is nearly all of its cost. The crate computes it on the
+/// CPU, with the `Timing` of the verifier; `Verifier::verify_batch_on` and
+/// `Verifier::find_invalid_on` hand it to another implementation instead, e.g. a GPU MSM for
+/// re-verifying archives of millions of proofs in a few calls. The terms are public (the
+/// proofs, their challenges and weights drawn after the proofs were made), so a backend may
+/// run in variable time. It must return the exact sum: a backend that fails, e.g. because
+/// the device was lost, falls back to the CPU (`DLogGroup::lincomb_vartime`) instead of
+/// returning a guess.
+pub trait MsmBackend<G: DLogGroup> {
+    /// Returns the sum of k_i * P_i over the terms (P_i, k_i)
+    fn msm(&self, terms: &[(G, G::Scalar)]) -> G;
+}
+
+impl<G: DLogGroup> MsmBackend<G> for Timing {
+    fn msm(&self, terms: &[(G, G::Scalar)]) -> G {
+        self.lincomb(terms)
+    }
+}
+
+impl<G: DLogGroup> DLogProof<G> {
+    /// Verifies many proofs over the same base point at once
+    ///
+    /// The equations s_i * G = t_i + c_i * y_i are combined with random weights w_i into the
+    /// single check (sum w_i * s_i) * G - sum w_i * t_i - sum (w_i * c_i) * y_i = 0, evaluated
+    /// as one multi-scalar multiplication. A batch containing an invalid proof passes only
+    /// with negligible probability, since the weights are unknown to the prover.
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If every proof is valid (also for an empty batch)
+    /// * `Ok(false)` - If at least one proof is invalid; see `find_invalid` for which
+    /// * `Err(DLogProofError)` - If a challenge can't be derived, or `IdentityPoint` if the base
+    ///   point or some y is the identity
+    pub fn verify_batch(items: &[BatchItem<G>], base_point: G) -> Result<bool, DLogProofError> {
+        Self::verify_batch_with(&ChallengeParams::default(), items, base_point)
+    }
+
+    /// Returns the indices of the invalid proofs in a batch, in order
+    ///
+    /// Runs the combined check first, so an all-valid batch costs the same as `verify_batch`.
+    /// When it fails, the batch is bisected: each half gets its own combined check and only
+    /// failing halves are split further, down to single proofs. k invalid proofs among n
+    /// are found with about 2k log2(n / k) combined checks instead of n verifications.
+    pub fn find_invalid(
+        items: &[BatchItem<G>],
+        base_point: G,
+    ) -> Result<Vec<usize>, DLogProofError> {
+        Self::find_invalid_with(&ChallengeParams::default(), items, base_point)
+    }
+
+    /// Batch-verifies under the given transcript parameters (see `Verifier::verify_batch`)
+    pub(crate) fn verify_batch_with(
+        params: &ChallengeParams,
+        items: &[BatchItem<G>],
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        Self::verify_batch_on(params, &params.timing, items, base_point)
+    }
+
+    /// Finds the invalid proofs under the given transcript parameters
+    pub(crate) fn find_invalid_with(
+        params: &ChallengeParams,
+        items: &[BatchItem<G>],
+        base_point: G,
+    ) -> Result<Vec<usize>, DLogProofError> {
+        Self::find_invalid_on(params, &params.timing, items, base_point)
+    }
+
+    /// Batch-verifies with the multi-scalar multiplications of `backend`
+    pub(crate) fn verify_batch_on(
+        params: &ChallengeParams,
+        backend: &(impl MsmBackend<G> + ?Sized),
+        items: &[BatchItem<G>],
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        let equations = Self::equations(params, items, base_point)?;
+        Ok(Self::equations_hold(&equations, base_point, backend))
+    }
+
+    /// Finds the invalid proofs with the multi-scalar multiplications of `backend`
+    pub(crate) fn find_invalid_on(
+        params: &ChallengeParams,
+        backend: &(impl MsmBackend<G> + ?Sized),
+        items: &[BatchItem<G>],
+        base_point: G,
+    ) -> Result<Vec<usize>, DLogProofError> {
+        let equations = Self::equations(params, items, base_point)?;
+        let mut invalid = Vec::new();
+        Self::bisect(&equations, 0, base_point, backend, &mut invalid);
+        Ok(invalid)
+    }
+
+    /// The verification equation of every proof, with the challenges derived once for all
+    /// the combined checks; `None` for a proof rejected before its equation (an odd BIP-340 t)
+    fn equations(
+        params: &ChallengeParams,
+        items: &[BatchItem<G>],
+        base_point: G,
+    ) -> Result<Vec<Option<Equation<G>>>, DLogProofError> {
+        items
+            .iter()
+            .map(|&(sid, pid, y, proof)| {
+                Self::check_statement(y, base_point)?;
+                let (c, _) = Self::challenge(params, sid, pid, &[base_point, y, proof.t])?;
+                let (y, t_acceptable) = proof.equation_key(params, y)?;
+                Ok(t_acceptable.then_some(Equation {
+                    t: proof.t,
+                    y,
+                    c,
+                    s: proof.s,
+                }))
+            })
+            .collect()
+    }
+
+    /// Whether all `equations` hold, checked as one random linear combination (see
+    /// `verify_batch`)
+    fn equations_hold(
+        equations: &[Option<Equation<G>>],
+        base_point: G,
+        backend: &(impl MsmBackend<G> + ?Sized),
+    ) -> bool {
+        let mut terms = Vec::with_capacity(2 * equations.len() + 1);
+        let mut s_sum = G::Scalar::ZERO;
+        for equation in equations {
+            let Some(Equation { t, y, c, s }) = equation else {
+                return false;
+            };
+            let w = G::Scalar::random(&mut OsRng);
+            s_sum += w * s;
+            terms.push((*t, -w));
+            terms.push((*y, -(w * c)));
+        }
+        terms.push((base_point, s_sum));
+
+        bool::from(backend.msm(&terms).is_identity())
+    }
+
+    /// Appends the indices (shifted by `offset`) of the equations that don't hold
+    fn bisect(
+        equations: &[Option<Equation<G>>],
+        offset: usize,
+        base_point: G,
+        backend: &(impl MsmBackend<G> + ?Sized),
+        invalid: &mut Vec<usize>,
+    ) {
+        if equations.is_empty() || Self::equations_hold(equations, base_point, backend) {
+            return;
+        }
+        if equations.len() == 1 {
+            invalid.push(offset);
+            return;
+        }
+        let (left, right) = equations.split_at(equations.len() / 2);
+        Self::bisect(left, offset, base_point, backend, invalid);
+        Self::bisect(right, offset + left.len(), base_point, backend, invalid);
+    }
+}
+
+/// Verification equation s * G = t + c * y of one proof in a batch
+struct Equation<G: DLogGroup> {
+    t: G,
+    y: G,
+    c: G::Scalar,
+    s: G::Scalar,
+}
+
+/// Number of proofs per chunk that `verify_batch_parallel` callers can start from
+///
+/// Large enough for the multi-scalar multiplication of a chunk to amortize well, small enough
+/// to spread a few thousand proofs over all cores.
+#[cfg(feature = "parallel")]
+pub const DEFAULT_CHUNK_SIZE: usize = 64;
+
+#[cfg(feature = "parallel")]
+impl<G: DLogGroup + Send + Sync> DLogProof<G> {
+    /// Verifies a batch like `verify_batch`, splitting it across threads (`parallel` feature)
+    ///
+    /// The batch is cut into chunks of `chunk_size` proofs (at least one), each chunk is
+    /// checked with its own random linear combination on the rayon thread pool, and the batch
+    /// passes if every chunk does. Smaller chunks balance the load better, larger ones benefit
+    /// more from the multi-scalar multiplication; `DEFAULT_CHUNK_SIZE` is a reasonable start.
+    pub fn verify_batch_parallel(
+        items: &[BatchItem<G>],
+        base_point: G,
+        chunk_size: usize,
+    ) -> Result<bool, DLogProofError> {
+        Self::verify_batch_parallel_with(&ChallengeParams::default(), items, base_point, chunk_size)
+    }
+
+    /// Returns the indices of the invalid proofs like `find_invalid`, splitting the work across
+    /// threads (`parallel` feature)
+    pub fn find_invalid_parallel(
+        items: &[BatchItem<G>],
+        base_point: G,
+        chunk_size: usize,
+    ) -> Result<Vec<usize>, DLogProofError> {
+        Self::find_invalid_parallel_with(&ChallengeParams::default(), items, base_point, chunk_size)
+    }
+
+    /// Parallel batch verification under the given transcript parameters
+    pub(crate) fn verify_batch_parallel_with(
+        params: &ChallengeParams,
+        items: &[BatchItem<G>],
+        base_point: G,
+        chunk_size: usize,
+    ) -> Result<bool, DLogProofError> {
+        items
+            .par_chunks(chunk_size.max(1))
+            .map(|chunk| Self::verify_batch_with(params, chunk, base_point))
+            .try_reduce(|| true, |a, b| Ok(a && b))
+    }
+
+    /// Parallel search for invalid proofs under the given transcript parameters
+    ///
+    /// Only the chunks failing their combined check are bisected.
+    pub(crate) fn find_invalid_parallel_with(
+        params: &ChallengeParams,
+        items: &[BatchItem<G>],
+        base_point: G,
+        chunk_size: usize,
+    ) -> Result<Vec<usize>, DLogProofError> {
+        let chunk_size = chunk_size.max(1);
+        let per_chunk = items
+            .par_chunks(chunk_size)
+            .enumerate()
+            .map(|(chunk_index, chunk)| {
+                let offset = chunk_index * chunk_size;
+                Self::find_invalid_with(params, chunk, base_point)
+                    .map(|invalid| invalid.into_iter().map(|index| offset + index).collect())
+            })
+            .collect::<Result<Vec<Vec<usize>>, _>>()?;
+        Ok(per_chunk.concat())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::{ProjectivePoint, Scalar};
+
+    fn proofs(count: u32) -> Vec<(String, u32, ProjectivePoint, DLogProof)> {
+        let g = ProjectivePoint::GENERATOR;
+        (0..count)
+            .map(|pid| {
+                let x = Scalar::random(&mut OsRng);
+                let sid = format!("session-{pid}");
+                let proof = DLogProof::prove(&sid, pid, &x, g * x, g).unwrap();
+                (sid, pid, g * x, proof)
+            })
+            .collect()
+    }
+
+    fn items(
+        proofs: &[(String, u32, ProjectivePoint, DLogProof)],
+    ) -> Vec<BatchItem<'_, ProjectivePoint>> {
+        proofs
+            .iter()
+            .map(|(sid, pid, y, proof)| (sid.into(), (*pid).into(), *y, proof))
+            .collect()
+    }
+
+    #[test]
+    fn valid_batches_pass() {
+        let g = ProjectivePoint::GENERATOR;
+        let proofs = proofs(16);
+        assert!(DLogProof::verify_batch(&items(&proofs), g).unwrap());
+        assert!(DLogProof::find_invalid(&items(&proofs), g)
+            .unwrap()
+            .is_empty());
+        assert!(DLogProof::verify_batch(&[], g).unwrap());
+    }
+
+    #[test]
+    fn binary_ids_are_batched() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let (session_hash, uuid) = ([0xff; 32], [0x5a; 16]);
+        let proofs = [
+            DLogProof::prove(&session_hash, 1, &x, g * x, g).unwrap(),
+            DLogProof::prove("sid", &uuid, &x, g * x, g).unwrap(),
+        ];
+        let mut items = [
+            (SessionId::from(&session_hash), 1.into(), g * x, &proofs[0]),
+            ("sid".into(), ParticipantId::from(&uuid), g * x, &proofs[1]),
+        ];
+        assert!(DLogProof::verify_batch(&items, g).unwrap());
+        items[0].0 = SessionId::new(&session_hash[1..]);
+        items[1].1 = ParticipantId::new(&uuid[1..]);
+        assert_eq!(DLogProof::find_invalid(&items, g).unwrap(), vec![0, 1]);
+    }
+
+    #[test]
+    fn invalid_proofs_are_identified() {
+        let g = ProjectivePoint::GENERATOR;
+        let mut proofs = proofs(16);
+        proofs[3].1 = 99;
+        proofs[11].2 = g;
+        assert!(!DLogProof::verify_batch(&items(&proofs), g).unwrap());
+        assert_eq!(
+            DLogProof::find_invalid(&items(&proofs), g).unwrap(),
+            vec![3, 11]
+        );
+    }
+
+    #[test]
+    fn bisection_finds_invalid_proofs_anywhere() {
+        let g = ProjectivePoint::GENERATOR;
+        let mut proofs = proofs(37);
+        let expected = [0, 17, 18, 36];
+        for &index in &expected {
+            proofs[index].1 += 100;
+        }
+        assert_eq!(
+            DLogProof::find_invalid(&items(&proofs), g).unwrap(),
+            expected
+        );
+
+        // The complement: the invalid proofs restored, every other one broken
+        for (index, proof) in proofs.iter_mut().enumerate() {
+            if expected.contains(&index) {
+                proof.1 -= 100;
+            } else {
+                proof.1 += 100;
+            }
+        }
+        assert_eq!(
+            DLogProof::find_invalid(&items(&proofs), g).unwrap(),
+            (0..37)
+                .filter(|index| !expected.contains(index))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn backends_compute_the_combined_checks() {
+        use crate::verifier::Verifier;
+        use core::cell::Cell;
+
+        /// Counts its calls, and computes the sums or answers the identity
+        struct Backend {
+            calls: Cell<usize>,
+            honest: bool,
+        }
+
+        impl MsmBackend<ProjectivePoint> for Backend {
+            fn msm(&self, terms: &[(ProjectivePoint, Scalar)]) -> ProjectivePoint {
+                self.calls.set(self.calls.get() + 1);
+                if self.honest {
+                    ProjectivePoint::lincomb_vartime(terms)
+                } else {
+                    ProjectivePoint::IDENTITY
+                }
+            }
+        }
+
+        let g = ProjectivePoint::GENERATOR;
+        let mut proofs = proofs(16);
+        proofs[9].1 = 99;
+        let verifier = Verifier::new();
+        let backend = Backend {
+            calls: Cell::new(0),
+            honest: true,
+        };
+        assert!(!verifier
+            .verify_batch_on(&backend, &items(&proofs), g)
+            .unwrap());
+        assert_eq!(backend.calls.get(), 1);
+        assert_eq!(
+            verifier
+                .find_invalid_on(&backend, &items(&proofs), g)
+                .unwrap(),
+            verifier.find_invalid(&items(&proofs), g).unwrap()
+        );
+        assert!(verifier
+            .verify_batch_on(&backend, &items(&proofs[..9]), g)
+            .unwrap());
+
+        // Only the backend decides
+        let lying = Backend {
+            calls: Cell::new(0),
+            honest: false,
+        };
+        assert!(verifier
+            .verify_batch_on(&lying, &items(&proofs), g)
+            .unwrap());
+        assert!(verifier
+            .find_invalid_on(&lying as &dyn MsmBackend<_>, &items(&proofs), g)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_batches_match_sequential_ones() {
+        let g = ProjectivePoint::GENERATOR;
+        let mut proofs = proofs(40);
+        for chunk_size in [0, 1, 7, DEFAULT_CHUNK_SIZE] {
+            assert!(DLogProof::verify_batch_parallel(&items(&proofs), g, chunk_size).unwrap());
+        }
+
+        proofs[5].1 = 99;
+        proofs[38].2 = g;
+        for chunk_size in [1, 7, DEFAULT_CHUNK_SIZE] {
+            assert!(!DLogProof::verify_batch_parallel(&items(&proofs), g, chunk_size).unwrap());
+            assert_eq!(
+                DLogProof::find_invalid_parallel(&items(&proofs), g, chunk_size).unwrap(),
+                vec![5, 38]
+            );
+        }
+    }
+}
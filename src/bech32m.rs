@@ -0,0 +1,111 @@
+use bech32::primitives::decode::CheckedHrpstring;
+use bech32::{Bech32m, Hrp};
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+
+/// Default human-readable part for proofs
+pub const PROOF_HRP: &str = "dlogproof";
+
+fn parse_hrp(hrp: &str) -> Result<Hrp, DLogProofError> {
+    Hrp::parse(hrp)
+        .map_err(|e| DLogProofError::Serialization(format!("Invalid bech32m prefix '{hrp}': {e}")))
+}
+
+impl DLogProof {
+    /// Encodes the proof's fixed binary form (`t || s`) as a bech32m string with the given
+    /// human-readable part, e.g. `dlogproof1...` with `PROOF_HRP`
+    ///
+    /// The string is lowercase, so it can be uppercased for alphanumeric QR codes and still
+    /// decode. The HRP must be 1 to 83 printable ASCII characters.
+    pub fn to_bech32(&self, hrp: &str) -> Result<String, DLogProofError> {
+        bech32::encode::<Bech32m>(parse_hrp(hrp)?, &self.to_bytes())
+            .map_err(|e| DLogProofError::Serialization(format!("bech32m encoding: {e}")))
+    }
+
+    /// Decodes a bech32m proof, requiring the given human-readable part
+    ///
+    /// The HRP is compared case-insensitively. Strings with a bech32 (not bech32m) checksum,
+    /// mixed case or a different HRP are rejected with an explanatory `Serialization` error;
+    /// a payload that isn't a valid proof fails like `DLogProof::from_bytes`.
+    pub fn from_bech32(encoded: &str, hrp: &str) -> Result<Self, DLogProofError> {
+        let expected = parse_hrp(hrp)?;
+        let checked = CheckedHrpstring::new::<Bech32m>(encoded)
+            .map_err(|e| DLogProofError::Serialization(format!("Invalid bech32m string: {e}")))?;
+        if checked.hrp() != expected {
+            return Err(DLogProofError::Serialization(format!(
+                "Unexpected bech32m prefix '{}', expected '{}'",
+                checked.hrp(),
+                expected
+            )));
+        }
+        let bytes: Vec<u8> = checked.byte_iter().collect();
+        DLogProof::from_bytes(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::Field;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_core::OsRng;
+
+    fn proof() -> DLogProof {
+        let x = Scalar::random(&mut OsRng);
+        let g = ProjectivePoint::GENERATOR;
+        DLogProof::prove("sid", 1, &x, g * x, g).unwrap()
+    }
+
+    fn message(error: DLogProofError) -> String {
+        match error {
+            DLogProofError::Serialization(message) => message,
+            other => panic!("unexpected error {other:?}"),
+        }
+    }
+
+    #[test]
+    fn round_trips_in_either_case() {
+        let proof = proof();
+        let encoded = proof.to_bech32(PROOF_HRP).unwrap();
+        assert!(encoded.starts_with("dlogproof1"));
+        assert_eq!(DLogProof::from_bech32(&encoded, PROOF_HRP).unwrap(), proof);
+        assert_eq!(
+            DLogProof::from_bech32(&encoded.to_uppercase(), PROOF_HRP).unwrap(),
+            proof
+        );
+    }
+
+    #[test]
+    fn decode_errors_explain_the_problem() {
+        let proof = proof();
+        let encoded = proof.to_bech32(PROOF_HRP).unwrap();
+
+        let error = DLogProof::from_bech32(&encoded, "other").unwrap_err();
+        assert_eq!(
+            message(error),
+            "Unexpected bech32m prefix 'dlogproof', expected 'other'"
+        );
+
+        // A single substituted character breaks the checksum
+        let mut typo = encoded.clone().into_bytes();
+        let last = typo.len() - 1;
+        typo[last] = if typo[last] == b'q' { b'p' } else { b'q' };
+        let error = DLogProof::from_bech32(&String::from_utf8(typo).unwrap(), PROOF_HRP);
+        assert!(message(error.unwrap_err()).contains("checksum"));
+
+        // Same payload with a bech32 (not bech32m) checksum
+        let hrp = Hrp::parse(PROOF_HRP).unwrap();
+        let bech32 = bech32::encode::<bech32::Bech32>(hrp, &proof.to_bytes()).unwrap();
+        assert!(DLogProof::from_bech32(&bech32, PROOF_HRP).is_err());
+
+        assert!(message(proof.to_bech32("").unwrap_err()).contains("Invalid bech32m prefix"));
+
+        // Valid bech32m, but the payload is too short to be a proof
+        let short = bech32::encode::<Bech32m>(hrp, &[0u8; 3]).unwrap();
+        assert!(matches!(
+            DLogProof::from_bech32(&short, PROOF_HRP),
+            Err(DLogProofError::Serialization(_))
+        ));
+    }
+}
@@ -0,0 +1,608 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Instant;
+
+use axum::body::Bytes;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{ConnectInfo, FromRequestParts, RawPathParams, State};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use clap::Parser;
+use schnorr_dlog_zkp::dlog_proof::ChallengeHash;
+use schnorr_dlog_zkp::k256::ProjectivePoint;
+use schnorr_dlog_zkp::{DLogGroup, DLogProof, Verifier};
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+
+/// HTTP service verifying Schnorr proofs of knowledge of secp256k1 secret keys
+///
+/// `POST /verify` takes `{"sid", "pid", "public", "proof"}`, with the hex SEC1 public key and
+/// the JSON proof `{"t": "<hex>", "s": "<hex>"}` written by `dlog prove`, and answers
+/// `{"valid": bool}`. `POST /verify-batch` takes an array of such requests and verifies them
+/// as one batch, answering with the indices of the invalid and malformed ones. Malformed
+/// requests get status 400 and `{"error": "<reason>"}`. `GET /verify-stream` upgrades to a
+/// WebSocket that takes one request per text message and answers each in order with
+/// `{"index": n, "valid": bool}` or `{"index": n, "error": "<reason>"}`.
+///
+/// With `--rate-limit`, every client IP address gets a bucket of `--burst` proofs refilled at
+/// the given rate; a batch takes one proof per entry, so bursts below `--max-batch` also cap
+/// the batch size. Requests beyond the limit get status 429, stream messages an error verdict.
+///
+/// With `--tenants`, the endpoints move to `/tenants/{tenant}/...` and each tenant's proofs
+/// are verified under its own domain and challenge hash, restricted to its session IDs and
+/// public keys. The file maps tenant names to
+/// `{"domain": "...", "hash": "SHA-512", "sids": [...], "keys": ["<hex>", ...]}`, where all
+/// but the domain are optional and absent lists allow any value.
+#[derive(Parser)]
+#[command(name = "dlog-server", version)]
+struct Args {
+    /// Address to listen on
+    #[arg(long, env = "DLOG_SERVER_LISTEN", default_value = "127.0.0.1:8080")]
+    listen: SocketAddr,
+    /// Largest number of proofs accepted by `/verify-batch`
+    #[arg(long, default_value_t = 10_000)]
+    max_batch: usize,
+    /// Proofs per second each client may have verified, unlimited if not given
+    #[arg(long)]
+    rate_limit: Option<f64>,
+    /// Proofs a client may have verified at once before the rate limit applies
+    #[arg(long, default_value_t = 1_000, requires = "rate_limit")]
+    burst: u32,
+    /// JSON file with the tenant configurations
+    #[arg(long, env = "DLOG_SERVER_TENANTS")]
+    tenants: Option<PathBuf>,
+}
+
+/// Configuration shared by the handlers
+#[derive(Clone)]
+struct AppState {
+    max_batch: usize,
+    limiter: Option<Arc<RateLimiter>>,
+    /// Tenants by name; without `--tenants`, a single unrestricted tenant named ""
+    tenants: Arc<HashMap<String, Arc<Tenant>>>,
+}
+
+/// A tenant as written in the `--tenants` file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TenantConfig {
+    /// Application domain the proofs are bound to (`Prover::domain`)
+    domain: String,
+    /// Challenge hash by name, e.g. `SHA-512`; SHA-256 if absent
+    hash: Option<String>,
+    /// Accepted session IDs, any if absent
+    sids: Option<Vec<String>>,
+    /// Accepted hex SEC1 public keys, any if absent
+    keys: Option<Vec<String>>,
+}
+
+/// The verification settings and allow-lists of one tenant
+struct Tenant {
+    verifier: Verifier,
+    sids: Option<HashSet<String>>,
+    /// Compressed SEC1 encodings of the accepted keys
+    keys: Option<HashSet<Vec<u8>>>,
+}
+
+impl Tenant {
+    /// The tenant served without `--tenants`: default transcript, any sid and key
+    fn unrestricted() -> Self {
+        Tenant {
+            verifier: Verifier::new(),
+            sids: None,
+            keys: None,
+        }
+    }
+
+    fn from_config(config: TenantConfig) -> Result<Self, String> {
+        let mut verifier = Verifier::new().domain(config.domain.as_bytes());
+        if let Some(hash) = config.hash {
+            verifier = verifier.hash(
+                ChallengeHash::from_name(&hash)
+                    .ok_or_else(|| format!("unknown challenge hash {hash}"))?,
+            );
+        }
+        let keys = config
+            .keys
+            .map(|keys| {
+                keys.iter()
+                    .map(|key| Ok(decode_public_key(key)?.encode_point()))
+                    .collect::<Result<_, String>>()
+            })
+            .transpose()?;
+        Ok(Tenant {
+            verifier,
+            sids: config.sids.map(HashSet::from_iter),
+            keys,
+        })
+    }
+
+    /// Decodes the public key of a request, failing if the tenant doesn't accept its sid or
+    /// key
+    fn admit(&self, request: &VerifyRequest) -> Result<ProjectivePoint, String> {
+        if self
+            .sids
+            .as_ref()
+            .is_some_and(|sids| !sids.contains(&request.sid))
+        {
+            return Err(format!("session ID {:?} not allowed", request.sid));
+        }
+        let y = decode_public_key(&request.public)?;
+        if self
+            .keys
+            .as_ref()
+            .is_some_and(|keys| !keys.contains(&y.encode_point()))
+        {
+            return Err("public key not allowed".to_string());
+        }
+        Ok(y)
+    }
+
+    /// Verifies a single request, failing on a malformed or disallowed statement
+    fn check(&self, request: &VerifyRequest) -> Result<bool, String> {
+        let y = self.admit(request)?;
+        self.verifier
+            .verify(
+                &request.proof,
+                &request.sid,
+                request.pid,
+                y,
+                ProjectivePoint::GENERATOR,
+            )
+            .map_err(|e| e.to_string())
+    }
+}
+
+fn load_tenants(path: &PathBuf) -> Result<HashMap<String, Arc<Tenant>>, Box<dyn Error>> {
+    let configs: HashMap<String, TenantConfig> = serde_json::from_slice(&std::fs::read(path)?)?;
+    configs
+        .into_iter()
+        .map(|(name, config)| {
+            let tenant = Tenant::from_config(config).map_err(|e| format!("tenant {name}: {e}"))?;
+            Ok((name, Arc::new(tenant)))
+        })
+        .collect()
+}
+
+/// The tenant a request is for, from the `{tenant}` path segment
+struct TenantScope(Arc<Tenant>);
+
+impl FromRequestParts<AppState> for TenantScope {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, ApiError> {
+        let params = RawPathParams::from_request_parts(parts, state)
+            .await
+            .map_err(ApiError::bad_request)?;
+        let name = params
+            .iter()
+            .find(|(key, _)| *key == "tenant")
+            .map_or("", |(_, value)| value);
+        state
+            .tenants
+            .get(name)
+            .map(|tenant| TenantScope(tenant.clone()))
+            .ok_or_else(|| ApiError(StatusCode::NOT_FOUND, format!("unknown tenant {name}")))
+    }
+}
+
+impl AppState {
+    /// Takes `proofs` from the bucket of `client`, failing with status 429 if it is empty
+    fn charge(&self, client: SocketAddr, proofs: usize) -> Result<(), ApiError> {
+        match &self.limiter {
+            Some(limiter) if !limiter.try_acquire(client.ip(), proofs, Instant::now()) => Err(
+                ApiError(StatusCode::TOO_MANY_REQUESTS, "rate limit exceeded".into()),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Token buckets per client address, counting proofs
+struct RateLimiter {
+    rate: f64,
+    burst: f64,
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    /// Buckets kept before full (i.e. idle) ones are dropped
+    const MAX_CLIENTS: usize = 100_000;
+
+    fn new(rate: f64, burst: u32) -> Self {
+        RateLimiter {
+            rate,
+            burst: f64::from(burst),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Takes `cost` tokens from the bucket of `client` if it holds that many
+    fn try_acquire(&self, client: IpAddr, cost: usize, now: Instant) -> bool {
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        if buckets.len() >= Self::MAX_CLIENTS {
+            buckets.retain(|_, bucket| self.refill(bucket, now) < self.burst);
+        }
+        let bucket = buckets.entry(client).or_insert(Bucket {
+            tokens: self.burst,
+            updated: now,
+        });
+        let tokens = self.refill(bucket, now);
+        let cost = cost as f64;
+        if tokens < cost {
+            return false;
+        }
+        bucket.tokens = tokens - cost;
+        true
+    }
+
+    fn refill(&self, bucket: &mut Bucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+        bucket.updated = now;
+        bucket.tokens
+    }
+}
+
+/// A proof with its statement
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct VerifyRequest {
+    sid: String,
+    pid: u32,
+    /// Hex SEC1 public key
+    public: String,
+    proof: DLogProof,
+}
+
+#[derive(Serialize)]
+struct Verdict {
+    valid: bool,
+}
+
+/// The verdict on a batch; `valid` only if every request was well-formed and verified
+#[derive(Serialize)]
+struct BatchVerdict {
+    valid: bool,
+    total: usize,
+    /// Indices of well-formed requests whose proof doesn't verify
+    invalid: Vec<usize>,
+    /// Requests that couldn't be parsed or that the tenant doesn't accept
+    malformed: Vec<Malformed>,
+}
+
+/// The answer to the `index`th message of a `/verify-stream` connection
+#[derive(Serialize)]
+struct StreamVerdict {
+    index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    valid: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Malformed {
+    index: usize,
+    error: String,
+}
+
+/// A rejected request, answered with its status and `{"error": "<reason>"}`
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        #[derive(Serialize)]
+        struct Body {
+            error: String,
+        }
+        (self.0, Json(Body { error: self.1 })).into_response()
+    }
+}
+
+impl ApiError {
+    fn bad_request(error: impl ToString) -> Self {
+        ApiError(StatusCode::BAD_REQUEST, error.to_string())
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let tenants = match &args.tenants {
+        Some(path) => load_tenants(path)?,
+        None => HashMap::from([(String::new(), Arc::new(Tenant::unrestricted()))]),
+    };
+    let state = AppState {
+        max_batch: args.max_batch,
+        limiter: args
+            .rate_limit
+            .map(|rate| Arc::new(RateLimiter::new(rate, args.burst))),
+        tenants: Arc::new(tenants),
+    };
+    let routes = Router::new()
+        .route("/verify", post(verify))
+        .route("/verify-batch", post(verify_batch))
+        .route("/verify-stream", get(verify_stream));
+    let app = match args.tenants {
+        Some(_) => Router::new().nest("/tenants/{tenant}", routes),
+        None => routes,
+    }
+    .with_state(state);
+    let listener = TcpListener::bind(args.listen).await?;
+    eprintln!("dlog-server: listening on {}", listener.local_addr()?);
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(async {
+        let _ = tokio::signal::ctrl_c().await;
+    })
+    .await?;
+    Ok(())
+}
+
+async fn verify(
+    State(state): State<AppState>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
+    TenantScope(tenant): TenantScope,
+    body: Bytes,
+) -> Result<Json<Verdict>, ApiError> {
+    state.charge(client, 1)?;
+    let request: VerifyRequest = serde_json::from_slice(&body).map_err(ApiError::bad_request)?;
+    let valid = tenant.check(&request).map_err(ApiError::bad_request)?;
+    Ok(Json(Verdict { valid }))
+}
+
+async fn verify_stream(
+    State(state): State<AppState>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
+    TenantScope(tenant): TenantScope,
+    ws: WebSocketUpgrade,
+) -> Response {
+    ws.on_upgrade(move |socket| stream_verdicts(socket, state, client, tenant))
+}
+
+/// Answers the proofs of one WebSocket connection until the client closes it
+async fn stream_verdicts(
+    mut socket: WebSocket,
+    state: AppState,
+    client: SocketAddr,
+    tenant: Arc<Tenant>,
+) {
+    let mut index = 0;
+    while let Some(Ok(message)) = socket.recv().await {
+        let text = match message {
+            Message::Text(text) => Ok(text.to_string()),
+            Message::Binary(_) => Err("expected a JSON text message".to_string()),
+            Message::Close(_) => break,
+            // Pings are answered by axum itself
+            Message::Ping(_) | Message::Pong(_) => continue,
+        };
+        let text = text.and_then(|text| {
+            state
+                .charge(client, 1)
+                .map(|()| text)
+                .map_err(|ApiError(_, error)| error)
+        });
+        let tenant = tenant.clone();
+        let verdict =
+            tokio::task::spawn_blocking(move || stream_verdict(&tenant, index, text.as_deref()));
+        let Ok(verdict) = verdict.await else {
+            break;
+        };
+        let Ok(verdict) = serde_json::to_string(&verdict) else {
+            break;
+        };
+        if socket.send(Message::Text(verdict.into())).await.is_err() {
+            break;
+        }
+        index += 1;
+    }
+}
+
+/// The verdict on a message, or the reason it isn't verified
+fn stream_verdict(tenant: &Tenant, index: usize, text: Result<&str, &String>) -> StreamVerdict {
+    let verdict = text
+        .map_err(String::clone)
+        .and_then(|text| serde_json::from_str::<VerifyRequest>(text).map_err(|e| e.to_string()))
+        .and_then(|request| tenant.check(&request));
+    let (valid, error) = match verdict {
+        Ok(valid) => (Some(valid), None),
+        Err(error) => (None, Some(error)),
+    };
+    StreamVerdict {
+        index,
+        valid,
+        error,
+    }
+}
+
+async fn verify_batch(
+    State(state): State<AppState>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
+    TenantScope(tenant): TenantScope,
+    body: Bytes,
+) -> Result<Json<BatchVerdict>, ApiError> {
+    let requests: Vec<serde_json::Value> =
+        serde_json::from_slice(&body).map_err(ApiError::bad_request)?;
+    if requests.len() > state.max_batch {
+        return Err(ApiError(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!(
+                "batch of {} proofs exceeds {}",
+                requests.len(),
+                state.max_batch
+            ),
+        ));
+    }
+    state.charge(client, requests.len())?;
+    let total = requests.len();
+    // Verification is CPU-bound, so it runs off the async workers
+    let verdict = tokio::task::spawn_blocking(move || {
+        let mut parsed = Vec::new();
+        let mut malformed = Vec::new();
+        for (index, request) in requests.into_iter().enumerate() {
+            let request = serde_json::from_value::<VerifyRequest>(request)
+                .map_err(|e| e.to_string())
+                .and_then(|request| Ok((tenant.admit(&request)?, request)));
+            match request {
+                Ok((y, request)) => parsed.push((index, y, request)),
+                Err(error) => malformed.push(Malformed { index, error }),
+            }
+        }
+        let items: Vec<_> = parsed
+            .iter()
+            .map(|(_, y, request)| {
+                (
+                    request.sid.as_str().into(),
+                    request.pid.into(),
+                    *y,
+                    &request.proof,
+                )
+            })
+            .collect();
+        let invalid: Vec<_> = tenant
+            .verifier
+            .find_invalid(&items, ProjectivePoint::GENERATOR)
+            .map_err(ApiError::bad_request)?
+            .into_iter()
+            .map(|i| parsed[i].0)
+            .collect();
+        Ok(BatchVerdict {
+            valid: invalid.is_empty() && malformed.is_empty(),
+            total,
+            invalid,
+            malformed,
+        })
+    })
+    .await
+    .map_err(|e| ApiError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))??;
+    Ok(Json(verdict))
+}
+
+fn decode_public_key(public: &str) -> Result<ProjectivePoint, String> {
+    let bytes = hex::decode(public).map_err(|e| format!("invalid public key hex: {e}"))?;
+    ProjectivePoint::decode_point(&bytes)
+        .filter(|y| *y != ProjectivePoint::IDENTITY)
+        .ok_or_else(|| "invalid public key".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schnorr_dlog_zkp::k256::Scalar;
+    use schnorr_dlog_zkp::Prover;
+    use std::time::Duration;
+
+    fn request(sid: &str) -> String {
+        let x = Scalar::from(7u64);
+        let y = ProjectivePoint::GENERATOR * x;
+        let proof = DLogProof::prove("s", 1, &x, y, ProjectivePoint::GENERATOR).unwrap();
+        serde_json::json!({
+            "sid": sid,
+            "pid": 1,
+            "public": hex::encode(y.encode_point()),
+            "proof": proof,
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn stream_messages_get_verdicts() {
+        fn verdict(index: usize, text: Result<&str, &String>) -> String {
+            serde_json::to_string(&stream_verdict(&Tenant::unrestricted(), index, text)).unwrap()
+        }
+        assert_eq!(verdict(0, Ok(&request("s"))), r#"{"index":0,"valid":true}"#);
+        assert_eq!(
+            verdict(1, Ok(&request("other"))),
+            r#"{"index":1,"valid":false}"#
+        );
+        assert_eq!(
+            verdict(2, Err(&"rate limit exceeded".to_string())),
+            r#"{"index":2,"error":"rate limit exceeded"}"#
+        );
+        assert!(verdict(3, Ok("{}")).starts_with(r#"{"index":3,"error":"missing field"#));
+    }
+
+    #[test]
+    fn clients_are_limited_separately() {
+        let limiter = RateLimiter::new(10.0, 20);
+        let (alice, bob) = ([127, 0, 0, 1].into(), [127, 0, 0, 2].into());
+        let start = Instant::now();
+        assert!(limiter.try_acquire(alice, 15, start));
+        assert!(!limiter.try_acquire(alice, 10, start));
+        assert!(limiter.try_acquire(bob, 20, start));
+
+        // 10 proofs per second refill the bucket, up to the burst
+        let later = start + Duration::from_millis(500);
+        assert!(limiter.try_acquire(alice, 10, later));
+        assert!(!limiter.try_acquire(alice, 1, later));
+        assert!(!limiter.try_acquire(bob, 21, later + Duration::from_secs(60)));
+        assert!(limiter.try_acquire(bob, 20, later + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn tenants_are_isolated() {
+        let config = |sids, keys| {
+            serde_json::from_value::<TenantConfig>(serde_json::json!({
+                "domain": "product-a",
+                "hash": "SHA-512",
+                "sids": sids,
+                "keys": keys,
+            }))
+            .unwrap()
+        };
+        let x = Scalar::from(7u64);
+        let y = ProjectivePoint::GENERATOR * x;
+        let public = hex::encode(y.encode_point());
+        let proof = Prover::new()
+            .domain(b"product-a")
+            .hash(ChallengeHash::Sha512)
+            .prove("s", 1, &x, y, ProjectivePoint::GENERATOR)
+            .unwrap();
+        let request: VerifyRequest = serde_json::from_value(serde_json::json!({
+            "sid": "s",
+            "pid": 1,
+            "public": public,
+            "proof": proof,
+        }))
+        .unwrap();
+
+        let tenant = Tenant::from_config(config(None, None)).unwrap();
+        assert_eq!(tenant.check(&request), Ok(true));
+        // Proofs for another tenant's transcript don't verify
+        let default: VerifyRequest = serde_json::from_str(&self::request("s")).unwrap();
+        assert_eq!(tenant.check(&default), Ok(false));
+        assert_eq!(Tenant::unrestricted().check(&request), Ok(false));
+
+        let tenant = Tenant::from_config(config(Some(vec!["t"]), None)).unwrap();
+        assert_eq!(
+            tenant.check(&request),
+            Err(r#"session ID "s" not allowed"#.to_string())
+        );
+        let other = hex::encode(ProjectivePoint::GENERATOR.encode_point());
+        let tenant = Tenant::from_config(config(Some(vec!["s"]), Some(vec![other]))).unwrap();
+        assert_eq!(
+            tenant.check(&request),
+            Err("public key not allowed".to_string())
+        );
+        let tenant = Tenant::from_config(config(None, Some(vec![public]))).unwrap();
+        assert_eq!(tenant.check(&request), Ok(true));
+
+        let mut bad = config(None, None);
+        bad.hash = Some("MD5".into());
+        assert!(Tenant::from_config(bad).is_err());
+    }
+}
@@ -0,0 +1,243 @@
+use elliptic_curve::Field;
+use rand_core::{CryptoRngCore, OsRng};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use zeroize::Zeroizing;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::secret::SecretScalar;
+use crate::serialization::{PointHex, ScalarHex};
+use crate::session::SessionId;
+use crate::sigma::{DLogStatement, SchnorrDLog, SigmaProof, SigmaProtocol};
+
+/// First message of the blind protocol, the signer's commitment R = k * G
+#[serde_as]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct BlindCommitment<G: DLogGroup> {
+    #[serde_as(as = "PointHex")]
+    r: G,
+}
+
+/// Second message, the user's blinded challenge c = c' + β
+#[serde_as]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct BlindChallenge<G: DLogGroup> {
+    #[serde_as(as = "ScalarHex")]
+    c: G::Scalar,
+}
+
+/// Third message, the signer's response s = k + c * x
+#[serde_as]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct BlindResponse<G: DLogGroup> {
+    #[serde_as(as = "ScalarHex")]
+    s: G::Scalar,
+}
+
+impl<G: DLogGroup> BlindCommitment<G> {
+    pub fn new(r: G) -> Self {
+        BlindCommitment { r }
+    }
+
+    pub fn r(&self) -> G {
+        self.r
+    }
+}
+
+impl<G: DLogGroup> BlindChallenge<G> {
+    pub fn new(c: G::Scalar) -> Self {
+        BlindChallenge { c }
+    }
+
+    pub fn c(&self) -> G::Scalar {
+        self.c
+    }
+}
+
+impl<G: DLogGroup> BlindResponse<G> {
+    pub fn new(s: G::Scalar) -> Self {
+        BlindResponse { s }
+    }
+
+    pub fn s(&self) -> G::Scalar {
+        self.s
+    }
+}
+
+/// Signer between sending the commitment and receiving the blinded challenge
+///
+/// Holds the secret and the nonce, both wiped on drop. `respond` consumes the state: answering
+/// two challenges with one nonce would reveal x. Deliberately neither `Clone` nor serializable.
+pub struct SignerState<G: DLogGroup> {
+    x: SecretScalar<G::Scalar>,
+    k: Zeroizing<G::Scalar>,
+}
+
+impl<G: DLogGroup> SignerState<G> {
+    /// Starts a blind issuance for the key y = x * G
+    ///
+    /// The three-message protocol in which the signer helps the user to a `DLogProof` for y
+    /// without seeing the proof or its session: send the `BlindCommitment`, answer the
+    /// received `BlindChallenge` with `respond`.
+    ///
+    /// ```
+    /// # use elliptic_curve::Field;
+    /// # use schnorr_dlog_zkp::k256::{ProjectivePoint, Scalar};
+    /// use schnorr_dlog_zkp::blind::{SignerState, UserState};
+    /// # let g = ProjectivePoint::GENERATOR;
+    /// # let x = Scalar::random(&mut rand_core::OsRng);
+    /// # let y = g * x;
+    ///
+    /// let (commitment, signer) = SignerState::commit(&x, y, g).unwrap();
+    /// let (challenge, user) = UserState::blind("token", 1, commitment, y, g).unwrap();
+    /// let proof = user.unblind(&signer.respond(&challenge)).unwrap();
+    /// assert!(proof.verify("token", 1, y, g).unwrap());
+    /// ```
+    pub fn commit(
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: G,
+        base_point: G,
+    ) -> Result<(BlindCommitment<G>, Self), DLogProofError> {
+        Self::commit_with_rng(&mut OsRng, x, y, base_point)
+    }
+
+    /// Starts a blind issuance like `commit`, drawing the nonce from `rng`
+    pub fn commit_with_rng(
+        rng: &mut impl CryptoRngCore,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: G,
+        base_point: G,
+    ) -> Result<(BlindCommitment<G>, Self), DLogProofError> {
+        DLogProof::check_statement(y, base_point)?;
+        let x = x.into();
+        let (r, k) = SchnorrDLog::commit(&DLogStatement { base_point, y }, &x, rng);
+        let state = SignerState {
+            x,
+            k: Zeroizing::new(k),
+        };
+        Ok((BlindCommitment { r }, state))
+    }
+
+    /// Answers the user's blinded challenge, ending the signer's side of the protocol
+    pub fn respond(self, challenge: &BlindChallenge<G>) -> BlindResponse<G> {
+        BlindResponse {
+            s: SchnorrDLog::<G>::respond(&self.x, &self.k, &challenge.c),
+        }
+    }
+}
+
+/// User between sending the blinded challenge and receiving the response
+///
+/// Holds the blinding factor α (β is folded into the challenges) and the unblinded proof
+/// commitment R' = R + α * G + β * y.
+pub struct UserState<G: DLogGroup> {
+    statement: DLogStatement<G>,
+    r: G,
+    c: G::Scalar,
+    alpha: Zeroizing<G::Scalar>,
+    blinded_r: G,
+}
+
+impl<G: DLogGroup> UserState<G> {
+    /// Blinds the signer's commitment into the challenge for a proof bound to `sid` and `pid`
+    ///
+    /// The user picks α and β, computes R' = R + α * G + β * y and its `DLogProof` challenge
+    /// c' = H(G, y, R'), and sends c = c' + β. The signer sees neither R' nor c', so it can't
+    /// link the final proof to this session.
+    pub fn blind<'s>(
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        commitment: BlindCommitment<G>,
+        y: G,
+        base_point: G,
+    ) -> Result<(BlindChallenge<G>, Self), DLogProofError> {
+        Self::blind_with_rng(&mut OsRng, sid, pid, commitment, y, base_point)
+    }
+
+    /// Blinds like `blind`, drawing α and β from `rng`
+    pub fn blind_with_rng<'s>(
+        rng: &mut impl CryptoRngCore,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        commitment: BlindCommitment<G>,
+        y: G,
+        base_point: G,
+    ) -> Result<(BlindChallenge<G>, Self), DLogProofError> {
+        DLogProof::check_statement(y, base_point)?;
+        let statement = DLogStatement { base_point, y };
+        let alpha = Zeroizing::new(G::Scalar::random(&mut *rng));
+        let beta = Zeroizing::new(G::Scalar::random(&mut *rng));
+
+        let blinded_r = commitment.r + base_point * *alpha + y * *beta;
+        let c = SigmaProof::<SchnorrDLog<G>>::challenge(
+            sid.into(),
+            pid.into(),
+            &statement,
+            &blinded_r,
+        )? + *beta;
+        let state = UserState {
+            statement,
+            r: commitment.r,
+            c,
+            alpha,
+            blinded_r,
+        };
+        Ok((BlindChallenge { c }, state))
+    }
+
+    /// Checks the signer's response and unblinds it into the proof (R', s + α)
+    ///
+    /// # Returns
+    /// * `Some(DLogProof)` - The proof for y under the session of `blind`
+    /// * `None` - If the response doesn't satisfy s * G = R + c * y
+    pub fn unblind(self, response: &BlindResponse<G>) -> Option<DLogProof<G>> {
+        if !SchnorrDLog::check(&self.statement, &self.r, &self.c, &response.s) {
+            return None;
+        }
+        Some(DLogProof::new(self.blinded_r, response.s + *self.alpha))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::{ProjectivePoint, Scalar};
+
+    #[test]
+    fn unblinded_proofs_verify_and_hide_the_session() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+
+        let (commitment, signer) = SignerState::commit(&x, y, g).unwrap();
+        let json = serde_json::to_string(&commitment).unwrap();
+        let (challenge, user) =
+            UserState::blind("token", 7, serde_json::from_str(&json).unwrap(), y, g).unwrap();
+        let json = serde_json::to_string(&challenge).unwrap();
+        let response = signer.respond(&serde_json::from_str(&json).unwrap());
+        let json = serde_json::to_string(&response).unwrap();
+        let proof = user.unblind(&serde_json::from_str(&json).unwrap()).unwrap();
+
+        assert!(proof.verify("token", 7, y, g).unwrap());
+        assert!(!proof.verify("token", 8, y, g).unwrap());
+        // Neither the commitment nor the response the signer saw appear in the proof
+        assert_ne!(proof.t(), commitment.r());
+        assert_ne!(proof.s(), response.s());
+    }
+
+    #[test]
+    fn wrong_responses_are_rejected() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+
+        let (commitment, signer) = SignerState::commit(&Scalar::ONE, y, g).unwrap();
+        let (challenge, user) = UserState::blind("token", 1, commitment, y, g).unwrap();
+        assert!(user.unblind(&signer.respond(&challenge)).is_none());
+    }
+}
@@ -0,0 +1,192 @@
+use core::array;
+
+use crate::dlog_proof::ProofId;
+use crate::error::DLogProofError;
+
+/// Probabilistic duplicate detector over proof identifiers
+///
+/// A Bloom filter answers "definitely not seen" or "probably seen" in constant memory, so a
+/// verifier pipeline can cheaply skip or flag proofs it has already processed. False
+/// positives occur at roughly the configured rate; false negatives never occur.
+///
+/// Proof IDs are already uniformly distributed SHA-256 outputs, so the k bit positions are
+/// derived from the ID itself by double hashing (h1 + i * h2) instead of rehashing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProofBloomFilter {
+    bits: Vec<u64>,
+    num_bits: u64,
+    num_hashes: u32,
+    inserted: u64,
+}
+
+impl ProofBloomFilter {
+    /// Magic bytes of the persisted form
+    const MAGIC: &'static [u8; 4] = b"DLBF";
+    /// Persisted header: magic, bit count, hash count, inserted count
+    const HEADER_LEN: usize = 4 + 8 + 4 + 8;
+
+    /// Creates a filter sized for `expected_items` with the target false-positive rate
+    ///
+    /// # Returns
+    /// * `Ok(ProofBloomFilter)` - An empty filter
+    /// * `Err(DLogProofError)` - `OutOfRange` if `expected_items` is zero or the rate is not
+    ///   in (0, 1)
+    pub fn new(expected_items: u64, false_positive_rate: f64) -> Result<Self, DLogProofError> {
+        if expected_items == 0 || !(false_positive_rate > 0.0 && false_positive_rate < 1.0) {
+            return Err(DLogProofError::OutOfRange);
+        }
+
+        // Optimal sizing: m = -n ln(p) / ln(2)^2 bits and k = (m / n) ln(2) hash functions
+        let n = expected_items as f64;
+        let ln2 = std::f64::consts::LN_2;
+        let num_bits = (-n * false_positive_rate.ln() / (ln2 * ln2))
+            .ceil()
+            .max(64.0) as u64;
+        let num_hashes = ((num_bits as f64 / n) * ln2).round().max(1.0) as u32;
+
+        Ok(ProofBloomFilter {
+            bits: vec![0; num_bits.div_ceil(64) as usize],
+            num_bits,
+            num_hashes,
+            inserted: 0,
+        })
+    }
+
+    /// Records a proof ID
+    ///
+    /// # Returns
+    /// `true` if the ID was probably seen before (all its bits were already set)
+    pub fn insert(&mut self, id: &ProofId) -> bool {
+        let mut seen = true;
+        for index in self.bit_indexes(id) {
+            let (word, mask) = (index / 64, 1u64 << (index % 64));
+            seen &= self.bits[word as usize] & mask != 0;
+            self.bits[word as usize] |= mask;
+        }
+        if !seen {
+            self.inserted += 1;
+        }
+        seen
+    }
+
+    /// Checks whether a proof ID was probably seen before, without recording it
+    pub fn contains(&self, id: &ProofId) -> bool {
+        self.bit_indexes(id)
+            .all(|index| self.bits[(index / 64) as usize] & (1u64 << (index % 64)) != 0)
+    }
+
+    /// Number of distinct IDs recorded so far (IDs reported as duplicates are not counted)
+    pub fn len(&self) -> u64 {
+        self.inserted
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inserted == 0
+    }
+
+    /// False-positive rate expected at the current fill level: (1 - e^(-k n / m))^k
+    pub fn estimated_false_positive_rate(&self) -> f64 {
+        let k = self.num_hashes as f64;
+        let fill = 1.0 - (-k * self.inserted as f64 / self.num_bits as f64).exp();
+        fill.powf(k)
+    }
+
+    /// Serializes the filter for persistence: header followed by the bit array (little-endian)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::HEADER_LEN + self.bits.len() * 8);
+        bytes.extend_from_slice(Self::MAGIC);
+        bytes.extend_from_slice(&self.num_bits.to_le_bytes());
+        bytes.extend_from_slice(&self.num_hashes.to_le_bytes());
+        bytes.extend_from_slice(&self.inserted.to_le_bytes());
+        for word in &self.bits {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Restores a filter persisted with `to_bytes`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DLogProofError> {
+        let (header, body) = bytes
+            .split_first_chunk::<{ Self::HEADER_LEN }>()
+            .filter(|(header, _)| header.starts_with(Self::MAGIC))
+            .ok_or_else(|| invalid("Invalid Bloom filter header"))?;
+        let num_bits = u64::from_le_bytes(array::from_fn(|i| header[4 + i]));
+        let num_hashes = u32::from_le_bytes(array::from_fn(|i| header[12 + i]));
+        let inserted = u64::from_le_bytes(array::from_fn(|i| header[16 + i]));
+
+        let invalid_parameters = || invalid("Invalid Bloom filter parameters");
+        if num_bits == 0 || num_hashes == 0 || body.len() as u64 != num_bits.div_ceil(64) * 8 {
+            return Err(invalid_parameters());
+        }
+        let bits = body
+            .chunks_exact(8)
+            .map(|word| word.try_into().map(u64::from_le_bytes))
+            .collect::<Result<_, _>>()
+            .map_err(|_| invalid_parameters())?;
+
+        Ok(ProofBloomFilter {
+            bits,
+            num_bits,
+            num_hashes,
+            inserted,
+        })
+    }
+
+    fn bit_indexes(&self, id: &ProofId) -> impl Iterator<Item = u64> {
+        let h1 = u64::from_le_bytes(array::from_fn(|i| id.0[i]));
+        // Odd step, so the k positions are distinct whenever num_bits is a power of two
+        let h2 = u64::from_le_bytes(array::from_fn(|i| id.0[8 + i])) | 1;
+        let num_bits = self.num_bits;
+        (0..self.num_hashes as u64).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % num_bits)
+    }
+}
+
+fn invalid(reason: &str) -> DLogProofError {
+    DLogProofError::Serialization(reason.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::Digest;
+
+    fn id(n: u32) -> ProofId {
+        let mut bytes = [0u8; 32];
+        bytes[..4].copy_from_slice(&n.to_le_bytes());
+        ProofId(sha2::Sha256::digest(bytes).into())
+    }
+
+    #[test]
+    fn inserted_ids_are_reported_as_seen() {
+        let mut filter = ProofBloomFilter::new(100, 0.01).unwrap();
+        for n in 0..100 {
+            filter.insert(&id(n));
+        }
+        assert!((0..100).all(|n| filter.contains(&id(n))));
+        assert!(filter.insert(&id(7)));
+
+        // Fresh IDs mostly come back as unseen at the configured 1% rate
+        let false_positives = (1_000..11_000).filter(|&n| filter.contains(&id(n))).count();
+        assert!(false_positives < 300, "{} false positives", false_positives);
+    }
+
+    #[test]
+    fn persisted_filter_round_trips() {
+        let mut filter = ProofBloomFilter::new(10, 0.001).unwrap();
+        filter.insert(&id(1));
+        let restored = ProofBloomFilter::from_bytes(&filter.to_bytes()).unwrap();
+        assert_eq!(restored, filter);
+        assert!(restored.contains(&id(1)));
+
+        let bytes = filter.to_bytes();
+        assert!(ProofBloomFilter::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert_eq!(
+            ProofBloomFilter::new(0, 0.01).unwrap_err(),
+            DLogProofError::OutOfRange
+        );
+        assert_eq!(
+            ProofBloomFilter::new(10, 1.0).unwrap_err(),
+            DLogProofError::OutOfRange
+        );
+    }
+}
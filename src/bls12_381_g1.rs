@@ -0,0 +1,62 @@
+use bls12_381::{G1Projective, Scalar};
+
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+
+/// The G1 group of BLS12-381 (`backend-bls12-381` feature)
+///
+/// Points are serialized in the 48-byte compressed G1 format of the Zcash/IETF BLS
+/// specifications, and decoding checks subgroup membership. Scalars are 32-byte little-endian
+/// hex. The group order is about 2^255, so the challenge hash is reduced modulo the order.
+///
+/// With the generator as base point and a BLS public key in G1 as y, a proof is a
+/// proof-of-possession of the BLS secret key.
+impl DLogGroup for G1Projective {
+    fn challenge_from_hash(hash: &[u8]) -> Result<Scalar, DLogProofError> {
+        if hash.len() > 64 {
+            return Err(DLogProofError::ChallengeDerivation);
+        }
+        let mut wide = [0u8; 64];
+        wide[..hash.len()].copy_from_slice(hash);
+        Ok(Scalar::from_bytes_wide(&wide))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlog_proof::DLogProof;
+    use elliptic_curve::Field;
+    use rand_core::OsRng;
+
+    #[test]
+    fn proves_possession_of_a_bls_secret_key() {
+        let g = G1Projective::generator();
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+
+        let proof = DLogProof::prove("sid", 1, &x, y, g).unwrap();
+        assert!(proof.verify("sid", 1, y, g).unwrap());
+        assert!(!proof.verify("sid", 1, y.double(), g).unwrap());
+    }
+
+    #[test]
+    fn points_use_the_48_byte_compressed_encoding() {
+        let g = G1Projective::generator();
+        let json = serde_json::to_string(&DLogProof::new(g, Scalar::one())).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"t":"{}","s":"{}"}}"#,
+                "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb",
+                "0100000000000000000000000000000000000000000000000000000000000000"
+            )
+        );
+        let parsed: DLogProof<G1Projective> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.t(), g);
+
+        // The same bytes without the compression flag are not a valid compressed point
+        let uncompressed_flag = json.replacen("\"97", "\"17", 1);
+        assert!(serde_json::from_str::<DLogProof<G1Projective>>(&uncompressed_flag).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+use borsh::io::{Error, ErrorKind, Read, Result, Write};
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::dlog_proof::DLogProof;
+
+/// Borsh encoding of a proof: the fixed 65-byte layout `t (compressed SEC1) || s (big-endian)`
+///
+/// Matches a `[u8; 33]` followed by a `[u8; 32]` in Borsh schemas, so Solana and NEAR programs
+/// can read it without length prefixes.
+impl BorshSerialize for DLogProof {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+}
+
+impl BorshDeserialize for DLogProof {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let mut bytes = [0u8; Self::ENCODED_LEN];
+        reader.read_exact(&mut bytes)?;
+        // Rejects invalid points and scalars >= curve order, so every proof has exactly one encoding
+        DLogProof::from_bytes(&bytes).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::Field;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_core::OsRng;
+
+    #[test]
+    fn round_trips_with_fixed_layout() {
+        let x = Scalar::random(&mut OsRng);
+        let g = ProjectivePoint::GENERATOR;
+        let proof = DLogProof::prove("sid", 1, &x, g * x, g).unwrap();
+
+        let encoded = borsh::to_vec(&proof).unwrap();
+        assert_eq!(encoded, proof.to_bytes());
+        assert_eq!(borsh::from_slice::<DLogProof>(&encoded).unwrap(), proof);
+    }
+
+    #[test]
+    fn rejects_truncated_and_non_canonical_input() {
+        let x = Scalar::random(&mut OsRng);
+        let g = ProjectivePoint::GENERATOR;
+        let mut encoded =
+            borsh::to_vec(&DLogProof::prove("sid", 1, &x, g * x, g).unwrap()).unwrap();
+
+        assert!(borsh::from_slice::<DLogProof>(&encoded[..64]).is_err());
+        assert!(borsh::from_slice::<DLogProof>(&[&encoded[..], &[0]].concat()).is_err());
+
+        // Uncompressed-looking tag
+        let mut bad_point = encoded.clone();
+        bad_point[0] = 0x04;
+        assert!(borsh::from_slice::<DLogProof>(&bad_point).is_err());
+
+        // s = 2^256 - 1 is above the group order
+        encoded[33..].fill(0xff);
+        let error = borsh::from_slice::<DLogProof>(&encoded).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+}
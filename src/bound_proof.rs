@@ -0,0 +1,137 @@
+use k256::ProjectivePoint;
+use serde::{Deserialize, Serialize};
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::secret::SecretScalar;
+use crate::verifier::Verifier;
+
+/// A proof together with the session ID and participant ID it was made for
+///
+/// Serialized as `{"sid": "...", "pid": 1, "proof": {"t": "<hex>", "s": "<hex>"}}`, so the
+/// verifier reads the identifiers from the proof instead of learning them out of band. A
+/// verifier that knows which session and participant it expects passes them to `verify`,
+/// which reports a differing embedded value as `SessionMismatch` or `ParticipantMismatch`
+/// rather than as an invalid proof.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "", deny_unknown_fields)]
+pub struct BoundProof<G: DLogGroup = ProjectivePoint> {
+    /// Session identifier the proof was made for
+    pub sid: String,
+    /// Participant ID the proof was made for
+    pub pid: u32,
+    pub proof: DLogProof<G>,
+}
+
+impl<G: DLogGroup> BoundProof<G> {
+    /// Attaches `sid` and `pid` to a proof made for them
+    pub fn new(sid: impl Into<String>, pid: u32, proof: DLogProof<G>) -> Self {
+        BoundProof {
+            sid: sid.into(),
+            pid,
+            proof,
+        }
+    }
+
+    /// Proves knowledge of x with y = x * G like `DLogProof::prove`, keeping `sid` and `pid`
+    /// with the proof
+    pub fn prove(
+        sid: impl Into<String>,
+        pid: u32,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: impl Into<G>,
+        base_point: G,
+    ) -> Result<Self, DLogProofError> {
+        let sid = sid.into();
+        let proof = DLogProof::prove(&sid, pid, x, y, base_point)?;
+        Ok(BoundProof { sid, pid, proof })
+    }
+
+    /// Verifies the proof for its embedded session ID and participant ID with the default
+    /// `Verifier`
+    ///
+    /// # Arguments
+    /// * `sid` - The session ID the verifier expects, or `None` to accept the embedded one
+    /// * `pid` - The participant ID the verifier expects, or `None` to accept the embedded one
+    /// * `y` - The public key y = x * G
+    /// * `base_point` - The base point G
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Validity of the proof
+    /// * `Err(DLogProofError)` - `SessionMismatch` or `ParticipantMismatch` if an expected
+    ///   value differs from the embedded one, or any error of `DLogProof::verify`
+    pub fn verify(
+        &self,
+        sid: Option<&str>,
+        pid: Option<u32>,
+        y: impl Into<G>,
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        self.verify_with(&Verifier::new(), sid, pid, y, base_point)
+    }
+
+    /// Verifies the proof like `verify`, with the transcript options of `verifier`
+    pub fn verify_with(
+        &self,
+        verifier: &Verifier,
+        sid: Option<&str>,
+        pid: Option<u32>,
+        y: impl Into<G>,
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        if sid.is_some_and(|sid| sid != self.sid) {
+            return Err(DLogProofError::SessionMismatch);
+        }
+        if pid.is_some_and(|pid| pid != self.pid) {
+            return Err(DLogProofError::ParticipantMismatch);
+        }
+        verifier.verify(&self.proof, &self.sid, self.pid, y, base_point)
+    }
+
+    /// Drops the identifiers, e.g. to pass the proof to an API that takes them separately
+    pub fn into_proof(self) -> DLogProof<G> {
+        self.proof
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::Field;
+    use k256::Scalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn embedded_identifiers_are_checked_against_expected_ones() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let bound = BoundProof::prove("session", 7, &x, y, g).unwrap();
+        assert!(bound.verify(None, None, y, g).unwrap());
+        assert!(bound.verify(Some("session"), Some(7), y, g).unwrap());
+        assert_eq!(
+            bound.verify(Some("other"), Some(7), y, g),
+            Err(DLogProofError::SessionMismatch)
+        );
+        assert_eq!(
+            bound.verify(None, Some(8), y, g),
+            Err(DLogProofError::ParticipantMismatch)
+        );
+
+        let json = serde_json::to_value(&bound).unwrap();
+        assert_eq!(json["sid"], "session");
+        assert_eq!(json["pid"], 7);
+        let decoded: BoundProof = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(decoded, bound);
+        let bytes = bincode::serialize(&bound).unwrap();
+        assert_eq!(bincode::deserialize::<BoundProof>(&bytes).unwrap(), bound);
+
+        // Editing the embedded identifiers invalidates the proof
+        let mut edited = json;
+        edited["pid"] = 8.into();
+        let edited: BoundProof = serde_json::from_value(edited).unwrap();
+        assert!(!edited.verify(None, None, y, g).unwrap());
+        assert!(bound.into_proof().verify("session", 7, y, g).unwrap());
+    }
+}
@@ -0,0 +1,149 @@
+use k256::{ProjectivePoint, Scalar};
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::prover::Prover;
+use crate::secret::SecretScalar;
+use crate::session::SessionId;
+
+/// Type-state marker: the session ID has not been set
+pub struct NoSid;
+/// Type-state marker: the session ID has been set
+pub struct Sid(Vec<u8>);
+/// Type-state marker: the participant ID has not been set
+pub struct NoPid;
+/// Type-state marker: the participant ID has been set
+pub struct Pid(u32);
+
+/// Builder for a single `DLogProof` that only offers `prove` once its context is complete
+///
+/// The session and participant IDs are tracked in the type, so forgetting either is a
+/// compile error rather than a proof bound to the wrong context:
+/// `ProofBuilder::new().sid("sid").pid(1).aad(b"request").prove(&x)`.
+/// Optional per-proof inputs (additional authenticated data, the base point, the `Prover`
+/// configuration) are plain setters that can be called in any state.
+pub struct ProofBuilder<S = NoSid, P = NoPid> {
+    sid: S,
+    pid: P,
+    prover: Prover,
+    base_point: ProjectivePoint,
+    aad: Option<Vec<u8>>,
+}
+
+impl Default for ProofBuilder {
+    fn default() -> Self {
+        ProofBuilder {
+            sid: NoSid,
+            pid: NoPid,
+            prover: Prover::default(),
+            base_point: ProjectivePoint::GENERATOR,
+            aad: None,
+        }
+    }
+}
+
+impl ProofBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<P> ProofBuilder<NoSid, P> {
+    /// Sets the session identifier used for domain separation
+    pub fn sid<'s>(self, sid: impl Into<SessionId<'s>>) -> ProofBuilder<Sid, P> {
+        ProofBuilder {
+            sid: Sid(sid.into().as_bytes().to_vec()),
+            pid: self.pid,
+            prover: self.prover,
+            base_point: self.base_point,
+            aad: self.aad,
+        }
+    }
+}
+
+impl<S> ProofBuilder<S, NoPid> {
+    /// Sets the participant ID distinguishing proofs within a session
+    pub fn pid(self, pid: u32) -> ProofBuilder<S, Pid> {
+        ProofBuilder {
+            sid: self.sid,
+            pid: Pid(pid),
+            prover: self.prover,
+            base_point: self.base_point,
+            aad: self.aad,
+        }
+    }
+}
+
+impl<S, P> ProofBuilder<S, P> {
+    /// Binds additional authenticated data (e.g. a request hash) into the challenge
+    ///
+    /// The proof then only verifies with a `Verifier` configured with the same data.
+    pub fn aad(mut self, aad: &[u8]) -> Self {
+        self.aad = Some(aad.to_vec());
+        self
+    }
+
+    /// Uses `prover` for the transcript and hardening options (defaults to `Prover::default()`)
+    pub fn prover(mut self, prover: Prover) -> Self {
+        self.prover = prover;
+        self
+    }
+
+    /// Sets the base point G (defaults to the secp256k1 generator)
+    pub fn base_point(mut self, base_point: ProjectivePoint) -> Self {
+        self.base_point = base_point;
+        self
+    }
+}
+
+impl ProofBuilder<Sid, Pid> {
+    /// Proves knowledge of `x` for y = x * G under the configured context
+    ///
+    /// # Returns
+    /// * `Ok(DLogProof)` - The proof
+    /// * `Err(DLogProofError)` - If proof generation fails
+    pub fn prove(&self, x: impl Into<SecretScalar<Scalar>>) -> Result<DLogProof, DLogProofError> {
+        let prover = match &self.aad {
+            Some(aad) => self.prover.clone().aad(aad),
+            None => self.prover.clone(),
+        };
+        let x = x.into();
+        let y = self.base_point * x.expose_secret();
+        prover.prove(&self.sid.0, self.pid.0, &x, y, self.base_point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verifier::Verifier;
+    use elliptic_curve::Field;
+    use rand_core::OsRng;
+
+    #[test]
+    fn builder_proofs_match_the_plain_api() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        // Setters can be called in either order
+        let proof = ProofBuilder::new().pid(3).sid("sid").prove(x).unwrap();
+        assert!(proof.verify("sid", 3, g * x, g).unwrap());
+    }
+
+    #[test]
+    fn aad_is_bound_into_the_challenge() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let proof = ProofBuilder::new()
+            .sid("sid")
+            .pid(1)
+            .aad(b"request-1")
+            .prove(x)
+            .unwrap();
+
+        let verify = |verifier: Verifier| verifier.verify(&proof, "sid", 1, y, g).unwrap();
+        assert!(verify(Verifier::new().aad(b"request-1")));
+        assert!(!verify(Verifier::new().aad(b"request-2")));
+        assert!(!verify(Verifier::new()));
+    }
+}
@@ -0,0 +1,406 @@
+use std::io::{self, Read, Write};
+
+use k256::ProjectivePoint;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::batch::BatchItem;
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::serialization::PointHex;
+use crate::verifier::Verifier;
+
+/// Current version of the bundle format, the only one `ProofBundle::verify_all` accepts
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Version of the storage envelope written by `ProofBundle::to_envelope`
+pub const BUNDLE_ENVELOPE_VERSION: u8 = 1;
+
+/// Envelope flag of a zstd-compressed payload
+pub const BUNDLE_FLAG_ZSTD: u8 = 0x01;
+
+/// One participant's proof in a `ProofBundle`
+#[serde_as]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "", deny_unknown_fields)]
+pub struct BundleEntry<G: DLogGroup = ProjectivePoint> {
+    /// Participant ID the proof was made for
+    pub pid: u32,
+    /// Public key y = x * G
+    #[serde_as(as = "PointHex")]
+    pub public: G,
+    pub proof: DLogProof<G>,
+}
+
+/// The proofs of one ceremony: every participant proves knowledge of its key under one
+/// session ID
+///
+/// Stored and sent as a unit, e.g. as JSON
+/// `{"version": 1, "sid": "...", "entries": [{"pid": 1, "public": "<hex>", "proof": {...}}]}`.
+/// `verify_all` checks every entry with one batch verification. Secp256k1 bundles also have a
+/// binary encoding for streams, see `write_to`.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "", deny_unknown_fields)]
+pub struct ProofBundle<G: DLogGroup = ProjectivePoint> {
+    /// Format version, `BUNDLE_VERSION` for bundles made by this version
+    pub version: u32,
+    /// Session identifier shared by all proofs
+    pub sid: String,
+    pub entries: Vec<BundleEntry<G>>,
+}
+
+impl<G: DLogGroup> ProofBundle<G> {
+    /// Starts an empty bundle for the session `sid`
+    pub fn new(sid: impl Into<String>) -> Self {
+        ProofBundle {
+            version: BUNDLE_VERSION,
+            sid: sid.into(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds the proof of participant `pid` for the public key `public`
+    pub fn push(&mut self, pid: u32, public: G, proof: DLogProof<G>) {
+        self.entries.push(BundleEntry { pid, public, proof });
+    }
+
+    /// Verifies all proofs in one batch with the default `Verifier`
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If every proof is valid (also for an empty bundle)
+    /// * `Ok(false)` - If at least one proof is invalid; see `find_invalid` for which
+    /// * `Err(DLogProofError)` - `Serialization` for an unsupported version,
+    ///   `InvalidParticipant` if a participant ID occurs twice, or any error of
+    ///   `DLogProof::verify_batch`
+    pub fn verify_all(&self, base_point: G) -> Result<bool, DLogProofError> {
+        self.verify_all_with(&Verifier::new(), base_point)
+    }
+
+    /// Verifies all proofs like `verify_all`, with the transcript options of `verifier`
+    pub fn verify_all_with(
+        &self,
+        verifier: &Verifier,
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        verifier.verify_batch(&self.items()?, base_point)
+    }
+
+    /// Returns the participant IDs of the invalid proofs, in bundle order
+    pub fn find_invalid(&self, base_point: G) -> Result<Vec<u32>, DLogProofError> {
+        let invalid = DLogProof::find_invalid(&self.items()?, base_point)?;
+        Ok(invalid
+            .into_iter()
+            .map(|index| self.entries[index].pid)
+            .collect())
+    }
+
+    fn items(&self) -> Result<Vec<BatchItem<'_, G>>, DLogProofError> {
+        if self.version != BUNDLE_VERSION {
+            return Err(DLogProofError::Serialization(format!(
+                "Unsupported bundle version {}, expected {BUNDLE_VERSION}",
+                self.version
+            )));
+        }
+        let mut pids: Vec<u32> = self.entries.iter().map(|entry| entry.pid).collect();
+        pids.sort_unstable();
+        if pids.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(DLogProofError::InvalidParticipant);
+        }
+        Ok(self
+            .entries
+            .iter()
+            .map(|entry| {
+                (
+                    self.sid.as_str().into(),
+                    entry.pid.into(),
+                    entry.public,
+                    &entry.proof,
+                )
+            })
+            .collect())
+    }
+}
+
+impl<G: DLogGroup> ProofBundle<G> {
+    /// Encodes the bundle for storage as `envelope version || flags || JSON`, uncompressed
+    ///
+    /// `from_envelope` reads it back, as well as the compressed envelopes of
+    /// `to_compressed_envelope`.
+    pub fn to_envelope(&self) -> Result<Vec<u8>, DLogProofError> {
+        Ok(envelope(0, self.to_json()?))
+    }
+
+    /// Encodes the bundle like `to_envelope`, with the JSON compressed by zstd at `level`
+    /// (1 to 22, 0 for zstd's default) and `BUNDLE_FLAG_ZSTD` set
+    ///
+    /// The hex strings of the JSON encoding compress well, so stored bundles shrink several
+    /// times over.
+    #[cfg(feature = "zstd")]
+    pub fn to_compressed_envelope(&self, level: i32) -> Result<Vec<u8>, DLogProofError> {
+        let json = self.to_json()?;
+        let compressed = zstd::encode_all(&json[..], level)
+            .map_err(|e| DLogProofError::Io(format!("zstd compression failed: {e}")))?;
+        Ok(envelope(BUNDLE_FLAG_ZSTD, compressed))
+    }
+
+    /// Decodes a bundle written by `to_envelope` or `to_compressed_envelope`
+    ///
+    /// # Returns
+    /// * `Ok(ProofBundle)` - The bundle, decompressed if the envelope says so
+    /// * `Err(DLogProofError)` - `Serialization` for an unknown envelope version or flag, a
+    ///   compressed envelope without the `zstd` feature, or invalid JSON; `Io` if the zstd
+    ///   stream is corrupt
+    pub fn from_envelope(bytes: &[u8]) -> Result<Self, DLogProofError> {
+        let [version, flags, payload @ ..] = bytes else {
+            return Err(DLogProofError::Serialization(
+                "Bundle envelope too short".to_string(),
+            ));
+        };
+        if *version != BUNDLE_ENVELOPE_VERSION {
+            return Err(DLogProofError::Serialization(format!(
+                "Unsupported bundle envelope version {version}"
+            )));
+        }
+        let json = match *flags {
+            0 => payload.to_vec(),
+            BUNDLE_FLAG_ZSTD => decompress(payload)?,
+            _ => {
+                return Err(DLogProofError::Serialization(format!(
+                    "Unknown bundle envelope flags {flags:#04x}"
+                )))
+            }
+        };
+        serde_json::from_slice(&json).map_err(|e| DLogProofError::Serialization(e.to_string()))
+    }
+
+    fn to_json(&self) -> Result<Vec<u8>, DLogProofError> {
+        serde_json::to_vec(self).map_err(|e| DLogProofError::Serialization(e.to_string()))
+    }
+}
+
+fn envelope(flags: u8, payload: Vec<u8>) -> Vec<u8> {
+    let mut bytes = vec![BUNDLE_ENVELOPE_VERSION, flags];
+    bytes.extend_from_slice(&payload);
+    bytes
+}
+
+#[cfg(feature = "zstd")]
+fn decompress(payload: &[u8]) -> Result<Vec<u8>, DLogProofError> {
+    zstd::decode_all(payload)
+        .map_err(|e| DLogProofError::Io(format!("zstd decompression failed: {e}")))
+}
+
+#[cfg(not(feature = "zstd"))]
+fn decompress(_payload: &[u8]) -> Result<Vec<u8>, DLogProofError> {
+    Err(DLogProofError::Serialization(
+        "Compressed bundle, enable the zstd feature".to_string(),
+    ))
+}
+
+impl ProofBundle {
+    /// Writes the bundle to `writer` in the binary encoding
+    ///
+    /// ```text
+    /// version (4, big-endian) || sid length (4, big-endian) || sid (UTF-8)
+    ///     || entry count (4, big-endian) || entries
+    /// ```
+    ///
+    /// where each entry is `pid (4, big-endian) || public (33, compressed SEC1) || proof (65,
+    /// DLogProof::to_bytes)`. Nothing is buffered, so bundles can be written straight into a
+    /// socket or file; wrap unbuffered writers in a `BufWriter`.
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let len = |len: usize| {
+            u32::try_from(len)
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "bundle too large"))
+        };
+        writer.write_all(&self.version.to_be_bytes())?;
+        writer.write_all(&len(self.sid.len())?.to_be_bytes())?;
+        writer.write_all(self.sid.as_bytes())?;
+        writer.write_all(&len(self.entries.len())?.to_be_bytes())?;
+        for entry in &self.entries {
+            writer.write_all(&entry.pid.to_be_bytes())?;
+            writer.write_all(&entry.public.encode_point())?;
+            entry.proof.write_to(writer)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a bundle written by `write_to`, consuming exactly its bytes
+    ///
+    /// The version is read as is, so `verify_all` reports a bundle of another version. A
+    /// stream ending early is an `io::ErrorKind::UnexpectedEof` error; an invalid sid, public
+    /// key or proof is `io::ErrorKind::InvalidData` wrapping the `DLogProofError`. Memory
+    /// grows with the bytes actually read, not with the lengths the stream claims.
+    pub fn read_from(reader: &mut impl Read) -> io::Result<Self> {
+        let invalid = |e: DLogProofError| io::Error::new(io::ErrorKind::InvalidData, e);
+        let version = read_u32(reader)?;
+        let sid_len = read_u32(reader)?;
+        let mut sid = Vec::new();
+        reader.take(u64::from(sid_len)).read_to_end(&mut sid)?;
+        if sid.len() as u64 != u64::from(sid_len) {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        let sid = String::from_utf8(sid).map_err(|_| {
+            invalid(DLogProofError::Serialization(
+                "Bundle sid is not UTF-8".to_string(),
+            ))
+        })?;
+
+        let count = read_u32(reader)?;
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            let pid = read_u32(reader)?;
+            let mut public = [0u8; 33];
+            reader.read_exact(&mut public)?;
+            let public = ProjectivePoint::decode_point(&public)
+                .ok_or(DLogProofError::InvalidPoint)
+                .map_err(invalid)?;
+            let proof = DLogProof::read_from(reader)?;
+            entries.push(BundleEntry { pid, public, proof });
+        }
+        Ok(ProofBundle {
+            version,
+            sid,
+            entries,
+        })
+    }
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_be_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::Field;
+    use k256::Scalar;
+    use rand_core::OsRng;
+
+    fn bundle(size: u32) -> ProofBundle {
+        let g = ProjectivePoint::GENERATOR;
+        let mut bundle = ProofBundle::new("ceremony-7");
+        for pid in 1..=size {
+            let x = Scalar::random(&mut OsRng);
+            let y = g * x;
+            bundle.push(
+                pid,
+                y,
+                DLogProof::prove("ceremony-7", pid, &x, y, g).unwrap(),
+            );
+        }
+        bundle
+    }
+
+    #[test]
+    fn bundles_verify_and_round_trip() {
+        let g = ProjectivePoint::GENERATOR;
+        let bundle = bundle(4);
+        assert!(bundle.verify_all(g).unwrap());
+        assert!(bundle.find_invalid(g).unwrap().is_empty());
+
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert!(json.starts_with(r#"{"version":1,"sid":"ceremony-7","entries":[{"pid":1,"#));
+        let parsed: ProofBundle = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, bundle);
+        assert!(serde_json::from_str::<ProofBundle>(&json.replace("sid", "session")).is_err());
+    }
+
+    #[test]
+    fn bundles_stream_in_binary() {
+        let g = ProjectivePoint::GENERATOR;
+        let bundles = [bundle(3), bundle(0)];
+        let mut stream = Vec::new();
+        for bundle in &bundles {
+            bundle.write_to(&mut stream).unwrap();
+        }
+        assert_eq!(
+            stream.len(),
+            2 * (12 + "ceremony-7".len()) + 3 * (4 + 33 + DLogProof::ENCODED_LEN)
+        );
+
+        let mut reader = &stream[..];
+        for bundle in &bundles {
+            let read = ProofBundle::read_from(&mut reader).unwrap();
+            assert_eq!(&read, bundle);
+            assert!(read.verify_all(g).unwrap());
+        }
+        assert!(reader.is_empty());
+
+        // A claimed length beyond the stream is an early end, not a huge allocation
+        let mut truncated = stream[..8].to_vec();
+        truncated[4..8].copy_from_slice(&u32::MAX.to_be_bytes());
+        let error = ProofBundle::read_from(&mut &truncated[..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+
+        let mut corrupted = stream.clone();
+        corrupted[26] = 0x05;
+        let error = ProofBundle::read_from(&mut &corrupted[..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn bundles_round_trip_through_envelopes() {
+        let from_envelope = ProofBundle::<ProjectivePoint>::from_envelope;
+        let bundle = bundle(16);
+        let stored = bundle.to_envelope().unwrap();
+        assert_eq!(stored[..2], [BUNDLE_ENVELOPE_VERSION, 0]);
+        assert_eq!(from_envelope(&stored).unwrap(), bundle);
+
+        let mut unknown = stored.clone();
+        unknown[1] = 0x80;
+        assert!(matches!(
+            from_envelope(&unknown),
+            Err(DLogProofError::Serialization(_))
+        ));
+        unknown[..2].copy_from_slice(&[2, 0]);
+        assert!(from_envelope(&unknown).is_err());
+        assert!(from_envelope(&[BUNDLE_ENVELOPE_VERSION]).is_err());
+
+        let mut compressed = stored;
+        compressed[1] = BUNDLE_FLAG_ZSTD;
+        #[cfg(not(feature = "zstd"))]
+        assert!(matches!(
+            from_envelope(&compressed),
+            Err(DLogProofError::Serialization(_))
+        ));
+        #[cfg(feature = "zstd")]
+        {
+            assert!(matches!(
+                from_envelope(&compressed),
+                Err(DLogProofError::Io(_))
+            ));
+            let compressed = bundle.to_compressed_envelope(0).unwrap();
+            assert_eq!(compressed[..2], [BUNDLE_ENVELOPE_VERSION, BUNDLE_FLAG_ZSTD]);
+            assert!(compressed.len() < bundle.to_envelope().unwrap().len());
+            assert_eq!(from_envelope(&compressed).unwrap(), bundle);
+        }
+    }
+
+    #[test]
+    fn bad_bundles_are_reported() {
+        let g = ProjectivePoint::GENERATOR;
+        let mut bundle = bundle(4);
+        bundle.entries.swap(1, 2);
+        bundle.entries[1].pid = 2;
+        bundle.entries[2].pid = 3;
+        assert!(!bundle.verify_all(g).unwrap());
+        assert_eq!(bundle.find_invalid(g).unwrap(), [2, 3]);
+
+        bundle.entries[3].pid = 1;
+        assert_eq!(
+            bundle.verify_all(g),
+            Err(DLogProofError::InvalidParticipant)
+        );
+
+        let mut bundle = self::bundle(1);
+        bundle.version = 2;
+        assert!(matches!(
+            bundle.verify_all(g),
+            Err(DLogProofError::Serialization(_))
+        ));
+    }
+}
@@ -0,0 +1,288 @@
+use core::marker::PhantomData;
+use elliptic_curve::PrimeField;
+use k256::ProjectivePoint;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, PoisonError};
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::participant::ParticipantId;
+use crate::session::SessionId;
+use crate::verifier::Verifier;
+
+const DOMAIN: &[u8] = b"SCHNORR_VERIFIER_CACHE";
+
+/// Counters of a `VerifierCache`, since it was created or last reset
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups answered from the cache
+    pub hits: u64,
+    /// Lookups that ran the verification
+    pub misses: u64,
+    /// Entries dropped to stay within the capacity
+    pub evictions: u64,
+    /// Entries removed by `invalidate`
+    pub invalidations: u64,
+}
+
+impl CacheStats {
+    /// Fraction of lookups answered from the cache, 0 before the first lookup
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// Cached results, with the recency order for evicting the least recently used one
+#[derive(Default)]
+struct Entries {
+    results: HashMap<[u8; 32], (bool, u64)>,
+    recency: BTreeMap<u64, [u8; 32]>,
+    tick: u64,
+    stats: CacheStats,
+}
+
+impl Entries {
+    fn touch(&mut self, key: [u8; 32], valid: bool) {
+        self.tick += 1;
+        if let Some((_, used)) = self.results.insert(key, (valid, self.tick)) {
+            self.recency.remove(&used);
+        }
+        self.recency.insert(self.tick, key);
+    }
+}
+
+/// Bounded LRU cache of verification results, for verifiers that see the same proofs again
+///
+/// A gateway whose clients retry with the same token verifies identical proofs over and over.
+/// The cache keys every result by SHA-256 over the session ID, participant ID, base point, y
+/// and the proof bytes, so a repeated proof costs one hash instead of the scalar
+/// multiplications. Invalid results are cached as well; errors (e.g. `IdentityPoint`) are not.
+/// All results come from one `Verifier` configuration, set with `verifier`.
+///
+/// The cache is shared through `&self` and can sit behind an `Arc`; verification runs
+/// outside the lock.
+///
+/// ```
+/// # use elliptic_curve::Field;
+/// # use schnorr_dlog_zkp::k256::{ProjectivePoint, Scalar};
+/// # use schnorr_dlog_zkp::{DLogProof, VerifierCache};
+/// # let g = ProjectivePoint::GENERATOR;
+/// # let x = Scalar::random(&mut rand_core::OsRng);
+/// # let y = g * x;
+/// let cache = VerifierCache::new(10_000);
+/// let proof = DLogProof::prove("session", 1, &x, y, g)?;
+/// for _ in 0..3 {
+///     assert!(cache.verify(&proof, "session", 1, y, g)?);
+/// }
+/// assert_eq!(cache.stats().hits, 2);
+/// # Ok::<(), schnorr_dlog_zkp::DLogProofError>(())
+/// ```
+pub struct VerifierCache<G: DLogGroup = ProjectivePoint> {
+    verifier: Verifier,
+    capacity: usize,
+    entries: Mutex<Entries>,
+    group: PhantomData<G>,
+}
+
+impl<G: DLogGroup> VerifierCache<G> {
+    /// Creates an empty cache holding up to `capacity` results of the default `Verifier`
+    ///
+    /// A capacity of 0 caches nothing, so every lookup is a miss.
+    pub fn new(capacity: usize) -> Self {
+        VerifierCache {
+            verifier: Verifier::default(),
+            capacity,
+            entries: Mutex::default(),
+            group: PhantomData,
+        }
+    }
+
+    /// Sets the verifier configuration, dropping the results cached under the previous one
+    pub fn verifier(mut self, verifier: Verifier) -> Self {
+        self.verifier = verifier;
+        self.clear();
+        self
+    }
+
+    /// Verifies `proof` like `Verifier::verify`, answering from the cache when it can
+    pub fn verify<'s, 'p>(
+        &self,
+        proof: &DLogProof<G>,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        y: G,
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        let sid = sid.into();
+        let pid = pid.into();
+        let key = Self::key(proof, sid, pid, y, base_point);
+        {
+            let mut entries = self.lock();
+            if let Some(&(valid, _)) = entries.results.get(&key) {
+                entries.stats.hits += 1;
+                entries.touch(key, valid);
+                return Ok(valid);
+            }
+            entries.stats.misses += 1;
+        }
+
+        let valid = self.verifier.verify(proof, sid, pid, y, base_point)?;
+        if self.capacity > 0 {
+            let mut entries = self.lock();
+            entries.touch(key, valid);
+            while entries.results.len() > self.capacity {
+                let Some((_, oldest)) = entries.recency.pop_first() else {
+                    break;
+                };
+                entries.results.remove(&oldest);
+                entries.stats.evictions += 1;
+            }
+        }
+        Ok(valid)
+    }
+
+    /// Removes the cached result for these arguments of `verify`, e.g. after revoking a token
+    ///
+    /// # Returns
+    /// `true` if a result was cached
+    pub fn invalidate<'s, 'p>(
+        &self,
+        proof: &DLogProof<G>,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        y: G,
+        base_point: G,
+    ) -> bool {
+        let key = Self::key(proof, sid.into(), pid.into(), y, base_point);
+        let mut entries = self.lock();
+        match entries.results.remove(&key) {
+            Some((_, used)) => {
+                entries.recency.remove(&used);
+                entries.stats.invalidations += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes all cached results, keeping the counters
+    pub fn clear(&self) {
+        let mut entries = self.lock();
+        entries.results.clear();
+        entries.recency.clear();
+    }
+
+    /// Number of cached results
+    pub fn len(&self) -> usize {
+        self.lock().results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.lock().stats
+    }
+
+    /// Sets all counters back to zero
+    pub fn reset_stats(&self) {
+        self.lock().stats = CacheStats::default();
+    }
+
+    /// The entries stay consistent if a holder of the lock panicked, so a poisoned lock is
+    /// used as is
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// SHA-256 over the arguments of `verify`, each prefixed with its 8-byte big-endian length
+    fn key(
+        proof: &DLogProof<G>,
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        y: G,
+        base_point: G,
+    ) -> [u8; 32] {
+        let (pid_label, pid_value) = pid.v2_field();
+        let mut hasher = Sha256::new();
+        hasher.update(DOMAIN);
+        for field in [
+            sid.as_bytes(),
+            pid_label,
+            &pid_value,
+            base_point.to_bytes().as_ref(),
+            y.to_bytes().as_ref(),
+            proof.t().to_bytes().as_ref(),
+            proof.s().to_repr().as_ref(),
+        ] {
+            hasher.update((field.len() as u64).to_be_bytes());
+            hasher.update(field);
+        }
+        hasher.finalize().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::Field;
+    use k256::Scalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn repeated_proofs_are_answered_from_the_cache() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let cache = VerifierCache::new(2);
+        let proofs: Vec<DLogProof> = (1..=3)
+            .map(|pid| DLogProof::prove("sid", pid, &x, y, g).unwrap())
+            .collect();
+
+        assert!(cache.verify(&proofs[0], "sid", 1, y, g).unwrap());
+        assert!(cache.verify(&proofs[0], "sid", 1, y, g).unwrap());
+        // The same proof under another participant ID is a different lookup, and invalid
+        assert!(!cache.verify(&proofs[0], "sid", 2, y, g).unwrap());
+        assert!(!cache.verify(&proofs[0], "sid", 2, y, g).unwrap());
+        assert_eq!(cache.stats().hits, 2);
+        assert_eq!(cache.stats().misses, 2);
+        assert_eq!(cache.stats().hit_rate(), 0.5);
+
+        // The least recently used result goes first
+        assert!(cache.verify(&proofs[0], "sid", 1, y, g).unwrap());
+        assert!(cache.verify(&proofs[2], "sid", 3, y, g).unwrap());
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.stats().evictions, 1);
+        assert!(!cache.invalidate(&proofs[0], "sid", 2, y, g));
+        assert!(cache.invalidate(&proofs[0], "sid", 1, y, g));
+        assert_eq!(cache.len(), 1);
+        assert!(cache.verify(&proofs[0], "sid", 1, y, g).unwrap());
+        assert_eq!(cache.stats().misses, 4);
+
+        // Errors aren't cached
+        let identity = ProjectivePoint::IDENTITY;
+        assert_eq!(
+            cache.verify(&proofs[1], "sid", 2, identity, g),
+            Err(DLogProofError::IdentityPoint)
+        );
+        assert_eq!(cache.len(), 2);
+
+        let cache = cache.verifier(Verifier::new().domain(b"app"));
+        assert!(cache.is_empty());
+        assert!(!cache.verify(&proofs[1], "sid", 2, y, g).unwrap());
+        cache.reset_stats();
+        assert_eq!(cache.stats(), CacheStats::default());
+    }
+}
@@ -0,0 +1,153 @@
+use serde::Serialize;
+use serde_json::{Map, Number, Value};
+
+use crate::error::DLogProofError;
+
+/// Serializes a value into canonical JSON as defined by RFC 8785 (JSON Canonicalization Scheme)
+///
+/// The output is byte-stable across implementations, so it can be hashed or signed:
+///  - no insignificant whitespace
+///  - object members sorted by the UTF-16 code units of their names
+///  - strings use the minimal JSON escaping (only `"`, `\` and control characters)
+///  - numbers use the ECMAScript `Number.prototype.toString` form
+///
+/// # Returns
+/// * `Ok(String)` - Canonical JSON text
+/// * `Err(DLogProofError)` - `Serialization` if the value cannot be represented as JSON
+pub fn to_canonical_json<T: Serialize>(value: &T) -> Result<String, DLogProofError> {
+    let value = serde_json::to_value(value)
+        .map_err(|e| invalid(format!("JSON serialization failed: {e}")))?;
+    let mut out = String::new();
+    write_value(&value, &mut out)?;
+    Ok(out)
+}
+
+fn write_value(value: &Value, out: &mut String) -> Result<(), DLogProofError> {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => out.push_str(&format_number(n)?),
+        Value::String(s) => write_string(s, out)?,
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(item, out)?;
+            }
+            out.push(']');
+        }
+        Value::Object(map) => write_object(map, out)?,
+    }
+    Ok(())
+}
+
+fn write_object(map: &Map<String, Value>, out: &mut String) -> Result<(), DLogProofError> {
+    // RFC 8785 orders members by UTF-16 code units, which differs from UTF-8 byte order
+    // for characters outside the Basic Multilingual Plane
+    let mut members: Vec<(&String, &Value)> = map.iter().collect();
+    members.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+    out.push('{');
+    for (i, (key, value)) in members.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_string(key, out)?;
+        out.push(':');
+        write_value(value, out)?;
+    }
+    out.push('}');
+    Ok(())
+}
+
+fn write_string(s: &str, out: &mut String) -> Result<(), DLogProofError> {
+    // serde_json already emits the RFC 8785 string form: short escapes for \b \t \n \f \r,
+    // lowercase \u00xx for other control characters and no escaping of non-ASCII text
+    let escaped =
+        serde_json::to_string(s).map_err(|e| invalid(format!("JSON serialization failed: {e}")))?;
+    out.push_str(&escaped);
+    Ok(())
+}
+
+/// Formats a number the way ECMAScript does, since RFC 8785 numbers are IEEE 754 doubles
+fn format_number(n: &Number) -> Result<String, DLogProofError> {
+    let v = n
+        .as_f64()
+        .ok_or_else(|| invalid("Number cannot be represented as a double".to_string()))?;
+    if !v.is_finite() {
+        return Err(invalid("Non-finite numbers are not valid JSON".to_string()));
+    }
+    if v == 0.0 {
+        // Also covers -0, which ECMAScript prints as "0"
+        return Ok("0".to_string());
+    }
+
+    let abs = v.abs();
+    if (1e-6..1e21).contains(&abs) {
+        // Rust's Display gives the shortest round-trip digits in plain decimal notation
+        return Ok(format!("{v}"));
+    }
+
+    // Exponent notation: ECMAScript always signs the exponent ("1e+21", "1e-7")
+    let formatted = format!("{v:e}");
+    Ok(match formatted.split_once('e') {
+        Some((mantissa, exp)) if !exp.starts_with('-') => format!("{mantissa}e+{exp}"),
+        _ => formatted,
+    })
+}
+
+fn invalid(reason: String) -> DLogProofError {
+    DLogProofError::Serialization(reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn sorts_members_and_strips_whitespace() {
+        let value = json!({ "s": "02", "t": { "y": 1, "x": 2 }, "a": [true, null] });
+        assert_eq!(
+            to_canonical_json(&value).unwrap(),
+            r#"{"a":[true,null],"s":"02","t":{"x":2,"y":1}}"#
+        );
+    }
+
+    #[test]
+    fn sorts_by_utf16_code_units() {
+        // U+1F600 (surrogate pair D83D DE00) sorts before U+FB01 in UTF-16 but after it in UTF-8
+        let value = json!({ "\u{fb01}": 1, "\u{1f600}": 2 });
+        assert_eq!(
+            to_canonical_json(&value).unwrap(),
+            "{\"\u{1f600}\":2,\"\u{fb01}\":1}"
+        );
+    }
+
+    #[test]
+    fn formats_numbers_like_ecmascript() {
+        // Values from RFC 8785 Appendix B
+        let cases = [
+            (json!(0.0), "0"),
+            (json!(-0.0), "0"),
+            (json!(1e21), "1e+21"),
+            (json!(1e20), "100000000000000000000"),
+            (json!(0.000001), "0.000001"),
+            (json!(1e-7), "1e-7"),
+            (json!(333333333.3333332), "333333333.3333332"),
+            (json!(9007199254740992.0), "9007199254740992"),
+            (json!(-5e-324), "-5e-324"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(to_canonical_json(&value).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn escapes_strings_minimally() {
+        let value = json!("\u{0}\u{8}\n\"\\/€");
+        assert_eq!(to_canonical_json(&value).unwrap(), r#""\u0000\b\n\"\\/€""#);
+    }
+}
@@ -0,0 +1,239 @@
+use elliptic_curve::subtle::{Choice, ConstantTimeEq};
+use elliptic_curve::PrimeField;
+use k256::{ProjectivePoint, Scalar};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::dlog_proof::{ChallengeParams, DLogProof};
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::participant::ParticipantId;
+use crate::report::{VerificationFailure, VerificationReport};
+use crate::secret::SecretScalar;
+use crate::serialization::ScalarHex;
+use crate::session::SessionId;
+use crate::stats::VerifyStats;
+
+/// Schnorr proof in challenge form (c, s) instead of commitment form (t, s)
+///
+/// The commitment is not transmitted: the verifier recomputes t = s * G - c * y and accepts
+/// if hashing it into the transcript reproduces `c`. This is equivalent to checking the
+/// `DLogProof` equation, and on secp256k1 it saves a byte over `DLogProof::ENCODED_LEN` (64
+/// bytes instead of 65). The challenge is the one of `DLogProof::prove`, so both forms can be
+/// converted into each other given the statement.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct CompactDLogProof<G: DLogGroup = ProjectivePoint> {
+    #[serde_as(as = "ScalarHex")]
+    c: G::Scalar,
+    #[serde_as(as = "ScalarHex")]
+    s: G::Scalar,
+}
+
+impl<G: DLogGroup> ConstantTimeEq for CompactDLogProof<G> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.c.ct_eq(&other.c) & self.s.ct_eq(&other.s)
+    }
+}
+
+/// Constant-time, like the equality of `DLogProof`
+impl<G: DLogGroup> PartialEq for CompactDLogProof<G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<G: DLogGroup> Eq for CompactDLogProof<G> {}
+
+impl<G: DLogGroup> CompactDLogProof<G> {
+    /// Assembles a proof from its challenge `c` and response `s`
+    pub fn new(c: G::Scalar, s: G::Scalar) -> Self {
+        CompactDLogProof { c, s }
+    }
+
+    /// The challenge c
+    pub fn c(&self) -> G::Scalar {
+        self.c
+    }
+
+    /// The response s = r + c * x
+    pub fn s(&self) -> G::Scalar {
+        self.s
+    }
+
+    /// Proves knowledge of `x` for y = x * G, see `DLogProof::prove`
+    pub fn prove<'s, 'p>(
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: G,
+        base_point: G,
+    ) -> Result<Self, DLogProofError> {
+        let sid = sid.into();
+        let pid = pid.into();
+        DLogProof::prove(sid, pid, x, y, base_point)?.to_compact(sid, pid, y, base_point)
+    }
+
+    /// Verifies the proof by recomputing t = s * G - c * y and its challenge
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Whether the recomputed challenge equals `c`
+    /// * `Err(DLogProofError)` - If the challenge can't be derived, or `IdentityPoint` for a
+    ///   degenerate statement
+    pub fn verify<'s, 'p>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        y: G,
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        DLogProof::check_statement(y, base_point)?;
+        let t = self.commitment(y, base_point);
+        let (c, _) = DLogProof::challenge(
+            &ChallengeParams::default(),
+            sid.into(),
+            pid.into(),
+            &[base_point, y, t],
+        )?;
+        Ok(c.ct_eq(&self.c).into())
+    }
+
+    /// Verifies the proof like `verify`, reporting a wrong challenge as
+    /// `VerificationFailure::ChallengeMismatch` and an identity statement as
+    /// `VerificationFailure::IdentityStatement`
+    pub fn verify_detailed<'s, 'p>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        y: G,
+        base_point: G,
+    ) -> Result<VerificationReport, DLogProofError> {
+        let report = self
+            .verify(sid, pid, y, base_point)
+            .map(|valid| VerificationReport {
+                failure: (!valid).then_some(VerificationFailure::ChallengeMismatch),
+                stats: VerifyStats::default(),
+            });
+        VerificationReport::from_result(report)
+    }
+
+    /// Converts to the (t, s) form, which verifies exactly when this proof does
+    pub fn to_proof(&self, y: G, base_point: G) -> DLogProof<G> {
+        DLogProof::new(self.commitment(y, base_point), self.s)
+    }
+
+    fn commitment(&self, y: G, base_point: G) -> G {
+        base_point * self.s - y * self.c
+    }
+}
+
+impl<G: DLogGroup> DLogProof<G> {
+    /// Converts to the (c, s) form by recomputing the challenge for the given statement
+    ///
+    /// The statement must be the one the proof was made for; otherwise the compact proof
+    /// carries a different challenge and won't verify.
+    pub fn to_compact<'s, 'p>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        y: G,
+        base_point: G,
+    ) -> Result<CompactDLogProof<G>, DLogProofError> {
+        let (c, _) = Self::challenge(
+            &ChallengeParams::default(),
+            sid.into(),
+            pid.into(),
+            &[base_point, y, self.t],
+        )?;
+        Ok(CompactDLogProof { c, s: self.s })
+    }
+}
+
+impl CompactDLogProof {
+    /// Size of the fixed binary encoding: big-endian `c` (32 bytes) || big-endian `s` (32 bytes)
+    pub const ENCODED_LEN: usize = 64;
+
+    /// Encodes the proof as `c || s`, both big-endian
+    pub fn to_bytes(&self) -> [u8; Self::ENCODED_LEN] {
+        let mut bytes = [0u8; Self::ENCODED_LEN];
+        bytes[..32].copy_from_slice(&self.c.to_repr());
+        bytes[32..].copy_from_slice(&self.s.to_repr());
+        bytes
+    }
+
+    /// Decodes the fixed binary encoding, rejecting non-canonical scalars
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DLogProofError> {
+        let invalid_length = || {
+            DLogProofError::Serialization(format!(
+                "Invalid compact proof length: expected {} bytes, got {}",
+                Self::ENCODED_LEN,
+                bytes.len()
+            ))
+        };
+        let (c_bytes, s_bytes) = bytes.split_first_chunk::<32>().ok_or_else(invalid_length)?;
+        let s_bytes: [u8; 32] = s_bytes.try_into().map_err(|_| invalid_length())?;
+        let scalar = |repr: [u8; 32]| {
+            Option::<Scalar>::from(Scalar::from_repr(repr.into()))
+                .ok_or(DLogProofError::InvalidScalar)
+        };
+        Ok(CompactDLogProof {
+            c: scalar(*c_bytes)?,
+            s: scalar(s_bytes)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::Field;
+    use rand_core::OsRng;
+
+    #[test]
+    fn compact_proofs_convert_and_verify() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+
+        let proof = DLogProof::prove("sid", 1, &x, y, g).unwrap();
+        let compact = proof.to_compact("sid", 1, y, g).unwrap();
+        assert!(compact.verify("sid", 1, y, g).unwrap());
+        assert!(!compact.verify("sid", 2, y, g).unwrap());
+        assert!(!compact.verify("sid", 1, g, g).unwrap());
+        assert_eq!(compact.to_proof(y, g), proof);
+
+        let compact = CompactDLogProof::prove("sid", 1, &x, y, g).unwrap();
+        assert!(compact.to_proof(y, g).verify("sid", 1, y, g).unwrap());
+
+        let tampered =
+            CompactDLogProof::<ProjectivePoint>::new(compact.c(), compact.s() + Scalar::ONE);
+        assert!(!tampered.verify("sid", 1, y, g).unwrap());
+        assert_eq!(
+            tampered.verify_detailed("sid", 1, y, g).unwrap().failure,
+            Some(VerificationFailure::ChallengeMismatch)
+        );
+        assert!(compact.verify_detailed("sid", 1, y, g).unwrap().is_valid());
+    }
+
+    #[test]
+    fn compact_proofs_roundtrip_through_bytes_and_json() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let compact = CompactDLogProof::prove("sid", 1, &x, g * x, g).unwrap();
+
+        let bytes = compact.to_bytes();
+        assert_eq!(CompactDLogProof::from_bytes(&bytes).unwrap(), compact);
+        assert!(CompactDLogProof::from_bytes(&bytes[1..]).is_err());
+        let mut above_order = bytes;
+        above_order[..32].fill(0xff);
+        assert_eq!(
+            CompactDLogProof::from_bytes(&above_order),
+            Err(DLogProofError::InvalidScalar)
+        );
+
+        let json = serde_json::to_string(&compact).unwrap();
+        let parsed: CompactDLogProof = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, compact);
+    }
+}
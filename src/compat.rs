@@ -0,0 +1,365 @@
+use elliptic_curve::Field;
+use rand_core::{CryptoRngCore, OsRng};
+use zeroize::Zeroizing;
+
+use crate::audit::digest;
+use crate::dlog_proof::{ChallengeHash, DLogProof};
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::participant::ParticipantId;
+use crate::secret::SecretScalar;
+use crate::session::SessionId;
+
+/// Encoding of the points hashed into a `CompatProfile` challenge
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PointEncoding {
+    /// `DLogGroup::encode_point`, compressed SEC1 on secp256k1
+    #[default]
+    Compressed,
+    /// Uncompressed SEC1, 0x04 || x || y; secp256k1 only
+    Uncompressed,
+}
+
+/// One input of a `CompatProfile` challenge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeField {
+    /// The profile's domain tag
+    Domain,
+    /// The base point G
+    Generator,
+    /// The public key y
+    PublicKey,
+    /// The commitment t
+    Commitment,
+    /// The session ID bytes
+    SessionId,
+    /// The participant ID: an index as 4 bytes big-endian, otherwise its bytes
+    ParticipantId,
+}
+
+/// How the response combines the nonce r and the secret x
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResponseForm {
+    /// s = r + c * x, checked as s * G = t + c * y (this crate's form)
+    #[default]
+    Add,
+    /// s = r - c * x, checked as s * G + c * y = t (RFC 8235)
+    Subtract,
+}
+
+/// Challenge derivation and response form of another Schnorr proof-of-knowledge
+/// implementation, for exchanging proofs with it
+///
+/// Many implementations derive the challenge as a hash of the concatenated points, such as
+/// `SHA-256(G || y || t)`, and differ only in the encodings, the field order and the hash.
+/// A profile describes such a layout: the challenge is the digest of the `fields` in order,
+/// each optionally preceded by its length as 4 bytes big-endian, read as a big-endian integer
+/// and reduced modulo the group order. `prove` makes proofs the other implementation accepts,
+/// and `verify` checks proofs it made; the proofs are plain `DLogProof`s, but only verify
+/// under the profile they were made with. None of this crate's transcript options apply.
+///
+/// `test_vectors/compat.json` has known-answer vectors for the presets and a custom
+/// profile, computed independently of this crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompatProfile {
+    hash: ChallengeHash,
+    domain: Vec<u8>,
+    fields: Vec<ChallengeField>,
+    encoding: PointEncoding,
+    length_prefixed: bool,
+    response: ResponseForm,
+}
+
+impl CompatProfile {
+    /// A profile hashing `fields` with SHA-256 over compressed points, without length
+    /// prefixes, with s = r + c * x
+    pub fn new(fields: &[ChallengeField]) -> Self {
+        CompatProfile {
+            hash: ChallengeHash::Sha256,
+            domain: Vec::new(),
+            fields: fields.to_vec(),
+            encoding: PointEncoding::Compressed,
+            length_prefixed: false,
+            response: ResponseForm::Add,
+        }
+    }
+
+    /// `SHA-256(G || y || t)` over compressed points with s = r + c * x, the layout of many
+    /// minimal implementations, e.g. JavaScript ones built on noble-curves
+    ///
+    /// The proof isn't bound to a session or participant; use a profile with `SessionId` and
+    /// `ParticipantId` fields where the other side supports them.
+    pub fn plain() -> Self {
+        Self::new(&[
+            ChallengeField::Generator,
+            ChallengeField::PublicKey,
+            ChallengeField::Commitment,
+        ])
+    }
+
+    /// The non-interactive Schnorr proof of RFC 8235 over an elliptic curve
+    ///
+    /// `c = SHA-256(G || V || A || UserID || OtherInfo)` with every item prefixed by its
+    /// 4-byte length, over uncompressed points, and s = r - c * x. The participant ID is the
+    /// UserID and the session ID the OtherInfo.
+    pub fn rfc8235() -> Self {
+        Self::new(&[
+            ChallengeField::Generator,
+            ChallengeField::Commitment,
+            ChallengeField::PublicKey,
+            ChallengeField::ParticipantId,
+            ChallengeField::SessionId,
+        ])
+        .encoding(PointEncoding::Uncompressed)
+        .length_prefixed(true)
+        .response(ResponseForm::Subtract)
+    }
+
+    /// Sets the hash function
+    pub fn hash(mut self, hash: ChallengeHash) -> Self {
+        self.hash = hash;
+        self
+    }
+
+    /// Sets the bytes of the `ChallengeField::Domain` field
+    pub fn domain(mut self, domain: &[u8]) -> Self {
+        self.domain = domain.to_vec();
+        self
+    }
+
+    pub fn encoding(mut self, encoding: PointEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Prefixes every field with its length as 4 bytes big-endian
+    pub fn length_prefixed(mut self, enabled: bool) -> Self {
+        self.length_prefixed = enabled;
+        self
+    }
+
+    pub fn response(mut self, response: ResponseForm) -> Self {
+        self.response = response;
+        self
+    }
+
+    /// Derives the challenge for the statement y = x * G and commitment t
+    ///
+    /// # Returns
+    /// * `Ok(Scalar)` - The challenge c
+    /// * `Err(DLogProofError)` - `UnsupportedChallengeMode` for uncompressed points on a
+    ///   group without SEC1 coordinates, or if the hash can't be reduced to a scalar
+    pub fn challenge<'s, 'p, G: DLogGroup>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        y: G,
+        base_point: G,
+        t: G,
+    ) -> Result<G::Scalar, DLogProofError> {
+        let (sid, pid) = (sid.into(), pid.into());
+        let mut transcript = Vec::new();
+        for field in &self.fields {
+            let bytes = match field {
+                ChallengeField::Domain => self.domain.clone(),
+                ChallengeField::Generator => self.encode(&base_point)?,
+                ChallengeField::PublicKey => self.encode(&y)?,
+                ChallengeField::Commitment => self.encode(&t)?,
+                ChallengeField::SessionId => sid.as_bytes().to_vec(),
+                ChallengeField::ParticipantId => match pid.index() {
+                    Some(index) => index.to_be_bytes().to_vec(),
+                    None => pid.as_bytes().unwrap_or_default().to_vec(),
+                },
+            };
+            if self.length_prefixed {
+                transcript.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+            }
+            transcript.extend_from_slice(&bytes);
+        }
+        G::challenge_from_hash(&digest(self.hash, &transcript))
+    }
+
+    /// Creates a proof of knowledge of `x` for y = x * G that the other implementation
+    /// accepts
+    ///
+    /// # Returns
+    /// * `Ok(DLogProof)` - The proof, in this profile's response form
+    /// * `Err(DLogProofError)` - `IdentityPoint` for a degenerate statement, or any error of
+    ///   `challenge`
+    pub fn prove<'s, 'p, G: DLogGroup>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: G,
+        base_point: G,
+    ) -> Result<DLogProof<G>, DLogProofError> {
+        self.prove_with_rng(&mut OsRng, sid, pid, x, y, base_point)
+    }
+
+    /// Creates a proof like `prove`, drawing the nonce from `rng`
+    pub fn prove_with_rng<'s, 'p, G: DLogGroup>(
+        &self,
+        rng: &mut impl CryptoRngCore,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: G,
+        base_point: G,
+    ) -> Result<DLogProof<G>, DLogProofError> {
+        DLogProof::check_statement(y, base_point)?;
+        let x = x.into();
+        let r = Zeroizing::new(G::Scalar::random(rng));
+        let t = base_point * *r;
+        let c = self.challenge(sid, pid, y, base_point, t)?;
+        let s = match self.response {
+            ResponseForm::Add => *r + c * x.expose_secret(),
+            ResponseForm::Subtract => *r - c * x.expose_secret(),
+        };
+        Ok(DLogProof::new(t, s))
+    }
+
+    /// Verifies a proof made under this profile, e.g. by the other implementation
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Validity of the proof
+    /// * `Err(DLogProofError)` - `IdentityPoint` for a degenerate statement, or any error of
+    ///   `challenge`
+    pub fn verify<'s, 'p, G: DLogGroup>(
+        &self,
+        proof: &DLogProof<G>,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        y: G,
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        DLogProof::check_statement(y, base_point)?;
+        let c = self.challenge(sid, pid, y, base_point, proof.t)?;
+        let c = match self.response {
+            ResponseForm::Add => -c,
+            ResponseForm::Subtract => c,
+        };
+        Ok(G::lincomb(&[(base_point, proof.s), (y, c)])
+            .ct_eq(&proof.t)
+            .into())
+    }
+
+    fn encode<G: DLogGroup>(&self, point: &G) -> Result<Vec<u8>, DLogProofError> {
+        match self.encoding {
+            PointEncoding::Compressed => Ok(point.encode_point()),
+            PointEncoding::Uncompressed => {
+                let (x, y) = point.coordinates()?;
+                Ok([&[0x04][..], &x, &y].concat())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::PrimeField;
+    use k256::{ProjectivePoint, Scalar};
+    use serde::Deserialize;
+
+    /// A vector of `test_vectors/compat.json`
+    #[derive(Deserialize)]
+    struct Vector {
+        name: String,
+        profile: Profile,
+        secret: String,
+        public: String,
+        sid: String,
+        pid: u32,
+        nonce: String,
+        challenge: String,
+        proof: String,
+    }
+
+    #[derive(Deserialize)]
+    struct Profile {
+        hash: String,
+        domain: String,
+        fields: Vec<String>,
+        encoding: String,
+        length_prefixed: bool,
+        response: String,
+    }
+
+    impl Profile {
+        fn to_profile(&self) -> CompatProfile {
+            let fields: Vec<ChallengeField> = self
+                .fields
+                .iter()
+                .map(|field| match field.as_str() {
+                    "domain" => ChallengeField::Domain,
+                    "generator" => ChallengeField::Generator,
+                    "public_key" => ChallengeField::PublicKey,
+                    "commitment" => ChallengeField::Commitment,
+                    "session_id" => ChallengeField::SessionId,
+                    "participant_id" => ChallengeField::ParticipantId,
+                    field => panic!("unknown field {field}"),
+                })
+                .collect();
+            CompatProfile::new(&fields)
+                .hash(ChallengeHash::from_name(&self.hash).unwrap())
+                .domain(self.domain.as_bytes())
+                .encoding(match self.encoding.as_str() {
+                    "compressed" => PointEncoding::Compressed,
+                    _ => PointEncoding::Uncompressed,
+                })
+                .length_prefixed(self.length_prefixed)
+                .response(match self.response.as_str() {
+                    "add" => ResponseForm::Add,
+                    _ => ResponseForm::Subtract,
+                })
+        }
+    }
+
+    fn scalar(value: &str) -> Scalar {
+        let bytes = <[u8; 32]>::try_from(hex::decode(value).unwrap()).unwrap();
+        Scalar::from_repr(bytes.into()).unwrap()
+    }
+
+    #[test]
+    fn profiles_match_the_shared_vectors() {
+        let vectors: Vec<Vector> =
+            serde_json::from_str(include_str!("../test_vectors/compat.json")).unwrap();
+        let g = ProjectivePoint::GENERATOR;
+        let profiles = [CompatProfile::plain(), CompatProfile::rfc8235()];
+        for vector in &vectors {
+            let profile = vector.profile.to_profile();
+            if vector.name.starts_with("plain") {
+                assert_eq!(profile, profiles[0]);
+            } else if vector.name == "rfc8235" {
+                assert_eq!(profile, profiles[1]);
+            }
+            let (x, r) = (scalar(&vector.secret), scalar(&vector.nonce));
+            let y = ProjectivePoint::decode_point(&hex::decode(&vector.public).unwrap()).unwrap();
+            assert_eq!(y, g * x, "{}", vector.name);
+            let proof = DLogProof::from_bytes(&hex::decode(&vector.proof).unwrap()).unwrap();
+            assert_eq!(proof.t, g * r, "{}", vector.name);
+
+            let c = profile
+                .challenge(vector.sid.as_str(), vector.pid, y, g, proof.t)
+                .unwrap();
+            assert_eq!(c, scalar(&vector.challenge), "{}", vector.name);
+            assert!(profile
+                .verify(&proof, vector.sid.as_str(), vector.pid, y, g)
+                .unwrap());
+            assert!(!profile
+                .verify(&proof, vector.sid.as_str(), vector.pid, y.double(), g)
+                .unwrap());
+
+            let proof = profile
+                .prove(vector.sid.as_str(), vector.pid, &x, y, g)
+                .unwrap();
+            assert!(profile
+                .verify(&proof, vector.sid.as_str(), vector.pid, y, g)
+                .unwrap());
+            // Profile proofs aren't proofs of this crate's transcript
+            assert!(!proof.verify(vector.sid.as_str(), vector.pid, y, g).unwrap());
+        }
+        assert_eq!(vectors.len(), 4);
+    }
+}
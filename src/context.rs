@@ -0,0 +1,186 @@
+use sha2::{Digest, Sha256};
+use std::io::{self, Write};
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::participant::ParticipantId;
+use crate::secret::SecretScalar;
+use crate::session::SessionId;
+
+/// Builds the application context of `DLogProof::prove_with_context` from labelled messages
+///
+/// Each message is absorbed as its length-prefixed label followed by the SHA-256 of its bytes,
+/// so messages can be written incrementally (`message` returns an `io::Write`) without knowing
+/// their length up front, and no two sequences of (label, message) pairs give the same
+/// context. A multi-MB file can be streamed in with `io::copy` instead of being read into
+/// a single slice:
+///
+/// ```
+/// # use elliptic_curve::Field;
+/// # use schnorr_dlog_zkp::k256::{ProjectivePoint, Scalar};
+/// use schnorr_dlog_zkp::context::ChallengeBuilder;
+/// # let g = ProjectivePoint::GENERATOR;
+/// # let x = Scalar::random(&mut rand_core::OsRng);
+/// # let y = g * x;
+/// # let mut file: &[u8] = &[0u8; 1 << 16];
+///
+/// let mut builder = ChallengeBuilder::new();
+/// builder.append(b"request", b"register device 7");
+/// std::io::copy(&mut file, &mut builder.message(b"firmware")).unwrap();
+/// let proof = builder.clone().prove("sid", 1, &x, y, g).unwrap();
+/// assert!(builder.verify(&proof, "sid", 1, y, g).unwrap());
+/// ```
+///
+/// The prover and verifier must append the same messages in the same order.
+#[derive(Debug, Clone)]
+pub struct ChallengeBuilder {
+    hasher: Sha256,
+}
+
+impl ChallengeBuilder {
+    /// Domain separation tag of the context hash
+    const DOMAIN_SEPARATOR: &'static [u8] = b"SCHNORR_PROOF/context";
+
+    pub fn new() -> Self {
+        ChallengeBuilder {
+            hasher: Sha256::new_with_prefix(Self::DOMAIN_SEPARATOR),
+        }
+    }
+
+    /// Appends a complete message under `label`
+    pub fn append(&mut self, label: &[u8], bytes: &[u8]) -> &mut Self {
+        self.message(label).absorb(bytes);
+        self
+    }
+
+    /// Starts a message under `label` whose bytes are written to the returned writer
+    ///
+    /// The message is appended when the writer is dropped.
+    pub fn message(&mut self, label: &[u8]) -> MessageWriter<'_> {
+        self.hasher.update((label.len() as u32).to_be_bytes());
+        self.hasher.update(label);
+        MessageWriter {
+            builder: self,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// The 32-byte context for `DLogProof::prove_with_context`, `ProofBuilder::aad` or
+    /// `Verifier::aad`
+    pub fn finalize(self) -> [u8; 32] {
+        self.hasher.finalize().into()
+    }
+
+    /// Creates a proof bound to the appended messages (see `DLogProof::prove_with_context`)
+    pub fn prove<'s, 'p, G: DLogGroup>(
+        self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: impl Into<G>,
+        base_point: G,
+    ) -> Result<DLogProof<G>, DLogProofError> {
+        DLogProof::prove_with_context(sid, pid, &self.finalize(), x, y, base_point)
+    }
+
+    /// Verifies a proof made for the same messages (see `DLogProof::verify_with_context`)
+    pub fn verify<'s, 'p, G: DLogGroup>(
+        self,
+        proof: &DLogProof<G>,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        y: G,
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        proof.verify_with_context(sid, pid, &self.finalize(), y, base_point)
+    }
+}
+
+impl Default for ChallengeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Writer for one message of a `ChallengeBuilder`, see `ChallengeBuilder::message`
+pub struct MessageWriter<'a> {
+    builder: &'a mut ChallengeBuilder,
+    hasher: Sha256,
+}
+
+impl MessageWriter<'_> {
+    fn absorb(&mut self, bytes: &[u8]) {
+        self.hasher.update(bytes);
+    }
+}
+
+impl Write for MessageWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.absorb(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for MessageWriter<'_> {
+    fn drop(&mut self) {
+        let digest = std::mem::take(&mut self.hasher).finalize();
+        self.builder.hasher.update(digest);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::Field;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_core::OsRng;
+
+    #[test]
+    fn streamed_and_appended_messages_agree() {
+        let data = vec![0xa5u8; 3 << 20];
+        let mut appended = ChallengeBuilder::new();
+        appended.append(b"header", b"v1").append(b"file", &data);
+
+        let mut streamed = ChallengeBuilder::new();
+        streamed.append(b"header", b"v1");
+        let mut writer = streamed.message(b"file");
+        for chunk in data.chunks(8191) {
+            writer.write_all(chunk).unwrap();
+        }
+        drop(writer);
+        assert_eq!(streamed.finalize(), appended.clone().finalize());
+
+        // Moving bytes between the label and the message, or between messages, changes it
+        let mut shifted = ChallengeBuilder::new();
+        shifted.append(b"headerv", b"1").append(b"file", &data);
+        assert_ne!(shifted.finalize(), appended.clone().finalize());
+        let mut split = ChallengeBuilder::new();
+        split
+            .append(b"header", b"v")
+            .append(b"", b"1")
+            .append(b"file", &data);
+        assert_ne!(split.finalize(), appended.finalize());
+    }
+
+    #[test]
+    fn builder_binds_the_proof() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let mut builder = ChallengeBuilder::new();
+        builder.append(b"request", b"register alice");
+
+        let proof = builder.clone().prove("sid", 1, &x, y, g).unwrap();
+        assert!(builder.clone().verify(&proof, "sid", 1, y, g).unwrap());
+        let context = builder.clone().finalize();
+        assert!(proof.verify_with_context("sid", 1, &context, y, g).unwrap());
+
+        builder.append(b"request", b"");
+        assert!(!builder.verify(&proof, "sid", 1, y, g).unwrap());
+    }
+}
@@ -0,0 +1,230 @@
+use k256::{ProjectivePoint, Scalar};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::canonical_json::to_canonical_json;
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::multiformats::{public_key_to_multibase, Multibase};
+use crate::prover::Prover;
+use crate::verifier::Verifier;
+
+/// Data Integrity proof type shared by all cryptosuites
+const PROOF_TYPE: &str = "DataIntegrityProof";
+
+/// Cryptosuite name of DLOG-ownership proofs over JCS-canonicalized documents
+pub const CRYPTOSUITE: &str = "schnorr-dlog-secp256k1-jcs-2025";
+
+/// Options of a Data Integrity proof, placed next to `proofValue` in the `proof` object
+#[derive(Debug, Clone)]
+pub struct ProofOptions {
+    /// DID URL or other identifier resolving to the public key y, e.g. from `did_key_url`
+    pub verification_method: String,
+    /// Why the proof was made, usually `assertionMethod` or `authentication`
+    pub proof_purpose: String,
+    /// Optional XML Schema `dateTimeStamp`, e.g. `2025-01-01T00:00:00Z`
+    pub created: Option<String>,
+}
+
+/// Returns the `did:key` verification method URL of a public key
+///
+/// The key is the multibase (base58btc) `secp256k1-pub` encoding, used both as the DID and
+/// as the fragment, as in `did:key:zQ3s...#zQ3s...`.
+pub fn did_key_url(y: &ProjectivePoint) -> String {
+    let key = public_key_to_multibase(y, Multibase::Base58Btc);
+    format!("did:key:{key}#{key}")
+}
+
+/// Secures a JSON document (e.g. a verifiable credential) with a proof of knowledge of x
+///
+/// Follows the W3C Data Integrity JCS transformation: the document without `proof` and the
+/// proof configuration (the proof object without `proofValue`, carrying the document's
+/// `@context`) are each canonicalized with RFC 8785 and hashed with SHA-256, and the two
+/// hashes are bound into the DLOG proof's challenge as additional authenticated data. The
+/// verification method is the session ID, and the cryptosuite name the application domain.
+///
+/// # Returns
+/// * `Ok(Value)` - A copy of the document with the `proof` object added
+/// * `Err(DLogProofError)` - `Serialization` if the document is not a JSON object or already
+///   has a proof, or any error of proving
+pub fn add_proof(
+    document: &Value,
+    options: &ProofOptions,
+    x: &Scalar,
+) -> Result<Value, DLogProofError> {
+    let mut unsecured = document
+        .as_object()
+        .cloned()
+        .ok_or_else(|| invalid("Document must be a JSON object"))?;
+    if unsecured.contains_key("proof") {
+        return Err(invalid("Document already has a proof"));
+    }
+
+    let mut proof = Map::new();
+    proof.insert("type".to_string(), PROOF_TYPE.into());
+    proof.insert("cryptosuite".to_string(), CRYPTOSUITE.into());
+    if let Some(created) = &options.created {
+        proof.insert("created".to_string(), created.as_str().into());
+    }
+    proof.insert(
+        "verificationMethod".to_string(),
+        options.verification_method.as_str().into(),
+    );
+    proof.insert(
+        "proofPurpose".to_string(),
+        options.proof_purpose.as_str().into(),
+    );
+
+    let hash_data = hash_data(&unsecured, &proof)?;
+    let base_point = ProjectivePoint::GENERATOR;
+    let dlog_proof = Prover::new()
+        .domain(CRYPTOSUITE.as_bytes())
+        .aad(&hash_data)
+        .prove(
+            &options.verification_method,
+            0,
+            x,
+            base_point * x,
+            base_point,
+        )?;
+
+    proof.insert(
+        "proofValue".to_string(),
+        dlog_proof.to_multibase(Multibase::Base58Btc).into(),
+    );
+    unsecured.insert("proof".to_string(), Value::Object(proof));
+    Ok(Value::Object(unsecured))
+}
+
+/// Verifies the Data Integrity proof of a secured document against the public key y
+///
+/// Resolving the proof's `verificationMethod` to y is up to the caller, e.g. by decoding a
+/// `did:key` URL from `did_key_url`.
+///
+/// # Returns
+/// * `Ok(bool)` - Validity of the proof
+/// * `Err(DLogProofError)` - `Serialization` if the document has no proof of this
+///   cryptosuite or it is malformed, or any error of verification
+pub fn verify_proof(secured: &Value, y: ProjectivePoint) -> Result<bool, DLogProofError> {
+    let mut unsecured = secured
+        .as_object()
+        .cloned()
+        .ok_or_else(|| invalid("Document must be a JSON object"))?;
+    let mut proof = match unsecured.remove("proof") {
+        Some(Value::Object(proof)) => proof,
+        _ => return Err(invalid("Document has no proof object")),
+    };
+    if proof.get("type") != Some(&Value::from(PROOF_TYPE))
+        || proof.get("cryptosuite") != Some(&Value::from(CRYPTOSUITE))
+    {
+        return Err(DLogProofError::Serialization(format!(
+            "Proof is not a {PROOF_TYPE} using {CRYPTOSUITE}"
+        )));
+    }
+    let proof_value = match proof.remove("proofValue") {
+        Some(Value::String(value)) => value,
+        _ => return Err(invalid("Proof has no proofValue")),
+    };
+    let verification_method = proof
+        .get("verificationMethod")
+        .and_then(Value::as_str)
+        .ok_or_else(|| invalid("Proof has no verificationMethod"))?
+        .to_string();
+    // A proof-level @context must not diverge from the document's
+    if let Some(context) = proof.remove("@context") {
+        if unsecured.get("@context") != Some(&context) {
+            return Ok(false);
+        }
+    }
+
+    let hash_data = hash_data(&unsecured, &proof)?;
+    let dlog_proof = DLogProof::from_multibase(&proof_value)?;
+    Verifier::new()
+        .domain(CRYPTOSUITE.as_bytes())
+        .aad(&hash_data)
+        .verify(
+            &dlog_proof,
+            &verification_method,
+            0,
+            y,
+            ProjectivePoint::GENERATOR,
+        )
+}
+
+/// SHA-256(JCS(proof configuration)) || SHA-256(JCS(unsecured document))
+fn hash_data(
+    unsecured: &Map<String, Value>,
+    proof: &Map<String, Value>,
+) -> Result<Vec<u8>, DLogProofError> {
+    let mut config = proof.clone();
+    if let Some(context) = unsecured.get("@context") {
+        config.insert("@context".to_string(), context.clone());
+    }
+
+    let mut hash_data = Sha256::digest(to_canonical_json(&config)?).to_vec();
+    hash_data.extend_from_slice(&Sha256::digest(to_canonical_json(unsecured)?));
+    Ok(hash_data)
+}
+
+fn invalid(reason: &str) -> DLogProofError {
+    DLogProofError::Serialization(reason.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::Field;
+    use rand_core::OsRng;
+    use serde_json::json;
+
+    fn credential() -> Value {
+        json!({
+            "@context": ["https://www.w3.org/ns/credentials/v2"],
+            "type": ["VerifiableCredential"],
+            "issuer": "did:example:issuer",
+            "credentialSubject": {"id": "did:example:subject", "controls": "key-1"}
+        })
+    }
+
+    fn secure() -> (Value, ProjectivePoint) {
+        let x = Scalar::random(&mut OsRng);
+        let y = ProjectivePoint::GENERATOR * x;
+        let options = ProofOptions {
+            verification_method: did_key_url(&y),
+            proof_purpose: "assertionMethod".to_string(),
+            created: Some("2025-01-01T00:00:00Z".to_string()),
+        };
+        (add_proof(&credential(), &options, &x).unwrap(), y)
+    }
+
+    #[test]
+    fn secured_document_verifies() {
+        let (secured, y) = secure();
+        assert_eq!(secured["proof"]["cryptosuite"], CRYPTOSUITE);
+        assert!(secured["proof"]["proofValue"]
+            .as_str()
+            .unwrap()
+            .starts_with('z'));
+        assert!(verify_proof(&secured, y).unwrap());
+
+        let other = ProjectivePoint::GENERATOR * Scalar::random(&mut OsRng);
+        assert!(!verify_proof(&secured, other).unwrap());
+    }
+
+    #[test]
+    fn document_and_proof_options_are_bound() {
+        let (secured, y) = secure();
+
+        let mut tampered = secured.clone();
+        tampered["credentialSubject"]["controls"] = "key-2".into();
+        assert!(!verify_proof(&tampered, y).unwrap());
+
+        let mut tampered = secured.clone();
+        tampered["proof"]["proofPurpose"] = "authentication".into();
+        assert!(!verify_proof(&tampered, y).unwrap());
+
+        let mut tampered = secured;
+        tampered["proof"]["cryptosuite"] = "eddsa-jcs-2022".into();
+        assert!(verify_proof(&tampered, y).is_err());
+    }
+}
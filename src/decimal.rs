@@ -0,0 +1,147 @@
+use elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
+use k256::elliptic_curve::PrimeField;
+use k256::{AffinePoint, EncodedPoint, FieldBytes, ProjectivePoint, Scalar};
+
+use crate::error::DLogProofError;
+
+/// Formats a scalar as a decimal integer in [0, n)
+///
+/// Python/Sage tooling exchanges curve values as plain base-10 integers, so this and the other
+/// helpers below convert them to and from k256 types, rejecting anything out of range.
+pub fn scalar_to_decimal(scalar: &Scalar) -> String {
+    bytes_to_decimal(&scalar.to_bytes().into())
+}
+
+/// Parses a decimal integer into a scalar
+///
+/// # Returns
+/// * `Ok(Scalar)` - The scalar
+/// * `Err(DLogProofError)` - `InvalidScalar` if the string is not a decimal integer, or not
+///   below the group order n
+pub fn scalar_from_decimal(decimal: &str) -> Result<Scalar, DLogProofError> {
+    let bytes = decimal_to_bytes(decimal).ok_or(DLogProofError::InvalidScalar)?;
+    Option::from(Scalar::from_repr(FieldBytes::from(bytes))).ok_or(DLogProofError::InvalidScalar)
+}
+
+/// Formats the affine (x, y) coordinates of a point as decimal integers
+///
+/// # Returns
+/// * `Ok((String, String))` - The x and y coordinates
+/// * `Err(DLogProofError)` - `IdentityPoint` for the identity, which has no affine
+///   coordinates
+pub fn point_to_decimal(point: &ProjectivePoint) -> Result<(String, String), DLogProofError> {
+    let encoded = point.to_affine().to_encoded_point(false);
+    match (encoded.x(), encoded.y()) {
+        (Some(x), Some(y)) => Ok((
+            bytes_to_decimal(&(*x).into()),
+            bytes_to_decimal(&(*y).into()),
+        )),
+        _ => Err(DLogProofError::IdentityPoint),
+    }
+}
+
+/// Parses decimal affine coordinates into a point
+///
+/// # Returns
+/// * `Ok(ProjectivePoint)` - The point
+/// * `Err(DLogProofError)` - `InvalidPoint` if a coordinate is malformed or (x, y) is not on
+///   the curve
+pub fn point_from_decimal(x: &str, y: &str) -> Result<ProjectivePoint, DLogProofError> {
+    let x = FieldBytes::from(decimal_to_bytes(x).ok_or(DLogProofError::InvalidPoint)?);
+    let y = FieldBytes::from(decimal_to_bytes(y).ok_or(DLogProofError::InvalidPoint)?);
+    let encoded = EncodedPoint::from_affine_coordinates(&x, &y, false);
+    Option::<AffinePoint>::from(AffinePoint::from_encoded_point(&encoded))
+        .map(ProjectivePoint::from)
+        .ok_or(DLogProofError::InvalidPoint)
+}
+
+/// Converts a 256-bit big-endian integer to decimal by repeated division by 10
+fn bytes_to_decimal(bytes: &[u8; 32]) -> String {
+    let mut value = *bytes;
+    let mut digits = Vec::new();
+    loop {
+        let mut remainder = 0u32;
+        for byte in value.iter_mut() {
+            let current = (remainder << 8) | *byte as u32;
+            *byte = (current / 10) as u8;
+            remainder = current % 10;
+        }
+        digits.push(b'0' + remainder as u8);
+        if value.iter().all(|&b| b == 0) {
+            break;
+        }
+    }
+    digits
+        .iter()
+        .rev()
+        .map(|&digit| char::from(digit))
+        .collect()
+}
+
+/// Converts a decimal integer to a 256-bit big-endian integer, or `None` if the string is not
+/// a decimal integer or doesn't fit in 256 bits
+fn decimal_to_bytes(decimal: &str) -> Option<[u8; 32]> {
+    if decimal.is_empty() || !decimal.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let mut value = [0u8; 32];
+    for digit in decimal.bytes() {
+        // value = value * 10 + digit, from the least significant byte up
+        let mut carry = (digit - b'0') as u32;
+        for byte in value.iter_mut().rev() {
+            let current = *byte as u32 * 10 + carry;
+            *byte = current as u8;
+            carry = current >> 8;
+        }
+        if carry != 0 {
+            return None;
+        }
+    }
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::Field;
+    use rand_core::OsRng;
+
+    // secp256k1 generator coordinates and group order, as printed by Sage
+    const GX: &str =
+        "55066263022277343669578718895168534326250603453777594175500187360389116729240";
+    const GY: &str =
+        "32670510020758816978083085130507043184471273380659243275938904335757337482424";
+    const N: &str =
+        "115792089237316195423570985008687907852837564279074904382605163141518161494337";
+
+    #[test]
+    fn generator_matches_reference_values() {
+        let g = ProjectivePoint::GENERATOR;
+        assert_eq!(point_to_decimal(&g), Ok((GX.to_string(), GY.to_string())));
+        assert_eq!(point_from_decimal(GX, GY), Ok(g));
+        assert_eq!(
+            scalar_to_decimal(&-Scalar::ONE),
+            "115792089237316195423570985008687907852837564279074904382605163141518161494336"
+        );
+        assert_eq!(scalar_from_decimal(N), Err(DLogProofError::InvalidScalar));
+    }
+
+    #[test]
+    fn round_trips_and_rejects_malformed_input() {
+        let x = Scalar::random(&mut OsRng);
+        assert_eq!(scalar_from_decimal(&scalar_to_decimal(&x)), Ok(x));
+        assert_eq!(scalar_to_decimal(&Scalar::ZERO), "0");
+
+        for bad in ["", "-1", "12a", "1".repeat(80).as_str()] {
+            assert!(scalar_from_decimal(bad).is_err(), "{:?}", bad);
+        }
+        assert_eq!(
+            point_from_decimal(GX, GX),
+            Err(DLogProofError::InvalidPoint)
+        );
+        assert_eq!(
+            point_to_decimal(&ProjectivePoint::IDENTITY),
+            Err(DLogProofError::IdentityPoint)
+        );
+    }
+}
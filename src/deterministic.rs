@@ -0,0 +1,264 @@
+use alloc::vec::Vec;
+use elliptic_curve::{Field, PrimeField};
+use rand_core::{CryptoRng, CryptoRngCore, RngCore};
+use rfc6979::HmacDrbg;
+use sha2::{Digest, Sha256};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::participant::ParticipantId;
+use crate::prover::Prover;
+use crate::secret::SecretScalar;
+use crate::session::SessionId;
+
+/// Personalization string of the nonce DRBG, separating it from other uses of the secret
+const NONCE_PERSONALIZATION: &[u8] = b"SCHNORR_PROOF/deterministic-nonce";
+
+/// Personalization string of synthetic nonces (see `Prover::synthetic_nonces`)
+const SYNTHETIC_NONCE_PERSONALIZATION: &[u8] = b"SCHNORR_PROOF/synthetic-nonce";
+
+/// HMAC-DRBG (NIST SP 800-90A, as in RFC 6979) exposed as an RNG for scalar sampling
+struct DrbgRng(HmacDrbg<Sha256>);
+
+impl RngCore for DrbgRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.0.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.0.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.0.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for DrbgRng {}
+
+impl<G: DLogGroup> DLogProof<G> {
+    /// Creates a proof with a nonce derived deterministically, without any system randomness
+    ///
+    /// The nonce r is drawn from an HMAC-DRBG over SHA-256 (the generator of RFC 6979) seeded
+    /// with the secret x as entropy input and a hash of the statement (domain, `sid`, `pid`,
+    /// G, y) as nonce. Equal inputs give equal proofs, and any change to the statement gives
+    /// an unrelated nonce, so a broken or missing RNG can't make two proofs share a nonce.
+    pub fn prove_deterministic<'s, 'p>(
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: G,
+        base_point: G,
+    ) -> Result<Self, DLogProofError> {
+        Prover::default().prove_deterministic(sid, pid, x, y, base_point)
+    }
+}
+
+impl Prover {
+    /// Creates a proof like `DLogProof::prove_deterministic` with this configuration
+    ///
+    /// The transcript options (domain, additional authenticated data) are part of the hashed
+    /// statement. With blinding enabled the masks come from the same DRBG, so the proof stays
+    /// deterministic.
+    pub fn prove_deterministic<'s, 'p, G: DLogGroup>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: G,
+        base_point: G,
+    ) -> Result<DLogProof<G>, DLogProofError> {
+        let pid = pid.into();
+        let (sid, x) = (sid.into(), x.into());
+
+        let mut entropy = x.expose_secret().to_repr();
+        let drbg = HmacDrbg::new(
+            entropy.as_ref(),
+            &self.statement_hash(sid, pid, y, base_point),
+            NONCE_PERSONALIZATION,
+        );
+        entropy.as_mut().zeroize();
+        // The DRBG already derives the nonce from the secret, so it's used as is
+        let prover = self.clone().synthetic_nonces(false);
+        DLogProof::prove_with(&prover, &mut DrbgRng(drbg), sid, pid, &x, y, base_point)
+    }
+
+    /// Draws a synthetic nonce for proving y = x * G (see `Prover::synthetic_nonces`)
+    pub(crate) fn synthetic_nonce<G: DLogGroup>(
+        &self,
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        x: &SecretScalar<G::Scalar>,
+        y: G,
+        base_point: G,
+        rng: &mut impl CryptoRngCore,
+    ) -> Zeroizing<G::Scalar> {
+        let mut entropy = Zeroizing::new(Vec::with_capacity(64));
+        entropy.extend_from_slice(x.expose_secret().to_repr().as_ref());
+        let mut aux = Zeroizing::new([0u8; 32]);
+        rng.fill_bytes(&mut *aux);
+        entropy.extend_from_slice(&*aux);
+
+        let drbg = HmacDrbg::new(
+            &entropy,
+            &self.statement_hash(sid, pid, y, base_point),
+            SYNTHETIC_NONCE_PERSONALIZATION,
+        );
+        Zeroizing::new(G::Scalar::random(&mut DrbgRng(drbg)))
+    }
+
+    /// Hash of the domain, `sid`, `pid`, G and y under this configuration's transcript
+    fn statement_hash<G: DLogGroup>(
+        &self,
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        y: G,
+        base_point: G,
+    ) -> [u8; 32] {
+        let params = self.params();
+        let mut statement = Sha256::new();
+        DLogProof::absorb_transcript(
+            &mut statement,
+            params.version,
+            &DLogProof::<G>::domain(params),
+            sid,
+            pid,
+            &[base_point, y],
+        );
+        DLogProof::<G>::absorb_aad(&mut statement, params);
+        statement.finalize().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::Field;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_chacha::rand_core::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::OsRng;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn deterministic_proofs_depend_only_on_the_inputs() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+
+        let proof = DLogProof::prove_deterministic("sid", 1, &x, y, g).unwrap();
+        assert!(proof.verify("sid", 1, y, g).unwrap());
+        assert_eq!(
+            DLogProof::prove_deterministic("sid", 1, &x, y, g).unwrap(),
+            proof
+        );
+
+        // A different statement or transcript option changes the commitment, not just s
+        let other_pid = DLogProof::prove_deterministic("sid", 2, &x, y, g).unwrap();
+        let other_domain = Prover::new()
+            .domain(b"app")
+            .prove_deterministic("sid", 1, &x, y, g)
+            .unwrap();
+        assert_ne!(other_pid.t(), proof.t());
+        assert_ne!(other_domain.t(), proof.t());
+
+        let blinded = Prover::new()
+            .blinded(true)
+            .prove_deterministic("sid", 1, &x, y, g)
+            .unwrap();
+        assert_eq!(blinded, proof);
+    }
+
+    #[test]
+    fn nonce_matches_a_reference_drbg() {
+        // x = 1 over the generator: the nonce is the first scalar drawn from the DRBG
+        let g = ProjectivePoint::GENERATOR;
+        let proof = DLogProof::prove_deterministic("sid", 1, &Scalar::ONE, g, g).unwrap();
+
+        let mut statement = Sha256::new();
+        statement.update(b"SCHNORR_PROOF");
+        statement.update(b"sid");
+        statement.update(1u32.to_le_bytes());
+        statement.update(
+            hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap(),
+        );
+        statement.update(
+            hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap(),
+        );
+        let mut drbg = DrbgRng(HmacDrbg::<Sha256>::new(
+            &Scalar::ONE.to_repr(),
+            &statement.finalize(),
+            NONCE_PERSONALIZATION,
+        ));
+        let r = Scalar::random(&mut drbg);
+        assert_eq!(proof.t(), g * r);
+    }
+
+    #[test]
+    fn synthetic_nonces_survive_a_repeating_rng() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let prove = |prover: &Prover, pid: u32| {
+            let mut rng = ChaCha20Rng::seed_from_u64(7);
+            prover
+                .prove_with_rng(&mut rng, "sid", pid, &x, y, g)
+                .unwrap()
+        };
+
+        // A plain prover reuses the nonce across statements when the RNG repeats
+        let plain = Prover::new();
+        assert_eq!(prove(&plain, 1).t(), prove(&plain, 2).t());
+
+        let hardened = Prover::hardened();
+        let proof = prove(&hardened, 1);
+        assert!(proof.verify("sid", 1, y, g).unwrap());
+        assert_ne!(proof.t(), prove(&hardened, 2).t());
+        assert_ne!(proof.t(), prove(&plain, 1).t());
+        let fresh = hardened.prove("sid", 1, &x, y, g).unwrap();
+        assert_ne!(fresh.t(), proof.t());
+        assert!(fresh.verify("sid", 1, y, g).unwrap());
+
+        // Deterministic proofs keep their own nonces under the hardened profile
+        assert_eq!(
+            hardened.prove_deterministic("sid", 1, &x, y, g).unwrap(),
+            Prover::new()
+                .blinded(true)
+                .prove_deterministic("sid", 1, &x, y, g)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn hardened_proving_time_is_independent_of_hamming_weight() {
+        // Secrets of Hamming weight 1 and 191 (n - 1), proven alternately; the fastest
+        // run of each is the least disturbed by scheduling noise
+        let g = ProjectivePoint::GENERATOR;
+        let prover = Prover::hardened();
+        let secrets = [Scalar::ONE, -Scalar::ONE];
+        let mut fastest = [Duration::MAX; 2];
+        for _ in 0..40 {
+            for (x, fastest) in secrets.iter().zip(&mut fastest) {
+                let y = g * x;
+                let start = Instant::now();
+                prover.prove("sid", 1, x, y, g).unwrap();
+                *fastest = (*fastest).min(start.elapsed());
+            }
+        }
+        let ratio = fastest[0].as_secs_f64() / fastest[1].as_secs_f64();
+        assert!((0.8..1.25).contains(&ratio), "timing ratio {ratio}");
+    }
+}
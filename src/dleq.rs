@@ -0,0 +1,236 @@
+use elliptic_curve::subtle::{Choice, ConstantTimeEq};
+use elliptic_curve::Field;
+use k256::ProjectivePoint;
+use rand_core::CryptoRngCore;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use std::marker::PhantomData;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::secret::SecretScalar;
+use crate::serialization::{PointHex, ScalarHex};
+use crate::session::SessionId;
+use crate::sigma::{SigmaProof, SigmaProtocol};
+
+/// Statement of `Dleq`: a = x * G and b = x * H for the same x
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DleqStatement<G> {
+    pub g: G,
+    pub h: G,
+    pub a: G,
+    pub b: G,
+}
+
+/// Chaum-Pedersen proof of equality of discrete logarithms, the protocol of `DleqProof`
+///
+/// One nonce r for both bases: commitments t1 = r * G and t2 = r * H, response
+/// s = r + c * x, checks s * G = t1 + c * a and s * H = t2 + c * b.
+pub struct Dleq<G>(PhantomData<G>);
+
+impl<G: DLogGroup> SigmaProtocol for Dleq<G> {
+    type Group = G;
+    type Statement = DleqStatement<G>;
+    type Witness = SecretScalar<G::Scalar>;
+    type Commitment = (G, G);
+    type Response = G::Scalar;
+    type Nonce = G::Scalar;
+
+    const DOMAIN: &'static [u8] = b"SCHNORR_DLEQ_PROOF";
+
+    fn statement_points(statement: &DleqStatement<G>) -> Vec<G> {
+        vec![statement.g, statement.h, statement.a, statement.b]
+    }
+
+    fn commitment_points(&(t1, t2): &(G, G)) -> Vec<G> {
+        vec![t1, t2]
+    }
+
+    fn commit(
+        statement: &DleqStatement<G>,
+        _x: &SecretScalar<G::Scalar>,
+        rng: &mut impl CryptoRngCore,
+    ) -> ((G, G), G::Scalar) {
+        let r = G::Scalar::random(rng);
+        ((statement.g * r, statement.h * r), r)
+    }
+
+    fn respond(x: &SecretScalar<G::Scalar>, r: &G::Scalar, c: &G::Scalar) -> G::Scalar {
+        *r + *c * x.expose_secret()
+    }
+
+    fn check(
+        statement: &DleqStatement<G>,
+        &(t1, t2): &(G, G),
+        c: &G::Scalar,
+        s: &G::Scalar,
+    ) -> bool {
+        let first = (statement.g * s).ct_eq(&(t1 + statement.a * c));
+        let second = (statement.h * s).ct_eq(&(t2 + statement.b * c));
+        (first & second).into()
+    }
+}
+
+/// Proof that log_G(a) = log_H(b), i.e. a = x * G and b = x * H for a secret x
+///
+/// The Chaum-Pedersen protocol (see `Dleq`) made non-interactive like `DLogProof`:
+/// * `t1` - Commitment t1 = r * G
+/// * `t2` - Commitment t2 = r * H
+/// * `s` - Response s = r + c * x
+///
+/// The challenge hashes sid, pid, G, H, a, b, t1 and t2 under its own domain separation tag,
+/// and the proof serializes with the same hex formats as `DLogProof`. H must be a generator
+/// whose discrete log relative to G is unknown, e.g. from `generators::nums_generator`.
+#[serde_as]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct DleqProof<G: DLogGroup = ProjectivePoint> {
+    #[serde_as(as = "PointHex")]
+    t1: G,
+    #[serde_as(as = "PointHex")]
+    t2: G,
+    #[serde_as(as = "ScalarHex")]
+    s: G::Scalar,
+}
+
+impl<G: DLogGroup> DleqProof<G> {
+    /// Creates a proof from its components, e.g. after decoding them
+    pub fn new(t1: G, t2: G, s: G::Scalar) -> Self {
+        DleqProof { t1, t2, s }
+    }
+
+    /// The commitment t1 = r * G
+    pub fn t1(&self) -> G {
+        self.t1
+    }
+
+    /// The commitment t2 = r * H
+    pub fn t2(&self) -> G {
+        self.t2
+    }
+
+    /// The response s = r + c * x
+    pub fn s(&self) -> G::Scalar {
+        self.s
+    }
+
+    /// Proves that a = x * G and b = x * H for the secret `x`
+    ///
+    /// # Arguments
+    /// * `sid` - Session identifier used for domain separation, a string or bytes (see `SessionId`)
+    /// * `pid` - Participant ID for uniqueness to distinguish different proofs
+    /// * `x` - The secret scalar
+    /// * `a` - The public point a = x * G
+    /// * `b` - The public point b = x * H
+    /// * `g` - The first base point G
+    /// * `h` - The second base point H
+    ///
+    /// # Returns
+    /// * `Ok(DleqProof)` - The proof
+    /// * `Err(DLogProofError)` - `IdentityPoint` if a base point or public point is the
+    ///   identity, or if the challenge can't be derived
+    pub fn prove<'s>(
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        a: G,
+        b: G,
+        g: G,
+        h: G,
+    ) -> Result<Self, DLogProofError> {
+        let statement = DleqStatement { g, h, a, b };
+        Self::check_statement(&statement)?;
+        let proof = SigmaProof::<Dleq<G>>::prove(sid, pid, &statement, &x.into())?;
+        let (t1, t2) = proof.commitment;
+        Ok(DleqProof {
+            t1,
+            t2,
+            s: proof.response,
+        })
+    }
+
+    /// Verifies that the proof shows log_G(a) = log_H(b)
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Validity of the proof
+    /// * `Err(DLogProofError)` - `IdentityPoint` for a degenerate statement, or if the
+    ///   challenge can't be derived
+    pub fn verify<'s>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        a: G,
+        b: G,
+        g: G,
+        h: G,
+    ) -> Result<bool, DLogProofError> {
+        let statement = DleqStatement { g, h, a, b };
+        Self::check_statement(&statement)?;
+        let proof = SigmaProof::<Dleq<G>> {
+            commitment: (self.t1, self.t2),
+            response: self.s,
+        };
+        proof.verify(sid, pid, &statement)
+    }
+
+    /// Rejects statements with an identity point, for which the proof shows nothing
+    fn check_statement(statement: &DleqStatement<G>) -> Result<(), DLogProofError> {
+        DLogProof::check_statement(statement.a, statement.g)?;
+        DLogProof::check_statement(statement.b, statement.h)
+    }
+}
+
+impl<G: DLogGroup> ConstantTimeEq for DleqProof<G> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.t1.ct_eq(&other.t1) & self.t2.ct_eq(&other.t2) & self.s.ct_eq(&other.s)
+    }
+}
+
+impl<G: DLogGroup> PartialEq for DleqProof<G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<G: DLogGroup> Eq for DleqProof<G> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::nums_generator;
+    use k256::Scalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn dleq_proofs_verify_and_roundtrip() {
+        let g = ProjectivePoint::GENERATOR;
+        let h = nums_generator("dleq-test").unwrap();
+        let x = Scalar::random(&mut OsRng);
+        let (a, b) = (g * x, h * x);
+
+        let proof = DleqProof::prove("sid", 1, &x, a, b, g, h).unwrap();
+        assert!(proof.verify("sid", 1, a, b, g, h).unwrap());
+        assert!(!proof.verify("sid", 2, a, b, g, h).unwrap());
+
+        let json = serde_json::to_string(&proof).unwrap();
+        let decoded: DleqProof = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(decoded.verify("sid", 1, a, b, g, h).unwrap());
+    }
+
+    #[test]
+    fn different_logarithms_are_rejected() {
+        let g = ProjectivePoint::GENERATOR;
+        let h = nums_generator("dleq-test").unwrap();
+        let x = Scalar::random(&mut OsRng);
+        let (a, b) = (g * x, h * (x + Scalar::ONE));
+
+        let proof = DleqProof::prove("sid", 1, &x, a, b, g, h).unwrap();
+        assert!(!proof.verify("sid", 1, a, b, g, h).unwrap());
+        assert_eq!(
+            DleqProof::prove("sid", 1, &x, a, ProjectivePoint::IDENTITY, g, h),
+            Err(DLogProofError::IdentityPoint)
+        );
+    }
+}
@@ -1,14 +1,52 @@
+use alloc::format;
+#[cfg(feature = "json")]
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use blake2::Blake2b512;
+use core::fmt;
 use elliptic_curve::group::GroupEncoding;
-use elliptic_curve::subtle::ConstantTimeEq;
-use elliptic_curve::{Field, PrimeField};
+use elliptic_curve::hash2curve::{ExpandMsg, ExpandMsgXmd, Expander};
+use elliptic_curve::subtle::{Choice, ConstantTimeEq};
+#[cfg(feature = "prove")]
+use elliptic_curve::Field;
+use elliptic_curve::PrimeField;
+use hmac::digest::core_api::BlockSizeUser;
+use hmac::{Mac, SimpleHmac};
 use k256::{ProjectivePoint, Scalar};
+#[cfg(feature = "prove")]
+use rand_core::CryptoRngCore;
+#[cfg(feature = "std")]
 use rand_core::OsRng;
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
+use serde_with::serde_as;
+use sha2::{Digest, Sha256, Sha512};
+use sha3::Sha3_256;
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+#[cfg(feature = "prove")]
+use zeroize::Zeroizing;
 
-use crate::serialization::{
-    deserialize_point_hex, deserialize_scalar_hex, serialize_point_hex, serialize_scalar_hex,
-};
+use crate::error::DLogProofError;
+use crate::group::{decode_canonical, DLogGroup};
+use crate::participant::ParticipantId;
+#[cfg(feature = "prove")]
+use crate::prover::{blinded_mul, blinded_response, scrub_stack, Prover};
+#[cfg(feature = "verify")]
+use crate::report::{VerificationFailure, VerificationReport};
+#[cfg(feature = "prove")]
+use crate::secret::SecretScalar;
+use crate::serialization::{PointHex, ScalarHex};
+use crate::session::SessionId;
+#[cfg(feature = "prove")]
+use crate::sigma::{SchnorrDLog, SigmaProtocol};
+#[cfg(feature = "prove")]
+use crate::stats::ProveStats;
+#[cfg(feature = "verify")]
+use crate::stats::VerifyStats;
+use crate::stats::{Span, Stopwatch};
+#[cfg(feature = "verify")]
+use crate::verifier::Timing;
 
 /// This struct represents a proof that demonstrates the prover knows a secret value x (the discrete logarithm)
 ///
@@ -20,54 +58,692 @@ use crate::serialization::{
 ///   - r is a random scalar
 ///   - c is challenge value
 ///   - x is the secret scalar that we're proving knowledge of
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct DLogProof {
-    #[serde(
-        serialize_with = "serialize_point_hex",
-        deserialize_with = "deserialize_point_hex"
-    )]
-    pub(crate) t: ProjectivePoint,
-    #[serde(
-        serialize_with = "serialize_scalar_hex",
-        deserialize_with = "deserialize_scalar_hex"
-    )]
-    pub(crate) s: Scalar,
+///
+/// The proof works over any `DLogGroup`; without a type argument `DLogProof` means a proof
+/// over secp256k1, and the fixed-size encodings and identifiers are only defined there.
+///
+/// Equality is constant-time (see the `ConstantTimeEq` implementation), so comparing a received
+/// proof against an expected one doesn't leak through timing where the two differ.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct DLogProof<G: DLogGroup = ProjectivePoint> {
+    #[serde_as(as = "PointHex")]
+    pub(crate) t: G,
+    #[serde_as(as = "ScalarHex")]
+    pub(crate) s: G::Scalar,
 }
 
-impl DLogProof {
+impl<G: DLogGroup> ConstantTimeEq for DLogProof<G> {
+    /// Compares both components without branching on either, so the time taken doesn't
+    /// depend on which component (or which bytes of it) differ
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.t.ct_eq(&other.t) & self.s.ct_eq(&other.s)
+    }
+}
+
+impl<G: DLogGroup> PartialEq for DLogProof<G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<G: DLogGroup> Eq for DLogProof<G> {}
+
+/// Stable content-addressed identifier of a proof, usable as a deduplication or storage key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ProofId(pub [u8; 32]);
+
+impl fmt::Display for ProofId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+/// Policy for fields in a serialized proof that this version doesn't know about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnknownFieldPolicy {
+    /// Reject the proof if it carries any unknown field
+    Strict,
+    /// Ignore unknown fields (e.g. metadata attached by newer provers) and report their names
+    Tolerant,
+}
+
+/// Hash function used to derive the Fiat-Shamir challenge
+///
+/// Every choice other than the default SHA-256 is named in the domain separation tag, so a
+/// transcript hashed with one function can never be mistaken for one hashed with another.
+/// The whole digest is reduced modulo the group order (see `DLogGroup::challenge_from_hash`),
+/// so the 512-bit hashes give statistically uniform challenges on any group.
+///
+/// Under `TranscriptVersion::V3` every hash is widened to 64 bytes before the reduction, so
+/// all of them give uniform challenges. The earlier versions reduce the digest as it is:
+/// the 32-byte hashes are then biased by about (2^256 mod n) / n, below 2^-127 on secp256k1
+/// but 2^-32 on P-256, and stay that way so existing proofs keep verifying.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChallengeHash {
+    /// SHA-256, the original challenge hash
+    #[default]
+    Sha256,
+    /// SHA-512
+    Sha512,
+    /// SHA3-256
+    Sha3_256,
+    /// BLAKE2b with 512-bit output
+    Blake2b512,
+}
+
+impl ChallengeHash {
+    pub(crate) const ALL: [ChallengeHash; 4] = [
+        ChallengeHash::Sha256,
+        ChallengeHash::Sha512,
+        ChallengeHash::Sha3_256,
+        ChallengeHash::Blake2b512,
+    ];
+
+    /// Length of the digest in bytes
+    pub(crate) fn output_len(self) -> usize {
+        match self {
+            ChallengeHash::Sha256 | ChallengeHash::Sha3_256 => 32,
+            ChallengeHash::Sha512 | ChallengeHash::Blake2b512 => 64,
+        }
+    }
+
+    /// Name of the hash function, e.g. `SHA-256` or `BLAKE2b-512`
+    pub fn name(self) -> &'static str {
+        match self {
+            ChallengeHash::Sha256 => "SHA-256",
+            ChallengeHash::Sha512 => "SHA-512",
+            ChallengeHash::Sha3_256 => "SHA3-256",
+            ChallengeHash::Blake2b512 => "BLAKE2b-512",
+        }
+    }
+
+    /// Looks up a hash function by its `name`
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|hash| hash.name() == name)
+    }
+
+    /// Suffix appended to the base domain separation tag
+    fn domain_suffix(self) -> &'static [u8] {
+        match self {
+            ChallengeHash::Sha256 => b"",
+            ChallengeHash::Sha512 => b"/SHA-512",
+            ChallengeHash::Sha3_256 => b"/SHA3-256",
+            ChallengeHash::Blake2b512 => b"/BLAKE2b-512",
+        }
+    }
+}
+
+/// How the Fiat-Shamir challenge is derived
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChallengeMode {
+    /// Hash of the crate's transcript: domain, sid, pid, G, y and t (see `ChallengeHash`)
+    #[default]
+    Standard,
+    /// BIP-340 challenge over x-only `t` and `y`, for secp256k1 only
+    ///
+    /// The challenge is the tagged hash `BIP0340/challenge` of x(t) || x(y) || m, where the
+    /// 32-byte message m commits to the domain, sid, pid, base point and additional
+    /// authenticated data (see `Verifier::bip340_message`). Provers normalize t and y to even
+    /// y-coordinates, so with the standard generator x(t) || s is a BIP-340 signature of m
+    /// under the x-only key of y. Can't be combined with an HMAC key or another hash.
+    Bip340Tagged,
+    /// keccak256 over ABI-encodable data, reproducible by an EVM contract (`evm` feature)
+    ///
+    /// The challenge is `uint256(keccak256(abi.encodePacked(domain, sid, uint32(pid), Gx, Gy,
+    /// yx, yy, tx, ty))) % N` over the uncompressed point coordinates, with the length-prefixed
+    /// additional authenticated data appended if set (see `DLogProof::evm_challenge`). For
+    /// secp256k1 only; can't be combined with an HMAC key or another hash.
+    #[cfg(feature = "evm")]
+    Keccak256Evm,
+    /// RFC 9380 `hash_to_field` over the crate's transcript, for interop with implementations
+    /// deriving challenges that way
+    ///
+    /// The transcript (as laid out by the `TranscriptVersion`, followed by the additional
+    /// authenticated data) is the message of `expand_message_xmd` under the DST set with
+    /// `Prover::xmd_dst`, expanded to 48 bytes and reduced modulo the group order. With SEC1
+    /// groups this is `hash_to_field(msg, 1)` for the scalar field with k = 128. The hash is
+    /// the XMD hash: `ChallengeHash::Sha256` or `Sha512`. Can't be combined with an HMAC key.
+    ExpandMessageXmd,
+}
+
+impl ChallengeMode {
+    /// Suffix appended to the base domain separation tag
+    fn domain_suffix(self) -> &'static [u8] {
+        match self {
+            ChallengeMode::Standard | ChallengeMode::Bip340Tagged => b"",
+            #[cfg(feature = "evm")]
+            ChallengeMode::Keccak256Evm => b"/EVM-keccak256",
+            ChallengeMode::ExpandMessageXmd => b"/expand_message_xmd",
+        }
+    }
+}
+
+/// Layout of the fields in the challenge transcript
+///
+/// Proofs only verify under the version they were made with; the version is part of the
+/// transcript parameters like the hash and mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TranscriptVersion {
+    /// The original transcript, domain || sid || pid (4 bytes little-endian) || points
+    ///
+    /// The fields are concatenated without framing. The sid is still determined by the
+    /// transcript length with an index pid, and an application domain leads the transcript
+    /// (see `Prover::domain`), but a byte-string `ParticipantId` directly follows the sid, so
+    /// sids and byte-string pids can trade bytes.
+    #[default]
+    V1,
+    /// Every field labelled and length-prefixed, with the domain tagged `/v2`
+    ///
+    /// Each field is absorbed as `label || len (8 bytes big-endian) || bytes`: `dom`, `sid`,
+    /// `pid` (4 bytes big-endian), `pts` (the point count, 4 bytes big-endian) and `pt` for
+    /// each point. Distinct inputs therefore never produce the same transcript.
+    /// Not available with `ChallengeMode::Keccak256Evm`, whose encoding is fixed by the
+    /// contract.
+    V2,
+    /// The v2 layout with the domain tagged `/v3`, and a challenge reduced from 64 bytes
+    ///
+    /// A hash with a 64-byte digest is used as it is; a 32-byte hash H is run twice, as
+    /// H(transcript || 0x00) || H(transcript || 0x01) over the whole transcript including the
+    /// additional authenticated data, and an HMAC the same way. The challenge is then
+    /// statistically uniform on any group, P-256 included. `ChallengeMode::ExpandMessageXmd`
+    /// already expands to 48 bytes and is unchanged. Not available with
+    /// `ChallengeMode::Bip340Tagged` or `Keccak256Evm`, whose 32-byte challenge hashes are
+    /// fixed by BIP-340 and the contract.
+    V3,
+}
+
+impl TranscriptVersion {
+    /// Suffix appended to the base domain separation tag
+    fn domain_suffix(self) -> &'static [u8] {
+        match self {
+            TranscriptVersion::V1 => b"",
+            TranscriptVersion::V2 => b"/v2",
+            TranscriptVersion::V3 => b"/v3",
+        }
+    }
+
+    /// Whether the fields are labelled and length-prefixed, as from v2 on
+    pub(crate) fn framed(self) -> bool {
+        self != TranscriptVersion::V1
+    }
+
+    /// The challenge hash output: `hash` over the transcript alone, or under v3 for a digest
+    /// of `output_len` bytes shorter than 64, the digests over the transcript followed by the
+    /// counter bytes 0, 1, ... concatenated up to at least 64 bytes
+    pub(crate) fn challenge_hash(
+        self,
+        output_len: usize,
+        hash: impl Fn(&[u8]) -> Vec<u8>,
+    ) -> Vec<u8> {
+        if self != TranscriptVersion::V3 || output_len >= WIDE_CHALLENGE_LEN {
+            return hash(&[]);
+        }
+        (0..WIDE_CHALLENGE_LEN.div_ceil(output_len) as u8)
+            .flat_map(|counter| hash(&[counter]))
+            .collect()
+    }
+}
+
+/// Bytes of hash output a `TranscriptVersion::V3` challenge is reduced from
+const WIDE_CHALLENGE_LEN: usize = 64;
+
+/// BIP-340 tag of the challenge hash
+const BIP340_CHALLENGE_TAG: &[u8] = b"BIP0340/challenge";
+
+/// Tag of the BIP-340 message committing to the proof context
+const BIP340_MESSAGE_TAG: &[u8] = b"SCHNORR_PROOF/BIP340/message";
+
+/// SHA-256 with the BIP-340 tagged-hash prefix SHA256(tag) || SHA256(tag) absorbed
+pub(crate) fn tagged_hasher(tag: &[u8]) -> Sha256 {
+    let tag_hash = Sha256::digest(tag);
+    let mut hasher = Sha256::new();
+    Digest::update(&mut hasher, tag_hash);
+    Digest::update(&mut hasher, tag_hash);
+    hasher
+}
+
+/// SHA-256 computed outside the crate, over the concatenation of `parts`
+///
+/// Set with `Verifier::sha256` (or `Prover::sha256`) to derive the default SHA-256 challenge
+/// with a platform's own implementation, e.g. the `sol_sha256` syscall of a Solana program
+/// (see the `solana` module) or a zkVM's accelerated SHA-256. The parts have the layout of
+/// Solana's syscall, so that one can be passed through as it is.
+pub type Sha256Hook = fn(&[&[u8]]) -> [u8; 32];
+
+/// Fiat-Shamir transcript parameters; prover and verifier must use the same ones
+///
+/// The default parameters reproduce the original transcript, so proofs created before
+/// these options existed keep verifying.
+#[derive(Clone, Default)]
+pub(crate) struct ChallengeParams {
+    /// Application domain separation tag, combined with the crate's base tag
+    pub(crate) app_domain: Option<Vec<u8>>,
+    /// Key for HMAC-SHA256 challenge derivation instead of plain SHA-256
+    pub(crate) hmac_key: Option<Vec<u8>>,
+    /// Additional authenticated data bound into the challenge after the points
+    pub(crate) aad: Option<Vec<u8>>,
+    /// Hash function (or the hash of the HMAC) deriving the challenge
+    pub(crate) hash: ChallengeHash,
+    /// Challenge derivation, the crate's transcript or BIP-340
+    pub(crate) mode: ChallengeMode,
+    /// Layout of the transcript fields
+    pub(crate) version: TranscriptVersion,
+    /// Domain separation tag of `ChallengeMode::ExpandMessageXmd`
+    pub(crate) xmd_dst: Option<Vec<u8>>,
+    /// Validity window (not before, not after) of a `TimedProof`, bound into the challenge
+    /// after the additional authenticated data
+    pub(crate) validity: Option<(u64, u64)>,
+    /// Replacement for the `sha2` crate's SHA-256 in the plain SHA-256 challenge
+    pub(crate) sha256: Option<Sha256Hook>,
+    /// Timing of the verification's group operations; not part of the transcript
+    #[cfg(feature = "verify")]
+    pub(crate) timing: Timing,
+}
+
+impl fmt::Debug for ChallengeParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The HMAC key is secret, only report whether one is set
+        let mut debug = f.debug_struct("ChallengeParams");
+        debug
+            .field("app_domain", &self.app_domain)
+            .field("hmac_key", &self.hmac_key.as_ref().map(|_| ".."))
+            .field("aad", &self.aad)
+            .field("hash", &self.hash)
+            .field("mode", &self.mode)
+            .field("version", &self.version)
+            .field("xmd_dst", &self.xmd_dst)
+            .field("validity", &self.validity)
+            .field("sha256", &self.sha256.map(|_| ".."));
+        #[cfg(feature = "verify")]
+        debug.field("timing", &self.timing);
+        debug.finish()
+    }
+}
+
+/// Marks the validity window in the transcript of a `TimedProof`
+const VALIDITY_TAG: &[u8] = b"SCHNORR_VALIDITY";
+
+/// Length of the `ChallengeMode::ExpandMessageXmd` output, L = ceil((256 + 128) / 8)
+const XMD_CHALLENGE_LEN: usize = 48;
+
+/// Transcript collected into a buffer, the message of `ChallengeMode::ExpandMessageXmd`
+#[derive(Default)]
+struct TranscriptBytes(Vec<u8>);
+
+impl hmac::digest::Update for TranscriptBytes {
+    fn update(&mut self, data: &[u8]) {
+        self.0.extend_from_slice(data);
+    }
+}
+
+/// RFC 9380 expand_message_xmd of `msg` to `XMD_CHALLENGE_LEN` bytes with `hash`
+fn expand_message_xmd(
+    hash: ChallengeHash,
+    msg: &[u8],
+    dst: &[u8],
+) -> Result<[u8; XMD_CHALLENGE_LEN], DLogProofError> {
+    let (msgs, dsts) = ([msg], [dst]);
+    let mut okm = [0u8; XMD_CHALLENGE_LEN];
+    match hash {
+        ChallengeHash::Sha256 => {
+            ExpandMsgXmd::<Sha256>::expand_message(&msgs, &dsts, okm.len())
+                .map_err(|_| DLogProofError::ChallengeDerivation)?
+                .fill_bytes(&mut okm);
+        }
+        ChallengeHash::Sha512 => {
+            ExpandMsgXmd::<Sha512>::expand_message(&msgs, &dsts, okm.len())
+                .map_err(|_| DLogProofError::ChallengeDerivation)?
+                .fill_bytes(&mut okm);
+        }
+        ChallengeHash::Sha3_256 | ChallengeHash::Blake2b512 => {
+            return Err(DLogProofError::UnsupportedChallengeMode)
+        }
+    }
+    Ok(okm)
+}
+
+/// Hash or MAC wrapper counting the bytes fed into it, for `ProveStats`/`VerifyStats`
+pub(crate) struct ByteCounter<H> {
+    pub(crate) inner: H,
+    pub(crate) count: usize,
+}
+
+impl<H> ByteCounter<H> {
+    pub(crate) fn new(inner: H) -> Self {
+        ByteCounter { inner, count: 0 }
+    }
+}
+
+impl<H: hmac::digest::Update> hmac::digest::Update for ByteCounter<H> {
+    fn update(&mut self, data: &[u8]) {
+        self.count += data.len();
+        self.inner.update(data);
+    }
+}
+
+impl<G: DLogGroup> DLogProof<G> {
     const DOMAIN_SEPARATOR: &'static [u8] = b"SCHNORR_PROOF";
 
-    /// Computes a challenge using Fiat-Shamir transform
+    /// Assembles a proof from its commitment `t` and response `s`, e.g. after custom decoding
     ///
-    /// Creates a challenge by hashing the session ID, participant ID, and a sequence of points.
-    /// The challenge is used as 'c' in the verification equation s * G = t + c * y.
+    /// Takes the components as they are; `from_parts` also rejects an identity commitment.
+    pub fn new(t: G, s: G::Scalar) -> Self {
+        DLogProof { t, s }
+    }
+
+    /// Assembles a proof from components received over a custom transport
     ///
-    /// # Arguments
-    /// * `sid` - Session identifier for domain separation
-    /// * `pid` - Participant ID for uniqueness to distinguish different proofs
-    /// * `points` - Sequence of points to be included in the challenge generation
+    /// An honest prover never sends t = 0, as it means the nonce r was zero and s = c * x
+    /// gives away the secret, so such a proof is rejected instead of being verified.
     ///
     /// # Returns
-    /// * `Ok(Scalar)` - Challenge scalar derived from the hash
-    /// * `Err(String)` - If the hash cannot be converted to a valid scalar
-    fn hash_points(sid: &str, pid: u32, points: &[ProjectivePoint]) -> Result<Scalar, String> {
+    /// * `Ok(DLogProof)` - The proof (t, s)
+    /// * `Err(DLogProofError::IdentityPoint)` - If `t` is the identity
+    pub fn from_parts(t: G, s: G::Scalar) -> Result<Self, DLogProofError> {
+        if bool::from(t.is_identity()) {
+            return Err(DLogProofError::IdentityPoint);
+        }
+        Ok(DLogProof { t, s })
+    }
+
+    /// Splits the proof into its commitment t and response s, the inverse of `from_parts`
+    pub fn into_parts(self) -> (G, G::Scalar) {
+        (self.t, self.s)
+    }
+
+    /// The commitment t = r * G
+    pub fn t(&self) -> G {
+        self.t
+    }
+
+    /// The response s = r + c * x
+    pub fn s(&self) -> G::Scalar {
+        self.s
+    }
+
+    /// Rejects degenerate statements, where the base point or y is the identity
+    ///
+    /// With y = 0 every proof (t, s) with t = s * G verifies, and with G = 0 every proof with
+    /// t = -c * y does, so neither statement shows knowledge of anything. Decoded points are
+    /// already on the curve (and in the prime-order subgroup), so this is the remaining check.
+    pub(crate) fn check_statement(y: G, base_point: G) -> Result<(), DLogProofError> {
+        if bool::from(base_point.is_identity() | y.is_identity()) {
+            return Err(DLogProofError::IdentityPoint);
+        }
+        Ok(())
+    }
+
+    /// Computes the Fiat-Shamir challenge c over the session ID, participant ID and `points`,
+    /// using the given transcript parameters
+    ///
+    /// Also returns the number of transcript bytes absorbed. The default parameters hash
+    /// SHA-256(DOMAIN_SEPARATOR || sid || pid || points).
+    pub(crate) fn challenge(
+        params: &ChallengeParams,
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        points: &[G],
+    ) -> Result<(G::Scalar, usize), DLogProofError> {
+        let domain = Self::domain(params);
+        if params.mode == ChallengeMode::Bip340Tagged {
+            return Self::bip340_challenge(params, &domain, sid, pid, points);
+        }
+        #[cfg(feature = "evm")]
+        if params.mode == ChallengeMode::Keccak256Evm {
+            return Self::evm_challenge(params, &domain, sid, pid, points);
+        }
+        if params.mode == ChallengeMode::ExpandMessageXmd {
+            return Self::xmd_challenge(params, &domain, sid, pid, points);
+        }
+
+        match params.hash {
+            ChallengeHash::Sha256 => match params.sha256 {
+                Some(sha256) if params.hmac_key.is_none() => {
+                    let mut msg = TranscriptBytes::default();
+                    Self::absorb_transcript(&mut msg, params.version, &domain, sid, pid, points);
+                    Self::absorb_aad(&mut msg, params);
+                    let hash = params
+                        .version
+                        .challenge_hash(32, |counter| sha256(&[&msg.0, counter]).to_vec());
+                    Ok((G::challenge_from_hash(&hash)?, msg.0.len()))
+                }
+                _ => Self::challenge_with::<Sha256>(params, &domain, sid, pid, points),
+            },
+            ChallengeHash::Sha512 => {
+                Self::challenge_with::<Sha512>(params, &domain, sid, pid, points)
+            }
+            ChallengeHash::Sha3_256 => {
+                Self::challenge_with::<Sha3_256>(params, &domain, sid, pid, points)
+            }
+            ChallengeHash::Blake2b512 => {
+                Self::challenge_with::<Blake2b512>(params, &domain, sid, pid, points)
+            }
+        }
+    }
+
+    /// Domain separation tag of the transcript under the given parameters
+    ///
+    /// An application tag is length-prefixed so tags of different applications can't collide.
+    /// Under `TranscriptVersion::V1` the sid follows the tag unframed, so a sid could spell
+    /// out any tag appended to the base tag: there the application tag goes first, as
+    /// `0x00 || len (4 bytes big-endian) || app_domain || base tag`, and a transcript without
+    /// one, which starts with the base tag, never starts with a zero byte. V2 and V3 frame the
+    /// whole tag as a field and append the application tag after the base tag instead.
+    pub(crate) fn domain(params: &ChallengeParams) -> Vec<u8> {
+        let mut domain = Vec::new();
+        let app_domain = params.app_domain.as_deref();
+        let put_app_domain = |domain: &mut Vec<u8>, app_domain: &[u8]| {
+            domain.extend_from_slice(&(app_domain.len() as u32).to_be_bytes());
+            domain.extend_from_slice(app_domain);
+        };
+        if let (Some(app_domain), TranscriptVersion::V1) = (app_domain, params.version) {
+            domain.push(0);
+            put_app_domain(&mut domain, app_domain);
+        }
+        domain.extend_from_slice(Self::DOMAIN_SEPARATOR);
+        domain.extend_from_slice(params.hash.domain_suffix());
+        domain.extend_from_slice(params.mode.domain_suffix());
+        domain.extend_from_slice(params.version.domain_suffix());
+        if let Some(app_domain) = app_domain.filter(|_| params.version.framed()) {
+            put_app_domain(&mut domain, app_domain);
+        }
+        domain
+    }
+
+    /// Computes the `ChallengeMode::Bip340Tagged` challenge for the points [G, y, t]
+    fn bip340_challenge(
+        params: &ChallengeParams,
+        domain: &[u8],
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        points: &[G],
+    ) -> Result<(G::Scalar, usize), DLogProofError> {
+        if params.hmac_key.is_some()
+            || params.hash != ChallengeHash::Sha256
+            || params.version == TranscriptVersion::V3
+        {
+            return Err(DLogProofError::UnsupportedChallengeMode);
+        }
+        let [base_point, y, t] = points else {
+            return Err(DLogProofError::UnsupportedChallengeMode);
+        };
+
+        let (message, message_bytes) = Self::bip340_message(params, domain, sid, pid, base_point);
+        let mut hasher = ByteCounter::new(tagged_hasher(BIP340_CHALLENGE_TAG));
+        for part in [t.x_only()?.0, y.x_only()?.0, message] {
+            hmac::digest::Update::update(&mut hasher, &part);
+        }
+        let c = G::challenge_from_hash(&hasher.inner.finalize())?;
+        Ok((c, message_bytes + hasher.count))
+    }
+
+    /// The message signed by a `ChallengeMode::Bip340Tagged` proof, and the bytes hashed for it
+    fn bip340_message(
+        params: &ChallengeParams,
+        domain: &[u8],
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        base_point: &G,
+    ) -> ([u8; 32], usize) {
+        let mut hasher = ByteCounter::new(tagged_hasher(BIP340_MESSAGE_TAG));
+        Self::absorb_transcript(
+            &mut hasher,
+            params.version,
+            domain,
+            sid,
+            pid,
+            &[*base_point],
+        );
+        Self::absorb_aad(&mut hasher, params);
+        (hasher.inner.finalize().into(), hasher.count)
+    }
+
+    /// Computes `bip340_message` for a verifier's parameters (see `Verifier::bip340_message`)
+    #[cfg(feature = "verify")]
+    pub(crate) fn bip340_message_for(
+        params: &ChallengeParams,
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        base_point: &G,
+    ) -> [u8; 32] {
+        Self::bip340_message(params, &Self::domain(params), sid, pid, base_point).0
+    }
+
+    /// Hashes (or MACs, if a key is set) the transcript with `D` into a challenge
+    fn challenge_with<D>(
+        params: &ChallengeParams,
+        domain: &[u8],
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        points: &[G],
+    ) -> Result<(G::Scalar, usize), DLogProofError>
+    where
+        D: Digest + BlockSizeUser + hmac::digest::Update + Clone,
+    {
+        let output_len = params.hash.output_len();
+        match &params.hmac_key {
+            None => {
+                let mut hasher = ByteCounter::new(D::new());
+                Self::absorb_transcript(&mut hasher, params.version, domain, sid, pid, points);
+                Self::absorb_aad(&mut hasher, params);
+                let hash = params.version.challenge_hash(output_len, |counter| {
+                    let mut hasher = hasher.inner.clone();
+                    Digest::update(&mut hasher, counter);
+                    hasher.finalize().to_vec()
+                });
+                Ok((G::challenge_from_hash(&hash)?, hasher.count))
+            }
+            Some(key) => {
+                // Keyed challenge: only holders of the verifier's key can produce or check it
+                let mac = SimpleHmac::<D>::new_from_slice(key)
+                    .map_err(|_| DLogProofError::ChallengeDerivation)?;
+                let mut mac = ByteCounter::new(mac);
+                Self::absorb_transcript(&mut mac, params.version, domain, sid, pid, points);
+                Self::absorb_aad(&mut mac, params);
+                let hash = params.version.challenge_hash(output_len, |counter| {
+                    let mut mac = mac.inner.clone();
+                    Mac::update(&mut mac, counter);
+                    mac.finalize().into_bytes().to_vec()
+                });
+                Ok((G::challenge_from_hash(&hash)?, mac.count))
+            }
+        }
+    }
+
+    /// Computes the `ChallengeMode::ExpandMessageXmd` challenge
+    fn xmd_challenge(
+        params: &ChallengeParams,
+        domain: &[u8],
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        points: &[G],
+    ) -> Result<(G::Scalar, usize), DLogProofError> {
+        let dst = params
+            .xmd_dst
+            .as_deref()
+            .ok_or(DLogProofError::UnsupportedChallengeMode)?;
+        if params.hmac_key.is_some() {
+            return Err(DLogProofError::UnsupportedChallengeMode);
+        }
+
+        let mut msg = TranscriptBytes::default();
+        Self::absorb_transcript(&mut msg, params.version, domain, sid, pid, points);
+        Self::absorb_aad(&mut msg, params);
+
+        let okm = expand_message_xmd(params.hash, &msg.0, dst)?;
+        Ok((G::challenge_from_hash(&okm)?, msg.0.len()))
+    }
+
+    /// Appends the length-prefixed additional authenticated data, if any, to the transcript,
+    /// followed by the tagged validity window of a `TimedProof`
+    pub(crate) fn absorb_aad(hasher: &mut impl hmac::digest::Update, params: &ChallengeParams) {
+        if let Some(aad) = &params.aad {
+            hasher.update(&(aad.len() as u32).to_be_bytes());
+            hasher.update(aad);
+        }
+        if let Some((not_before, not_after)) = params.validity {
+            hasher.update(VALIDITY_TAG);
+            hasher.update(&not_before.to_be_bytes());
+            hasher.update(&not_after.to_be_bytes());
+        }
+    }
+
+    /// Computes a default-parameter challenge like `challenge`, but under the given domain
+    /// separation tag
+    ///
+    /// Other sigma protocols in this crate reuse the transcript layout with their own tag,
+    /// so their challenges can never coincide with those of a DLOG proof. Tags must not be
+    /// prefixes of one another, since the transcript isn't length-prefixed.
+    #[cfg(feature = "prove")]
+    pub(crate) fn hash_points_with_domain(
+        domain: &[u8],
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        points: &[G],
+    ) -> Result<G::Scalar, DLogProofError> {
         let mut hasher = Sha256::new();
+        Self::absorb_transcript(&mut hasher, TranscriptVersion::V1, domain, sid, pid, points);
+        G::challenge_from_hash(&hasher.finalize())
+    }
+
+    /// Feeds the challenge transcript into a hash function or MAC, laid out as `version`
+    pub(crate) fn absorb_transcript(
+        hasher: &mut impl hmac::digest::Update,
+        version: TranscriptVersion,
+        domain: &[u8],
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        points: &[G],
+    ) {
+        if version.framed() {
+            let mut field = |label: &[u8], bytes: &[u8]| {
+                hasher.update(label);
+                hasher.update(&(bytes.len() as u64).to_be_bytes());
+                hasher.update(bytes);
+            };
+            field(b"dom", domain);
+            field(b"sid", sid.as_bytes());
+            let (label, pid) = pid.v2_field();
+            field(label, &pid);
+            field(b"pts", &(points.len() as u32).to_be_bytes());
+            for point in points {
+                field(b"pt", point.to_bytes().as_ref());
+            }
+            return;
+        }
+
         // Add domain separation tag to prevent cross-protocol attacks
-        hasher.update(Self::DOMAIN_SEPARATOR);
+        hasher.update(domain);
         // Add session ID to bind challenge to specific session
         hasher.update(sid.as_bytes());
         // Add participant ID to bind challenge to specific participant
-        hasher.update(pid.to_le_bytes());
+        pid.absorb_v1(hasher);
         // Include all provided points in the hash
         for point in points {
-            hasher.update(point.to_bytes());
+            hasher.update(point.to_bytes().as_ref());
         }
-
-        let challenge = hasher.finalize();
-
-        // Attempt to convert hash to scalar for use in verification equation
-        let scalar_option: Option<Scalar> = Scalar::from_repr(challenge).into();
-        scalar_option.ok_or_else(|| "Failed to convert hash to scalar".to_string())
     }
 
     /// Creates a Schnorr Zero-Knowledge Proof that demonstrates knowledge of a discrete logarithm.
@@ -77,43 +753,210 @@ impl DLogProof {
     /// transform, which eliminates the need for back-and-forth communication between prover and verifier.
     ///
     /// # Arguments
-    /// * `sid` - Session identifier string used for domain separation
-    /// * `pid` - Participant ID for uniqueness to distinguish different proofs
-    /// * `x` - The secret scalar (private key) that we're proving knowledge of
-    /// * `y` - The public point, must satisfy y = x * G
-    /// * `base_point` - Base point of secp256k1 curve
+    /// * `sid` - Session identifier used for domain separation, a string or bytes (see `SessionId`)
+    /// * `pid` - Participant ID for uniqueness to distinguish different proofs, an index or bytes
+    ///   (see `ParticipantId`)
+    /// * `x` - The secret scalar (private key) that we're proving knowledge of, as a
+    ///   `SecretScalar`, a `SecretKey` or a plain `&Scalar`; the prover's copy is wiped after use
+    /// * `y` - The public point, must satisfy y = x * G; a point or a `PublicKey`
+    /// * `base_point` - Base point G of the group, e.g. the secp256k1 generator
     ///
     /// # Returns
     /// * `Ok(DLogProof)` - A proof consisting of (t, s) values if successful
-    /// * `Err(String)` - An error message if proof generation fails
-    pub fn prove(
-        sid: &str,
-        pid: u32,
-        x: &Scalar,
-        y: ProjectivePoint,
-        base_point: ProjectivePoint,
-    ) -> Result<Self, String> {
-        // Step 1: Generate random scalar r (the commitment randomness)
+    /// * `Err(DLogProofError)` - An error message if proof generation fails, e.g.
+    ///   `IdentityPoint` if `y` or `base_point` is the identity
+    #[cfg(feature = "std")]
+    pub fn prove<'s, 'p>(
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: impl Into<G>,
+        base_point: G,
+    ) -> Result<Self, DLogProofError> {
+        Self::prove_with_rng(&mut OsRng, sid, pid, x, y, base_point)
+    }
+
+    /// Creates a proof like `prove`, also bound to an application message `context`
+    ///
+    /// The context (e.g. the hash of a registration request) is appended to the challenge
+    /// transcript after the points, prefixed with its 4-byte big-endian length, so the proof
+    /// only verifies with `verify_with_context` for the same bytes. An empty context is still
+    /// a context: such proofs don't verify with plain `verify`, nor the other way round.
+    ///
+    /// This is the additional authenticated data of `ProofBuilder::aad` and `Verifier::aad`,
+    /// which can be combined with the other transcript options.
+    #[cfg(feature = "std")]
+    pub fn prove_with_context<'s, 'p>(
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        context: &[u8],
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: impl Into<G>,
+        base_point: G,
+    ) -> Result<Self, DLogProofError> {
+        Prover::new().aad(context).prove(sid, pid, x, y, base_point)
+    }
+
+    /// Creates a proof like `prove`, drawing the nonce from `rng` instead of `OsRng`
+    ///
+    /// For targets without an OS RNG (HSM- or TRNG-fed entropy) and for reproducible tests
+    /// with a seeded RNG such as `ChaCha20Rng`. The nonce must never repeat for the same x,
+    /// so `rng` has to be a cryptographically secure generator that is seeded freshly per
+    /// process; two proofs with the same nonce for different challenges reveal x.
+    #[cfg(feature = "prove")]
+    pub fn prove_with_rng<'s, 'p>(
+        rng: &mut impl CryptoRngCore,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: impl Into<G>,
+        base_point: G,
+    ) -> Result<Self, DLogProofError> {
+        Self::prove_with(
+            &Prover::default(),
+            rng,
+            sid.into(),
+            pid.into(),
+            &x.into(),
+            y.into(),
+            base_point,
+        )
+    }
+
+    /// Creates a proof using the options configured on `prover` (see `Prover`)
+    #[cfg(feature = "prove")]
+    pub(crate) fn prove_with(
+        prover: &Prover,
+        rng: &mut impl CryptoRngCore,
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        x: &SecretScalar<G::Scalar>,
+        y: G,
+        base_point: G,
+    ) -> Result<Self, DLogProofError> {
+        Self::prove_with_base_mul(prover, rng, sid, pid, x, y, base_point, false, |k| {
+            base_point * k
+        })
+        .map(|(proof, _)| proof)
+    }
+
+    /// Creates a proof like `prove_with`, also reporting what it cost (see `ProveStats`)
+    #[cfg(feature = "std")]
+    pub(crate) fn prove_with_stats(
+        prover: &Prover,
+        rng: &mut impl CryptoRngCore,
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        x: &SecretScalar<G::Scalar>,
+        y: G,
+        base_point: G,
+    ) -> Result<(Self, ProveStats), DLogProofError> {
+        Self::prove_with_base_mul(prover, rng, sid, pid, x, y, base_point, true, |k| {
+            base_point * k
+        })
+    }
+
+    /// Creates a proof like `prove_with_stats`, computing multiples of the base point with
+    /// `mul_base` (e.g. from the precomputed table of a `ProverContext`)
+    ///
+    /// The phases are only timed if `timed` (or a `tracing` span records them), so proving
+    /// without stats never reads the clock; the counters are always kept.
+    #[cfg(feature = "prove")]
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn prove_with_base_mul(
+        prover: &Prover,
+        rng: &mut impl CryptoRngCore,
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        x: &SecretScalar<G::Scalar>,
+        y: G,
+        base_point: G,
+        timed: bool,
+        mul_base: impl Fn(&G::Scalar) -> G,
+    ) -> Result<(Self, ProveStats), DLogProofError> {
+        let span = Span::prove(pid);
+        Self::check_statement(y, base_point)?;
+        let timed = timed || span.is_recording();
+        let start = Stopwatch::start(timed);
+        let mut stats = ProveStats::default();
+
+        // Steps 1 and 2: Generate random scalar r (the commitment randomness) and compute the
+        // commitment t = r * G, the first message of the Schnorr protocol (see `SchnorrDLog`)
         // The random r ensures that multiple proofs of the same secret x look completely different
-        let r = Scalar::random(&mut OsRng);
+        // It reveals x together with s, so it is wiped on drop like the copies of x below
+        // Blinding splits r * G into (r - b) * G + b * G for a fresh random b (see `blinded_mul`)
+        // Synthetic nonces also depend on x and the statement (see `Prover::synthetic_nonces`)
+        let r = if prover.uses_synthetic_nonces() {
+            prover.synthetic_nonce(sid, pid, x, y, base_point, &mut *rng)
+        } else {
+            Zeroizing::new(G::Scalar::random(&mut *rng))
+        };
+        let t = if prover.is_blinded() {
+            stats.scalar_muls += 2;
+            blinded_mul(&mul_base, &r, &mut *rng)
+        } else {
+            stats.scalar_muls += 1;
+            mul_base(&r)
+        };
 
-        // Step 2: Compute the commitment t = r * G
-        let t = base_point * r;
+        // BIP-340 fixes the y-coordinates of t and y to be even: negating r (and x for an odd
+        // y) proves the same statement for the even-y points
+        let bip340 = prover.params().mode == ChallengeMode::Bip340Tagged;
+        let (r, t) = if bip340 && t.x_only()?.1 {
+            (Zeroizing::new(-*r), -t)
+        } else {
+            (r, t)
+        };
+        let x = SecretScalar::new(if bip340 && y.x_only()?.1 {
+            -*x.expose_secret()
+        } else {
+            *x.expose_secret()
+        });
+        stats.commitment = start.elapsed();
 
         // Step 3: Compute the challenge c using Fiat-Shamir transform
         // This makes the proof non-interactive (instead of Verifier sending challenge (interactive)),
         // by deriving the challenge from the hash of all public values
-        let c = Self::hash_points(sid, pid, &[base_point, y, t])?;
+        let phase = Stopwatch::start(timed);
+        let (c, hash_bytes) = Self::challenge(prover.params(), sid, pid, &[base_point, y, t])?;
+        stats.hash_bytes += hash_bytes;
+        stats.challenge = phase.elapsed();
 
         // Step 4: Compute the proof value s = r + c * x
         // This allows the verifier to check the proof without knowing x
         // Note: Numbers are converted to Montgomery form,
         // i.e., results are automatically reduced mod q, so they are never larger than q,
         // where q is the curve order (number of points on the elliptic curve)
-        let s = r + (c * x);
+        let phase = Stopwatch::start(timed);
+        let s = if prover.is_blinded() {
+            blinded_response(&*r, &c, x.expose_secret(), rng)
+        } else {
+            SchnorrDLog::<G>::respond(&x, &r, &c)
+        };
+        stats.response = phase.elapsed();
+
+        let proof = DLogProof { t, s };
+
+        // Fault-attack countermeasure: never release a proof that doesn't verify
+        #[cfg(feature = "verify")]
+        if prover.verifies_after_prove() {
+            let report = proof.verify_report(prover.params(), sid, pid, y, base_point, timed)?;
+            let (valid, verify_stats) = (report.is_valid(), report.stats);
+            stats.scalar_muls += verify_stats.scalar_muls;
+            stats.hash_bytes += verify_stats.hash_bytes;
+            stats.verification = verify_stats.total;
+            if !valid {
+                return Err(DLogProofError::FaultDetected);
+            }
+        }
+        if prover.scrubs_stack() {
+            scrub_stack();
+        }
+        stats.total = start.elapsed();
+        span.record_prove(&stats);
 
         // Finally return the proof with the commitment t and the proof value s
-        Ok(DLogProof { t, s })
+        Ok((proof, stats))
     }
 
     /// Verifies a Schnorr Zero-Knowledge Proof
@@ -127,29 +970,969 @@ impl DLogProof {
     /// # Arguments
     /// * `sid` - Session identifier (must match the one used in proof generation)
     /// * `pid` - Participant ID (must match the one used in proof generation)
-    /// * `y` - The public point to verify against (y = x * G)
-    /// * `base_point` - Base point of secp256k1 curve
+    /// * `y` - The public point to verify against (y = x * G), a point or a `PublicKey`
+    /// * `base_point` - Base point G of the group, e.g. the secp256k1 generator
     ///
     /// # Returns
     /// * `Ok(bool)` - Validity of proof, indicating whether the prover knows the secret value x
-    /// * `Err(String)` - Any error during verification
-    pub fn verify(
+    /// * `Err(DLogProofError)` - Any error during verification, e.g. `IdentityPoint` if `y` or
+    ///   `base_point` is the identity
+    #[cfg(feature = "verify")]
+    pub fn verify<'s, 'p>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        y: impl Into<G>,
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        self.verify_with(
+            &ChallengeParams::default(),
+            sid.into(),
+            pid.into(),
+            y.into(),
+            base_point,
+        )
+    }
+
+    /// Verifies a proof made with `prove_with_context` for the same `context`
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Validity of the proof, `false` if it was made for another context
+    /// * `Err(DLogProofError)` - Any error of `verify`
+    #[cfg(feature = "verify")]
+    pub fn verify_with_context<'s, 'p>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        context: &[u8],
+        y: impl Into<G>,
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        let params = ChallengeParams {
+            aad: Some(context.to_vec()),
+            ..ChallengeParams::default()
+        };
+        self.verify_with(&params, sid.into(), pid.into(), y.into(), base_point)
+    }
+
+    /// Verifies the proof under the given transcript parameters (see `Verifier`)
+    #[cfg(feature = "verify")]
+    pub(crate) fn verify_with(
+        &self,
+        params: &ChallengeParams,
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        y: G,
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        self.verify_report(params, sid, pid, y, base_point, false)
+            .map(|report| report.is_valid())
+    }
+
+    /// Verifies the proof like `verify`, reporting why it failed instead of `false`
+    ///
+    /// An identity statement is reported as `VerificationFailure::IdentityStatement` rather
+    /// than an error; errors are left for transcript options the group can't support.
+    ///
+    /// # Returns
+    /// * `Ok(VerificationReport)` - Whether the proof is valid, else why not, and its cost
+    /// * `Err(DLogProofError)` - If the challenge can't be derived
+    #[cfg(feature = "verify")]
+    pub fn verify_detailed<'s, 'p>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        y: impl Into<G>,
+        base_point: G,
+    ) -> Result<VerificationReport, DLogProofError> {
+        self.verify_detailed_with(
+            &ChallengeParams::default(),
+            sid.into(),
+            pid.into(),
+            y.into(),
+            base_point,
+        )
+    }
+
+    /// Verifies the proof like `verify_detailed` under the given transcript parameters
+    #[cfg(feature = "verify")]
+    pub(crate) fn verify_detailed_with(
+        &self,
+        params: &ChallengeParams,
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        y: G,
+        base_point: G,
+    ) -> Result<VerificationReport, DLogProofError> {
+        VerificationReport::from_result(self.verify_report(params, sid, pid, y, base_point, true))
+    }
+
+    /// Verifies the proof like `verify_with`, also reporting what it cost (see `VerifyStats`)
+    #[cfg(feature = "verify")]
+    pub(crate) fn verify_with_stats(
+        &self,
+        params: &ChallengeParams,
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        y: G,
+        base_point: G,
+    ) -> Result<(bool, VerifyStats), DLogProofError> {
+        self.verify_report(params, sid, pid, y, base_point, true)
+            .map(|report| (report.is_valid(), report.stats))
+    }
+
+    /// Verifies the proof, timing the phases only if `timed`
+    #[cfg(feature = "verify")]
+    fn verify_report(
+        &self,
+        params: &ChallengeParams,
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        y: G,
+        base_point: G,
+        timed: bool,
+    ) -> Result<VerificationReport, DLogProofError> {
+        // Shamir's trick: s * G - c * y as one double-scalar multiplication sharing the
+        // doublings, instead of two separate ones
+        self.verify_with_equation(params, sid, pid, y, base_point, timed, |s, c| {
+            (params.timing.lincomb(&[(base_point, *s), (y, -*c)]), 1)
+        })
+    }
+
+    /// Verifies the proof like `verify_detailed_with`, computing s * G - c * y for the response s
+    /// and a challenge c with `combine` (e.g. from the tables of a `VerifierContext`)
+    ///
+    /// `combine` also returns the number of scalar multiplications it performed, for the
+    /// stats. The phases are only timed if `timed` (or a `tracing` span records them). An
+    /// identity statement is still an error here, as `verify` returns it.
+    #[cfg(feature = "verify")]
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn verify_with_equation(
+        &self,
+        params: &ChallengeParams,
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        y: G,
+        base_point: G,
+        timed: bool,
+        combine: impl Fn(&G::Scalar, &G::Scalar) -> (G, u32),
+    ) -> Result<VerificationReport, DLogProofError> {
+        let span = Span::verify(pid);
+        Self::check_statement(y, base_point)?;
+        let timed = timed || span.is_recording();
+        let start = Stopwatch::start(timed);
+
+        // Recompute challenge c using Fiat-Shamir transform
+        let (c, hash_bytes) = Self::challenge(params, sid, pid, &[base_point, y, self.t])?;
+        let challenge = start.elapsed();
+
+        // c * (-y) = (-c) * y, so a negated key negates the challenge instead
+        let (key, even_t) = self.equation_key(params, y)?;
+        let c = if key == y { c } else { -c };
+
+        let phase = Stopwatch::start(timed);
+        // Check the verification equation s * G = t + c * y as s * G - c * y = t, comparing both
+        // sides in constant time to prevent timing attacks. Whether t is even is public.
+        let (failure, scalar_muls) = if even_t {
+            let (lhs, scalar_muls) = combine(&self.s, &c);
+            let failure = if bool::from(lhs.ct_eq(&self.t)) {
+                None
+            } else {
+                Some(VerificationFailure::EquationFailure)
+            };
+            (failure, scalar_muls)
+        } else {
+            let failure = VerificationFailure::Encoding(DLogProofError::InvalidPoint);
+            (Some(failure), 0)
+        };
+
+        let stats = VerifyStats {
+            scalar_muls,
+            hash_bytes,
+            challenge,
+            equation: phase.elapsed(),
+            total: start.elapsed(),
+        };
+        span.record_verify(failure.is_none(), &stats);
+        Ok(VerificationReport { failure, stats })
+    }
+
+    /// Returns the public key to check s * G = t + c * y against, and whether t is acceptable
+    ///
+    /// BIP-340 proofs are made for the even-y lifts of t and y, so an odd t is rejected and
+    /// an odd y replaced by its negation. Other modes use y and t as they are.
+    #[cfg(feature = "verify")]
+    pub(crate) fn equation_key(
         &self,
+        params: &ChallengeParams,
+        y: G,
+    ) -> Result<(G, bool), DLogProofError> {
+        if params.mode != ChallengeMode::Bip340Tagged {
+            return Ok((y, true));
+        }
+        let even_t = !self.t.x_only()?.1;
+        let y = if y.x_only()?.1 { -y } else { y };
+        Ok((y, even_t))
+    }
+
+    /// Parses a proof from JSON, handling unknown fields according to `policy`
+    ///
+    /// # Arguments
+    /// * `json` - JSON object containing at least the `t` and `s` fields
+    /// * `policy` - Whether unknown fields are rejected or ignored
+    ///
+    /// # Returns
+    /// * `Ok((DLogProof, Vec<String>))` - The proof and the sorted names of ignored fields
+    /// * `Err(DLogProofError)` - If the JSON is malformed, a known field is invalid, or an unknown
+    ///   field is present under `UnknownFieldPolicy::Strict`
+    #[cfg(feature = "json")]
+    pub fn from_json(
+        json: &str,
+        policy: UnknownFieldPolicy,
+    ) -> Result<(Self, Vec<String>), DLogProofError> {
+        const KNOWN_FIELDS: [&str; 2] = ["t", "s"];
+        let span = Span::serialization("decode", "json");
+        span.record_len(json.len());
+
+        let mut fields: serde_json::Map<String, serde_json::Value> = serde_json::from_str(json)
+            .map_err(|e| DLogProofError::Serialization(format!("Invalid proof JSON: {e}")))?;
+
+        let unknown: Vec<String> = fields
+            .keys()
+            .filter(|key| !KNOWN_FIELDS.contains(&key.as_str()))
+            .cloned()
+            .collect();
+        if policy == UnknownFieldPolicy::Strict && !unknown.is_empty() {
+            return Err(DLogProofError::Serialization(format!(
+                "Unknown proof fields: {}",
+                unknown.join(", ")
+            )));
+        }
+        for key in &unknown {
+            fields.remove(key);
+        }
+
+        let proof = serde_json::from_value(serde_json::Value::Object(fields))
+            .map_err(|e| DLogProofError::Serialization(format!("Invalid proof JSON: {e}")))?;
+        Ok((proof, unknown))
+    }
+}
+
+impl DLogProof {
+    /// Identifies the curve, challenge hash and proof encoding used by this implementation
+    pub const CIPHERSUITE: &'static str = "SCHNORR_PROOF/secp256k1/SHA-256/sec1-compressed";
+
+    /// Domain separation tag for proof identifiers, distinct from the challenge domain
+    const ID_DOMAIN_SEPARATOR: &'static [u8] = b"SCHNORR_PROOF_ID";
+
+    /// Size of the fixed binary encoding: compressed SEC1 `t` (33 bytes) || big-endian `s` (32 bytes)
+    pub const ENCODED_LEN: usize = 65;
+
+    /// Encodes the proof as `t (compressed SEC1) || s (big-endian)`
+    ///
+    /// `serialization::ProofBytes` uses this encoding for serde, as do the SCALE, base58check
+    /// and multibase encodings.
+    pub fn to_bytes(&self) -> [u8; Self::ENCODED_LEN] {
+        let span = Span::serialization("encode", "bytes");
+        span.record_len(Self::ENCODED_LEN);
+        let mut bytes = [0u8; Self::ENCODED_LEN];
+        bytes[..33].copy_from_slice(&self.t.to_bytes());
+        bytes[33..].copy_from_slice(&self.s.to_repr());
+        bytes
+    }
+
+    /// Decodes the fixed binary encoding, rejecting invalid points and non-canonical scalars
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DLogProofError> {
+        let span = Span::serialization("decode", "bytes");
+        span.record_len(bytes.len());
+        let invalid_length = || {
+            DLogProofError::Serialization(format!(
+                "Invalid proof length: expected {} bytes, got {}",
+                Self::ENCODED_LEN,
+                bytes.len()
+            ))
+        };
+        let (t_bytes, s_bytes) = bytes.split_first_chunk::<33>().ok_or_else(invalid_length)?;
+        let s_bytes: [u8; 32] = s_bytes.try_into().map_err(|_| invalid_length())?;
+
+        let t = decode_canonical(&(*t_bytes).into()).ok_or(DLogProofError::InvalidPoint)?;
+        let s =
+            Option::from(Scalar::from_repr(s_bytes.into())).ok_or(DLogProofError::InvalidScalar)?;
+        Ok(DLogProof { t, s })
+    }
+
+    /// Writes the encoding of `to_bytes` to `writer`, without allocating
+    ///
+    /// Proofs written back to back are read again one at a time with `read_from`.
+    #[cfg(feature = "std")]
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+
+    /// Reads one proof written by `write_to`, consuming exactly `ENCODED_LEN` bytes
+    ///
+    /// A stream ending early is an `io::ErrorKind::UnexpectedEof` error; a malformed proof is
+    /// `io::ErrorKind::InvalidData` wrapping the `DLogProofError` of `from_bytes`.
+    #[cfg(feature = "std")]
+    pub fn read_from(reader: &mut impl Read) -> io::Result<Self> {
+        let mut bytes = [0u8; Self::ENCODED_LEN];
+        reader.read_exact(&mut bytes)?;
+        Self::from_bytes(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Encodes a `ChallengeMode::Bip340Tagged` proof as a 64-byte BIP-340 signature x(t) || s
+    ///
+    /// Only meaningful for proofs over the standard generator; verify it against the x-only
+    /// key of y and `Verifier::bip340_message`.
+    pub fn to_bip340_signature(&self) -> Result<[u8; 64], DLogProofError> {
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(&self.t.x_only()?.0);
+        signature[32..].copy_from_slice(&self.s.to_repr());
+        Ok(signature)
+    }
+
+    /// Computes the content-addressed identifier of this proof
+    ///
+    /// The identifier is SHA256(tag || len(ciphersuite) || ciphersuite || t || s), where `t` is
+    /// the compressed SEC1 encoding of the commitment and `s` the big-endian scalar. Both
+    /// encodings are canonical, so equal proofs always get equal identifiers and the
+    /// identifier doesn't depend on how the proof was serialized in transit.
+    pub fn id(&self) -> ProofId {
+        let mut hasher = Sha256::new();
+        hasher.update(Self::ID_DOMAIN_SEPARATOR);
+        hasher.update((Self::CIPHERSUITE.len() as u32).to_be_bytes());
+        hasher.update(Self::CIPHERSUITE.as_bytes());
+        hasher.update(self.to_bytes());
+        ProofId(hasher.finalize().into())
+    }
+}
+
+impl TryFrom<&[u8]> for DLogProof {
+    type Error = DLogProofError;
+
+    /// Decodes the binary encoding, see `DLogProof::from_bytes`
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl TryFrom<[u8; DLogProof::ENCODED_LEN]> for DLogProof {
+    type Error = DLogProofError;
+
+    /// Decodes the binary encoding, see `DLogProof::from_bytes`
+    fn try_from(bytes: [u8; DLogProof::ENCODED_LEN]) -> Result<Self, Self::Error> {
+        Self::from_bytes(&bytes)
+    }
+}
+
+impl From<&DLogProof> for [u8; DLogProof::ENCODED_LEN] {
+    /// The binary encoding, see `DLogProof::to_bytes`
+    fn from(proof: &DLogProof) -> Self {
+        proof.to_bytes()
+    }
+}
+
+/// The canonical string form `hex(t) ":" hex(s)`, with `t` compressed SEC1 and `s` big-endian,
+/// both lowercase (the two halves of `to_bytes`)
+///
+/// Meant for environment variables, command-line arguments and configuration files, where a
+/// proof shouldn't need JSON quoting.
+impl fmt::Display for DLogProof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = self.to_bytes();
+        write!(
+            f,
+            "{}:{}",
+            hex::encode(&bytes[..33]),
+            hex::encode(&bytes[33..])
+        )
+    }
+}
+
+/// Parses the string form of `Display`, and only that: uppercase hex or an uncompressed `t`
+/// is rejected, so every proof has a single string
+impl core::str::FromStr for DLogProof {
+    type Err = DLogProofError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (t, s) = value.split_once(':').ok_or_else(|| {
+            DLogProofError::Serialization("Proof string is not \"<t>:<s>\"".to_string())
+        })?;
+        let mut bytes = Vec::with_capacity(Self::ENCODED_LEN);
+        for half in [t, s] {
+            let decoded = hex::decode(half)
+                .map_err(|e| DLogProofError::Serialization(format!("Invalid hex: {e}")))?;
+            bytes.extend_from_slice(&decoded);
+        }
+        // Splits other than 33 + 32 bytes decode to the same bytes but aren't canonical
+        let proof = Self::from_bytes(&bytes)?;
+        if proof.to_string() != value {
+            return Err(DLogProofError::Serialization(
+                "Non-canonical proof string".to_string(),
+            ));
+        }
+        Ok(proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proof_strings_are_canonical() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let proof = DLogProof::prove("sid", 1, &x, g * x, g).unwrap();
+        let string = proof.to_string();
+        let bytes = hex::encode(proof.to_bytes());
+        assert_eq!(string, format!("{}:{}", &bytes[..66], &bytes[66..]));
+        assert_eq!(string.parse::<DLogProof>().unwrap(), proof);
+
+        let shifted = format!("{}:{}", &bytes[..64], &bytes[64..]);
+        for invalid in [
+            string.to_uppercase(),
+            shifted,
+            bytes,
+            string.replace(':', "::"),
+            format!("{string} "),
+        ] {
+            assert!(invalid.parse::<DLogProof>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn proofs_convert_to_and_from_bytes() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let proof = DLogProof::prove("sid", 1, &x, g * x, g).unwrap();
+        let bytes: [u8; DLogProof::ENCODED_LEN] = (&proof).into();
+        assert_eq!(bytes, proof.to_bytes());
+        assert_eq!(DLogProof::try_from(bytes).unwrap(), proof);
+        assert_eq!(DLogProof::try_from(&bytes[..]).unwrap(), proof);
+        assert!(DLogProof::try_from(&bytes[1..]).is_err());
+    }
+
+    #[test]
+    fn proofs_stream_through_readers_and_writers() {
+        let g = ProjectivePoint::GENERATOR;
+        let proofs: Vec<DLogProof> = (1..=3)
+            .map(|pid| {
+                let x = Scalar::random(&mut OsRng);
+                DLogProof::prove("sid", pid, &x, g * x, g).unwrap()
+            })
+            .collect();
+        let mut log = Vec::new();
+        for proof in &proofs {
+            proof.write_to(&mut log).unwrap();
+        }
+        assert_eq!(log.len(), 3 * DLogProof::ENCODED_LEN);
+
+        let mut reader = &log[..];
+        for proof in &proofs {
+            assert_eq!(&DLogProof::read_from(&mut reader).unwrap(), proof);
+        }
+        let error = DLogProof::read_from(&mut reader).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+
+        log[33..DLogProof::ENCODED_LEN].fill(0xff);
+        let error = DLogProof::read_from(&mut &log[..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            error.into_inner().unwrap().to_string(),
+            DLogProofError::InvalidScalar.to_string()
+        );
+    }
+
+    #[test]
+    fn degenerate_statements_are_rejected() {
+        let g = ProjectivePoint::GENERATOR;
+        let identity = ProjectivePoint::IDENTITY;
+        let x = Scalar::random(&mut OsRng);
+
+        // y = 0: any t = s * G would verify
+        assert_eq!(
+            DLogProof::prove("sid", 1, &Scalar::ZERO, identity, g),
+            Err(DLogProofError::IdentityPoint)
+        );
+        let forged = DLogProof::new(g, Scalar::ONE);
+        assert_eq!(
+            forged.verify("sid", 1, identity, g),
+            Err(DLogProofError::IdentityPoint)
+        );
+
+        // G = 0
+        assert_eq!(
+            DLogProof::prove("sid", 1, &x, identity, identity),
+            Err(DLogProofError::IdentityPoint)
+        );
+        assert_eq!(
+            forged.verify("sid", 1, g * x, identity),
+            Err(DLogProofError::IdentityPoint)
+        );
+
+        let batch = [("sid".into(), 1.into(), identity, &forged)];
+        assert_eq!(
+            DLogProof::verify_batch(&batch, g),
+            Err(DLogProofError::IdentityPoint)
+        );
+    }
+
+    #[test]
+    fn equality_compares_both_components() {
+        let g = ProjectivePoint::GENERATOR;
+        let proof = DLogProof::new(g, Scalar::ONE);
+        assert!(bool::from(proof.ct_eq(&DLogProof::new(g, Scalar::ONE))));
+        assert_eq!(proof, DLogProof::new(g, Scalar::ONE));
+        assert_ne!(proof, DLogProof::new(g.double(), Scalar::ONE));
+        assert_ne!(proof, DLogProof::new(g, Scalar::ZERO));
+    }
+
+    #[test]
+    fn seeded_rngs_make_reproducible_proofs() {
+        use crate::prover::Prover;
+        use rand_chacha::rand_core::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let prove = |seed: u64| {
+            DLogProof::prove_with_rng(&mut ChaCha20Rng::seed_from_u64(seed), "sid", 1, &x, y, g)
+                .unwrap()
+        };
+
+        let proof = prove(7);
+        assert!(proof.verify("sid", 1, y, g).unwrap());
+        assert_eq!(prove(7), proof);
+        assert_ne!(prove(8), proof);
+
+        // Blinding draws its masks from the same RNG and doesn't change the result
+        let blinded = Prover::new()
+            .blinded(true)
+            .prove_with_rng(&mut ChaCha20Rng::seed_from_u64(7), "sid", 1, &x, y, g)
+            .unwrap();
+        assert_eq!(blinded, proof);
+    }
+
+    #[test]
+    fn context_binds_the_proof() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let request = Sha256::digest(b"register alice");
+
+        let proof = DLogProof::prove_with_context("sid", 1, &request, &x, y, g).unwrap();
+        assert!(proof.verify_with_context("sid", 1, &request, y, g).unwrap());
+        let other = Sha256::digest(b"register mallory");
+        assert!(!proof.verify_with_context("sid", 1, &other, y, g).unwrap());
+        assert!(!proof.verify("sid", 1, y, g).unwrap());
+
+        // The length prefix keeps an empty context apart from no context
+        let empty = DLogProof::prove_with_context("sid", 1, b"", &x, y, g).unwrap();
+        assert!(empty.verify_with_context("sid", 1, b"", y, g).unwrap());
+        assert!(!empty.verify("sid", 1, y, g).unwrap());
+        assert!(!DLogProof::prove("sid", 1, &x, y, g)
+            .unwrap()
+            .verify_with_context("sid", 1, b"", y, g)
+            .unwrap());
+    }
+
+    #[test]
+    fn binary_encodings_are_canonical() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let proof = DLogProof::prove("sid", 1, &x, g * x, g).unwrap();
+        let bytes = proof.to_bytes();
+        assert_eq!(DLogProof::from_bytes(&bytes).unwrap(), proof);
+
+        // The compact SEC1 tag (found by the proof_bytes fuzz target) decodes t to the same
+        // point or its negation, and must not give the proof a second encoding
+        let mut compact = bytes;
+        compact[0] = 0x05;
+        assert_eq!(
+            DLogProof::from_bytes(&compact),
+            Err(DLogProofError::InvalidPoint)
+        );
+        assert_eq!(ProjectivePoint::decode_point(&compact[..33]), None);
+    }
+
+    #[test]
+    fn proofs_split_into_and_rebuild_from_parts() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let proof = DLogProof::prove("sid", 1, &x, y, g).unwrap();
+        let (t, s) = proof.into_parts();
+        let proof = DLogProof::from_parts(t, s).unwrap();
+        assert_eq!((proof.t(), proof.s()), (t, s));
+        assert!(proof.verify("sid", 1, y, g).unwrap());
+        assert_eq!(
+            DLogProof::from_parts(ProjectivePoint::IDENTITY, s),
+            Err(DLogProofError::IdentityPoint)
+        );
+    }
+}
+
+/// Differential tests against an independent reference implementation.
+///
+/// The reference below deliberately avoids the code paths used by `DLogProof`:
+/// points are hashed via their SEC1 encoding, the digest is reduced modulo the curve
+/// order on big integers and the verification equation is compared on affine
+/// encodings. Any transcript or encoding drift between the two shows up
+/// as a disagreement.
+#[cfg(test)]
+mod differential_tests {
+    use super::*;
+    use elliptic_curve::bigint::{ArrayEncoding, NonZero, U256};
+    use elliptic_curve::ops::MulByGenerator;
+    use elliptic_curve::sec1::ToEncodedPoint;
+    use elliptic_curve::Curve;
+    use k256::Secp256k1;
+    use rand_core::RngCore;
+
+    const ITERATIONS: usize = 64;
+
+    /// Reference challenge: SHA256(tag || sid || pid_le || sec1(G) || sec1(y) || sec1(t)),
+    /// read as a big-endian integer and reduced modulo the group order
+    fn reference_challenge(sid: &str, pid: u32, points: &[ProjectivePoint]) -> Scalar {
+        let mut transcript = Vec::new();
+        transcript.extend_from_slice(b"SCHNORR_PROOF");
+        transcript.extend_from_slice(sid.as_bytes());
+        transcript.extend_from_slice(&pid.to_le_bytes());
+        for point in points {
+            transcript.extend_from_slice(point.to_affine().to_encoded_point(true).as_bytes());
+        }
+        let digest = Sha256::digest(&transcript);
+
+        let digest = U256::from_be_byte_array(digest);
+        Scalar::from_repr(
+            digest
+                .rem(&NonZero::new(Secp256k1::ORDER).unwrap())
+                .to_be_byte_array(),
+        )
+        .unwrap()
+    }
+
+    fn reference_prove(
         sid: &str,
         pid: u32,
+        x: &Scalar,
         y: ProjectivePoint,
-        base_point: ProjectivePoint,
-    ) -> Result<bool, String> {
-        // Recompute challenge c using Fiat-Shamir transform
-        let c = Self::hash_points(sid, pid, &[base_point, y, self.t])?;
+    ) -> (ProjectivePoint, Scalar) {
+        let g = ProjectivePoint::GENERATOR;
+        let r = Scalar::random(&mut OsRng);
+        let t = ProjectivePoint::mul_by_generator(&r);
+        let c = reference_challenge(sid, pid, &[g, y, t]);
+        (t, r + c * x)
+    }
 
-        // Compute left side of verification equation: s * G
-        let lhs = base_point * self.s;
+    fn reference_verify(
+        sid: &str,
+        pid: u32,
+        y: ProjectivePoint,
+        t: ProjectivePoint,
+        s: Scalar,
+    ) -> bool {
+        let g = ProjectivePoint::GENERATOR;
+        let c = reference_challenge(sid, pid, &[g, y, t]);
+        let lhs = ProjectivePoint::mul_by_generator(&s).to_affine();
+        let rhs = (t + y * c).to_affine();
+        lhs.to_encoded_point(false) == rhs.to_encoded_point(false)
+    }
+
+    fn random_statement(i: usize) -> (String, u32, Scalar, ProjectivePoint) {
+        let x = Scalar::random(&mut OsRng);
+        let y = ProjectivePoint::GENERATOR * x;
+        (format!("session-{i}"), OsRng.next_u32(), x, y)
+    }
+
+    #[test]
+    fn crate_proofs_verify_under_reference() {
+        for i in 0..ITERATIONS {
+            let (sid, pid, x, y) = random_statement(i);
+            let proof = DLogProof::prove(&sid, pid, &x, y, ProjectivePoint::GENERATOR).unwrap();
+            assert!(reference_verify(&sid, pid, y, proof.t, proof.s));
+        }
+    }
+
+    #[test]
+    fn reference_proofs_verify_under_crate() {
+        for i in 0..ITERATIONS {
+            let (sid, pid, x, y) = random_statement(i);
+            let (t, s) = reference_prove(&sid, pid, &x, y);
+            let proof = DLogProof { t, s };
+            assert!(proof
+                .verify(&sid, pid, y, ProjectivePoint::GENERATOR)
+                .unwrap());
+        }
+    }
+
+    #[test]
+    fn implementations_agree_on_tampered_proofs() {
+        for i in 0..ITERATIONS {
+            let (sid, pid, x, y) = random_statement(i);
+            let g = ProjectivePoint::GENERATOR;
+            let proof = DLogProof::prove(&sid, pid, &x, y, g).unwrap();
+
+            let tampered = [
+                DLogProof {
+                    t: proof.t,
+                    s: proof.s + Scalar::ONE,
+                },
+                DLogProof {
+                    t: proof.t + g,
+                    s: proof.s,
+                },
+            ];
+            for bad in tampered {
+                let ours = bad.verify(&sid, pid, y, g).unwrap_or(false);
+                let reference = reference_verify(&sid, pid, y, bad.t, bad.s);
+                assert_eq!(ours, reference);
+                assert!(!ours);
+            }
+
+            // Wrong session context must be rejected by both
+            let other_sid = format!("{sid}-other");
+            assert_eq!(
+                proof.verify(&other_sid, pid, y, g).unwrap_or(false),
+                reference_verify(&other_sid, pid, y, proof.t, proof.s)
+            );
+            assert!(!reference_verify(
+                &sid,
+                pid.wrapping_add(1),
+                y,
+                proof.t,
+                proof.s
+            ));
+        }
+    }
+}
+
+/// Property tests: honest proofs verify, any one-bit change to the proof or the statement is
+/// rejected, and every encoding of a proof round-trips
+#[cfg(test)]
+mod property_tests {
+    use super::*;
+    use crate::envelope::ProofEnvelope;
+    use crate::multiformats::Multibase;
+    use elliptic_curve::bigint::U256;
+    use elliptic_curve::ops::Reduce;
+    use proptest::prelude::*;
+
+    /// A statement (x, y = x * G, sid, pid)
+    fn statement() -> impl Strategy<Value = (Scalar, ProjectivePoint, Vec<u8>, u32)> {
+        (
+            any::<[u8; 32]>(),
+            proptest::collection::vec(any::<u8>(), 1..48),
+            any::<u32>(),
+        )
+            .prop_filter_map("zero secret", |(x, sid, pid)| {
+                let x = <Scalar as Reduce<U256>>::reduce_bytes(&x.into());
+                (!bool::from(x.is_zero())).then(|| (x, ProjectivePoint::GENERATOR * x, sid, pid))
+            })
+    }
+
+    fn flip(bytes: &mut [u8], bit: usize) {
+        let bit = bit % (bytes.len() * 8);
+        bytes[bit / 8] ^= 1 << (bit % 8);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn honest_proofs_verify((x, y, sid, pid) in statement()) {
+            let g = ProjectivePoint::GENERATOR;
+            let proof = DLogProof::prove(SessionId::new(&sid), pid, &x, y, g).unwrap();
+            prop_assert_eq!(proof.verify(SessionId::new(&sid), pid, y, g), Ok(true));
+        }
+
+        #[test]
+        fn single_bit_changes_are_rejected(
+            (x, y, sid, pid) in statement(),
+            bit in any::<usize>(),
+        ) {
+            let g = ProjectivePoint::GENERATOR;
+            let proof = DLogProof::prove(SessionId::new(&sid), pid, &x, y, g).unwrap();
+            let verifies = |proof: &DLogProof, sid: &[u8], pid, y| {
+                proof.verify(SessionId::new(sid), pid, y, g) == Ok(true)
+            };
+
+            // t is the first 33 bytes of the encoding and s the last 32; a change either
+            // makes the encoding invalid or gives another proof
+            for range in [0..33, 33..DLogProof::ENCODED_LEN] {
+                let mut bytes = proof.to_bytes();
+                flip(&mut bytes[range], bit);
+                if let Ok(mutated) = DLogProof::from_bytes(&bytes) {
+                    prop_assert!(!verifies(&mutated, &sid, pid, y));
+                }
+            }
+
+            let mut mutated_sid = sid.clone();
+            flip(&mut mutated_sid, bit);
+            prop_assert!(!verifies(&proof, &mutated_sid, pid, y));
+
+            prop_assert!(!verifies(&proof, &sid, pid ^ (1 << (bit % 32)), y));
+
+            let mut y_bytes = y.encode_point();
+            flip(&mut y_bytes, bit);
+            if let Some(mutated_y) = ProjectivePoint::decode_point(&y_bytes) {
+                prop_assert!(!verifies(&proof, &sid, pid, mutated_y));
+            }
+        }
+
+        #[test]
+        fn encodings_round_trip((x, y, sid, pid) in statement()) {
+            let g = ProjectivePoint::GENERATOR;
+            let proof = DLogProof::prove(SessionId::new(&sid), pid, &x, y, g).unwrap();
+            // DLogProof isn't Clone, so decoded proofs are compared by reference
+            let proof = &proof;
+
+            prop_assert_eq!(&DLogProof::from_bytes(&proof.to_bytes()).unwrap(), proof);
+            let json = serde_json::to_string(proof).unwrap();
+            prop_assert_eq!(&serde_json::from_str::<DLogProof>(&json).unwrap(), proof);
+            let (parsed, ignored) =
+                DLogProof::from_json(&json, UnknownFieldPolicy::Strict).unwrap();
+            prop_assert_eq!(&parsed, proof);
+            prop_assert!(ignored.is_empty());
+            let bincode = bincode::serialize(proof).unwrap();
+            prop_assert_eq!(&bincode::deserialize::<DLogProof>(&bincode).unwrap(), proof);
+            let postcard = postcard::to_allocvec(proof).unwrap();
+            prop_assert_eq!(&postcard::from_bytes::<DLogProof>(&postcard).unwrap(), proof);
+
+            prop_assert_eq!(&DLogProof::from_pem(&proof.to_pem()).unwrap(), proof);
+            let base58 = proof.to_base58check(0x3f);
+            prop_assert_eq!(&DLogProof::from_base58check(&base58, 0x3f).unwrap(), proof);
+            let bech32 = proof.to_bech32("dlog").unwrap();
+            prop_assert_eq!(&DLogProof::from_bech32(&bech32, "dlog").unwrap(), proof);
+            for base in [Multibase::Base16, Multibase::Base58Btc] {
+                let multibase = proof.to_multibase(base);
+                prop_assert_eq!(&DLogProof::from_multibase(&multibase).unwrap(), proof);
+            }
+            let envelope = ProofEnvelope::seal(proof, ChallengeHash::Sha256).to_bytes();
+            let opened: DLogProof = ProofEnvelope::from_bytes(&envelope).unwrap().open().unwrap();
+            prop_assert_eq!(&opened, proof);
+
+            #[cfg(feature = "msgpack")]
+            {
+                let msgpack = proof.to_msgpack().unwrap();
+                prop_assert_eq!(&DLogProof::from_msgpack(&msgpack).unwrap(), proof);
+            }
+            #[cfg(feature = "der")]
+            prop_assert_eq!(&DLogProof::from_der(&proof.to_der().unwrap()).unwrap(), proof);
+            #[cfg(feature = "borsh")]
+            {
+                let borsh = borsh::to_vec(proof).unwrap();
+                prop_assert_eq!(&borsh::from_slice::<DLogProof>(&borsh).unwrap(), proof);
+            }
+            #[cfg(feature = "scale")]
+            {
+                use parity_scale_codec::{Decode, Encode};
+                prop_assert_eq!(&DLogProof::decode(&mut &proof.encode()[..]).unwrap(), proof);
+            }
+        }
+    }
+}
+
+/// Kani harnesses for the glue code around the curve arithmetic.
+///
+/// Run with `cargo kani --lib`. The field and group arithmetic and SHA-256 are trusted (they
+/// come from `k256` and `sha2`) and far too large to model-check, so the harnesses stub the
+/// challenge hash and the verification's group operation with `kani::stub` and pin down the
+/// encodings and how this module wires the pieces together.
+#[cfg(kani)]
+mod verification {
+    use super::*;
+    use core::sync::atomic::{AtomicBool, Ordering};
+
+    /// The challenge `challenge_stub` derives for every transcript
+    const CHALLENGE: u64 = 7;
+
+    /// Whether the last `lincomb_stub` call reported s * G - c * y = t
+    static EQUATION_HOLDS: AtomicBool = AtomicBool::new(false);
+
+    /// Produces an arbitrary scalar from arbitrary bytes, skipping non-canonical encodings
+    fn any_scalar() -> Scalar {
+        let bytes: [u8; 32] = kani::any();
+        let scalar: Option<Scalar> = Scalar::from_repr(bytes.into()).into();
+        kani::assume(scalar.is_some());
+        scalar.unwrap()
+    }
+
+    /// Stands in for `DLogProof::challenge`: checks the statement is hashed as (G, y, t)
+    /// and returns `CHALLENGE` without hashing
+    fn challenge_stub<G: DLogGroup>(
+        _params: &ChallengeParams,
+        _sid: SessionId<'_>,
+        _pid: ParticipantId<'_>,
+        points: &[G],
+    ) -> Result<(G::Scalar, usize), DLogProofError> {
+        assert_eq!(points.len(), 3);
+        Ok((G::Scalar::from(CHALLENGE), 0))
+    }
+
+    /// Stands in for `Timing::lincomb` in `verify`: checks it gets s * G - c * y for the
+    /// stubbed challenge, and returns the commitment t = G (equation holds) or not at will
+    fn lincomb_stub<G: DLogGroup>(_timing: Timing, terms: &[(G, G::Scalar)]) -> G {
+        assert_eq!(terms.len(), 2);
+        assert!(terms[1].1 == -G::Scalar::from(CHALLENGE));
+        let holds: bool = kani::any();
+        EQUATION_HOLDS.store(holds, Ordering::Relaxed);
+        if holds {
+            G::generator()
+        } else {
+            G::identity()
+        }
+    }
+
+    /// Scalar encoding used by the serializers round-trips exactly
+    #[kani::proof]
+    fn scalar_repr_round_trip() {
+        let scalar = any_scalar();
+        let decoded: Option<Scalar> = Scalar::from_repr(scalar.to_repr()).into();
+        assert_eq!(decoded, Some(scalar));
+    }
+
+    /// Both transcript layouts encode the participant index injectively
+    #[kani::proof]
+    fn transcript_binds_the_participant() {
+        let (a, b): (u32, u32) = (kani::any(), kani::any());
+        for version in [TranscriptVersion::V1, TranscriptVersion::V2] {
+            let transcript = |pid: u32| {
+                let mut bytes = TranscriptBytes::default();
+                DLogProof::<ProjectivePoint>::absorb_transcript(
+                    &mut bytes,
+                    version,
+                    DLogProof::<ProjectivePoint>::DOMAIN_SEPARATOR,
+                    "sid".into(),
+                    pid.into(),
+                    &[],
+                );
+                bytes.0
+            };
+            assert_eq!(transcript(a) == transcript(b), a == b);
+        }
+    }
 
-        // Compute right side of verification equation: t + c * y
-        let rhs = self.t + (y * c);
+    /// `verify` accepts exactly when s * G - c * y = t for the challenge c of (G, y, t), and
+    /// rejects identity statements before computing anything
+    #[kani::proof]
+    #[kani::stub(DLogProof::challenge, challenge_stub)]
+    #[kani::stub(Timing::lincomb, lincomb_stub)]
+    fn verify_matches_equation() {
+        let g = ProjectivePoint::GENERATOR;
+        let proof = DLogProof {
+            t: g,
+            s: any_scalar(),
+        };
+        let pid: u32 = kani::any();
 
-        // Constant time equality comparison to prevent timing attacks
-        Ok(lhs.ct_eq(&rhs).into())
+        let accepted = proof.verify("sid", pid, g, g);
+        assert_eq!(accepted, Ok(EQUATION_HOLDS.load(Ordering::Relaxed)));
+        assert_eq!(
+            proof.verify("sid", pid, ProjectivePoint::IDENTITY, g),
+            Err(DLogProofError::IdentityPoint)
+        );
     }
 }
@@ -0,0 +1,125 @@
+use curve25519_dalek::scalar::clamp_integer;
+use curve25519_dalek::traits::{MultiscalarMul, VartimeMultiscalarMul};
+use curve25519_dalek::{EdwardsPoint, Scalar};
+use sha2::{Digest, Sha512};
+
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+
+/// Edwards25519, the curve of Ed25519 keys (`backend-ed25519` feature)
+///
+/// Points are serialized as compressed Edwards y-coordinates (RFC 8032) and scalars as
+/// 32-byte little-endian hex. The curve has cofactor 8: decoding decompresses the point and
+/// additionally rejects any point outside the prime-order subgroup, so a proof's commitment
+/// (or a public key from `public_key_from_bytes`) can't carry a small-order component that
+/// would let the verification equation hold only up to torsion. As on Ristretto, the
+/// challenge hash is reduced modulo the group order.
+impl DLogGroup for EdwardsPoint {
+    fn lincomb(terms: &[(Self, Scalar)]) -> Self {
+        EdwardsPoint::multiscalar_mul(
+            terms.iter().map(|(_, scalar)| scalar),
+            terms.iter().map(|(point, _)| point),
+        )
+    }
+
+    fn lincomb_vartime(terms: &[(Self, Scalar)]) -> Self {
+        EdwardsPoint::vartime_multiscalar_mul(
+            terms.iter().map(|(_, scalar)| scalar),
+            terms.iter().map(|(point, _)| point),
+        )
+    }
+
+    fn decode_point(bytes: &[u8]) -> Option<Self> {
+        let bytes: [u8; 32] = bytes.try_into().ok()?;
+        let point = curve25519_dalek::edwards::CompressedEdwardsY(bytes).decompress()?;
+        point.is_torsion_free().then_some(point)
+    }
+
+    fn challenge_from_hash(hash: &[u8]) -> Result<Scalar, DLogProofError> {
+        // Little-endian, so zero-extending a 32-byte hash keeps its value
+        let mut wide = [0u8; 64];
+        wide.get_mut(..hash.len())
+            .ok_or(DLogProofError::ChallengeDerivation)?
+            .copy_from_slice(hash);
+        Ok(Scalar::from_bytes_mod_order_wide(&wide))
+    }
+}
+
+/// Derives the secret scalar of an Ed25519 key from its 32-byte seed (the "private key")
+///
+/// This is the RFC 8032 expansion: the first half of SHA-512(seed), clamped. Proving
+/// knowledge of it for the base point `ED25519_BASEPOINT_POINT` proves ownership of the
+/// Ed25519 public key.
+pub fn secret_scalar_from_seed(seed: &[u8; 32]) -> Scalar {
+    let hash = Sha512::digest(seed);
+    let mut lower = [0u8; 32];
+    lower.copy_from_slice(&hash[..32]);
+    Scalar::from_bytes_mod_order(clamp_integer(lower))
+}
+
+/// Decompresses an Ed25519 public key, rejecting points outside the prime-order subgroup
+pub fn public_key_from_bytes(bytes: &[u8; 32]) -> Result<EdwardsPoint, DLogProofError> {
+    EdwardsPoint::decode_point(bytes).ok_or(DLogProofError::InvalidPoint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlog_proof::DLogProof;
+    use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+    use curve25519_dalek::edwards::CompressedEdwardsY;
+
+    // RFC 8032, section 7.1, test 1
+    const SEED: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+    const PUBLIC_KEY: &str = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+
+    fn key_pair() -> (Scalar, EdwardsPoint) {
+        let seed: [u8; 32] = hex::decode(SEED).unwrap().try_into().unwrap();
+        let public_key: [u8; 32] = hex::decode(PUBLIC_KEY).unwrap().try_into().unwrap();
+        (
+            secret_scalar_from_seed(&seed),
+            public_key_from_bytes(&public_key).unwrap(),
+        )
+    }
+
+    #[test]
+    fn proves_ownership_of_an_ed25519_key() {
+        let (x, y) = key_pair();
+        let g = ED25519_BASEPOINT_POINT;
+        assert_eq!(g * x, y);
+
+        let proof = DLogProof::prove("sid", 1, &x, y, g).unwrap();
+        let json = serde_json::to_string(&proof).unwrap();
+        let parsed: DLogProof<EdwardsPoint> = serde_json::from_str(&json).unwrap();
+        assert!(parsed.verify("sid", 1, y, g).unwrap());
+        assert!(!parsed.verify("sid", 1, y + g, g).unwrap());
+    }
+
+    #[test]
+    fn points_with_a_torsion_component_are_rejected() {
+        let (x, y) = key_pair();
+        // A point of order 8
+        let torsion = CompressedEdwardsY(
+            hex::decode("26e8958fc2b227b045c3f489f2ef98f0d5dfac05d3c63339b13802886d53fc05")
+                .unwrap()
+                .try_into()
+                .unwrap(),
+        )
+        .decompress()
+        .unwrap();
+        assert!(!torsion.is_torsion_free());
+
+        let mixed = (y + torsion).compress().to_bytes();
+        assert_eq!(
+            public_key_from_bytes(&mixed),
+            Err(DLogProofError::InvalidPoint)
+        );
+
+        // A commitment shifted by the torsion point doesn't decode either
+        let g = ED25519_BASEPOINT_POINT;
+        let proof = DLogProof::prove("sid", 1, &x, y, g).unwrap();
+        let shifted = DLogProof::new(proof.t() + torsion, proof.s());
+        let json = serde_json::to_string(&shifted).unwrap();
+        assert!(serde_json::from_str::<DLogProof<EdwardsPoint>>(&json).is_err());
+    }
+}
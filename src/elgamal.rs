@@ -0,0 +1,165 @@
+use elliptic_curve::subtle::{Choice, ConstantTimeEq};
+use elliptic_curve::Field;
+use k256::ProjectivePoint;
+use rand_core::{CryptoRngCore, OsRng};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::dleq::DleqProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::secret::SecretScalar;
+use crate::serialization::PointHex;
+use crate::session::SessionId;
+
+/// ElGamal encryption of a group element M under the public key P = sk * G
+///
+/// * `c1` - The ephemeral key k * G
+/// * `c2` - The masked message M + k * P
+///
+/// `encrypt_dlog` encrypts the public key y = x * G of a prover to an auditor and proves with
+/// a `DleqProof` that the ciphertext holds y: log_G(c1) = log_P(c2 - y) = k. The auditor
+/// decrypts y without the verifier learning anything beyond that; combined with a `DLogProof`
+/// for y under the same session, it shows the encrypted key is one the prover controls.
+#[serde_as]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ElGamalCiphertext<G: DLogGroup = ProjectivePoint> {
+    #[serde_as(as = "PointHex")]
+    c1: G,
+    #[serde_as(as = "PointHex")]
+    c2: G,
+}
+
+impl<G: DLogGroup> ElGamalCiphertext<G> {
+    /// Assembles a ciphertext from its components
+    pub fn new(c1: G, c2: G) -> Self {
+        ElGamalCiphertext { c1, c2 }
+    }
+
+    /// The ephemeral key k * G
+    pub fn c1(&self) -> G {
+        self.c1
+    }
+
+    /// The masked message M + k * P
+    pub fn c2(&self) -> G {
+        self.c2
+    }
+
+    /// Encrypts `message` under `public_key`, returning the ephemeral secret k with it
+    pub fn encrypt(
+        rng: &mut impl CryptoRngCore,
+        message: G,
+        public_key: G,
+        base_point: G,
+    ) -> (Self, SecretScalar<G::Scalar>) {
+        let k = SecretScalar::new(G::Scalar::random(rng));
+        let ciphertext = ElGamalCiphertext {
+            c1: base_point * k.expose_secret(),
+            c2: message + public_key * k.expose_secret(),
+        };
+        (ciphertext, k)
+    }
+
+    /// Decrypts the message M = c2 - sk * c1
+    pub fn decrypt(&self, secret_key: impl Into<SecretScalar<G::Scalar>>) -> G {
+        self.c2 - self.c1 * secret_key.into().expose_secret()
+    }
+
+    /// Encrypts `y` to the auditor key `public_key` and proves the ciphertext holds y
+    ///
+    /// # Arguments
+    /// * `sid` - Session identifier used for domain separation, a string or bytes (see `SessionId`)
+    /// * `pid` - Participant ID for uniqueness to distinguish different proofs
+    /// * `y` - The point to escrow, e.g. the prover's public key x * G
+    /// * `public_key` - The auditor's ElGamal public key P
+    /// * `base_point` - Base point G of the group
+    ///
+    /// # Returns
+    /// * `Ok((ElGamalCiphertext, DleqProof))` - The ciphertext and its consistency proof
+    /// * `Err(DLogProofError)` - `IdentityPoint` for an identity key or base point, or if the
+    ///   challenge can't be derived
+    pub fn encrypt_dlog<'s>(
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        y: G,
+        public_key: G,
+        base_point: G,
+    ) -> Result<(Self, DleqProof<G>), DLogProofError> {
+        let (ciphertext, k) = Self::encrypt(&mut OsRng, y, public_key, base_point);
+        let proof = DleqProof::prove(
+            sid,
+            pid,
+            &k,
+            ciphertext.c1,
+            ciphertext.c2 - y,
+            base_point,
+            public_key,
+        )?;
+        Ok((ciphertext, proof))
+    }
+
+    /// Verifies that the ciphertext encrypts `y` under `public_key`
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Validity of the consistency proof
+    /// * `Err(DLogProofError)` - `IdentityPoint` for a degenerate ciphertext, key or base
+    ///   point, or if the challenge can't be derived
+    pub fn verify_dlog<'s>(
+        &self,
+        proof: &DleqProof<G>,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        y: G,
+        public_key: G,
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        proof.verify(sid, pid, self.c1, self.c2 - y, base_point, public_key)
+    }
+}
+
+impl<G: DLogGroup> ConstantTimeEq for ElGamalCiphertext<G> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.c1.ct_eq(&other.c1) & self.c2.ct_eq(&other.c2)
+    }
+}
+
+impl<G: DLogGroup> PartialEq for ElGamalCiphertext<G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<G: DLogGroup> Eq for ElGamalCiphertext<G> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::Scalar;
+
+    #[test]
+    fn escrowed_keys_verify_and_decrypt() {
+        let g = ProjectivePoint::GENERATOR;
+        let y = g * Scalar::random(&mut OsRng);
+        let auditor_key = Scalar::random(&mut OsRng);
+        let public_key = g * auditor_key;
+
+        let (ciphertext, proof) =
+            ElGamalCiphertext::encrypt_dlog("sid", 1, y, public_key, g).unwrap();
+        assert!(ciphertext
+            .verify_dlog(&proof, "sid", 1, y, public_key, g)
+            .unwrap());
+        assert!(!ciphertext
+            .verify_dlog(&proof, "sid", 2, y, public_key, g)
+            .unwrap());
+        assert!(!ciphertext
+            .verify_dlog(&proof, "sid", 1, y + g, public_key, g)
+            .unwrap());
+        assert_eq!(ciphertext.decrypt(&auditor_key), y);
+
+        let json = serde_json::to_string(&ciphertext).unwrap();
+        let decoded: ElGamalCiphertext = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, ciphertext);
+    }
+}
@@ -0,0 +1,328 @@
+use elliptic_curve::PrimeField;
+
+use crate::dlog_proof::{ChallengeHash, DLogProof};
+use crate::error::DLogProofError;
+use crate::group::{decode_canonical, DLogGroup};
+use crate::participant::ParticipantId;
+use crate::session::SessionId;
+use crate::verifier::Verifier;
+
+/// Current version of the envelope format, the only one `ProofEnvelope::from_bytes` accepts
+pub const ENVELOPE_VERSION: u8 = 1;
+
+/// Group a proof in a `ProofEnvelope` was made over
+///
+/// All groups the crate knows have an identifier, whether or not their backend feature is
+/// enabled, so envelopes can always be parsed and inspected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Curve {
+    /// secp256k1 (always available)
+    Secp256k1,
+    /// NIST P-256 (`p256` feature)
+    P256,
+    /// Ristretto255 (`backend-ristretto` feature)
+    Ristretto255,
+    /// edwards25519 (`backend-ed25519` feature)
+    Edwards25519,
+    /// BLS12-381 G1 (`backend-bls12-381` feature)
+    Bls12381G1,
+}
+
+impl Curve {
+    /// Identifier byte in the envelope encoding
+    pub fn id(self) -> u8 {
+        match self {
+            Curve::Secp256k1 => 1,
+            Curve::P256 => 2,
+            Curve::Ristretto255 => 3,
+            Curve::Edwards25519 => 4,
+            Curve::Bls12381G1 => 5,
+        }
+    }
+
+    /// Looks up a curve by its identifier byte
+    pub fn from_id(id: u8) -> Option<Self> {
+        [
+            Curve::Secp256k1,
+            Curve::P256,
+            Curve::Ristretto255,
+            Curve::Edwards25519,
+            Curve::Bls12381G1,
+        ]
+        .into_iter()
+        .find(|curve| curve.id() == id)
+    }
+}
+
+pub(crate) fn hash_id(hash: ChallengeHash) -> u8 {
+    match hash {
+        ChallengeHash::Sha256 => 1,
+        ChallengeHash::Sha512 => 2,
+        ChallengeHash::Sha3_256 => 3,
+        ChallengeHash::Blake2b512 => 4,
+    }
+}
+
+pub(crate) fn hash_from_id(id: u8) -> Option<ChallengeHash> {
+    ChallengeHash::ALL
+        .into_iter()
+        .find(|hash| hash_id(*hash) == id)
+}
+
+/// A `DLogGroup` with a `Curve` identifier, i.e. one that proofs can be enveloped for
+pub trait EnvelopeCurve: DLogGroup {
+    const CURVE: Curve;
+}
+
+impl EnvelopeCurve for k256::ProjectivePoint {
+    const CURVE: Curve = Curve::Secp256k1;
+}
+
+#[cfg(feature = "p256")]
+impl EnvelopeCurve for p256::ProjectivePoint {
+    const CURVE: Curve = Curve::P256;
+}
+
+#[cfg(feature = "backend-ristretto")]
+impl EnvelopeCurve for curve25519_dalek::RistrettoPoint {
+    const CURVE: Curve = Curve::Ristretto255;
+}
+
+#[cfg(feature = "backend-ed25519")]
+impl EnvelopeCurve for curve25519_dalek::EdwardsPoint {
+    const CURVE: Curve = Curve::Edwards25519;
+}
+
+#[cfg(feature = "backend-bls12-381")]
+impl EnvelopeCurve for bls12_381::G1Projective {
+    const CURVE: Curve = Curve::Bls12381G1;
+}
+
+/// Self-describing proof: format version, curve and challenge hash alongside the proof bytes
+///
+/// The encoding is `version || curve id || hash id || t || s`, with `t` in the group's
+/// `GroupEncoding` and `s` in its canonical scalar representation (the 65 bytes of
+/// `DLogProof::to_bytes` on secp256k1). A reader can tell how to verify the proof without
+/// out-of-band agreement, and envelopes of an unknown version, curve or hash are rejected when
+/// parsed rather than misinterpreted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofEnvelope {
+    pub(crate) curve: Curve,
+    pub(crate) hash: ChallengeHash,
+    pub(crate) proof: Vec<u8>,
+}
+
+impl ProofEnvelope {
+    /// Wraps a proof made with the given challenge hash (`ChallengeHash::Sha256` for proofs
+    /// from `DLogProof::prove`)
+    pub fn seal<G: EnvelopeCurve>(proof: &DLogProof<G>, hash: ChallengeHash) -> Self {
+        let mut bytes = proof.t.to_bytes().as_ref().to_vec();
+        bytes.extend_from_slice(proof.s.to_repr().as_ref());
+        ProofEnvelope {
+            curve: G::CURVE,
+            hash,
+            proof: bytes,
+        }
+    }
+
+    /// The group the proof was made over
+    pub fn curve(&self) -> Curve {
+        self.curve
+    }
+
+    /// The challenge hash the proof was made with
+    pub fn hash(&self) -> ChallengeHash {
+        self.hash
+    }
+
+    /// Encodes the envelope as `version || curve id || hash id || proof`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![ENVELOPE_VERSION, self.curve.id(), hash_id(self.hash)];
+        bytes.extend_from_slice(&self.proof);
+        bytes
+    }
+
+    /// Decodes an envelope, rejecting unknown versions, curves and hashes
+    ///
+    /// The proof bytes are only checked when the proof is opened, since their format depends
+    /// on the curve, whose backend may not be compiled in.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DLogProofError> {
+        let [version, curve, hash, proof @ ..] = bytes else {
+            return Err(DLogProofError::Serialization(
+                "Truncated proof envelope".to_string(),
+            ));
+        };
+        if *version != ENVELOPE_VERSION {
+            return Err(DLogProofError::Serialization(format!(
+                "Unsupported proof envelope version {version}, expected {ENVELOPE_VERSION}"
+            )));
+        }
+        let curve = Curve::from_id(*curve).ok_or_else(|| {
+            DLogProofError::Serialization(format!("Unknown curve identifier {curve}"))
+        })?;
+        let hash = hash_from_id(*hash).ok_or_else(|| {
+            DLogProofError::Serialization(format!("Unknown hash identifier {hash}"))
+        })?;
+        Ok(ProofEnvelope {
+            curve,
+            hash,
+            proof: proof.to_vec(),
+        })
+    }
+
+    /// Extracts the proof as a `DLogProof<G>`, failing if the envelope is for another curve
+    pub fn open<G: EnvelopeCurve>(&self) -> Result<DLogProof<G>, DLogProofError> {
+        if self.curve != G::CURVE {
+            return Err(DLogProofError::Serialization(format!(
+                "Envelope holds a {:?} proof, not {:?}",
+                self.curve,
+                G::CURVE
+            )));
+        }
+        let mut t_repr = G::Repr::default();
+        let mut s_repr = <G::Scalar as PrimeField>::Repr::default();
+        let t_len = t_repr.as_ref().len();
+        if self.proof.len() != t_len + s_repr.as_ref().len() {
+            return Err(DLogProofError::Serialization(format!(
+                "Invalid proof length for {:?}: {} bytes",
+                self.curve,
+                self.proof.len()
+            )));
+        }
+        t_repr.as_mut().copy_from_slice(&self.proof[..t_len]);
+        s_repr.as_mut().copy_from_slice(&self.proof[t_len..]);
+
+        let t = decode_canonical::<G>(&t_repr).ok_or(DLogProofError::InvalidPoint)?;
+        let s = Option::from(G::Scalar::from_repr(s_repr)).ok_or(DLogProofError::InvalidScalar)?;
+        Ok(DLogProof::new(t, s))
+    }
+
+    /// Verifies the enveloped proof with the curve's standard generator as base point
+    ///
+    /// `y` is the public point in the curve's `GroupEncoding` (compressed SEC1 for the
+    /// Weierstrass curves). The backend is picked from the envelope; a curve whose feature
+    /// isn't enabled is reported as an error.
+    pub fn verify<'s, 'p>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        y: &[u8],
+    ) -> Result<bool, DLogProofError> {
+        let pid = pid.into();
+        match self.curve {
+            Curve::Secp256k1 => self.verify_in::<k256::ProjectivePoint>(sid.into(), pid, y),
+            #[cfg(feature = "p256")]
+            Curve::P256 => self.verify_in::<p256::ProjectivePoint>(sid.into(), pid, y),
+            #[cfg(feature = "backend-ristretto")]
+            Curve::Ristretto255 => {
+                self.verify_in::<curve25519_dalek::RistrettoPoint>(sid.into(), pid, y)
+            }
+            #[cfg(feature = "backend-ed25519")]
+            Curve::Edwards25519 => {
+                self.verify_in::<curve25519_dalek::EdwardsPoint>(sid.into(), pid, y)
+            }
+            #[cfg(feature = "backend-bls12-381")]
+            Curve::Bls12381G1 => self.verify_in::<bls12_381::G1Projective>(sid.into(), pid, y),
+            #[allow(unreachable_patterns)]
+            curve => Err(DLogProofError::Serialization(format!(
+                "Backend for {curve:?} proofs is not enabled"
+            ))),
+        }
+    }
+
+    fn verify_in<G: EnvelopeCurve>(
+        &self,
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        y: &[u8],
+    ) -> Result<bool, DLogProofError> {
+        let proof = self.open::<G>()?;
+        let y = G::decode_point(y).ok_or(DLogProofError::InvalidPoint)?;
+        Verifier::new()
+            .hash(self.hash)
+            .verify(&proof, sid, pid, y, G::generator())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prover::Prover;
+    use elliptic_curve::group::GroupEncoding;
+    use elliptic_curve::Field;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_core::OsRng;
+
+    #[test]
+    fn envelopes_round_trip_and_verify() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let proof = Prover::new()
+            .hash(ChallengeHash::Sha3_256)
+            .prove("sid", 1, &x, y, g)
+            .unwrap();
+
+        let envelope = ProofEnvelope::seal(&proof, ChallengeHash::Sha3_256);
+        let bytes = envelope.to_bytes();
+        assert_eq!(bytes[..3], [ENVELOPE_VERSION, 1, 3]);
+        assert_eq!(bytes[3..], proof.to_bytes());
+
+        let parsed = ProofEnvelope::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed.curve(), Curve::Secp256k1);
+        assert_eq!(parsed.open::<ProjectivePoint>().unwrap(), proof);
+        assert!(parsed.verify("sid", 1, &y.to_bytes()).unwrap());
+        assert!(!parsed.verify("sid", 2, &y.to_bytes()).unwrap());
+
+        // Claiming another hash makes the proof fail instead of passing under the wrong rules
+        let mut wrong_hash = bytes.clone();
+        wrong_hash[2] = 1;
+        let wrong_hash = ProofEnvelope::from_bytes(&wrong_hash).unwrap();
+        assert!(!wrong_hash.verify("sid", 1, &y.to_bytes()).unwrap());
+    }
+
+    #[test]
+    fn unknown_formats_are_rejected() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let proof = DLogProof::prove("sid", 1, &x, g * x, g).unwrap();
+        let bytes = ProofEnvelope::seal(&proof, ChallengeHash::Sha256).to_bytes();
+
+        for (index, value, message) in [
+            (0, 2, "Unsupported proof envelope version 2, expected 1"),
+            (1, 9, "Unknown curve identifier 9"),
+            (2, 0, "Unknown hash identifier 0"),
+        ] {
+            let mut tampered = bytes.clone();
+            tampered[index] = value;
+            assert_eq!(
+                ProofEnvelope::from_bytes(&tampered),
+                Err(DLogProofError::Serialization(message.to_string()))
+            );
+        }
+        assert!(ProofEnvelope::from_bytes(&bytes[..2]).is_err());
+
+        let truncated = ProofEnvelope::from_bytes(&bytes[..40]).unwrap();
+        assert!(truncated.open::<ProjectivePoint>().is_err());
+    }
+
+    #[cfg(feature = "backend-ristretto")]
+    #[test]
+    fn envelopes_dispatch_to_the_right_backend() {
+        use curve25519_dalek::RistrettoPoint;
+        use elliptic_curve::group::Group;
+
+        let g = RistrettoPoint::generator();
+        let x = curve25519_dalek::Scalar::random(&mut OsRng);
+        let y = g * x;
+        let proof = DLogProof::prove("sid", 1, &x, y, g).unwrap();
+
+        let envelope = ProofEnvelope::from_bytes(
+            &ProofEnvelope::seal(&proof, ChallengeHash::Sha256).to_bytes(),
+        )
+        .unwrap();
+        assert_eq!(envelope.curve(), Curve::Ristretto255);
+        assert!(envelope.verify("sid", 1, &y.to_bytes()).unwrap());
+        assert!(envelope.open::<ProjectivePoint>().is_err());
+    }
+}
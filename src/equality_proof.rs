@@ -0,0 +1,268 @@
+use elliptic_curve::subtle::{Choice, ConstantTimeEq};
+use elliptic_curve::Field;
+use k256::{ProjectivePoint, Scalar};
+use rand_core::OsRng;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::product_proof::PedersenOpening;
+use crate::serialization::{PointHex, ScalarHex};
+use crate::session::SessionId;
+
+/// Proof that a Pedersen commitment C = x * G + b * H commits to the secret key x of the
+/// public key y = x * G
+///
+/// Both relations share the witness x, so one nonce α covers it in both commitments:
+/// * `t1 = α * G + ρ * H` - commitment for C
+/// * `t2 = α * G` - commitment for y
+///
+/// and one response per witness, z = nonce + c * witness. Links a committed attribute to an
+/// existing key without revealing either x or b.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct CommittedKeyProof {
+    #[serde_as(as = "PointHex")]
+    pub(crate) t1: ProjectivePoint,
+    #[serde_as(as = "PointHex")]
+    pub(crate) t2: ProjectivePoint,
+    #[serde_as(as = "ScalarHex")]
+    pub(crate) z_x: Scalar,
+    #[serde_as(as = "ScalarHex")]
+    pub(crate) z_b: Scalar,
+}
+
+impl CommittedKeyProof {
+    const DOMAIN_SEPARATOR: &'static [u8] = b"SCHNORR_COMMITTED_KEY_PROOF";
+
+    /// Proves that C = opening.commit(G, H) commits to the secret key of y = opening.value * G
+    ///
+    /// # Arguments
+    /// * `sid` - Session identifier used for domain separation, a string or bytes (see `SessionId`)
+    /// * `pid` - Participant ID for uniqueness to distinguish different proofs
+    /// * `opening` - Opening of the commitment, its value being the secret key x
+    /// * `base_point` - Base point G of secp256k1 curve
+    /// * `h` - Second Pedersen generator H, whose discrete log relative to G must be unknown
+    ///
+    /// # Returns
+    /// * `Ok(CommittedKeyProof)` - The proof
+    /// * `Err(DLogProofError)` - If proof generation fails
+    pub fn prove<'s>(
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        opening: &PedersenOpening,
+        base_point: ProjectivePoint,
+        h: ProjectivePoint,
+    ) -> Result<Self, DLogProofError> {
+        let commitment = opening.commit(base_point, h);
+        let y = base_point * opening.value;
+
+        let alpha = Scalar::random(&mut OsRng);
+        let rho = Scalar::random(&mut OsRng);
+        let t1 = base_point * alpha + h * rho;
+        let t2 = base_point * alpha;
+
+        let c = Self::challenge(sid.into(), pid, commitment, y, base_point, h, [t1, t2])?;
+
+        Ok(CommittedKeyProof {
+            t1,
+            t2,
+            z_x: alpha + c * opening.value,
+            z_b: rho + c * opening.blinding,
+        })
+    }
+
+    /// Verifies that `commitment` commits to the secret key of `y`
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Validity of the proof
+    /// * `Err(DLogProofError)` - Any error during verification
+    pub fn verify<'s>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        commitment: ProjectivePoint,
+        y: ProjectivePoint,
+        base_point: ProjectivePoint,
+        h: ProjectivePoint,
+    ) -> Result<bool, DLogProofError> {
+        let c = Self::challenge(
+            sid.into(),
+            pid,
+            commitment,
+            y,
+            base_point,
+            h,
+            [self.t1, self.t2],
+        )?;
+
+        let first: Choice =
+            (base_point * self.z_x + h * self.z_b).ct_eq(&(self.t1 + commitment * c));
+        let second: Choice = (base_point * self.z_x).ct_eq(&(self.t2 + y * c));
+
+        Ok((first & second).into())
+    }
+
+    fn challenge(
+        sid: SessionId<'_>,
+        pid: u32,
+        commitment: ProjectivePoint,
+        y: ProjectivePoint,
+        base_point: ProjectivePoint,
+        h: ProjectivePoint,
+        t: [ProjectivePoint; 2],
+    ) -> Result<Scalar, DLogProofError> {
+        let [t1, t2] = t;
+        DLogProof::hash_points_with_domain(
+            Self::DOMAIN_SEPARATOR,
+            sid,
+            pid.into(),
+            &[base_point, h, commitment, y, t1, t2],
+        )
+    }
+}
+
+/// Proof that two Pedersen commitments C1 = x * G + b1 * H and C2 = x * G + b2 * H open to
+/// the same value x
+///
+/// The values cancel in C1 - C2 = (b1 - b2) * H, so the proof is a Schnorr proof of
+/// knowledge of b1 - b2 over the base H, under its own domain separation tag:
+/// * `t = ρ * H` - commitment
+/// * `z = ρ + c * (b1 - b2)` - response
+///
+/// If the values differ, C1 - C2 has a G component and no such proof can be made without
+/// knowing the discrete log of H relative to G.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct CommitmentEqualityProof {
+    #[serde_as(as = "PointHex")]
+    pub(crate) t: ProjectivePoint,
+    #[serde_as(as = "ScalarHex")]
+    pub(crate) z: Scalar,
+}
+
+impl CommitmentEqualityProof {
+    const DOMAIN_SEPARATOR: &'static [u8] = b"SCHNORR_COMMITMENT_EQUALITY_PROOF";
+
+    /// Proves that the commitments of `first` and `second` hide the same value
+    ///
+    /// # Arguments
+    /// * `sid` - Session identifier used for domain separation, a string or bytes (see `SessionId`)
+    /// * `pid` - Participant ID for uniqueness to distinguish different proofs
+    /// * `first`, `second` - Openings of the two commitments
+    /// * `base_point` - Base point G of secp256k1 curve
+    /// * `h` - Second Pedersen generator H, whose discrete log relative to G must be unknown
+    ///
+    /// # Returns
+    /// * `Ok(CommitmentEqualityProof)` - The proof
+    /// * `Err(DLogProofError)` - `InvalidWitness` if the committed values differ, or any
+    ///   error of proof generation
+    pub fn prove<'s>(
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        first: &PedersenOpening,
+        second: &PedersenOpening,
+        base_point: ProjectivePoint,
+        h: ProjectivePoint,
+    ) -> Result<Self, DLogProofError> {
+        if first.value != second.value {
+            return Err(DLogProofError::InvalidWitness);
+        }
+        let commitments = [first.commit(base_point, h), second.commit(base_point, h)];
+
+        let rho = Scalar::random(&mut OsRng);
+        let t = h * rho;
+        let c = Self::challenge(sid.into(), pid, &commitments, base_point, h, t)?;
+
+        Ok(CommitmentEqualityProof {
+            t,
+            z: rho + c * (first.blinding - second.blinding),
+        })
+    }
+
+    /// Verifies that `commitments` = [C1, C2] hide the same value
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Validity of the proof
+    /// * `Err(DLogProofError)` - Any error during verification
+    pub fn verify<'s>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        commitments: &[ProjectivePoint; 2],
+        base_point: ProjectivePoint,
+        h: ProjectivePoint,
+    ) -> Result<bool, DLogProofError> {
+        let [c1, c2] = *commitments;
+        let c = Self::challenge(sid.into(), pid, commitments, base_point, h, self.t)?;
+        Ok((h * self.z).ct_eq(&(self.t + (c1 - c2) * c)).into())
+    }
+
+    fn challenge(
+        sid: SessionId<'_>,
+        pid: u32,
+        commitments: &[ProjectivePoint; 2],
+        base_point: ProjectivePoint,
+        h: ProjectivePoint,
+        t: ProjectivePoint,
+    ) -> Result<Scalar, DLogProofError> {
+        let [c1, c2] = *commitments;
+        DLogProof::hash_points_with_domain(
+            Self::DOMAIN_SEPARATOR,
+            sid,
+            pid.into(),
+            &[base_point, h, c1, c2, t],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (ProjectivePoint, ProjectivePoint, PedersenOpening) {
+        let g = ProjectivePoint::GENERATOR;
+        let h = g * Scalar::random(&mut OsRng);
+        let opening = PedersenOpening::new(Scalar::random(&mut OsRng), Scalar::random(&mut OsRng));
+        (g, h, opening)
+    }
+
+    #[test]
+    fn committed_key_is_linked_to_the_public_key() {
+        let (g, h, opening) = setup();
+        let proof = CommittedKeyProof::prove("sid", 1, &opening, g, h).unwrap();
+
+        let commitment = opening.commit(g, h);
+        let y = g * opening.value;
+        assert!(proof.verify("sid", 1, commitment, y, g, h).unwrap());
+        assert!(!proof.verify("sid", 2, commitment, y, g, h).unwrap());
+
+        // Another key, or a commitment to another value, breaks the link
+        assert!(!proof.verify("sid", 1, commitment, y + g, g, h).unwrap());
+        assert!(!proof.verify("sid", 1, commitment + g, y, g, h).unwrap());
+
+        let json = serde_json::to_string(&proof).unwrap();
+        let decoded: CommittedKeyProof = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, proof);
+    }
+
+    #[test]
+    fn commitments_to_the_same_value_are_linked() {
+        let (g, h, first) = setup();
+        let second = PedersenOpening::new(first.value, Scalar::random(&mut OsRng));
+        let proof = CommitmentEqualityProof::prove("sid", 1, &first, &second, g, h).unwrap();
+
+        let commitments = [first.commit(g, h), second.commit(g, h)];
+        assert!(proof.verify("sid", 1, &commitments, g, h).unwrap());
+        assert!(!proof.verify("sid", 2, &commitments, g, h).unwrap());
+
+        let other = PedersenOpening::new(first.value + Scalar::ONE, second.blinding);
+        assert_eq!(
+            CommitmentEqualityProof::prove("sid", 1, &first, &other, g, h),
+            Err(DLogProofError::InvalidWitness)
+        );
+        let commitments = [first.commit(g, h), other.commit(g, h)];
+        assert!(!proof.verify("sid", 1, &commitments, g, h).unwrap());
+    }
+}
@@ -0,0 +1,172 @@
+use alloc::string::String;
+use core::fmt;
+
+/// Errors returned by proof generation, verification and (de)serialization
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DLogProofError {
+    /// A point encoding is malformed or doesn't describe a point on the curve
+    InvalidPoint,
+    /// A scalar encoding is malformed or not below the group order
+    InvalidScalar,
+    /// The Fiat-Shamir challenge could not be derived from the transcript
+    ChallengeDerivation,
+    /// A proof could not be encoded or decoded, with the reason
+    Serialization(String),
+    /// The challenge mode isn't available for this group or with the other transcript options
+    UnsupportedChallengeMode,
+    /// A freshly created proof failed its own verification (see `Prover::verify_after_prove`)
+    FaultDetected,
+    /// The base point or the public key y is the identity, which anyone can "prove" knowledge
+    /// of a logarithm for
+    IdentityPoint,
+    /// A composed proof got no statements, or a different number of witnesses or responses
+    /// than statements; also statements that can't be aggregated, e.g. over different base
+    /// points
+    StatementCount,
+    /// A participant index is zero, repeated, or not part of the session or service
+    InvalidParticipant,
+    /// A value or parameter is outside its allowed range: a range proof value or bit length,
+    /// a Bloom filter sizing, or zero rounds of a truncated-challenge proof
+    OutOfRange,
+    /// A `BoundProof` carries another session ID than the verifier expects
+    SessionMismatch,
+    /// A `BoundProof` carries another participant ID than the verifier expects
+    ParticipantMismatch,
+    /// A `TimedProof` is checked outside its validity window, or made with an empty one
+    OutsideValidityWindow,
+    /// The secret values given to a prover don't satisfy the relation it proves, e.g.
+    /// commitments to different values for an equality proof
+    InvalidWitness,
+    /// Reading or writing a stream failed, with the reason
+    Io(String),
+}
+
+impl DLogProofError {
+    /// A stable number for the variant, for reporting errors across language boundaries
+    ///
+    /// Codes are never reused or renumbered; new variants get the next free number. They
+    /// don't carry the reason of `Serialization` and `Io`, which is only in the `Display` text.
+    ///
+    /// | Code | Variant |
+    /// |------|---------|
+    /// | 1 | `InvalidPoint` |
+    /// | 2 | `InvalidScalar` |
+    /// | 3 | `ChallengeDerivation` |
+    /// | 4 | `Serialization` |
+    /// | 5 | `UnsupportedChallengeMode` |
+    /// | 6 | `FaultDetected` |
+    /// | 7 | `IdentityPoint` |
+    /// | 8 | `StatementCount` |
+    /// | 9 | `InvalidParticipant` |
+    /// | 10 | `OutOfRange` |
+    /// | 11 | `SessionMismatch` |
+    /// | 12 | `ParticipantMismatch` |
+    /// | 13 | `OutsideValidityWindow` |
+    /// | 14 | `InvalidWitness` |
+    /// | 15 | `Io` |
+    pub const fn code(&self) -> u32 {
+        match self {
+            DLogProofError::InvalidPoint => 1,
+            DLogProofError::InvalidScalar => 2,
+            DLogProofError::ChallengeDerivation => 3,
+            DLogProofError::Serialization(_) => 4,
+            DLogProofError::UnsupportedChallengeMode => 5,
+            DLogProofError::FaultDetected => 6,
+            DLogProofError::IdentityPoint => 7,
+            DLogProofError::StatementCount => 8,
+            DLogProofError::InvalidParticipant => 9,
+            DLogProofError::OutOfRange => 10,
+            DLogProofError::SessionMismatch => 11,
+            DLogProofError::ParticipantMismatch => 12,
+            DLogProofError::OutsideValidityWindow => 13,
+            DLogProofError::InvalidWitness => 14,
+            DLogProofError::Io(_) => 15,
+        }
+    }
+}
+
+impl fmt::Display for DLogProofError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DLogProofError::InvalidPoint => f.write_str("Invalid point encoding"),
+            DLogProofError::InvalidScalar => f.write_str("Invalid Scalar value"),
+            DLogProofError::ChallengeDerivation => f.write_str("Failed to convert hash to scalar"),
+            DLogProofError::Serialization(reason) => write!(f, "Serialization failed: {reason}"),
+            DLogProofError::UnsupportedChallengeMode => {
+                f.write_str("Challenge mode not supported with these parameters")
+            }
+            DLogProofError::FaultDetected => f.write_str("Proof failed verification after proving"),
+            DLogProofError::IdentityPoint => f.write_str("Identity point in the statement"),
+            DLogProofError::StatementCount => f.write_str("Wrong number of statements"),
+            DLogProofError::InvalidParticipant => f.write_str("Invalid participant index"),
+            DLogProofError::OutOfRange => f.write_str("Value or bit length out of range"),
+            DLogProofError::SessionMismatch => f.write_str("Proof made for another session"),
+            DLogProofError::ParticipantMismatch => {
+                f.write_str("Proof made for another participant")
+            }
+            DLogProofError::OutsideValidityWindow => {
+                f.write_str("Timestamp outside the proof's validity window")
+            }
+            DLogProofError::InvalidWitness => {
+                f.write_str("Witness does not satisfy the proven relation")
+            }
+            DLogProofError::Io(reason) => write!(f, "I/O failed: {reason}"),
+        }
+    }
+}
+
+impl core::error::Error for DLogProofError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlog_proof::DLogProof;
+
+    #[test]
+    fn decoding_errors_are_typed() {
+        assert!(matches!(
+            DLogProof::from_bytes(&[0u8; 3]),
+            Err(DLogProofError::Serialization(_))
+        ));
+
+        // 0x05 is not a SEC1 tag
+        let mut bytes = [0u8; DLogProof::ENCODED_LEN];
+        bytes[0] = 0x05;
+        assert_eq!(
+            DLogProof::from_bytes(&bytes),
+            Err(DLogProofError::InvalidPoint)
+        );
+
+        // Generator followed by s = 2^256 - 1, which is above the group order
+        let generator = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+        bytes[..33].copy_from_slice(&hex::decode(generator).unwrap());
+        bytes[33..].fill(0xff);
+        assert_eq!(
+            DLogProof::from_bytes(&bytes),
+            Err(DLogProofError::InvalidScalar)
+        );
+
+        let json = format!(r#"{{"t":"{}","s":"zz"}}"#, generator);
+        let error = serde_json::from_str::<DLogProof>(&json).unwrap_err();
+        assert!(error.to_string().contains("Invalid Scalar value"));
+    }
+
+    #[test]
+    fn codes_are_stable() {
+        assert_eq!(DLogProofError::InvalidPoint.code(), 1);
+        assert_eq!(DLogProofError::InvalidScalar.code(), 2);
+        assert_eq!(DLogProofError::ChallengeDerivation.code(), 3);
+        assert_eq!(DLogProofError::Serialization("any reason".into()).code(), 4);
+        assert_eq!(DLogProofError::UnsupportedChallengeMode.code(), 5);
+        assert_eq!(DLogProofError::FaultDetected.code(), 6);
+        assert_eq!(DLogProofError::IdentityPoint.code(), 7);
+        assert_eq!(DLogProofError::StatementCount.code(), 8);
+        assert_eq!(DLogProofError::InvalidParticipant.code(), 9);
+        assert_eq!(DLogProofError::OutOfRange.code(), 10);
+        assert_eq!(DLogProofError::SessionMismatch.code(), 11);
+        assert_eq!(DLogProofError::ParticipantMismatch.code(), 12);
+        assert_eq!(DLogProofError::OutsideValidityWindow.code(), 13);
+        assert_eq!(DLogProofError::InvalidWitness.code(), 14);
+        assert_eq!(DLogProofError::Io("any reason".into()).code(), 15);
+    }
+}
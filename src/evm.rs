@@ -0,0 +1,287 @@
+use elliptic_curve::PrimeField;
+use k256::{ProjectivePoint, Scalar};
+use sha3::{Digest, Keccak256};
+
+use crate::dlog_proof::{
+    ByteCounter, ChallengeHash, ChallengeMode, ChallengeParams, DLogProof, TranscriptVersion,
+};
+use crate::error::DLogProofError;
+use crate::group::{reduce_be, DLogGroup};
+use crate::participant::ParticipantId;
+use crate::session::SessionId;
+
+/// Size of the calldata encoding `(uint256 tx, uint256 ty, uint256 s)`
+pub const CALLDATA_LEN: usize = 96;
+
+/// Solidity source of the reference verifier, see `solidity_verifier`
+const SOLIDITY_TEMPLATE: &str = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// Verifies schnorr-dlog-zkp proofs made with ChallengeMode::Keccak256Evm over the secp256k1
+/// generator, without additional authenticated data.
+contract {{CONTRACT}} {
+    uint256 internal constant N = 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141;
+    uint256 internal constant GX = 0x79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798;
+    uint256 internal constant GY = 0x483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8;
+    bytes internal constant DOMAIN = hex"{{DOMAIN}}";
+
+    function challenge(string calldata sid, uint32 pid, uint256 yx, uint256 yy, uint256 tx, uint256 ty)
+        public
+        pure
+        returns (uint256)
+    {
+        return uint256(keccak256(abi.encodePacked(DOMAIN, sid, pid, GX, GY, yx, yy, tx, ty))) % N;
+    }
+
+    /// Checks s * G == t + c * y for the public key (yx, yy) and the proof (tx, ty, s)
+    function verify(string calldata sid, uint32 pid, uint256 yx, uint256 yy, uint256 tx, uint256 ty, uint256 s)
+        external
+        pure
+        returns (bool)
+    {
+        if (s == 0 || s >= N || yx == 0 || yx >= N) {
+            return false;
+        }
+        uint256 c = challenge(sid, pid, yx, yy, tx, ty);
+        if (c == 0) {
+            return false;
+        }
+        // ecrecover(h, v, r, sig) returns the address of r^-1 * (sig * R - h * G), where R is the
+        // point with x = r and the parity given by v. With R = y, h = -s * yx and sig = -c * yx
+        // that point is s * G - c * y, which must equal t.
+        bytes32 h = bytes32((N - mulmod(s, yx, N)) % N);
+        bytes32 sig = bytes32(N - mulmod(c, yx, N));
+        uint8 v = yy % 2 == 0 ? 27 : 28;
+        address recovered = ecrecover(h, v, bytes32(yx), sig);
+        address expected = address(uint160(uint256(keccak256(abi.encodePacked(tx, ty)))));
+        return recovered != address(0) && recovered == expected;
+    }
+}
+"#;
+
+impl DLogProof {
+    /// Encodes the proof as EVM calldata `(uint256 tx, uint256 ty, uint256 s)`
+    ///
+    /// Each value is a 32-byte big-endian word, the layout `abi.encode(tx, ty, s)` produces and
+    /// the verifier from `solidity_verifier` takes.
+    pub fn to_evm_calldata(&self) -> Result<[u8; CALLDATA_LEN], DLogProofError> {
+        let (x, y) = self.t.coordinates()?;
+        let mut calldata = [0u8; CALLDATA_LEN];
+        calldata[..32].copy_from_slice(&x);
+        calldata[32..64].copy_from_slice(&y);
+        calldata[64..].copy_from_slice(&self.s.to_repr());
+        Ok(calldata)
+    }
+
+    /// Decodes `to_evm_calldata` output, rejecting points off the curve and non-canonical scalars
+    pub fn from_evm_calldata(calldata: &[u8]) -> Result<Self, DLogProofError> {
+        let invalid_length = || {
+            DLogProofError::Serialization(format!(
+                "Invalid calldata length: expected {CALLDATA_LEN} bytes, got {}",
+                calldata.len()
+            ))
+        };
+        let (t_bytes, s_bytes) = calldata
+            .split_first_chunk::<64>()
+            .ok_or_else(invalid_length)?;
+        let s_bytes: [u8; 32] = s_bytes.try_into().map_err(|_| invalid_length())?;
+        let mut uncompressed = [0x04; 65];
+        uncompressed[1..].copy_from_slice(t_bytes);
+        let t = ProjectivePoint::decode_point(&uncompressed).ok_or(DLogProofError::InvalidPoint)?;
+        let s =
+            Option::from(Scalar::from_repr(s_bytes.into())).ok_or(DLogProofError::InvalidScalar)?;
+        Ok(DLogProof { t, s })
+    }
+}
+
+/// Returns the source of a Solidity contract verifying `Keccak256Evm` proofs on chain
+///
+/// The contract checks proofs over the secp256k1 generator for the given application domain
+/// (see `Prover::domain`); proofs with additional authenticated data aren't supported. As the
+/// EVM has no secp256k1 arithmetic, it recovers s * G - c * y with the `ecrecover` precompile
+/// and compares its address to the one of t, so callers must pass y's true coordinates.
+pub fn solidity_verifier(contract_name: &str, app_domain: Option<&[u8]>) -> String {
+    let params = ChallengeParams {
+        app_domain: app_domain.map(<[u8]>::to_vec),
+        mode: ChallengeMode::Keccak256Evm,
+        ..Default::default()
+    };
+    SOLIDITY_TEMPLATE
+        .replace("{{CONTRACT}}", contract_name)
+        .replace(
+            "{{DOMAIN}}",
+            &hex::encode(DLogProof::<ProjectivePoint>::domain(&params)),
+        )
+}
+
+impl<G: DLogGroup> DLogProof<G> {
+    /// Computes the `ChallengeMode::Keccak256Evm` challenge for the points [G, y, t]
+    ///
+    /// Solidity reference, with `domain` the bytes `SCHNORR_PROOF/EVM-keccak256` (preceded by
+    /// `0x00` and the length-prefixed application domain, if any):
+    ///
+    /// ```solidity
+    /// uint256 constant N = 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141;
+    ///
+    /// function challenge(bytes memory domain, string memory sid, uint32 pid, uint256[6] memory p)
+    ///     internal pure returns (uint256)
+    /// {
+    ///     // p = [Gx, Gy, yx, yy, tx, ty]; with AAD, append uint32(aad.length), aad
+    ///     return uint256(keccak256(abi.encodePacked(domain, sid, pid, p))) % N;
+    /// }
+    /// ```
+    pub(crate) fn evm_challenge(
+        params: &ChallengeParams,
+        domain: &[u8],
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        points: &[G],
+    ) -> Result<(G::Scalar, usize), DLogProofError> {
+        if params.hmac_key.is_some()
+            || params.hash != ChallengeHash::Sha256
+            || params.version != TranscriptVersion::V1
+        {
+            return Err(DLogProofError::UnsupportedChallengeMode);
+        }
+        // The Solidity verifier takes the participant ID as a uint32
+        let pid = pid
+            .index()
+            .ok_or(DLogProofError::UnsupportedChallengeMode)?;
+
+        let mut hasher = ByteCounter::new(Keccak256::new());
+        let mut update = |data: &[u8]| hmac::digest::Update::update(&mut hasher, data);
+        update(domain);
+        update(sid.as_bytes());
+        // Solidity packs integers big-endian, unlike the standard transcript
+        update(&pid.to_be_bytes());
+        for point in points {
+            let (x, y) = point.coordinates()?;
+            update(&x);
+            update(&y);
+        }
+        Self::absorb_aad(&mut hasher, params);
+
+        // Like Solidity's `% N`
+        let c = reduce_be(&hasher.inner.finalize());
+        Ok((c, hasher.count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlog_proof::ChallengeMode;
+    use crate::prover::Prover;
+    use crate::verifier::Verifier;
+    use elliptic_curve::Field;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_core::OsRng;
+
+    fn scalar(hex: &str) -> Scalar {
+        let bytes: [u8; 32] = hex::decode(hex).unwrap().try_into().unwrap();
+        Scalar::from_repr(bytes.into()).unwrap()
+    }
+
+    #[test]
+    fn matches_the_solidity_reference() {
+        // Computed with the Solidity expression above: x = 0x1f1e..00, r = 0x0101..01
+        let g = ProjectivePoint::GENERATOR;
+        let x = scalar("1f1e1d1c1b1a191817161514131211100f0e0d0c0b0a09080706050403020100");
+        let r = scalar("0101010101010101010101010101010101010101010101010101010101010101");
+        let (y, t) = (g * x, g * r);
+        assert_eq!(
+            hex::encode(t.coordinates().unwrap().0),
+            "1b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+        );
+
+        let params = ChallengeParams {
+            mode: ChallengeMode::Keccak256Evm,
+            ..Default::default()
+        };
+        let (c, _) = DLogProof::challenge(&params, "sid".into(), 1.into(), &[g, y, t]).unwrap();
+        assert_eq!(
+            c,
+            scalar("6cb5fad7bbceac78bc166c64e1bc4dbf0e39bb2bcae168a49322a61ea231f2f7")
+        );
+
+        let s = scalar("b260feb90de149e45c9c501e50664662f5a6edd95934fd4fe03b68d46d19ac3d");
+        let proof = DLogProof::new(t, s);
+        let verifier = Verifier::new().challenge_mode(ChallengeMode::Keccak256Evm);
+        assert!(verifier.verify(&proof, "sid", 1, y, g).unwrap());
+        assert!(!proof.verify("sid", 1, y, g).unwrap());
+    }
+
+    #[test]
+    fn evm_proofs_verify() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let proof = Prover::new()
+            .challenge_mode(ChallengeMode::Keccak256Evm)
+            .aad(b"tx")
+            .prove("sid", 1, &x, y, g)
+            .unwrap();
+        let verifier = Verifier::new().challenge_mode(ChallengeMode::Keccak256Evm);
+        assert!(verifier
+            .clone()
+            .aad(b"tx")
+            .verify(&proof, "sid", 1, y, g)
+            .unwrap());
+        assert!(!verifier.verify(&proof, "sid", 1, y, g).unwrap());
+    }
+
+    #[test]
+    fn calldata_roundtrips_and_matches_the_ecrecover_check() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let proof = Prover::new()
+            .challenge_mode(ChallengeMode::Keccak256Evm)
+            .prove("sid", 1, &x, y, g)
+            .unwrap();
+
+        let calldata = proof.to_evm_calldata().unwrap();
+        assert_eq!(DLogProof::from_evm_calldata(&calldata).unwrap(), proof);
+        let mut off_curve = calldata;
+        off_curve[63] ^= 1;
+        assert_eq!(
+            DLogProof::from_evm_calldata(&off_curve),
+            Err(DLogProofError::InvalidPoint)
+        );
+
+        // The contract's ecrecover inputs: r^-1 * (sig * y - h * G) must be t
+        let params = ChallengeParams {
+            mode: ChallengeMode::Keccak256Evm,
+            ..Default::default()
+        };
+        let (c, _) =
+            DLogProof::challenge(&params, "sid".into(), 1.into(), &[g, y, proof.t]).unwrap();
+        let r = reduce_be::<Scalar>(&y.coordinates().unwrap().0);
+        let h = -(proof.s * r);
+        let sig = -(c * r);
+        let recovered = (y * sig - g * h) * r.invert().unwrap();
+        assert_eq!(recovered, proof.t);
+    }
+
+    #[test]
+    fn solidity_verifier_embeds_the_domain() {
+        let source = solidity_verifier("AppVerifier", Some(b"app"));
+        assert!(source.contains("contract AppVerifier {"));
+        // 0x00 || u32 length || "app" || "SCHNORR_PROOF/EVM-keccak256"
+        let domain = format!(
+            "0000000003{}{}",
+            hex::encode("app"),
+            hex::encode("SCHNORR_PROOF/EVM-keccak256")
+        );
+        assert!(source.contains(&format!("hex\"{domain}\"")));
+        assert!(!source.contains("{{"));
+    }
+
+    #[test]
+    fn reduction_is_modular() {
+        // N + 5 reduces to 5
+        let n_plus_5 = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364146";
+        let reduced: Scalar = reduce_be(&hex::decode(n_plus_5).unwrap());
+        assert_eq!(reduced, Scalar::from(5u64));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::dlog_proof::{ChallengeMode, DLogProof};
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::participant::ParticipantId;
+use crate::prover::Prover;
+use crate::session::SessionId;
+
+/// Holder of a secret key x that computes the prover's messages without releasing x
+///
+/// Implement this for an HSM, a remote signer or any other device that can multiply the base
+/// point by a nonce it keeps and compute a response, so `DLogProof::prove_external` can make
+/// proofs while x never enters process memory. The nonce r lives in the holder between the
+/// two calls: each `respond` must answer the latest `commitment` and forget its nonce, since
+/// two responses for one r to different challenges reveal x.
+pub trait DlogProver<G: DLogGroup> {
+    /// Error of the device, convertible from the crate's errors
+    type Error: From<DLogProofError>;
+
+    /// Draws a fresh nonce r and returns the commitment t = r * G for the key's base point
+    fn commitment(&mut self) -> Result<G, Self::Error>;
+
+    /// Returns the response s = r + c * x for the nonce of the last commitment
+    fn respond(&mut self, challenge: &G::Scalar) -> Result<G::Scalar, Self::Error>;
+}
+
+impl<G: DLogGroup> DLogProof<G> {
+    /// Creates a proof of knowledge of the secret key held by `prover`
+    ///
+    /// Like `prove`, with the commitment and response computed by `prover` and the challenge
+    /// derived here. The response is verified before the proof is returned, so a faulty or
+    /// misbehaving device can't produce an invalid proof.
+    ///
+    /// # Arguments
+    /// * `sid` - Session identifier used for domain separation, a string or bytes (see `SessionId`)
+    /// * `pid` - Participant ID for uniqueness to distinguish different proofs
+    /// * `prover` - The device holding x
+    /// * `y` - The public point, must satisfy y = x * G
+    /// * `base_point` - Base point G of the group the device uses
+    ///
+    /// # Returns
+    /// * `Ok(DLogProof)` - The proof
+    /// * `Err(P::Error)` - Any error of the device, `IdentityPoint` for a degenerate statement
+    ///   or `FaultDetected` if the device's response doesn't verify
+    pub fn prove_external<'s, 'p, P: DlogProver<G>>(
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        prover: &mut P,
+        y: G,
+        base_point: G,
+    ) -> Result<Self, P::Error> {
+        Prover::new().prove_external(sid, pid, prover, y, base_point)
+    }
+}
+
+impl Prover {
+    /// Creates a proof like `DLogProof::prove_external` with this configuration
+    ///
+    /// The transcript options apply as for `prove`; blinding is up to the device.
+    /// `ChallengeMode::Bip340Tagged` isn't supported, as it may require negating the nonce.
+    pub fn prove_external<'s, 'p, G: DLogGroup, P: DlogProver<G>>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        prover: &mut P,
+        y: G,
+        base_point: G,
+    ) -> Result<DLogProof<G>, P::Error> {
+        let sid = sid.into();
+        let pid = pid.into();
+        if self.params().mode == ChallengeMode::Bip340Tagged {
+            return Err(DLogProofError::UnsupportedChallengeMode.into());
+        }
+        DLogProof::check_statement(y, base_point)?;
+
+        let t = prover.commitment()?;
+        let (c, _) = DLogProof::challenge(self.params(), sid, pid, &[base_point, y, t])?;
+        let proof = DLogProof::new(t, prover.respond(&c)?);
+        if !proof.verify_with(self.params(), sid, pid, y, base_point)? {
+            return Err(DLogProofError::FaultDetected.into());
+        }
+        Ok(proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secret::SecretScalar;
+    use crate::verifier::Verifier;
+    use elliptic_curve::Field;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_core::OsRng;
+    use zeroize::Zeroizing;
+
+    /// Stand-in for an HSM: x stays inside, and each nonce answers one challenge
+    struct Device {
+        x: SecretScalar<Scalar>,
+        nonce: Option<Zeroizing<Scalar>>,
+    }
+
+    impl DlogProver<ProjectivePoint> for Device {
+        type Error = DLogProofError;
+
+        fn commitment(&mut self) -> Result<ProjectivePoint, DLogProofError> {
+            let r = Zeroizing::new(Scalar::random(&mut OsRng));
+            let t = ProjectivePoint::GENERATOR * *r;
+            self.nonce = Some(r);
+            Ok(t)
+        }
+
+        fn respond(&mut self, challenge: &Scalar) -> Result<Scalar, DLogProofError> {
+            let r = self.nonce.take().ok_or(DLogProofError::InvalidScalar)?;
+            Ok(*r + *challenge * self.x.expose_secret())
+        }
+    }
+
+    #[test]
+    fn external_provers_make_verifiable_proofs() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let mut device = Device {
+            x: SecretScalar::new(x),
+            nonce: None,
+        };
+
+        let proof = DLogProof::prove_external("sid", 1, &mut device, y, g).unwrap();
+        assert!(proof.verify("sid", 1, y, g).unwrap());
+
+        let prover = Prover::new().domain(b"app");
+        let proof = prover.prove_external("sid", 1, &mut device, y, g).unwrap();
+        assert!(Verifier::new()
+            .domain(b"app")
+            .verify(&proof, "sid", 1, y, g)
+            .unwrap());
+
+        // A device holding another key is caught before the proof is released
+        assert_eq!(
+            DLogProof::prove_external("sid", 1, &mut device, y + g, g).unwrap_err(),
+            DLogProofError::FaultDetected
+        );
+    }
+}
@@ -0,0 +1,313 @@
+//! C ABI (`ffi` feature)
+//!
+//! `extern "C"` functions over secp256k1 proofs for the standard generator, declared in
+//! `include/schnorr_dlog_zkp.h`. Regenerate the header with cbindgen after changing them:
+//!
+//! ```text
+//! cbindgen --config cbindgen.toml --output include/schnorr_dlog_zkp.h src/ffi.rs
+//! ```
+//!
+//! Proofs are opaque `DlogProofHandle`s owned by the caller and released with
+//! `dlog_proof_free`. Every other function returns a `DlogStatus`; its values are part of
+//! the ABI and never renumbered. Byte strings are passed as pointer and length, outputs are
+//! written to caller-provided buffers, and nothing is kept between calls, so the functions
+//! can be used from any thread.
+
+use core::slice;
+
+use k256::ProjectivePoint;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::keypair::KeyPair;
+
+/// Result of an FFI call
+///
+/// The numbering predates `DLogProofError::code` and is kept for the ABI. Errors of the
+/// Rust API map to statuses by their code:
+///
+/// | `DLogProofError::code` | Status |
+/// |---|---|
+/// | 1 (`InvalidPoint`) | `InvalidPoint` |
+/// | 2 (`InvalidScalar`) | `InvalidScalar` |
+/// | 4 (`Serialization`) | `InvalidEncoding` |
+/// | 7 (`IdentityPoint`) | `IdentityPoint` |
+/// | any other | `Internal` |
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DlogStatus {
+    /// Success; for `dlog_verify`, the proof is valid
+    Ok = 0,
+    /// `dlog_verify` only: the proof is well-formed but doesn't verify
+    InvalidProof = 1,
+    /// A required pointer is null
+    NullPointer = 2,
+    /// The output buffer is smaller than the length written to `written`
+    BufferTooSmall = 3,
+    /// A point (public key or commitment) is malformed or not on the curve
+    InvalidPoint = 4,
+    /// A scalar (secret key or response) is malformed, zero where it must not be, or not
+    /// below the group order
+    InvalidScalar = 5,
+    /// An encoding has the wrong length or format
+    InvalidEncoding = 6,
+    /// The public key is the identity point
+    IdentityPoint = 7,
+    /// Any other error
+    Internal = 8,
+}
+
+impl From<DLogProofError> for DlogStatus {
+    fn from(e: DLogProofError) -> Self {
+        match e.code() {
+            1 => DlogStatus::InvalidPoint,
+            2 => DlogStatus::InvalidScalar,
+            4 => DlogStatus::InvalidEncoding,
+            7 => DlogStatus::IdentityPoint,
+            _ => DlogStatus::Internal,
+        }
+    }
+}
+
+/// A proof owned by the caller, released with `dlog_proof_free`
+pub struct DlogProofHandle(DLogProof);
+
+/// Length of the encoding written by `dlog_proof_serialize`
+pub const DLOG_PROOF_LEN: usize = 65;
+const _: () = assert!(DLOG_PROOF_LEN == DLogProof::ENCODED_LEN);
+
+/// Length of the big-endian secret key taken by `dlog_prove`
+pub const DLOG_SECRET_KEY_LEN: usize = 32;
+
+/// Proves knowledge of a secret key for the session and participant
+///
+/// On success `*proof_out` holds a new proof, to be released with `dlog_proof_free`.
+///
+/// # Safety
+/// `sid` must point to `sid_len` readable bytes (or be null with `sid_len` 0), `secret_key`
+/// to `DLOG_SECRET_KEY_LEN` readable bytes, and `proof_out` must be valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn dlog_prove(
+    sid: *const u8,
+    sid_len: usize,
+    pid: u32,
+    secret_key: *const u8,
+    proof_out: *mut *mut DlogProofHandle,
+) -> DlogStatus {
+    let (Some(sid), Some(secret_key)) =
+        (bytes(sid, sid_len), bytes(secret_key, DLOG_SECRET_KEY_LEN))
+    else {
+        return DlogStatus::NullPointer;
+    };
+    if proof_out.is_null() {
+        return DlogStatus::NullPointer;
+    }
+    let proof = KeyPair::<ProjectivePoint>::from_secret_bytes(secret_key)
+        .and_then(|key_pair| key_pair.prove(sid, pid));
+    match proof {
+        Ok(proof) => {
+            *proof_out = Box::into_raw(Box::new(DlogProofHandle(proof)));
+            DlogStatus::Ok
+        }
+        Err(e) => e.into(),
+    }
+}
+
+/// Verifies a proof against a SEC1 public key, returning `Ok` if it is valid and
+/// `InvalidProof` if not
+///
+/// # Safety
+/// `proof` must be a live handle, `sid` must point to `sid_len` readable bytes (or be null
+/// with `sid_len` 0) and `public_key` to `public_key_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn dlog_verify(
+    proof: *const DlogProofHandle,
+    sid: *const u8,
+    sid_len: usize,
+    pid: u32,
+    public_key: *const u8,
+    public_key_len: usize,
+) -> DlogStatus {
+    let (Some(proof), Some(sid), Some(public_key)) = (
+        proof.as_ref(),
+        bytes(sid, sid_len),
+        bytes(public_key, public_key_len),
+    ) else {
+        return DlogStatus::NullPointer;
+    };
+    let Some(y) = ProjectivePoint::decode_point(public_key) else {
+        return DlogStatus::InvalidPoint;
+    };
+    match proof.0.verify(sid, pid, y, ProjectivePoint::GENERATOR) {
+        Ok(true) => DlogStatus::Ok,
+        Ok(false) => DlogStatus::InvalidProof,
+        Err(e) => e.into(),
+    }
+}
+
+/// Writes the `DLOG_PROOF_LEN`-byte encoding of a proof into `out`
+///
+/// `*written` receives the encoding's length, also when `out_len` is too small and
+/// `BufferTooSmall` is returned.
+///
+/// # Safety
+/// `proof` must be a live handle, `out` must point to `out_len` writable bytes and `written`
+/// must be valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn dlog_proof_serialize(
+    proof: *const DlogProofHandle,
+    out: *mut u8,
+    out_len: usize,
+    written: *mut usize,
+) -> DlogStatus {
+    let Some(proof) = proof.as_ref() else {
+        return DlogStatus::NullPointer;
+    };
+    if out.is_null() || written.is_null() {
+        return DlogStatus::NullPointer;
+    }
+    *written = DLOG_PROOF_LEN;
+    if out_len < DLOG_PROOF_LEN {
+        return DlogStatus::BufferTooSmall;
+    }
+    slice::from_raw_parts_mut(out, DLOG_PROOF_LEN).copy_from_slice(&proof.0.to_bytes());
+    DlogStatus::Ok
+}
+
+/// Decodes a proof from the encoding of `dlog_proof_serialize`
+///
+/// On success `*proof_out` holds a new proof, to be released with `dlog_proof_free`.
+///
+/// # Safety
+/// `bytes` must point to `len` readable bytes and `proof_out` must be valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn dlog_proof_deserialize(
+    bytes: *const u8,
+    len: usize,
+    proof_out: *mut *mut DlogProofHandle,
+) -> DlogStatus {
+    let Some(encoded) = self::bytes(bytes, len) else {
+        return DlogStatus::NullPointer;
+    };
+    if proof_out.is_null() {
+        return DlogStatus::NullPointer;
+    }
+    match DLogProof::from_bytes(encoded) {
+        Ok(proof) => {
+            *proof_out = Box::into_raw(Box::new(DlogProofHandle(proof)));
+            DlogStatus::Ok
+        }
+        Err(e) => e.into(),
+    }
+}
+
+/// Releases a proof; null is ignored
+///
+/// # Safety
+/// `proof` must be null or a handle from this library that hasn't been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn dlog_proof_free(proof: *mut DlogProofHandle) {
+    if !proof.is_null() {
+        drop(Box::from_raw(proof));
+    }
+}
+
+/// The slice behind a pointer and length; null is only accepted for an empty slice
+unsafe fn bytes<'a>(ptr: *const u8, len: usize) -> Option<&'a [u8]> {
+    match (ptr.is_null(), len) {
+        (true, 0) => Some(&[]),
+        (true, _) => None,
+        (false, _) => Some(slice::from_raw_parts(ptr, len)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ptr;
+    use elliptic_curve::{Field, PrimeField};
+    use k256::Scalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn ffi_proves_serializes_and_verifies() {
+        let x = Scalar::random(&mut OsRng);
+        let secret_key = x.to_repr();
+        let y = (ProjectivePoint::GENERATOR * x).encode_point();
+        let sid = b"sid";
+
+        unsafe {
+            let mut proof = ptr::null_mut();
+            assert_eq!(
+                dlog_prove(sid.as_ptr(), sid.len(), 1, secret_key.as_ptr(), &mut proof),
+                DlogStatus::Ok
+            );
+            assert_eq!(
+                dlog_verify(proof, sid.as_ptr(), sid.len(), 1, y.as_ptr(), y.len()),
+                DlogStatus::Ok
+            );
+            assert_eq!(
+                dlog_verify(proof, sid.as_ptr(), sid.len(), 2, y.as_ptr(), y.len()),
+                DlogStatus::InvalidProof
+            );
+
+            let mut written = 0;
+            let mut short = [0u8; 10];
+            assert_eq!(
+                dlog_proof_serialize(proof, short.as_mut_ptr(), short.len(), &mut written),
+                DlogStatus::BufferTooSmall
+            );
+            assert_eq!(written, DLOG_PROOF_LEN);
+            let mut encoded = [0u8; DLOG_PROOF_LEN];
+            assert_eq!(
+                dlog_proof_serialize(proof, encoded.as_mut_ptr(), encoded.len(), &mut written),
+                DlogStatus::Ok
+            );
+            dlog_proof_free(proof);
+
+            let mut decoded = ptr::null_mut();
+            assert_eq!(
+                dlog_proof_deserialize(encoded.as_ptr(), encoded.len(), &mut decoded),
+                DlogStatus::Ok
+            );
+            assert_eq!(
+                dlog_verify(decoded, sid.as_ptr(), sid.len(), 1, y.as_ptr(), y.len()),
+                DlogStatus::Ok
+            );
+            dlog_proof_free(decoded);
+
+            assert_eq!(
+                dlog_proof_deserialize(encoded.as_ptr(), 3, &mut decoded),
+                DlogStatus::InvalidEncoding
+            );
+            assert_eq!(
+                dlog_prove(sid.as_ptr(), sid.len(), 1, ptr::null(), &mut decoded),
+                DlogStatus::NullPointer
+            );
+        }
+    }
+    #[test]
+    fn statuses_follow_error_codes() {
+        assert_eq!(
+            DlogStatus::from(DLogProofError::InvalidPoint),
+            DlogStatus::InvalidPoint
+        );
+        assert_eq!(
+            DlogStatus::from(DLogProofError::InvalidScalar),
+            DlogStatus::InvalidScalar
+        );
+        assert_eq!(
+            DlogStatus::from(DLogProofError::Serialization("any reason".into())),
+            DlogStatus::InvalidEncoding
+        );
+        assert_eq!(
+            DlogStatus::from(DLogProofError::IdentityPoint),
+            DlogStatus::IdentityPoint
+        );
+        assert_eq!(
+            DlogStatus::from(DLogProofError::FaultDetected),
+            DlogStatus::Internal
+        );
+    }
+}
@@ -0,0 +1,432 @@
+use elliptic_curve::Field;
+use rand_core::{CryptoRngCore, OsRng};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use zeroize::Zeroizing;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::secret::SecretScalar;
+use crate::serialization::{PointHex, ScalarHex};
+use crate::session::SessionId;
+use crate::sigma::{DLogStatement, SchnorrDLog, SigmaProof};
+
+const BINDING_DOMAIN: &[u8] = b"SCHNORR_FROST/binding";
+
+/// A participant's Shamir share x_i = f(i) of the group secret x = f(0)
+#[derive(Debug, Clone)]
+pub struct KeyShare<G: DLogGroup> {
+    index: u32,
+    x: SecretScalar<G::Scalar>,
+}
+
+impl<G: DLogGroup> KeyShare<G> {
+    /// Wraps the share `x` of participant `index`, which must be nonzero
+    pub fn new(index: u32, x: impl Into<SecretScalar<G::Scalar>>) -> Result<Self, DLogProofError> {
+        if index == 0 {
+            return Err(DLogProofError::InvalidParticipant);
+        }
+        Ok(KeyShare { index, x: x.into() })
+    }
+
+    /// Splits `x` into `n` shares, any `threshold` of which can prove for x * G
+    ///
+    /// A trusted-dealer setup: the shares are the evaluations f(1), ..., f(n) of a random
+    /// polynomial of degree threshold - 1 with f(0) = x. The dealer must forget x and the
+    /// polynomial afterwards.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<KeyShare>)` - The shares of participants 1 to n
+    /// * `Err(DLogProofError)` - `StatementCount` unless 1 <= threshold <= n
+    pub fn deal(
+        rng: &mut impl CryptoRngCore,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        threshold: usize,
+        n: u32,
+    ) -> Result<Vec<Self>, DLogProofError> {
+        if threshold == 0 || threshold > n as usize {
+            return Err(DLogProofError::StatementCount);
+        }
+        let x = x.into();
+        let mut coefficients = Zeroizing::new(vec![*x.expose_secret()]);
+        coefficients.extend((1..threshold).map(|_| G::Scalar::random(&mut *rng)));
+
+        Ok((1..=n)
+            .map(|index| {
+                let i = G::Scalar::from(u64::from(index));
+                // Horner's rule from the highest coefficient down
+                let share = coefficients
+                    .iter()
+                    .rev()
+                    .fold(G::Scalar::ZERO, |acc, a| acc * i + a);
+                KeyShare {
+                    index,
+                    x: SecretScalar::new(share),
+                }
+            })
+            .collect())
+    }
+
+    /// The participant index i
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// The public verifying share y_i = x_i * G, for checking partial proofs
+    pub fn verifying_share(&self, base_point: G) -> G {
+        base_point * self.x.expose_secret()
+    }
+}
+
+/// Lagrange coefficient λ_i of participant `index` for interpolating f(0) from `indices`
+///
+/// # Returns
+/// * `Ok(Scalar)` - λ_i = Π_{j != i} j / (j - i)
+/// * `Err(DLogProofError)` - `InvalidParticipant` if an index is zero or repeated, or `index`
+///   is not among `indices`
+pub fn lagrange_coefficient<G: DLogGroup>(
+    index: u32,
+    indices: &[u32],
+) -> Result<G::Scalar, DLogProofError> {
+    if !indices.contains(&index) {
+        return Err(DLogProofError::InvalidParticipant);
+    }
+    let i = G::Scalar::from(u64::from(index));
+    let (mut numerator, mut denominator) = (G::Scalar::ONE, G::Scalar::ONE);
+    for (position, &other) in indices.iter().enumerate() {
+        if other == 0 || indices[..position].contains(&other) {
+            return Err(DLogProofError::InvalidParticipant);
+        }
+        if other != index {
+            let j = G::Scalar::from(u64::from(other));
+            numerator *= j;
+            denominator *= j - i;
+        }
+    }
+    let denominator: Option<G::Scalar> = denominator.invert().into();
+    Ok(numerator * denominator.ok_or(DLogProofError::InvalidParticipant)?)
+}
+
+/// First-round message of participant `index`: its nonce commitments D_i = d_i * G, E_i = e_i * G
+#[serde_as]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SigningCommitment<G: DLogGroup> {
+    index: u32,
+    #[serde_as(as = "PointHex")]
+    d: G,
+    #[serde_as(as = "PointHex")]
+    e: G,
+}
+
+impl<G: DLogGroup> SigningCommitment<G> {
+    pub fn new(index: u32, d: G, e: G) -> Self {
+        SigningCommitment { index, d, e }
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn d(&self) -> G {
+        self.d
+    }
+
+    pub fn e(&self) -> G {
+        self.e
+    }
+}
+
+/// Second-round message of participant `index`: its partial response z_i
+#[serde_as]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct PartialProof<G: DLogGroup> {
+    index: u32,
+    #[serde_as(as = "ScalarHex")]
+    z: G::Scalar,
+}
+
+impl<G: DLogGroup> PartialProof<G> {
+    pub fn new(index: u32, z: G::Scalar) -> Self {
+        PartialProof { index, z }
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn z(&self) -> G::Scalar {
+        self.z
+    }
+}
+
+/// A participant's secret nonces between the two rounds
+///
+/// Wiped on drop and consumed by `respond`: reusing nonces across sessions reveals the
+/// participant's share. Deliberately neither `Clone` nor serializable.
+pub struct SigningNonces<G: DLogGroup> {
+    index: u32,
+    d: Zeroizing<G::Scalar>,
+    e: Zeroizing<G::Scalar>,
+}
+
+impl<G: DLogGroup> SigningNonces<G> {
+    /// First round: draws the hiding and binding nonces of `share`'s holder and commits to them
+    pub fn commit(share: &KeyShare<G>, base_point: G) -> (SigningCommitment<G>, Self) {
+        Self::commit_with_rng(&mut OsRng, share, base_point)
+    }
+
+    /// Draws the nonces like `commit`, from `rng`
+    pub fn commit_with_rng(
+        rng: &mut impl CryptoRngCore,
+        share: &KeyShare<G>,
+        base_point: G,
+    ) -> (SigningCommitment<G>, Self) {
+        let d = Zeroizing::new(G::Scalar::random(&mut *rng));
+        let e = Zeroizing::new(G::Scalar::random(&mut *rng));
+        let commitment = SigningCommitment {
+            index: share.index,
+            d: base_point * *d,
+            e: base_point * *e,
+        };
+        let nonces = SigningNonces {
+            index: share.index,
+            d,
+            e,
+        };
+        (commitment, nonces)
+    }
+
+    /// Second round: the partial response z_i = d_i + ρ_i * e_i + λ_i * c * x_i
+    ///
+    /// # Returns
+    /// * `Ok(PartialProof)` - The partial response
+    /// * `Err(DLogProofError)` - `InvalidParticipant` if `share` doesn't belong to these nonces
+    ///   or its holder is not part of the session
+    pub fn respond(
+        self,
+        session: &FrostSession<G>,
+        share: &KeyShare<G>,
+    ) -> Result<PartialProof<G>, DLogProofError> {
+        if share.index != self.index {
+            return Err(DLogProofError::InvalidParticipant);
+        }
+        let (rho, lambda) = session.factors(self.index)?;
+        Ok(PartialProof {
+            index: self.index,
+            z: *self.d + *self.e * rho + lambda * session.c * share.x.expose_secret(),
+        })
+    }
+}
+
+/// Public state of one threshold proof, computed by every signer and the coordinator
+///
+/// Each signer's binding factor ρ_i = H(i, Y, commitments) ties its second nonce to the whole
+/// signing set, and the group nonce is R = Σ D_i + ρ_i * E_i. The challenge is the ordinary
+/// `DLogProof` challenge c = H(G, Y, R); with the Lagrange coefficients λ_i the partial
+/// responses add up to a response for the group secret, so the combined proof (R, Σ z_i)
+/// verifies with `DLogProof::verify` for Y.
+#[derive(Debug, Clone)]
+pub struct FrostSession<G: DLogGroup> {
+    commitments: Vec<SigningCommitment<G>>,
+    indices: Vec<u32>,
+    binding_factors: Vec<G::Scalar>,
+    base_point: G,
+    r: G,
+    c: G::Scalar,
+}
+
+impl<G: DLogGroup> FrostSession<G> {
+    /// Sets up the session of the signers that sent `commitments`, for a proof bound to `sid`
+    /// and `pid`
+    ///
+    /// # Arguments
+    /// * `sid` - Session identifier used for domain separation, a string or bytes (see `SessionId`)
+    /// * `pid` - Participant ID for uniqueness to distinguish different proofs
+    /// * `group_key` - The group public key Y = x * G
+    /// * `commitments` - The nonce commitments of at least `threshold` signers, in any order
+    /// * `base_point` - Base point of the group
+    ///
+    /// # Returns
+    /// * `Ok(FrostSession)` - The session
+    /// * `Err(DLogProofError)` - `InvalidParticipant` for a zero or repeated index,
+    ///   `StatementCount` if there are no commitments, or if a challenge can't be derived
+    pub fn new<'s>(
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        group_key: G,
+        commitments: &[SigningCommitment<G>],
+        base_point: G,
+    ) -> Result<Self, DLogProofError> {
+        if commitments.is_empty() {
+            return Err(DLogProofError::StatementCount);
+        }
+        DLogProof::check_statement(group_key, base_point)?;
+        let sid = sid.into();
+        let mut commitments = commitments.to_vec();
+        commitments.sort_by_key(|commitment| commitment.index);
+        let indices: Vec<u32> = commitments.iter().map(|c| c.index).collect();
+        if indices[0] == 0 || indices.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(DLogProofError::InvalidParticipant);
+        }
+
+        let mut points = vec![group_key];
+        points.extend(commitments.iter().flat_map(|c| [c.d, c.e]));
+        let binding_factors = indices
+            .iter()
+            .map(|index| {
+                let mut domain = BINDING_DOMAIN.to_vec();
+                domain.extend_from_slice(&index.to_be_bytes());
+                DLogProof::hash_points_with_domain(&domain, sid, pid.into(), &points)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let r = commitments
+            .iter()
+            .zip(&binding_factors)
+            .fold(G::identity(), |sum, (c, rho)| sum + c.d + c.e * rho);
+        let statement = DLogStatement {
+            base_point,
+            y: group_key,
+        };
+        let c = SigmaProof::<SchnorrDLog<G>>::challenge(sid, pid.into(), &statement, &r)?;
+        Ok(FrostSession {
+            commitments,
+            indices,
+            binding_factors,
+            base_point,
+            r,
+            c,
+        })
+    }
+
+    /// The indices of the signers, in ascending order
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// Checks a partial proof against the signer's verifying share y_i, to identify who
+    /// broke a failed proof
+    ///
+    /// Verifies z_i * G = D_i + ρ_i * E_i + λ_i * c * y_i.
+    pub fn verify_partial(&self, partial: &PartialProof<G>, verifying_share: G) -> bool {
+        let Ok((rho, lambda)) = self.factors(partial.index) else {
+            return false;
+        };
+        let Some(commitment) = self.commitments.iter().find(|c| c.index == partial.index) else {
+            return false;
+        };
+        let expected = commitment.d + commitment.e * rho + verifying_share * (lambda * self.c);
+        (self.base_point * partial.z).ct_eq(&expected).into()
+    }
+
+    /// Coordinator: combines one partial proof per signer into the proof (R, Σ z_i) for Y
+    ///
+    /// # Returns
+    /// * `Ok(DLogProof)` - The threshold proof; check it with `DLogProof::verify`, or the
+    ///   partial proofs with `verify_partial`, before relying on it
+    /// * `Err(DLogProofError)` - `InvalidParticipant` unless the partial proofs come from
+    ///   exactly the session's signers
+    pub fn aggregate(&self, partials: &[PartialProof<G>]) -> Result<DLogProof<G>, DLogProofError> {
+        let mut indices: Vec<u32> = partials.iter().map(|p| p.index).collect();
+        indices.sort_unstable();
+        if indices != self.indices {
+            return Err(DLogProofError::InvalidParticipant);
+        }
+        let z = partials
+            .iter()
+            .fold(G::Scalar::ZERO, |sum, partial| sum + partial.z);
+        Ok(DLogProof::new(self.r, z))
+    }
+
+    /// Binding factor ρ_i and Lagrange coefficient λ_i of signer `index`
+    fn factors(&self, index: u32) -> Result<(G::Scalar, G::Scalar), DLogProofError> {
+        let position = self
+            .indices
+            .binary_search(&index)
+            .map_err(|_| DLogProofError::InvalidParticipant)?;
+        let lambda = lagrange_coefficient::<G>(index, &self.indices)?;
+        Ok((self.binding_factors[position], lambda))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::{ProjectivePoint, Scalar};
+
+    fn sign(
+        shares: &[KeyShare<ProjectivePoint>],
+        y: ProjectivePoint,
+        g: ProjectivePoint,
+    ) -> (
+        FrostSession<ProjectivePoint>,
+        Vec<PartialProof<ProjectivePoint>>,
+    ) {
+        let (commitments, nonces): (Vec<_>, Vec<_>) = shares
+            .iter()
+            .map(|share| SigningNonces::commit(share, g))
+            .unzip();
+        let session = FrostSession::new("sid", 1, y, &commitments, g).unwrap();
+        let partials = nonces
+            .into_iter()
+            .zip(shares)
+            .map(|(nonces, share)| nonces.respond(&session, share).unwrap())
+            .collect();
+        (session, partials)
+    }
+
+    #[test]
+    fn any_threshold_subset_proves_for_the_group_key() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let shares = KeyShare::deal(&mut OsRng, &x, 2, 3).unwrap();
+
+        for subset in [[0, 1], [0, 2], [2, 1]] {
+            let signers: Vec<_> = subset.iter().map(|&i| shares[i].clone()).collect();
+            let (session, partials) = sign(&signers, y, g);
+            for (partial, share) in partials.iter().zip(&signers) {
+                assert!(session.verify_partial(partial, share.verifying_share(g)));
+            }
+
+            let json = serde_json::to_string(&partials).unwrap();
+            let partials: Vec<PartialProof<_>> = serde_json::from_str(&json).unwrap();
+            let proof = session.aggregate(&partials).unwrap();
+            assert!(proof.verify("sid", 1, y, g).unwrap());
+            assert!(!proof.verify("sid", 2, y, g).unwrap());
+        }
+    }
+
+    #[test]
+    fn too_few_or_invalid_signers_fail() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let shares = KeyShare::deal(&mut OsRng, &x, 3, 4).unwrap();
+
+        // Two shares of a 3-of-4 sharing interpolate the wrong secret
+        let (session, partials) = sign(&shares[..2], y, g);
+        assert!(!session
+            .aggregate(&partials)
+            .unwrap()
+            .verify("sid", 1, y, g)
+            .unwrap());
+        assert_eq!(
+            session.aggregate(&partials[..1]).unwrap_err(),
+            DLogProofError::InvalidParticipant
+        );
+
+        let (commitment, _) = SigningNonces::commit(&shares[0], g);
+        assert_eq!(
+            FrostSession::new("sid", 1, y, &[commitment, commitment], g).unwrap_err(),
+            DLogProofError::InvalidParticipant
+        );
+        assert_eq!(
+            lagrange_coefficient::<ProjectivePoint>(5, &[1, 2]).unwrap_err(),
+            DLogProofError::InvalidParticipant
+        );
+    }
+}
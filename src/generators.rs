@@ -0,0 +1,105 @@
+use elliptic_curve::hash2curve::{ExpandMsgXmd, GroupDigest};
+use k256::{ProjectivePoint, Secp256k1};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+use crate::error::DLogProofError;
+
+/// RFC 9380 domain separation tag for this crate's auxiliary generators
+const GENERATOR_DST: &[u8] = b"SCHNORR_PROOF-V01-CS01-with-secp256k1_XMD:SHA-256_SSWU_RO_";
+
+/// Label of the second Pedersen generator H used with `PedersenOpening`
+pub const PEDERSEN_H: &str = "pedersen/H";
+
+/// Derives a nothing-up-my-sleeve generator for `label`
+///
+/// The point is hash_to_curve(label) with the RFC 9380 suite secp256k1_XMD:SHA-256_SSWU_RO_,
+/// so its discrete log relative to G (or to any other label's point) is unknown to everyone,
+/// and anyone can re-derive it to check. Use a distinct label per role, e.g. `PEDERSEN_H`,
+/// "okamoto/G2" or "dleq/H". Derivations are cached for the life of the process.
+///
+/// # Returns
+/// * `Ok(ProjectivePoint)` - The generator
+/// * `Err(DLogProofError)` - `InvalidPoint` if hashing to the curve fails
+pub fn nums_generator(label: &str) -> Result<ProjectivePoint, DLogProofError> {
+    static CACHE: OnceLock<Mutex<HashMap<String, ProjectivePoint>>> = OnceLock::new();
+    // Entries are only inserted once computed, so a panic elsewhere can't leave the cache in
+    // an inconsistent state
+    let mut cache = CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    if let Some(point) = cache.get(label) {
+        return Ok(*point);
+    }
+    let point = hash_to_curve(label.as_bytes(), GENERATOR_DST)?;
+    cache.insert(label.to_string(), point);
+    Ok(point)
+}
+
+/// Derives `count` independent generators for `label`, e.g. the bases of a
+/// `RepresentationProof`
+///
+/// The i-th point is `nums_generator` of "{label}/{i}", so a vector of length n is a prefix
+/// of any longer one for the same label and each entry can be re-derived on its own.
+///
+/// # Returns
+/// * `Ok(Vec<ProjectivePoint>)` - The generators, in index order
+/// * `Err(DLogProofError)` - `InvalidPoint` if hashing to the curve fails
+pub fn nums_generators(label: &str, count: usize) -> Result<Vec<ProjectivePoint>, DLogProofError> {
+    (0..count)
+        .map(|i| nums_generator(&format!("{label}/{i}")))
+        .collect()
+}
+
+fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Result<ProjectivePoint, DLogProofError> {
+    Secp256k1::hash_from_bytes::<ExpandMsgXmd<Sha256>>(&[msg], &[dst])
+        .map_err(|_| DLogProofError::InvalidPoint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::sec1::ToEncodedPoint;
+
+    #[test]
+    fn matches_rfc_9380_test_vector() {
+        // RFC 9380, appendix J.8.1, msg = ""
+        let point =
+            hash_to_curve(b"", b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_").unwrap();
+        let encoded = point.to_affine().to_encoded_point(false);
+        assert_eq!(
+            hex::encode(encoded.x().unwrap()),
+            "c1cae290e291aee617ebaef1be6d73861479c48b841eaba9b7b5852ddfeb1346"
+        );
+        assert_eq!(
+            hex::encode(encoded.y().unwrap()),
+            "64fa678e07ae116126f08b022a94af6de15985c996c3a91b64c406a960e51067"
+        );
+    }
+
+    #[test]
+    fn generators_are_deterministic_and_label_separated() {
+        let h = nums_generator(PEDERSEN_H).unwrap();
+        assert_eq!(nums_generator(PEDERSEN_H).unwrap(), h);
+        assert_eq!(
+            hash_to_curve(PEDERSEN_H.as_bytes(), GENERATOR_DST).unwrap(),
+            h
+        );
+        assert_ne!(nums_generator("dleq/H").unwrap(), h);
+        assert_ne!(h, ProjectivePoint::GENERATOR);
+        assert_ne!(h, ProjectivePoint::IDENTITY);
+    }
+
+    #[test]
+    fn generator_vectors_extend_by_index() {
+        let bases = nums_generators("okamoto", 3).unwrap();
+        assert_eq!(bases.len(), 3);
+        assert_eq!(bases[2], nums_generator("okamoto/2").unwrap());
+        assert_eq!(nums_generators("okamoto", 4).unwrap()[..3], bases[..]);
+        assert_ne!(bases[0], bases[1]);
+        assert!(nums_generators("okamoto", 0).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,295 @@
+use alloc::vec::Vec;
+use elliptic_curve::bigint::U512;
+use elliptic_curve::group::{Group, GroupEncoding};
+use elliptic_curve::ops::LinearCombinationExt;
+use elliptic_curve::ops::Reduce;
+use elliptic_curve::sec1::{EncodedPoint, FromEncodedPoint, ModulusSize, ToEncodedPoint};
+use elliptic_curve::subtle::ConstantTimeEq;
+use elliptic_curve::{CurveArithmetic, FieldBytesSize, PrimeField};
+use k256::{ProjectivePoint, Scalar, Secp256k1, WideBytes};
+use zeroize::Zeroize;
+
+use crate::error::DLogProofError;
+
+/// Prime-order group a `DLogProof` can be made over
+///
+/// Any `group::Group` with a canonical byte encoding and zeroizable scalars qualifies, which
+/// covers the curves of the RustCrypto `elliptic-curve` family as well as other `group`-based
+/// implementations. The provided methods fix how points are serialized and how the
+/// Fiat-Shamir hash output becomes a challenge; a backend only overrides them where its
+/// encoding conventions differ.
+pub trait DLogGroup: Group<Scalar: Zeroize> + GroupEncoding + ConstantTimeEq {
+    /// Encodes a point for serialization (the `GroupEncoding` bytes by default)
+    fn encode_point(&self) -> Vec<u8> {
+        self.to_bytes().as_ref().to_vec()
+    }
+
+    /// Decodes a point produced by `encode_point`, or `None` if it is not a valid encoding
+    fn decode_point(bytes: &[u8]) -> Option<Self> {
+        let mut repr = Self::Repr::default();
+        if repr.as_ref().len() != bytes.len() {
+            return None;
+        }
+        repr.as_mut().copy_from_slice(bytes);
+        Option::from(Self::from_bytes(&repr))
+    }
+
+    /// Big-endian affine coordinates (x, y), for the BIP-340 and EVM challenge modes
+    ///
+    /// Only secp256k1 has these; other groups return `DLogProofError::UnsupportedChallengeMode`.
+    fn coordinates(&self) -> Result<([u8; 32], [u8; 32]), DLogProofError> {
+        Err(DLogProofError::UnsupportedChallengeMode)
+    }
+
+    /// x-only encoding of the point and whether its y-coordinate is odd, for BIP-340
+    fn x_only(&self) -> Result<([u8; 32], bool), DLogProofError> {
+        let (x, y) = self.coordinates()?;
+        Ok((x, y[31] & 1 == 1))
+    }
+
+    /// Computes the linear combination sum k_i * P_i of `(P_i, k_i)` pairs
+    ///
+    /// Used by verification, as s * G - c * y, and batch verification; backends override it
+    /// with a multi-scalar multiplication (e.g. Straus/Shamir's trick for two terms).
+    fn lincomb(terms: &[(Self, Self::Scalar)]) -> Self {
+        terms.iter().fold(Self::identity(), |sum, (point, scalar)| {
+            sum + *point * scalar
+        })
+    }
+
+    /// Computes `lincomb` in variable time, for public scalars only
+    ///
+    /// Used by verification under `Timing::Variable`, where every scalar is derived from the
+    /// proof and the statement. The default is an interleaved width-5 wNAF multiplication,
+    /// whose running time and memory accesses depend on the scalars; backends with a faster
+    /// variable-time multi-scalar multiplication override it.
+    fn lincomb_vartime(terms: &[(Self, Self::Scalar)]) -> Self {
+        wnaf_lincomb(terms)
+    }
+
+    /// Converts the output of the challenge hash into the challenge scalar
+    ///
+    /// By default the whole hash is read as a big-endian integer and reduced modulo the group
+    /// order, so derivation never fails. A 64-byte hash (`ChallengeHash::Sha512` or
+    /// `Blake2b512`, or any hash widened by `TranscriptVersion::V3`) gives a statistically
+    /// uniform challenge; a 32-byte one is biased by about (2^256 mod n) / n, which is below
+    /// 2^-127 for secp256k1 but 2^-32 for P-256. For hashes
+    /// no longer than the scalar encoding, the result equals the canonical scalar whenever
+    /// the hash is below the order, so proofs from the earlier rejecting derivation still
+    /// verify.
+    fn challenge_from_hash(hash: &[u8]) -> Result<Self::Scalar, DLogProofError> {
+        if hash.len() > 64 {
+            return Err(DLogProofError::ChallengeDerivation);
+        }
+        Ok(reduce_be(hash))
+    }
+}
+
+/// secp256k1, the default group of `DLogProof`
+///
+/// Points are serialized in SEC1 format. Decoding also accepts the uncompressed form
+/// (0x04 || x || y) and the single-byte identity, as the hex serde functions always did.
+impl DLogGroup for ProjectivePoint {
+    fn encode_point(&self) -> Vec<u8> {
+        encode_sec1::<Secp256k1>(self)
+    }
+
+    fn decode_point(bytes: &[u8]) -> Option<Self> {
+        decode_sec1::<Secp256k1>(bytes)
+    }
+
+    fn lincomb(terms: &[(Self, Scalar)]) -> Self {
+        ProjectivePoint::lincomb_ext(terms)
+    }
+
+    fn coordinates(&self) -> Result<([u8; 32], [u8; 32]), DLogProofError> {
+        let encoded = self.to_affine().to_encoded_point(false);
+        match (encoded.x(), encoded.y()) {
+            (Some(x), Some(y)) => Ok(((*x).into(), (*y).into())),
+            _ => Err(DLogProofError::InvalidPoint),
+        }
+    }
+
+    fn challenge_from_hash(hash: &[u8]) -> Result<Scalar, DLogProofError> {
+        // Right-aligned in 64 bytes, the same integer the generic reduction computes
+        let mut wide = WideBytes::default();
+        let offset = wide
+            .len()
+            .checked_sub(hash.len())
+            .ok_or(DLogProofError::ChallengeDerivation)?;
+        wide[offset..].copy_from_slice(hash);
+        Ok(<Scalar as Reduce<U512>>::reduce_bytes(&wide))
+    }
+}
+
+/// Window width of `wnaf_lincomb`: tables of 8 odd multiples per point
+const WNAF_WIDTH: usize = 5;
+
+/// Variable-time sum k_i * P_i by Straus' method over width-`WNAF_WIDTH` NAFs of the k_i
+pub(crate) fn wnaf_lincomb<G: Group>(terms: &[(G, G::Scalar)]) -> G {
+    let nafs: Vec<Vec<i8>> = terms.iter().map(|(_, k)| wnaf(k)).collect();
+    // Odd multiples P, 3P, ..., 15P of every point
+    let tables: Vec<[G; 1 << (WNAF_WIDTH - 2)]> = terms
+        .iter()
+        .map(|(point, _)| {
+            let double = point.double();
+            let mut table = [*point; 1 << (WNAF_WIDTH - 2)];
+            for j in 1..table.len() {
+                table[j] = table[j - 1] + double;
+            }
+            table
+        })
+        .collect();
+
+    let len = nafs.iter().map(Vec::len).max().unwrap_or(0);
+    let mut sum = G::identity();
+    for i in (0..len).rev() {
+        sum = sum.double();
+        for (naf, table) in nafs.iter().zip(&tables) {
+            match naf.get(i).copied().unwrap_or(0) {
+                0 => {}
+                d if d > 0 => sum += table[(d / 2) as usize],
+                d => sum -= table[(-d / 2) as usize],
+            }
+        }
+    }
+    sum
+}
+
+/// Width-`WNAF_WIDTH` non-adjacent form of a scalar, least significant digit first
+///
+/// Every digit is zero or odd with absolute value below 2^(w-1), and any w consecutive
+/// digits hold at most one that isn't zero. Trailing zero digits are cut off.
+fn wnaf<F: PrimeField>(k: &F) -> Vec<i8> {
+    // Little-endian bits; the encoding of one tells the byte order (see `digit`)
+    let repr = k.to_repr();
+    let mut bytes = repr.as_ref().to_vec();
+    if F::ONE.to_repr().as_ref()[0] == 0 {
+        bytes.reverse();
+    }
+    let bit = |i: usize| bytes.get(i / 8).map_or(0, |byte| (byte >> (i % 8)) & 1);
+
+    let width = 1i16 << WNAF_WIDTH;
+    let mut naf = Vec::with_capacity(8 * bytes.len() + 1);
+    let mut carry = 0i16;
+    let mut i = 0;
+    while i < 8 * bytes.len() || carry != 0 {
+        if i16::from(bit(i)) + carry != 1 {
+            // Even: this digit is zero, the carry moves on
+            carry = (i16::from(bit(i)) + carry) >> 1;
+            naf.push(0);
+            i += 1;
+            continue;
+        }
+        let mut window = carry;
+        for j in 0..WNAF_WIDTH {
+            window += i16::from(bit(i + j)) << j;
+        }
+        let digit = if window >= width / 2 {
+            window - width
+        } else {
+            window
+        };
+        carry = i16::from(digit < 0);
+        naf.push(digit as i8);
+        naf.extend(core::iter::repeat_n(0, WNAF_WIDTH - 1));
+        i += WNAF_WIDTH;
+    }
+    while naf.last() == Some(&0) {
+        naf.pop();
+    }
+    naf
+}
+
+/// Reads big-endian bytes as an integer modulo the field's order
+pub(crate) fn reduce_be<F: PrimeField>(bytes: &[u8]) -> F {
+    bytes.iter().fold(F::ZERO, |acc, &byte| {
+        acc * F::from(256) + F::from(u64::from(byte))
+    })
+}
+
+/// Compressed SEC1 encoding of a point on a short Weierstrass curve
+pub(crate) fn encode_sec1<C>(point: &C::ProjectivePoint) -> Vec<u8>
+where
+    C: CurveArithmetic,
+    C::AffinePoint: ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    let affine: C::AffinePoint = (*point).into();
+    affine.to_encoded_point(true).as_bytes().to_vec()
+}
+
+/// Decodes any SEC1 encoding (compressed, uncompressed or identity) of a point
+///
+/// The compact form (0x05 || x) of the `sec1` crate is rejected: it isn't SEC1, and it would
+/// give a point a second 33-byte encoding next to the compressed one.
+pub(crate) fn decode_sec1<C>(bytes: &[u8]) -> Option<C::ProjectivePoint>
+where
+    C: CurveArithmetic,
+    C::AffinePoint: FromEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    let encoded = EncodedPoint::<C>::from_bytes(bytes).ok()?;
+    if encoded.is_compact() {
+        return None;
+    }
+    Option::<C::AffinePoint>::from(C::AffinePoint::from_encoded_point(&encoded)).map(Into::into)
+}
+
+/// Decodes a point with `GroupEncoding::from_bytes`, accepting only the encoding `to_bytes`
+/// gives it
+///
+/// `from_bytes` of the SEC1 curves also takes the compact form (0x05 || x) for a compressed
+/// point, and that of edwards25519 non-canonical y-coordinates, so without the check the same
+/// proof or key would have several valid encodings.
+pub(crate) fn decode_canonical<G: GroupEncoding>(bytes: &G::Repr) -> Option<G> {
+    Option::<G>::from(G::from_bytes(bytes))
+        .filter(|point| point.to_bytes().as_ref() == bytes.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn challenges_are_reduced_not_rejected() {
+        // 2^256 - 1 is above the order; the rejecting derivation failed on it
+        let c = ProjectivePoint::challenge_from_hash(&[0xff; 32]).unwrap();
+        assert_eq!(c, reduce_be::<Scalar>(&[0xff; 32]));
+
+        let below = [0x7f; 32];
+        let c = ProjectivePoint::challenge_from_hash(&below).unwrap();
+        assert_eq!(c, Scalar::from_repr(below.into()).unwrap());
+
+        let wide = [0xa5; 64];
+        let c = ProjectivePoint::challenge_from_hash(&wide).unwrap();
+        assert_eq!(c, reduce_be::<Scalar>(&wide));
+        assert!(ProjectivePoint::challenge_from_hash(&[0; 65]).is_err());
+    }
+
+    #[test]
+    fn vartime_combinations_match_constant_time_ones() {
+        use elliptic_curve::Field;
+        use rand_core::OsRng;
+
+        let g = ProjectivePoint::GENERATOR;
+        let h = g * Scalar::random(&mut OsRng);
+        for k in [Scalar::ZERO, Scalar::ONE, -Scalar::ONE, Scalar::from(31u64)] {
+            assert_eq!(ProjectivePoint::lincomb_vartime(&[(g, k)]), g * k);
+        }
+        for _ in 0..32 {
+            let terms = [
+                (g, Scalar::random(&mut OsRng)),
+                (h, Scalar::random(&mut OsRng)),
+            ];
+            assert_eq!(
+                ProjectivePoint::lincomb_vartime(&terms),
+                ProjectivePoint::lincomb(&terms)
+            );
+        }
+        assert_eq!(
+            ProjectivePoint::lincomb_vartime(&[]),
+            ProjectivePoint::IDENTITY
+        );
+    }
+}
@@ -0,0 +1,349 @@
+//! gRPC proving and verification service through tonic (`grpc` feature)
+//!
+//! The messages and the `DlogService` trait are generated from `proto/dlog.proto` into
+//! `proto`; `ProofService` implements them over secp256k1 and the standard generator, with
+//! proofs in the 65-byte encoding of `DLogProof::to_bytes`. Mount it in a tonic server:
+//!
+//! ```no_run
+//! # async fn run(key_pair: schnorr_dlog_zkp::KeyPair) -> Result<(), Box<dyn std::error::Error>> {
+//! use schnorr_dlog_zkp::grpc::ProofService;
+//!
+//! tonic::transport::Server::builder()
+//!     .add_service(ProofService::new().key_pair(key_pair).into_server())
+//!     .serve("127.0.0.1:50051".parse()?)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Every request's sid and pid are checked before any proof work: the sid must be nonempty and
+//! at most `max_sid_len` bytes, the pid within `pids`. Deadlines sent by the client in the
+//! `grpc-timeout` header, or set for the service with `batch_timeout`, bound the whole
+//! `VerifyBatch` call including the reading of its stream, and end it with
+//! `DEADLINE_EXCEEDED`.
+
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use k256::ProjectivePoint;
+use tokio::time::Instant;
+use tonic::{Request, Response, Status, Streaming};
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::keypair::KeyPair;
+
+/// Code generated from `proto/dlog.proto`
+#[allow(missing_docs, clippy::all)]
+pub mod proto {
+    tonic::include_proto!("schnorr_dlog_zkp.v1");
+}
+
+use proto::dlog_service_server::{DlogService, DlogServiceServer};
+use proto::{
+    Malformed, ProveRequest, ProveResponse, VerifyBatchResponse, VerifyRequest, VerifyResponse,
+};
+
+/// Default bound of `ProofService::max_sid_len`
+pub const DEFAULT_MAX_SID_LEN: usize = 256;
+
+/// Default bound of `ProofService::max_batch`
+pub const DEFAULT_MAX_BATCH: usize = 10_000;
+
+/// Implementation of the generated `DlogService`
+///
+/// Without a key pair the service only verifies, and `Prove` fails with `FAILED_PRECONDITION`.
+#[derive(Debug, Clone)]
+pub struct ProofService {
+    key_pair: Option<KeyPair>,
+    max_sid_len: usize,
+    pids: RangeInclusive<u32>,
+    max_batch: usize,
+    batch_timeout: Option<Duration>,
+}
+
+impl Default for ProofService {
+    fn default() -> Self {
+        ProofService {
+            key_pair: None,
+            max_sid_len: DEFAULT_MAX_SID_LEN,
+            pids: 0..=u32::MAX,
+            max_batch: DEFAULT_MAX_BATCH,
+            batch_timeout: None,
+        }
+    }
+}
+
+impl ProofService {
+    /// A verification-only service accepting any pid and sids up to `DEFAULT_MAX_SID_LEN`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Key pair `Prove` proves knowledge of
+    pub fn key_pair(mut self, key_pair: KeyPair) -> Self {
+        self.key_pair = Some(key_pair);
+        self
+    }
+
+    /// Longest accepted session identifier, in bytes
+    pub fn max_sid_len(mut self, max_sid_len: usize) -> Self {
+        self.max_sid_len = max_sid_len;
+        self
+    }
+
+    /// Accepted participant IDs
+    pub fn pids(mut self, pids: RangeInclusive<u32>) -> Self {
+        self.pids = pids;
+        self
+    }
+
+    /// Most requests accepted in one `VerifyBatch` stream
+    pub fn max_batch(mut self, max_batch: usize) -> Self {
+        self.max_batch = max_batch;
+        self
+    }
+
+    /// Longest time a `VerifyBatch` call may take, also when the client sets no deadline
+    pub fn batch_timeout(mut self, timeout: Duration) -> Self {
+        self.batch_timeout = Some(timeout);
+        self
+    }
+
+    /// Wraps the service for `tonic::transport::Server::add_service`
+    pub fn into_server(self) -> DlogServiceServer<Self> {
+        DlogServiceServer::new(self)
+    }
+
+    fn check_statement(&self, sid: &str, pid: u32) -> Result<(), DLogProofError> {
+        if sid.is_empty() {
+            return Err(DLogProofError::Serialization("empty sid".to_string()));
+        }
+        if sid.len() > self.max_sid_len {
+            return Err(DLogProofError::Serialization(format!(
+                "sid of {} bytes exceeds {}",
+                sid.len(),
+                self.max_sid_len
+            )));
+        }
+        if !self.pids.contains(&pid) {
+            return Err(DLogProofError::InvalidParticipant);
+        }
+        Ok(())
+    }
+
+    /// Checks and decodes a verification request
+    fn decode(
+        &self,
+        request: &VerifyRequest,
+    ) -> Result<(ProjectivePoint, DLogProof), DLogProofError> {
+        self.check_statement(&request.sid, request.pid)?;
+        let y = ProjectivePoint::decode_point(&request.public_key)
+            .ok_or(DLogProofError::InvalidPoint)?;
+        Ok((y, DLogProof::from_bytes(&request.proof)?))
+    }
+}
+
+#[tonic::async_trait]
+impl DlogService for ProofService {
+    async fn prove(
+        &self,
+        request: Request<ProveRequest>,
+    ) -> Result<Response<ProveResponse>, Status> {
+        let ProveRequest { sid, pid } = request.into_inner();
+        self.check_statement(&sid, pid).map_err(invalid_argument)?;
+        let key_pair = self
+            .key_pair
+            .as_ref()
+            .ok_or_else(|| Status::failed_precondition("no proving key configured"))?;
+        let proof = key_pair.prove(&sid, pid).map_err(internal)?;
+        Ok(Response::new(ProveResponse {
+            proof: proof.to_bytes().to_vec(),
+            public_key: key_pair.public().encode_point(),
+        }))
+    }
+
+    async fn verify(
+        &self,
+        request: Request<VerifyRequest>,
+    ) -> Result<Response<VerifyResponse>, Status> {
+        let request = request.into_inner();
+        let (y, proof) = self.decode(&request).map_err(invalid_argument)?;
+        let valid = proof
+            .verify(&request.sid, request.pid, y, ProjectivePoint::GENERATOR)
+            .map_err(invalid_argument)?;
+        Ok(Response::new(VerifyResponse { valid }))
+    }
+
+    async fn verify_batch(
+        &self,
+        request: Request<Streaming<VerifyRequest>>,
+    ) -> Result<Response<VerifyBatchResponse>, Status> {
+        let own_deadline = self.batch_timeout.map(|timeout| Instant::now() + timeout);
+        let deadline = match (deadline(&request), own_deadline) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        let mut stream = request.into_inner();
+        let mut parsed = Vec::new();
+        let mut malformed = Vec::new();
+        let mut total = 0u32;
+        loop {
+            let message = match deadline {
+                Some(deadline) => tokio::time::timeout_at(deadline, stream.message())
+                    .await
+                    .map_err(|_| Status::deadline_exceeded("batch not received in time"))?,
+                None => stream.message().await,
+            };
+            let Some(request) = message? else {
+                break;
+            };
+            if total as usize == self.max_batch {
+                return Err(Status::resource_exhausted(format!(
+                    "batch exceeds {} proofs",
+                    self.max_batch
+                )));
+            }
+            match self.decode(&request) {
+                Ok((y, proof)) => parsed.push((total, request, y, proof)),
+                Err(e) => malformed.push(Malformed {
+                    index: total,
+                    error: e.to_string(),
+                }),
+            }
+            total += 1;
+        }
+
+        let verify = move || {
+            let items: Vec<_> = parsed
+                .iter()
+                .map(|(_, request, y, proof)| {
+                    (request.sid.as_str().into(), request.pid.into(), *y, proof)
+                })
+                .collect();
+            let invalid = DLogProof::find_invalid(&items, ProjectivePoint::GENERATOR)?;
+            Ok::<_, DLogProofError>(invalid.into_iter().map(|i| parsed[i].0).collect::<Vec<_>>())
+        };
+        // Batch verification is CPU-bound, so it runs off the async workers
+        let verification = tokio::task::spawn_blocking(verify);
+        let invalid = match deadline {
+            Some(deadline) => tokio::time::timeout_at(deadline, verification)
+                .await
+                .map_err(|_| Status::deadline_exceeded("batch not verified in time"))?,
+            None => verification.await,
+        }
+        .map_err(internal)?
+        .map_err(invalid_argument)?;
+
+        Ok(Response::new(VerifyBatchResponse {
+            valid: invalid.is_empty() && malformed.is_empty(),
+            total,
+            invalid,
+            malformed,
+        }))
+    }
+}
+
+/// The deadline of a call from its `grpc-timeout` header, if it has a valid one
+fn deadline<T>(request: &Request<T>) -> Option<Instant> {
+    let timeout = request.metadata().get("grpc-timeout")?.to_str().ok()?;
+    let (value, unit) = timeout.split_at(timeout.len().checked_sub(1)?);
+    // The value has at most 8 digits
+    let value: u64 = value.parse().ok().filter(|_| value.len() <= 8)?;
+    let timeout = match unit {
+        "H" => Duration::from_secs(value * 3600),
+        "M" => Duration::from_secs(value * 60),
+        "S" => Duration::from_secs(value),
+        "m" => Duration::from_millis(value),
+        "u" => Duration::from_micros(value),
+        "n" => Duration::from_nanos(value),
+        _ => return None,
+    };
+    Some(Instant::now() + timeout)
+}
+
+fn invalid_argument(e: DLogProofError) -> Status {
+    Status::invalid_argument(e.to_string())
+}
+
+fn internal(e: impl ToString) -> Status {
+    Status::internal(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proto::dlog_service_client::DlogServiceClient;
+    use rand_core::OsRng;
+    use tokio::net::TcpListener;
+    use tonic::codegen::tokio_stream;
+    use tonic::transport::server::TcpIncoming;
+    use tonic::transport::Server;
+    use tonic::Code;
+
+    #[tokio::test]
+    async fn service_proves_verifies_and_validates() {
+        let key_pair = KeyPair::generate(&mut OsRng);
+        let service = ProofService::new()
+            .key_pair(key_pair)
+            .pids(1..=10)
+            .batch_timeout(Duration::from_millis(100));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let incoming = TcpIncoming::from_listener(listener, true, None).unwrap();
+        tokio::spawn(
+            Server::builder()
+                .add_service(service.into_server())
+                .serve_with_incoming(incoming),
+        );
+        let mut client = DlogServiceClient::connect(format!("http://{address}"))
+            .await
+            .unwrap();
+
+        let proved = client
+            .prove(ProveRequest {
+                sid: "sid".to_string(),
+                pid: 1,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        let request = |pid| VerifyRequest {
+            sid: "sid".to_string(),
+            pid,
+            public_key: proved.public_key.clone(),
+            proof: proved.proof.clone(),
+        };
+        assert!(client.verify(request(1)).await.unwrap().into_inner().valid);
+        assert!(!client.verify(request(2)).await.unwrap().into_inner().valid);
+
+        let status = client.verify(request(11)).await.unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+        let status = client
+            .prove(ProveRequest {
+                sid: String::new(),
+                pid: 1,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        let batch = vec![request(1), request(2), request(0)];
+        let verdict = client
+            .verify_batch(tokio_stream::iter(batch))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(!verdict.valid);
+        assert_eq!(verdict.total, 3);
+        assert_eq!(verdict.invalid, [1]);
+        assert_eq!(verdict.malformed.len(), 1);
+        assert_eq!(verdict.malformed[0].index, 2);
+
+        // A stream the client never closes runs into the deadline
+        let never_closed = tokio_stream::pending::<VerifyRequest>();
+        let status = client.verify_batch(never_closed).await.unwrap_err();
+        assert_eq!(status.code(), Code::DeadlineExceeded);
+    }
+}
@@ -0,0 +1,110 @@
+use k256::{ProjectivePoint, Scalar};
+use std::fmt;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::session::SessionId;
+
+/// Public statement "I know x such that y = x * G" for a fixed base point G
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Statement {
+    pub y: ProjectivePoint,
+    pub base_point: ProjectivePoint,
+}
+
+/// Secret witness x of a `Statement`
+///
+/// `Debug` is redacted so the scalar doesn't end up in logs by accident.
+#[derive(Clone)]
+pub struct Witness(Scalar);
+
+impl Statement {
+    pub fn new(y: ProjectivePoint, base_point: ProjectivePoint) -> Self {
+        Statement { y, base_point }
+    }
+
+    /// Computes the statement y = x * G matching a witness
+    pub fn from_witness(witness: &Witness, base_point: ProjectivePoint) -> Self {
+        Statement {
+            y: base_point * witness.0,
+            base_point,
+        }
+    }
+
+    /// Applies an additive tweak: y' = y + Δ * G, matching `Witness::tweak`
+    pub fn tweak(&self, delta: &Scalar) -> Self {
+        Statement {
+            y: self.y + self.base_point * delta,
+            base_point: self.base_point,
+        }
+    }
+
+    /// Combines statements into Y = y1 + y2 + ..., matching `Witness::aggregate`
+    ///
+    /// # Returns
+    /// * `Ok(Statement)` - The aggregated statement
+    /// * `Err(DLogProofError)` - `StatementCount` if the list is empty or the statements use
+    ///   different base points
+    pub fn aggregate(statements: &[Statement]) -> Result<Self, DLogProofError> {
+        let (first, rest) = statements
+            .split_first()
+            .ok_or(DLogProofError::StatementCount)?;
+        if rest.iter().any(|s| s.base_point != first.base_point) {
+            return Err(DLogProofError::StatementCount);
+        }
+        Ok(Statement {
+            y: statements.iter().map(|s| s.y).sum(),
+            base_point: first.base_point,
+        })
+    }
+
+    /// Verifies a proof of knowledge for this statement
+    pub fn verify<'s>(
+        &self,
+        proof: &DLogProof,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+    ) -> Result<bool, DLogProofError> {
+        proof.verify(sid, pid, self.y, self.base_point)
+    }
+}
+
+impl Witness {
+    pub fn new(x: Scalar) -> Self {
+        Witness(x)
+    }
+
+    /// Applies an additive tweak: x' = x + Δ
+    pub fn tweak(&self, delta: &Scalar) -> Self {
+        Witness(self.0 + delta)
+    }
+
+    /// Combines witnesses into X = x1 + x2 + ...
+    pub fn aggregate(witnesses: &[Witness]) -> Self {
+        Witness(witnesses.iter().map(|w| w.0).sum())
+    }
+
+    /// Proves knowledge of this witness for `statement`
+    ///
+    /// # Returns
+    /// * `Ok(DLogProof)` - The proof
+    /// * `Err(DLogProofError)` - `InvalidWitness` if the witness doesn't match the statement,
+    ///   or any error of proof generation
+    pub fn prove<'s>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        statement: &Statement,
+    ) -> Result<DLogProof, DLogProofError> {
+        if statement.base_point * self.0 != statement.y {
+            return Err(DLogProofError::InvalidWitness);
+        }
+        DLogProof::prove(sid, pid, &self.0, statement.y, statement.base_point)
+    }
+}
+
+impl fmt::Debug for Witness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Witness(..)")
+    }
+}
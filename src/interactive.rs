@@ -0,0 +1,235 @@
+use elliptic_curve::Field;
+use rand_core::{CryptoRngCore, OsRng};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use zeroize::Zeroizing;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::prover::{blinded_mul, blinded_response, Prover};
+use crate::secret::SecretScalar;
+use crate::serialization::{PointHex, ScalarHex};
+use crate::sigma::{DLogStatement, SchnorrDLog, SigmaProtocol};
+use crate::verifier::Verifier;
+
+/// First message of the interactive protocol, the prover's commitment t = r * G
+#[serde_as]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Commitment<G: DLogGroup> {
+    #[serde_as(as = "PointHex")]
+    t: G,
+}
+
+/// Second message, the challenge c chosen by the verifier
+#[serde_as]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Challenge<G: DLogGroup> {
+    #[serde_as(as = "ScalarHex")]
+    c: G::Scalar,
+}
+
+/// Third message, the prover's response s = r + c * x
+#[serde_as]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Response<G: DLogGroup> {
+    #[serde_as(as = "ScalarHex")]
+    s: G::Scalar,
+}
+
+impl<G: DLogGroup> Commitment<G> {
+    pub fn new(t: G) -> Self {
+        Commitment { t }
+    }
+
+    pub fn t(&self) -> G {
+        self.t
+    }
+}
+
+impl<G: DLogGroup> Challenge<G> {
+    pub fn new(c: G::Scalar) -> Self {
+        Challenge { c }
+    }
+
+    pub fn c(&self) -> G::Scalar {
+        self.c
+    }
+}
+
+impl<G: DLogGroup> Response<G> {
+    pub fn new(s: G::Scalar) -> Self {
+        Response { s }
+    }
+
+    pub fn s(&self) -> G::Scalar {
+        self.s
+    }
+}
+
+/// Prover between sending the commitment and receiving the challenge
+///
+/// Holds the secret and the nonce, both wiped on drop. `respond` consumes the state, so a
+/// nonce can answer only one challenge: two responses for the same commitment would reveal x.
+/// Deliberately neither `Clone` nor serializable.
+pub struct ProverState<G: DLogGroup> {
+    x: SecretScalar<G::Scalar>,
+    r: Zeroizing<G::Scalar>,
+    blinded: bool,
+}
+
+impl<G: DLogGroup> ProverState<G> {
+    /// Answers the verifier's challenge, ending the prover's side of the protocol
+    pub fn respond(self, challenge: &Challenge<G>) -> Response<G> {
+        let s = if self.blinded {
+            blinded_response(&*self.r, &challenge.c, self.x.expose_secret(), &mut OsRng)
+        } else {
+            SchnorrDLog::<G>::respond(&self.x, &self.r, &challenge.c)
+        };
+        Response { s }
+    }
+}
+
+/// Verifier between sending the challenge and receiving the response
+pub struct VerifierState<G: DLogGroup> {
+    statement: DLogStatement<G>,
+    t: G,
+    c: G::Scalar,
+}
+
+impl<G: DLogGroup> VerifierState<G> {
+    /// The challenge to send to the prover
+    pub fn challenge(&self) -> Challenge<G> {
+        Challenge { c: self.c }
+    }
+
+    /// Checks the response s * G = t + c * y, ending the protocol
+    pub fn check(self, response: &Response<G>) -> bool {
+        SchnorrDLog::check(&self.statement, &self.t, &self.c, &response.s)
+    }
+}
+
+impl Prover {
+    /// Starts an interactive proof of knowledge of `x` for y = x * G
+    ///
+    /// The three-move protocol for deployments where the verifier picks the challenge:
+    /// send the `Commitment`, answer the received `Challenge` with `ProverState::respond`.
+    /// Blinding applies as in `prove`; the transcript options don't, as no challenge is
+    /// derived by hashing.
+    ///
+    /// ```
+    /// # use elliptic_curve::Field;
+    /// # use schnorr_dlog_zkp::k256::{ProjectivePoint, Scalar};
+    /// use schnorr_dlog_zkp::{Prover, Verifier};
+    /// # let g = ProjectivePoint::GENERATOR;
+    /// # let x = Scalar::random(&mut rand_core::OsRng);
+    /// # let y = g * x;
+    ///
+    /// let (commitment, prover) = Prover::new().commit(&x, y, g).unwrap();
+    /// let verifier = Verifier::new().challenge(commitment, y, g).unwrap();
+    /// let response = prover.respond(&verifier.challenge());
+    /// assert!(verifier.check(&response));
+    /// ```
+    pub fn commit<G: DLogGroup>(
+        &self,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: G,
+        base_point: G,
+    ) -> Result<(Commitment<G>, ProverState<G>), DLogProofError> {
+        self.commit_with_rng(&mut OsRng, x, y, base_point)
+    }
+
+    /// Starts an interactive proof like `commit`, drawing the nonce from `rng`
+    pub fn commit_with_rng<G: DLogGroup>(
+        &self,
+        rng: &mut impl CryptoRngCore,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: G,
+        base_point: G,
+    ) -> Result<(Commitment<G>, ProverState<G>), DLogProofError> {
+        DLogProof::check_statement(y, base_point)?;
+        let x = x.into();
+        let (t, r) = if self.is_blinded() {
+            let r = Zeroizing::new(G::Scalar::random(&mut *rng));
+            (blinded_mul(|k| base_point * k, &*r, rng), r)
+        } else {
+            let (t, r) = SchnorrDLog::commit(&DLogStatement { base_point, y }, &x, rng);
+            (t, Zeroizing::new(r))
+        };
+        let state = ProverState {
+            x,
+            r,
+            blinded: self.is_blinded(),
+        };
+        Ok((Commitment { t }, state))
+    }
+}
+
+impl Verifier {
+    /// Receives the prover's commitment and picks a uniformly random challenge
+    ///
+    /// Send `VerifierState::challenge` to the prover and check its response with
+    /// `VerifierState::check`. The transcript options of this verifier don't apply.
+    pub fn challenge<G: DLogGroup>(
+        &self,
+        commitment: Commitment<G>,
+        y: G,
+        base_point: G,
+    ) -> Result<VerifierState<G>, DLogProofError> {
+        self.challenge_with_rng(&mut OsRng, commitment, y, base_point)
+    }
+
+    /// Picks the challenge like `challenge`, drawing it from `rng`
+    pub fn challenge_with_rng<G: DLogGroup>(
+        &self,
+        rng: &mut impl CryptoRngCore,
+        commitment: Commitment<G>,
+        y: G,
+        base_point: G,
+    ) -> Result<VerifierState<G>, DLogProofError> {
+        DLogProof::check_statement(y, base_point)?;
+        Ok(VerifierState {
+            statement: DLogStatement { base_point, y },
+            t: commitment.t,
+            c: G::Scalar::random(rng),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::{ProjectivePoint, Scalar};
+
+    #[test]
+    fn interactive_runs_accept_only_the_right_secret() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+
+        for prover in [Prover::new(), Prover::new().blinded(true)] {
+            let (commitment, state) = prover.commit(&x, y, g).unwrap();
+
+            // The messages travel as JSON
+            let json = serde_json::to_string(&commitment).unwrap();
+            let verifier = Verifier::new()
+                .challenge(serde_json::from_str(&json).unwrap(), y, g)
+                .unwrap();
+            let response = state.respond(&verifier.challenge());
+            assert!(verifier.check(&response));
+        }
+
+        let (commitment, state) = Prover::new().commit(&Scalar::ONE, y, g).unwrap();
+        let verifier = Verifier::new().challenge(commitment, y, g).unwrap();
+        let response = state.respond(&verifier.challenge());
+        assert!(!verifier.check(&response));
+
+        assert!(matches!(
+            Prover::new().commit(&x, ProjectivePoint::IDENTITY, g),
+            Err(DLogProofError::IdentityPoint)
+        ));
+    }
+}
@@ -0,0 +1,137 @@
+use elliptic_curve::subtle::{Choice, ConstantTimeEq};
+use elliptic_curve::Field;
+use k256::{ProjectivePoint, Scalar};
+use rand_core::OsRng;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::serialization::{PointHex, ScalarHex};
+use crate::session::SessionId;
+
+/// Proof that a public pair (y, z) satisfies y = x * G and z = x⁻¹ * G for a secret x
+///
+/// Since z = x⁻¹ * G is equivalent to G = x * z, the statement is an equality of discrete
+/// logs: log_G(y) = log_z(G) = x. The proof uses one nonce r for both bases:
+/// * `t1` - Commitment t1 = r * G
+/// * `t2` - Commitment t2 = r * z
+/// * `s` - Response s = r + c * x
+///
+/// and the verifier checks s * G = t1 + c * y and s * z = t2 + c * G.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct InverseProof {
+    #[serde_as(as = "PointHex")]
+    pub(crate) t1: ProjectivePoint,
+    #[serde_as(as = "PointHex")]
+    pub(crate) t2: ProjectivePoint,
+    #[serde_as(as = "ScalarHex")]
+    pub(crate) s: Scalar,
+}
+
+impl InverseProof {
+    const DOMAIN_SEPARATOR: &'static [u8] = b"SCHNORR_INVERSE_PROOF";
+
+    /// Proves that z = x⁻¹ * G for the secret x behind y = x * G
+    ///
+    /// # Arguments
+    /// * `sid` - Session identifier used for domain separation, a string or bytes (see `SessionId`)
+    /// * `pid` - Participant ID for uniqueness to distinguish different proofs
+    /// * `x` - The secret scalar, must be non-zero
+    /// * `y` - The public point y = x * G
+    /// * `z` - The public point z = x⁻¹ * G
+    /// * `base_point` - Base point of secp256k1 curve
+    ///
+    /// # Returns
+    /// * `Ok(InverseProof)` - The proof
+    /// * `Err(DLogProofError)` - `InvalidWitness` if x is zero (has no inverse), or any error
+    ///   of proof generation
+    pub fn prove<'s>(
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        x: &Scalar,
+        y: ProjectivePoint,
+        z: ProjectivePoint,
+        base_point: ProjectivePoint,
+    ) -> Result<Self, DLogProofError> {
+        if bool::from(x.is_zero()) {
+            return Err(DLogProofError::InvalidWitness);
+        }
+
+        let r = Scalar::random(&mut OsRng);
+        let t1 = base_point * r;
+        let t2 = z * r;
+
+        let c = DLogProof::hash_points_with_domain(
+            Self::DOMAIN_SEPARATOR,
+            sid.into(),
+            pid.into(),
+            &[base_point, y, z, t1, t2],
+        )?;
+
+        Ok(InverseProof {
+            t1,
+            t2,
+            s: r + c * x,
+        })
+    }
+
+    /// Verifies that z = x⁻¹ * G where y = x * G
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Validity of the proof
+    /// * `Err(DLogProofError)` - Any error during verification
+    pub fn verify<'s>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        y: ProjectivePoint,
+        z: ProjectivePoint,
+        base_point: ProjectivePoint,
+    ) -> Result<bool, DLogProofError> {
+        let c = DLogProof::hash_points_with_domain(
+            Self::DOMAIN_SEPARATOR,
+            sid.into(),
+            pid.into(),
+            &[base_point, y, z, self.t1, self.t2],
+        )?;
+
+        let first: Choice = (base_point * self.s).ct_eq(&(self.t1 + y * c));
+        let second: Choice = (z * self.s).ct_eq(&(self.t2 + base_point * c));
+        Ok((first & second).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverse_pair_verifies() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let (y, z) = (g * x, g * x.invert().unwrap());
+
+        let proof = InverseProof::prove("sid", 1, &x, y, z, g).unwrap();
+        assert!(proof.verify("sid", 1, y, z, g).unwrap());
+    }
+
+    #[test]
+    fn non_inverse_pair_is_rejected() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let (y, z) = (g * x, g * x.invert().unwrap());
+        let other = g * Scalar::random(&mut OsRng);
+
+        let proof = InverseProof::prove("sid", 1, &x, y, other, g).unwrap();
+        assert!(!proof.verify("sid", 1, y, other, g).unwrap());
+
+        let proof = InverseProof::prove("sid", 1, &x, y, z, g).unwrap();
+        assert!(!proof.verify("sid", 1, y, other, g).unwrap());
+        assert_eq!(
+            InverseProof::prove("sid", 1, &Scalar::ZERO, y, z, g),
+            Err(DLogProofError::InvalidWitness)
+        );
+    }
+}
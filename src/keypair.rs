@@ -0,0 +1,261 @@
+use elliptic_curve::{Field, PrimeField};
+use k256::ProjectivePoint;
+use rand_core::CryptoRngCore;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_with::serde_as;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::keys::PublicKey;
+use crate::prover::Prover;
+use crate::secret::SecretScalar;
+use crate::serialization::PointHex;
+use crate::session::SessionId;
+use crate::verifier::Verifier;
+
+/// Secret key x with its public key y = x * G for the group's standard generator G
+///
+/// The secret is a `SecretScalar`, wiped when the key pair is dropped and never printed by
+/// `Debug`. Serializing a key pair writes only the public half, `{"public": "<hex>"}`; there
+/// is deliberately no way to serialize or deserialize the secret through serde.
+#[derive(Debug, Clone)]
+pub struct KeyPair<G: DLogGroup = ProjectivePoint> {
+    secret: SecretScalar<G::Scalar>,
+    public: G,
+}
+
+/// What a proof of possession made at key registration is bound to
+///
+/// The registrar's session ID, the participant ID of the new key, and application data such
+/// as the account the key is registered for. The data is the additional authenticated data
+/// of the proof (see `DLogProof::prove_with_context`), so a proof made for one account can't
+/// be replayed to register the key for another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegistrationContext<'a> {
+    pub sid: SessionId<'a>,
+    pub pid: u32,
+    pub data: &'a [u8],
+}
+
+impl<'a> RegistrationContext<'a> {
+    pub fn new(sid: impl Into<SessionId<'a>>, pid: u32, data: &'a [u8]) -> Self {
+        RegistrationContext {
+            sid: sid.into(),
+            pid,
+            data,
+        }
+    }
+}
+
+/// A public key with its proof of possession, as sent to the registrar
+///
+/// Made by `KeyPair::generate_attested` or `KeyPair::attest` and checked with
+/// `verify_registration`. Serialized as `{"public": "<hex>", "proof": {"t": "<hex>", "s":
+/// "<hex>"}}`.
+#[serde_as]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "", deny_unknown_fields)]
+pub struct Registration<G: DLogGroup = ProjectivePoint> {
+    #[serde_as(as = "PointHex")]
+    pub public: G,
+    pub proof: DLogProof<G>,
+}
+
+/// The serialized form of a `KeyPair`
+#[serde_as]
+#[derive(Serialize)]
+#[serde(bound = "")]
+struct PublicHalf<G: DLogGroup> {
+    #[serde_as(as = "PointHex")]
+    public: G,
+}
+
+impl<G: DLogGroup> KeyPair<G> {
+    /// Generates a key pair with a uniformly random nonzero secret
+    pub fn generate(rng: &mut impl CryptoRngCore) -> Self {
+        loop {
+            let secret = SecretScalar::new(G::Scalar::random(&mut *rng));
+            if let Ok(key_pair) = Self::from_secret(secret) {
+                return key_pair;
+            }
+        }
+    }
+
+    /// Creates the key pair of a nonzero secret scalar
+    ///
+    /// # Returns
+    /// * `Ok(KeyPair)` - The key pair
+    /// * `Err(DLogProofError)` - `InvalidScalar` if the secret is zero
+    pub fn from_secret(secret: impl Into<SecretScalar<G::Scalar>>) -> Result<Self, DLogProofError> {
+        let secret = secret.into();
+        if bool::from(secret.expose_secret().is_zero()) {
+            return Err(DLogProofError::InvalidScalar);
+        }
+        let public = G::generator() * secret.expose_secret();
+        Ok(KeyPair { secret, public })
+    }
+
+    /// Creates the key pair of a secret in its canonical scalar encoding (32 bytes big-endian
+    /// for secp256k1)
+    ///
+    /// # Returns
+    /// * `Ok(KeyPair)` - The key pair
+    /// * `Err(DLogProofError)` - `InvalidScalar` for a wrong length, a value not below the
+    ///   group order, or zero
+    pub fn from_secret_bytes(bytes: &[u8]) -> Result<Self, DLogProofError> {
+        let mut repr = <G::Scalar as PrimeField>::Repr::default();
+        if repr.as_ref().len() != bytes.len() {
+            return Err(DLogProofError::InvalidScalar);
+        }
+        repr.as_mut().copy_from_slice(bytes);
+        let secret = Option::from(G::Scalar::from_repr(repr)).ok_or(DLogProofError::InvalidScalar);
+        repr.as_mut().fill(0);
+        Self::from_secret(SecretScalar::new(secret?))
+    }
+
+    /// The secret key x
+    pub fn secret(&self) -> &SecretScalar<G::Scalar> {
+        &self.secret
+    }
+
+    /// The public key y = x * G
+    pub fn public(&self) -> G {
+        self.public
+    }
+
+    /// The public key as a `PublicKey`
+    ///
+    /// The secret is nonzero, so this only fails with `IdentityPoint` if the group's
+    /// generator is the identity.
+    pub fn public_key(&self) -> Result<PublicKey<G>, DLogProofError> {
+        PublicKey::new(self.public)
+    }
+
+    /// Generates a key pair together with a proof of possession for `ctx`
+    ///
+    /// The key pair stays with the caller; the `Registration` is what goes to the registrar,
+    /// which checks it with `verify_registration`.
+    pub fn generate_attested(
+        rng: &mut impl CryptoRngCore,
+        ctx: &RegistrationContext<'_>,
+    ) -> Result<(Self, Registration<G>), DLogProofError> {
+        let key_pair = Self::generate(rng);
+        let registration = key_pair.attest(rng, ctx)?;
+        Ok((key_pair, registration))
+    }
+
+    /// Proves possession of the secret key for `ctx`, e.g. to register an existing key
+    pub fn attest(
+        &self,
+        rng: &mut impl CryptoRngCore,
+        ctx: &RegistrationContext<'_>,
+    ) -> Result<Registration<G>, DLogProofError> {
+        let proof = Prover::new().aad(ctx.data).prove_with_rng(
+            rng,
+            ctx.sid,
+            ctx.pid,
+            &self.secret,
+            self.public,
+            G::generator(),
+        )?;
+        Ok(Registration {
+            public: self.public,
+            proof,
+        })
+    }
+
+    /// Proves knowledge of the secret key for the standard generator
+    ///
+    /// Shorthand for `DLogProof::prove(sid, pid, key_pair.secret(), key_pair.public(), G)`.
+    pub fn prove<'s>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+    ) -> Result<DLogProof<G>, DLogProofError> {
+        DLogProof::prove(sid, pid, &self.secret, self.public, G::generator())
+    }
+}
+
+impl<G: DLogGroup> Serialize for KeyPair<G> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PublicHalf {
+            public: self.public,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Registrar side of `KeyPair::generate_attested`: checks that `registration` proves
+/// possession of its public key for `ctx`
+///
+/// # Returns
+/// * `Ok(bool)` - Whether the proof is valid for the public key and the context
+/// * `Err(DLogProofError)` - `IdentityPoint` if the public key is the identity, or any other
+///   error of `Verifier::verify`
+pub fn verify_registration<G: DLogGroup>(
+    registration: &Registration<G>,
+    ctx: &RegistrationContext<'_>,
+) -> Result<bool, DLogProofError> {
+    Verifier::new().aad(ctx.data).verify(
+        &registration.proof,
+        ctx.sid,
+        ctx.pid,
+        registration.public,
+        G::generator(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+
+    #[test]
+    fn key_pairs_prove_and_serialize_only_the_public_key() {
+        let key_pair = KeyPair::<ProjectivePoint>::generate(&mut OsRng);
+        let g = ProjectivePoint::GENERATOR;
+        assert_eq!(key_pair.public(), g * key_pair.secret().expose_secret());
+
+        let proof = key_pair.prove("sid", 1).unwrap();
+        assert!(proof.verify("sid", 1, key_pair.public(), g).unwrap());
+
+        let bytes = key_pair.secret().expose_secret().to_repr();
+        let restored = KeyPair::<ProjectivePoint>::from_secret_bytes(&bytes).unwrap();
+        assert_eq!(restored.public(), key_pair.public());
+        assert!(KeyPair::<ProjectivePoint>::from_secret_bytes(&[0; 32]).is_err());
+        assert!(KeyPair::<ProjectivePoint>::from_secret_bytes(&bytes[1..]).is_err());
+
+        let json = serde_json::to_value(&key_pair).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "public": hex::encode(key_pair.public().encode_point()) })
+        );
+        assert!(!format!("{key_pair:?}").contains(&hex::encode(bytes)));
+    }
+    #[test]
+    fn attested_keys_register_only_in_their_context() {
+        let ctx = RegistrationContext::new("registrar", 7, b"account 42");
+        let (key_pair, registration) =
+            KeyPair::<ProjectivePoint>::generate_attested(&mut OsRng, &ctx).unwrap();
+        assert_eq!(registration.public, key_pair.public());
+        assert!(verify_registration(&registration, &ctx).unwrap());
+
+        let json = serde_json::to_string(&registration).unwrap();
+        let received: Registration = serde_json::from_str(&json).unwrap();
+        assert!(verify_registration(&received, &ctx).unwrap());
+
+        for other in [
+            RegistrationContext::new("registrar", 7, b"account 43"),
+            RegistrationContext::new("registrar", 8, b"account 42"),
+            RegistrationContext::new("other", 7, b"account 42"),
+        ] {
+            assert!(!verify_registration(&registration, &other).unwrap());
+        }
+        let forged = Registration {
+            public: KeyPair::<ProjectivePoint>::generate(&mut OsRng).public(),
+            proof: registration.proof,
+        };
+        assert!(!verify_registration(&forged, &ctx).unwrap());
+    }
+}
@@ -0,0 +1,240 @@
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "prove")]
+use elliptic_curve::{Field, PrimeField};
+use k256::ProjectivePoint;
+#[cfg(feature = "prove")]
+use rand_core::CryptoRngCore;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_with::{DeserializeAs, SerializeAs};
+
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+#[cfg(feature = "prove")]
+use crate::secret::SecretScalar;
+use crate::serialization::PointHex;
+
+/// Public key y = x * G, a point other than the identity
+///
+/// Keeps the group backend out of signatures that only pass keys along. The proving and
+/// verification functions take `impl Into<G>` for y, so a `PublicKey` and a bare point both
+/// work. Serialized as the hex of `DLogGroup::encode_point` (compressed SEC1 on secp256k1),
+/// which `Display` prints as well.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey<G: DLogGroup = ProjectivePoint>(G);
+
+impl<G: DLogGroup> PublicKey<G> {
+    /// Wraps a point
+    ///
+    /// # Returns
+    /// * `Ok(PublicKey)` - The public key
+    /// * `Err(DLogProofError)` - `IdentityPoint` for the identity, which has no secret key
+    pub fn new(point: G) -> Result<Self, DLogProofError> {
+        if bool::from(point.is_identity()) {
+            return Err(DLogProofError::IdentityPoint);
+        }
+        Ok(PublicKey(point))
+    }
+
+    /// Decodes a public key from the encoding of `to_bytes`
+    ///
+    /// # Returns
+    /// * `Ok(PublicKey)` - The public key
+    /// * `Err(DLogProofError)` - `InvalidPoint` for bytes that aren't a point, or
+    ///   `IdentityPoint`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DLogProofError> {
+        Self::new(G::decode_point(bytes).ok_or(DLogProofError::InvalidPoint)?)
+    }
+
+    /// The encoded point (compressed SEC1 on secp256k1)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.encode_point()
+    }
+
+    pub fn point(&self) -> G {
+        self.0
+    }
+}
+
+impl TryFrom<ProjectivePoint> for PublicKey {
+    type Error = DLogProofError;
+
+    fn try_from(point: ProjectivePoint) -> Result<Self, Self::Error> {
+        Self::new(point)
+    }
+}
+
+impl From<PublicKey> for ProjectivePoint {
+    fn from(key: PublicKey) -> Self {
+        key.0
+    }
+}
+
+impl From<&PublicKey> for ProjectivePoint {
+    fn from(key: &PublicKey) -> Self {
+        key.0
+    }
+}
+
+impl<G: DLogGroup> fmt::Display for PublicKey<G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode(self.to_bytes()))
+    }
+}
+
+impl<G: DLogGroup> fmt::Debug for PublicKey<G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PublicKey({self})")
+    }
+}
+
+impl<G: DLogGroup> Serialize for PublicKey<G> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PointHex::serialize_as(&self.0, serializer)
+    }
+}
+
+impl<'de, G: DLogGroup> Deserialize<'de> for PublicKey<G> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let point: G = PointHex::deserialize_as(deserializer)?;
+        Self::new(point).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Secret key x, a nonzero scalar that is wiped from memory when dropped
+///
+/// Passes to the proving functions like a `SecretScalar`: `DLogProof::prove(sid, pid, &key,
+/// key.public_key(), G)`. `Debug` never prints the value, and there is deliberately no serde
+/// support; `from_bytes` and `expose_secret` are the way in and out. Part of the prover
+/// (`prove` feature).
+#[cfg(feature = "prove")]
+#[derive(Clone)]
+pub struct SecretKey<G: DLogGroup = ProjectivePoint>(SecretScalar<G::Scalar>);
+
+#[cfg(feature = "prove")]
+impl<G: DLogGroup> SecretKey<G> {
+    /// Takes ownership of `x`
+    ///
+    /// # Returns
+    /// * `Ok(SecretKey)` - The secret key
+    /// * `Err(DLogProofError)` - `InvalidScalar` if `x` is zero
+    pub fn new(x: impl Into<SecretScalar<G::Scalar>>) -> Result<Self, DLogProofError> {
+        let x = x.into();
+        if bool::from(x.expose_secret().is_zero()) {
+            return Err(DLogProofError::InvalidScalar);
+        }
+        Ok(SecretKey(x))
+    }
+
+    /// Generates a uniformly random nonzero secret key
+    pub fn generate(rng: &mut impl CryptoRngCore) -> Self {
+        loop {
+            if let Ok(key) = Self::new(G::Scalar::random(&mut *rng)) {
+                return key;
+            }
+        }
+    }
+
+    /// Decodes a secret key from its canonical scalar encoding (32 bytes big-endian for
+    /// secp256k1)
+    ///
+    /// # Returns
+    /// * `Ok(SecretKey)` - The secret key
+    /// * `Err(DLogProofError)` - `InvalidScalar` for a wrong length, a value not below the
+    ///   group order, or zero
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DLogProofError> {
+        let mut repr = <G::Scalar as PrimeField>::Repr::default();
+        if repr.as_ref().len() != bytes.len() {
+            return Err(DLogProofError::InvalidScalar);
+        }
+        repr.as_mut().copy_from_slice(bytes);
+        let x = Option::from(G::Scalar::from_repr(repr)).ok_or(DLogProofError::InvalidScalar);
+        repr.as_mut().fill(0);
+        Self::new(SecretScalar::new(x?))
+    }
+
+    /// The public key x * G for the group's standard generator G
+    pub fn public_key(&self) -> PublicKey<G> {
+        PublicKey(G::generator() * self.0.expose_secret())
+    }
+
+    /// The secret value, for computations that need it directly
+    pub fn expose_secret(&self) -> &G::Scalar {
+        self.0.expose_secret()
+    }
+}
+
+#[cfg(feature = "prove")]
+impl<G: DLogGroup> From<SecretKey<G>> for SecretScalar<G::Scalar> {
+    fn from(key: SecretKey<G>) -> Self {
+        key.0
+    }
+}
+
+#[cfg(feature = "prove")]
+impl<G: DLogGroup> From<&SecretKey<G>> for SecretScalar<G::Scalar> {
+    fn from(key: &SecretKey<G>) -> Self {
+        SecretScalar::from(&key.0)
+    }
+}
+
+#[cfg(feature = "prove")]
+impl<G: DLogGroup> fmt::Debug for SecretKey<G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKey(<redacted>)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlog_proof::DLogProof;
+    use crate::prover::Prover;
+    use k256::Scalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn keys_prove_validate_and_serialize() {
+        let g = ProjectivePoint::GENERATOR;
+        let secret = SecretKey::<ProjectivePoint>::generate(&mut OsRng);
+        let public = secret.public_key();
+        assert_eq!(public.point(), g * secret.expose_secret());
+
+        let proof = DLogProof::prove("sid", 1, &secret, public, g).unwrap();
+        assert!(proof.verify("sid", 1, public, g).unwrap());
+        assert!(proof.verify("sid", 1, public.point(), g).unwrap());
+        let proof = Prover::new()
+            .prove("sid", 1, secret.clone(), &public, g)
+            .unwrap();
+        assert!(proof.verify("sid", 1, &public, g).unwrap());
+        assert!(proof
+            .verify_detailed("sid", 1, public, g)
+            .unwrap()
+            .is_valid());
+        let proof = DLogProof::prove_with_context("sid", 1, b"ctx", &secret, public, g).unwrap();
+        assert!(proof
+            .verify_with_context("sid", 1, b"ctx", &public, g)
+            .unwrap());
+
+        let json = serde_json::to_string(&public).unwrap();
+        assert_eq!(json, format!("\"{public}\""));
+        assert_eq!(serde_json::from_str::<PublicKey>(&json).unwrap(), public);
+        assert_eq!(PublicKey::from_bytes(&public.to_bytes()).unwrap(), public);
+        let identity = format!("\"{}\"", hex::encode([0u8]));
+        assert!(serde_json::from_str::<PublicKey>(&identity).is_err());
+        assert_eq!(
+            PublicKey::try_from(ProjectivePoint::IDENTITY),
+            Err(DLogProofError::IdentityPoint)
+        );
+
+        let bytes = secret.expose_secret().to_repr();
+        let restored = SecretKey::<ProjectivePoint>::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.public_key(), public);
+        assert_eq!(
+            SecretKey::<ProjectivePoint>::new(Scalar::ZERO).unwrap_err(),
+            DLogProofError::InvalidScalar
+        );
+        assert!(SecretKey::<ProjectivePoint>::from_bytes(&bytes[1..]).is_err());
+        assert_eq!(format!("{secret:?}"), "SecretKey(<redacted>)");
+    }
+}
@@ -0,0 +1,231 @@
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use elliptic_curve::PrimeField;
+use k256::ProjectivePoint;
+use rand_core::CryptoRngCore;
+use serde::{Deserialize, Serialize};
+use serde_with::hex::Hex;
+use serde_with::serde_as;
+use zeroize::Zeroizing;
+
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::keypair::KeyPair;
+use crate::serialization::PointHex;
+
+/// Current version of the keystore format, the only one `Keystore::decrypt` accepts
+pub const KEYSTORE_VERSION: u32 = 1;
+
+/// Cost of the scrypt key derivation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScryptParams {
+    /// Base-2 logarithm of the CPU/memory cost N
+    pub log_n: u8,
+    /// Block size r
+    pub r: u32,
+    /// Parallelism p
+    pub p: u32,
+}
+
+/// N = 2^17, r = 8, p = 1: 128 MiB of memory and a fraction of a second per unlock
+impl Default for ScryptParams {
+    fn default() -> Self {
+        ScryptParams {
+            log_n: 17,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+/// A secret key encrypted under a passphrase (`keystore` feature)
+///
+/// The passphrase is stretched with scrypt into a key for XChaCha20-Poly1305, which encrypts
+/// the canonical secret scalar with the public key as associated data. Serialized, a keystore
+/// is a JSON object like
+///
+/// ```text
+/// {"version": 1, "public": "<hex>",
+///  "kdf": {"function": "scrypt", "log_n": 17, "r": 8, "p": 1, "salt": "<hex>"},
+///  "cipher": "xchacha20-poly1305", "nonce": "<hex>", "ciphertext": "<hex>"}
+/// ```
+///
+/// so the public key can be read without the passphrase.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Keystore<G: DLogGroup = ProjectivePoint> {
+    version: u32,
+    #[serde_as(as = "PointHex")]
+    public: G,
+    kdf: Kdf,
+    cipher: Cipher,
+    #[serde_as(as = "Hex")]
+    nonce: Vec<u8>,
+    #[serde_as(as = "Hex")]
+    ciphertext: Vec<u8>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "function", rename_all = "lowercase")]
+enum Kdf {
+    Scrypt {
+        log_n: u8,
+        r: u32,
+        p: u32,
+        #[serde_as(as = "Hex")]
+        salt: Vec<u8>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+enum Cipher {
+    #[serde(rename = "xchacha20-poly1305")]
+    XChaCha20Poly1305,
+}
+
+impl<G: DLogGroup> Keystore<G> {
+    /// Encrypts a key pair under `passphrase` with a fresh salt and nonce
+    ///
+    /// # Returns
+    /// * `Ok(Keystore)` - The encrypted key pair
+    /// * `Err(DLogProofError)` - `Serialization` if the scrypt parameters are out of range
+    pub fn encrypt(
+        key_pair: &KeyPair<G>,
+        passphrase: &[u8],
+        params: ScryptParams,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, DLogProofError> {
+        let mut salt = vec![0u8; 32];
+        rng.fill_bytes(&mut salt);
+        let mut nonce = [0u8; 24];
+        rng.fill_bytes(&mut nonce);
+
+        let kdf = Kdf::Scrypt {
+            log_n: params.log_n,
+            r: params.r,
+            p: params.p,
+            salt,
+        };
+        let public = key_pair.public();
+        let mut repr = key_pair.secret().expose_secret().to_repr();
+        let secret = Zeroizing::new(repr.as_ref().to_vec());
+        repr.as_mut().fill(0);
+        let ciphertext = kdf
+            .cipher(passphrase)?
+            .encrypt(
+                &XNonce::from(nonce),
+                Payload {
+                    msg: &secret,
+                    aad: &public.encode_point(),
+                },
+            )
+            .map_err(|_| DLogProofError::Serialization("Keystore encryption failed".into()))?;
+        Ok(Keystore {
+            version: KEYSTORE_VERSION,
+            public,
+            kdf,
+            cipher: Cipher::XChaCha20Poly1305,
+            nonce: nonce.to_vec(),
+            ciphertext,
+        })
+    }
+
+    /// The public key, readable without the passphrase
+    pub fn public(&self) -> G {
+        self.public
+    }
+
+    /// Decrypts the key pair
+    ///
+    /// The decrypted secret is only held in memory that is wiped after use.
+    ///
+    /// # Returns
+    /// * `Ok(KeyPair)` - The key pair
+    /// * `Err(DLogProofError)` - `Serialization` for a wrong passphrase, a tampered or
+    ///   unsupported keystore, or a secret that doesn't match the public key
+    pub fn decrypt(&self, passphrase: &[u8]) -> Result<KeyPair<G>, DLogProofError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(DLogProofError::Serialization(format!(
+                "Unsupported keystore version {}",
+                self.version
+            )));
+        }
+        let nonce: [u8; 24] =
+            self.nonce.as_slice().try_into().map_err(|_| {
+                DLogProofError::Serialization("Invalid keystore nonce length".into())
+            })?;
+        let wrong_passphrase =
+            || DLogProofError::Serialization("Wrong passphrase or corrupted keystore".into());
+        let secret = Zeroizing::new(
+            self.kdf
+                .cipher(passphrase)?
+                .decrypt(
+                    &XNonce::from(nonce),
+                    Payload {
+                        msg: &self.ciphertext,
+                        aad: &self.public.encode_point(),
+                    },
+                )
+                .map_err(|_| wrong_passphrase())?,
+        );
+        let key_pair = KeyPair::from_secret_bytes(&secret).map_err(|_| wrong_passphrase())?;
+        if key_pair.public() != self.public {
+            return Err(wrong_passphrase());
+        }
+        Ok(key_pair)
+    }
+}
+
+impl Kdf {
+    /// The cipher keyed with the derived key
+    fn cipher(&self, passphrase: &[u8]) -> Result<XChaCha20Poly1305, DLogProofError> {
+        let Kdf::Scrypt { log_n, r, p, salt } = self;
+        let invalid = || DLogProofError::Serialization("Invalid scrypt parameters".into());
+        let params = scrypt::Params::new(*log_n, *r, *p, 32).map_err(|_| invalid())?;
+        let mut key = Zeroizing::new([0u8; 32]);
+        scrypt::scrypt(passphrase, salt, &params, key.as_mut()).map_err(|_| invalid())?;
+        Ok(XChaCha20Poly1305::new(key.as_ref().into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+
+    /// Cheap parameters, so the tests don't spend a second per derivation
+    const TEST_PARAMS: ScryptParams = ScryptParams {
+        log_n: 10,
+        r: 8,
+        p: 1,
+    };
+
+    #[test]
+    fn keystores_round_trip_and_reject_wrong_passphrases() {
+        let key_pair = KeyPair::<ProjectivePoint>::generate(&mut OsRng);
+        let keystore =
+            Keystore::encrypt(&key_pair, b"correct horse", TEST_PARAMS, &mut OsRng).unwrap();
+
+        let json = serde_json::to_string(&keystore).unwrap();
+        assert!(!json.contains(&hex::encode(key_pair.secret().expose_secret().to_repr())));
+        let keystore: Keystore = serde_json::from_str(&json).unwrap();
+        assert_eq!(keystore.public(), key_pair.public());
+
+        let unlocked = keystore.decrypt(b"correct horse").unwrap();
+        assert_eq!(
+            unlocked.secret().expose_secret(),
+            key_pair.secret().expose_secret()
+        );
+        assert!(keystore.decrypt(b"wrong horse").is_err());
+
+        // The public key is authenticated, so it can't be swapped
+        let mut swapped = keystore.clone();
+        swapped.public = ProjectivePoint::GENERATOR;
+        assert!(swapped.decrypt(b"correct horse").is_err());
+
+        let unknown_kdf = json.replace("\"scrypt\"", "\"bcrypt\"");
+        assert!(serde_json::from_str::<Keystore>(&unknown_kdf).is_err());
+    }
+}
@@ -0,0 +1,298 @@
+//! Non-interactive Schnorr zero-knowledge proofs of knowledge of a discrete logarithm
+//!
+//! A `DLogProof` shows that the prover knows x with y = x * G on secp256k1, without revealing
+//! x. The proof is made non-interactive with the Fiat-Shamir transform, binding the challenge
+//! to a session ID and participant ID:
+//!
+//! ```
+//! use elliptic_curve::Field;
+//! use schnorr_dlog_zkp::k256::{ProjectivePoint, Scalar};
+//! use schnorr_dlog_zkp::DLogProof;
+//!
+//! let g = ProjectivePoint::GENERATOR;
+//! let x = Scalar::random(&mut rand_core::OsRng);
+//! let y = g * x;
+//!
+//! let proof = DLogProof::prove("sid", 1, &x, y, g).unwrap();
+//! assert!(proof.verify("sid", 1, y, g).unwrap());
+//! ```
+//!
+//! `DLogProof` is generic over the group through the `DLogGroup` trait and defaults to
+//! secp256k1, the only group most of the crate's encodings are defined for.
+//!
+//! `Prover` and `Verifier` configure the transcript (application domain, HMAC key, additional
+//! authenticated data) and prover hardening; `ProofBuilder` is the type-checked way to set up
+//! a single proof. `serialization` holds the serde formats used for points and scalars,
+//! gathered with `with`-style helpers for other crates' types in `serde_utils`, and the
+//! remaining modules provide encodings and related sigma protocols.
+//!
+//! Without the default `std` feature the crate is `no_std` with `alloc`, and the prover and
+//! the verifier are separate features. `prove` adds `Prover`, `SecretScalar` and `SecretKey`,
+//! the sigma protocol framework and proving with an explicit RNG
+//! (`DLogProof::prove_with_rng`, `Prover::prove_with_rng`); `verify` adds `DLogProof::verify`,
+//! `Verifier`, `VerifierContext` and the verification reports. `json` adds
+//! `DLogProof::from_json`. The proof types, their binary encoding and the serde formats are
+//! always there.
+//!
+//! An embedded verifier builds with `default-features = false, features = ["verify-only"]`,
+//! which enables `verify` alone: `DLogProof::verify`, `from_bytes` and the challenge hashing,
+//! with no prover code, no RNG-taking API, no rand_core or rfc6979 dependency, no serde_json
+//! and no OsRng. rand_core is still compiled as a dependency of `elliptic-curve`, for its
+//! traits only. The same build runs in zkVM guests (RISC Zero, SP1) and Solana programs,
+//! which can hash the challenges with their accelerated SHA-256 through `Sha256Hook`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// Without either side nothing derives challenges, and only the encodings are used
+#![cfg_attr(
+    not(any(feature = "prove", feature = "verify")),
+    allow(dead_code, unused_imports)
+)]
+#![cfg_attr(
+    not(test),
+    deny(clippy::unwrap_used, clippy::expect_used, clippy::panic)
+)]
+
+extern crate alloc;
+
+#[cfg(feature = "mobile")]
+uniffi::setup_scaffolding!();
+
+#[cfg(feature = "std")]
+pub mod adaptor;
+#[cfg(feature = "anchor")]
+pub mod anchor;
+#[cfg(feature = "std")]
+pub mod and_proof;
+#[cfg(feature = "mmap")]
+pub mod archive;
+#[cfg(feature = "std")]
+pub mod armor;
+#[cfg(feature = "der")]
+pub mod asn1;
+#[cfg(feature = "async")]
+pub mod async_verify;
+#[cfg(feature = "std")]
+pub mod audit;
+#[cfg(feature = "std")]
+pub mod base58check;
+#[cfg(feature = "std")]
+pub mod batch;
+#[cfg(feature = "std")]
+pub mod bech32m;
+#[cfg(feature = "std")]
+pub mod blind;
+#[cfg(feature = "std")]
+pub mod bloom;
+#[cfg(feature = "backend-bls12-381")]
+pub mod bls12_381_g1;
+#[cfg(feature = "borsh")]
+pub mod borsh_codec;
+#[cfg(feature = "std")]
+pub mod bound_proof;
+#[cfg(feature = "std")]
+pub mod builder;
+#[cfg(feature = "std")]
+pub mod bundle;
+#[cfg(feature = "std")]
+pub mod cache;
+#[cfg(feature = "std")]
+pub mod canonical_json;
+#[cfg(feature = "std")]
+pub mod compact;
+#[cfg(feature = "std")]
+pub mod compat;
+#[cfg(feature = "std")]
+pub mod context;
+#[cfg(feature = "std")]
+pub mod data_integrity;
+#[cfg(feature = "std")]
+pub mod decimal;
+#[cfg(feature = "prove")]
+pub mod deterministic;
+#[cfg(feature = "std")]
+pub mod dleq;
+pub mod dlog_proof;
+#[cfg(feature = "backend-ed25519")]
+pub mod ed25519;
+#[cfg(feature = "std")]
+pub mod elgamal;
+#[cfg(feature = "std")]
+pub mod envelope;
+#[cfg(feature = "std")]
+pub mod equality_proof;
+pub mod error;
+#[cfg(feature = "evm")]
+pub mod evm;
+#[cfg(feature = "std")]
+pub mod external;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod frost;
+#[cfg(feature = "std")]
+pub mod generators;
+pub mod group;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "std")]
+pub mod homomorphic;
+#[cfg(feature = "std")]
+pub mod interactive;
+#[cfg(feature = "std")]
+pub mod inverse_proof;
+#[cfg(feature = "std")]
+pub mod keypair;
+pub mod keys;
+#[cfg(feature = "keystore")]
+pub mod keystore;
+#[cfg(feature = "std")]
+pub mod linear_relation;
+#[cfg(feature = "std")]
+pub mod membership;
+#[cfg(feature = "std")]
+pub mod merkle;
+#[cfg(feature = "mnemonic")]
+pub mod mnemonic;
+#[cfg(feature = "mobile")]
+pub mod mobile;
+#[cfg(feature = "msgpack")]
+pub mod msgpack;
+#[cfg(feature = "std")]
+pub mod multiformats;
+#[cfg(feature = "std")]
+pub mod musig;
+#[cfg(feature = "p256")]
+pub mod nist_p256;
+#[cfg(feature = "node")]
+pub mod node;
+#[cfg(feature = "std")]
+pub mod or_proof;
+pub mod participant;
+pub mod precomputed;
+#[cfg(feature = "std")]
+pub mod product_proof;
+#[cfg(feature = "std")]
+pub mod proof_file;
+#[cfg(feature = "prove")]
+pub mod prover;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "std")]
+pub mod range_proof;
+#[cfg(feature = "remote")]
+pub mod remote;
+#[cfg(feature = "verify")]
+pub mod report;
+#[cfg(feature = "std")]
+pub mod representation;
+#[cfg(feature = "backend-ristretto")]
+pub mod ristretto;
+#[cfg(feature = "scale")]
+pub mod scale;
+#[cfg(feature = "prove")]
+pub mod secret;
+pub mod serde_utils;
+pub mod serialization;
+pub mod session;
+#[cfg(feature = "std")]
+pub mod shuffle;
+#[cfg(feature = "prove")]
+pub mod sigma;
+#[cfg(feature = "std")]
+pub mod signature;
+#[cfg(feature = "solana")]
+pub mod solana;
+#[cfg(feature = "ssz")]
+pub mod ssz_codec;
+pub mod stats;
+#[cfg(feature = "std")]
+pub mod streaming;
+#[cfg(feature = "std")]
+pub mod strict;
+#[cfg(feature = "test-vectors")]
+pub mod test_vectors;
+#[cfg(feature = "merlin")]
+pub mod transcript;
+#[cfg(feature = "transport")]
+pub mod transport;
+#[cfg(feature = "std")]
+pub mod truncated;
+pub mod validity;
+#[cfg(feature = "verify")]
+pub mod verifier;
+#[cfg(feature = "std")]
+pub mod vrf;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "std")]
+pub use and_proof::AndProof;
+#[cfg(feature = "std")]
+pub use audit::TranscriptLog;
+#[cfg(feature = "std")]
+pub use batch::MsmBackend;
+#[cfg(feature = "std")]
+pub use bound_proof::BoundProof;
+#[cfg(feature = "std")]
+pub use builder::ProofBuilder;
+#[cfg(feature = "std")]
+pub use bundle::ProofBundle;
+#[cfg(feature = "std")]
+pub use cache::{CacheStats, VerifierCache};
+#[cfg(feature = "std")]
+pub use compact::CompactDLogProof;
+#[cfg(feature = "std")]
+pub use compat::CompatProfile;
+#[cfg(feature = "std")]
+pub use dleq::DleqProof;
+pub use dlog_proof::{
+    ChallengeHash, ChallengeMode, DLogProof, ProofId, Sha256Hook, TranscriptVersion,
+    UnknownFieldPolicy,
+};
+#[cfg(feature = "std")]
+pub use envelope::ProofEnvelope;
+pub use error::DLogProofError;
+#[cfg(feature = "std")]
+pub use external::DlogProver;
+pub use group::DLogGroup;
+#[cfg(feature = "std")]
+pub use keypair::{KeyPair, Registration, RegistrationContext};
+pub use keys::PublicKey;
+#[cfg(feature = "prove")]
+pub use keys::SecretKey;
+#[cfg(feature = "keystore")]
+pub use keystore::Keystore;
+#[cfg(feature = "std")]
+pub use linear_relation::{LinearRelation, LinearRelationProof};
+#[cfg(feature = "std")]
+pub use membership::MembershipProof;
+#[cfg(feature = "std")]
+pub use merkle::InclusionProof;
+#[cfg(feature = "std")]
+pub use or_proof::OrProof;
+pub use participant::ParticipantId;
+#[cfg(feature = "verify")]
+pub use precomputed::VerifierContext;
+#[cfg(feature = "prove")]
+pub use precomputed::{ProveItem, ProverContext};
+#[cfg(feature = "std")]
+pub use proof_file::ProofFile;
+#[cfg(feature = "prove")]
+pub use prover::Prover;
+#[cfg(feature = "std")]
+pub use range_proof::RangeProof;
+#[cfg(feature = "verify")]
+pub use report::{VerificationFailure, VerificationReport};
+#[cfg(feature = "std")]
+pub use representation::RepresentationProof;
+#[cfg(feature = "prove")]
+pub use secret::SecretScalar;
+pub use session::SessionId;
+#[cfg(feature = "std")]
+pub use shuffle::ShuffleProof;
+pub use validity::{Clock, TimedProof};
+#[cfg(feature = "verify")]
+pub use verifier::{Timing, Verifier};
+
+/// The curve implementation used by the public API, re-exported so dependents use the same version
+pub use k256;
@@ -0,0 +1,288 @@
+use elliptic_curve::subtle::{Choice, ConstantTimeEq};
+use elliptic_curve::{Field, PrimeField};
+use k256::ProjectivePoint;
+use rand_core::{CryptoRngCore, OsRng};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use zeroize::Zeroizing;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::serialization::{PointHex, ScalarHex};
+use crate::session::SessionId;
+
+const DOMAIN: &[u8] = b"SCHNORR_LINEAR_RELATION_PROOF";
+
+/// Public linear relation a_1 * x_1 + ... + a_n * x_n = b between secret scalars
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinearRelation<F> {
+    /// The coefficients a_i, one per secret
+    pub coefficients: Vec<F>,
+    /// The right-hand side b
+    pub b: F,
+}
+
+impl<F: PrimeField> LinearRelation<F> {
+    pub fn new(coefficients: Vec<F>, b: F) -> Self {
+        LinearRelation { coefficients, b }
+    }
+
+    /// The relation x_1 + ... + x_n = x_agg of `shares` key shares and their aggregate, with
+    /// the aggregate as the last secret
+    pub fn sum(shares: usize) -> Self {
+        let mut coefficients = vec![F::ONE; shares];
+        coefficients.push(-F::ONE);
+        LinearRelation {
+            coefficients,
+            b: F::ZERO,
+        }
+    }
+
+    /// Evaluates Σ a_i * xs[i]
+    fn evaluate(&self, xs: &[F]) -> F {
+        self.coefficients
+            .iter()
+            .zip(xs)
+            .fold(F::ZERO, |sum, (a, x)| sum + *a * x)
+    }
+
+    /// Domain separation tag binding the coefficients and b into the challenge
+    fn domain(&self) -> Vec<u8> {
+        let mut domain = DOMAIN.to_vec();
+        domain.extend_from_slice(&(self.coefficients.len() as u32).to_be_bytes());
+        for a in self.coefficients.iter().chain([&self.b]) {
+            domain.extend_from_slice(a.to_repr().as_ref());
+        }
+        domain
+    }
+}
+
+/// Proof of knowledge of x_1, ..., x_n with y_i = x_i * G satisfying a public
+/// `LinearRelation` Σ a_i * x_i = b
+///
+/// Generalizes `DLogProof` from one discrete logarithm to several tied together by a linear
+/// equation, e.g. that the public key of an aggregate is the sum of the key shares
+/// (`LinearRelation::sum`). The nonces r_i are drawn subject to Σ a_i * r_i = 0, so the
+/// responses satisfy the relation too:
+/// * `t` - Commitments t_i = r_i * G
+/// * `s` - Responses s_i = r_i + c * x_i, checked as s_i * G = t_i + c * y_i and
+///   Σ a_i * s_i = c * b
+///
+/// The challenge covers the coefficients and b along with the points.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct LinearRelationProof<G: DLogGroup = ProjectivePoint> {
+    #[serde_as(as = "Vec<PointHex>")]
+    t: Vec<G>,
+    #[serde_as(as = "Vec<ScalarHex>")]
+    s: Vec<G::Scalar>,
+}
+
+impl<G: DLogGroup> LinearRelationProof<G> {
+    /// The commitments, one per secret
+    pub fn t(&self) -> &[G] {
+        &self.t
+    }
+
+    /// The responses, one per secret
+    pub fn s(&self) -> &[G::Scalar] {
+        &self.s
+    }
+
+    /// Proves knowledge of `xs` with ys[i] = xs[i] * G satisfying `relation`
+    ///
+    /// # Arguments
+    /// * `sid` - Session identifier used for domain separation, a string or bytes (see `SessionId`)
+    /// * `pid` - Participant ID for uniqueness to distinguish different proofs
+    /// * `xs` - The secret scalars
+    /// * `relation` - The relation, with one coefficient per secret
+    /// * `ys` - The public points ys[i] = xs[i] * G
+    /// * `base_point` - The base point G
+    ///
+    /// # Returns
+    /// * `Ok(LinearRelationProof)` - The proof
+    /// * `Err(DLogProofError)` - `StatementCount` if there are no secrets or `xs`, `ys` and
+    ///   the coefficients differ in length, `IdentityPoint` for a degenerate statement, or if
+    ///   the challenge can't be derived
+    pub fn prove<'s>(
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        xs: &[G::Scalar],
+        relation: &LinearRelation<G::Scalar>,
+        ys: &[G],
+        base_point: G,
+    ) -> Result<Self, DLogProofError> {
+        Self::prove_with_rng(&mut OsRng, sid, pid, xs, relation, ys, base_point)
+    }
+
+    /// Proves like `prove`, drawing the nonces from `rng` (see `DLogProof::prove_with_rng`)
+    pub fn prove_with_rng<'s>(
+        rng: &mut impl CryptoRngCore,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        xs: &[G::Scalar],
+        relation: &LinearRelation<G::Scalar>,
+        ys: &[G],
+        base_point: G,
+    ) -> Result<Self, DLogProofError> {
+        if xs.len() != ys.len() {
+            return Err(DLogProofError::StatementCount);
+        }
+        Self::check_statement(relation, ys, base_point)?;
+
+        // Free nonces for all secrets but the last one with a non-zero coefficient, which is
+        // solved for so that Σ a_i * r_i = 0
+        let mut rs: Zeroizing<Vec<G::Scalar>> = Zeroizing::new(
+            (0..xs.len())
+                .map(|_| G::Scalar::random(&mut *rng))
+                .collect(),
+        );
+        let pivot = relation
+            .coefficients
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(i, a)| Option::<G::Scalar>::from(a.invert()).map(|a_inv| (i, a_inv)));
+        if let Some((pivot, a_inv)) = pivot {
+            rs[pivot] = G::Scalar::ZERO;
+            rs[pivot] = -relation.evaluate(&rs) * a_inv;
+        }
+
+        let t: Vec<G> = rs.iter().map(|r| base_point * r).collect();
+        let c = Self::challenge(sid.into(), pid, relation, ys, base_point, &t)?;
+        let s = rs.iter().zip(xs).map(|(r, x)| *r + c * x).collect();
+        Ok(LinearRelationProof { t, s })
+    }
+
+    /// Verifies the proof for `ys` and `relation`
+    ///
+    /// A proof with a different number of commitments or responses than secrets is invalid,
+    /// not an error.
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Validity of the proof
+    /// * `Err(DLogProofError)` - `StatementCount` if `ys` is empty or differs in length from
+    ///   the coefficients, `IdentityPoint` for a degenerate statement, or if the challenge
+    ///   can't be derived
+    pub fn verify<'s>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        relation: &LinearRelation<G::Scalar>,
+        ys: &[G],
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        Self::check_statement(relation, ys, base_point)?;
+        if self.t.len() != ys.len() || self.s.len() != ys.len() {
+            return Ok(false);
+        }
+        let c = Self::challenge(sid.into(), pid, relation, ys, base_point, &self.t)?;
+
+        let mut valid = relation.evaluate(&self.s).ct_eq(&(c * relation.b));
+        for ((y, t), s) in ys.iter().zip(&self.t).zip(&self.s) {
+            valid &= (base_point * s).ct_eq(&(*t + *y * c));
+        }
+        Ok(valid.into())
+    }
+
+    fn check_statement(
+        relation: &LinearRelation<G::Scalar>,
+        ys: &[G],
+        base_point: G,
+    ) -> Result<(), DLogProofError> {
+        if ys.is_empty() || relation.coefficients.len() != ys.len() {
+            return Err(DLogProofError::StatementCount);
+        }
+        for &y in ys {
+            DLogProof::check_statement(y, base_point)?;
+        }
+        Ok(())
+    }
+
+    fn challenge(
+        sid: SessionId<'_>,
+        pid: u32,
+        relation: &LinearRelation<G::Scalar>,
+        ys: &[G],
+        base_point: G,
+        t: &[G],
+    ) -> Result<G::Scalar, DLogProofError> {
+        let mut points = vec![base_point];
+        points.extend_from_slice(ys);
+        points.extend_from_slice(t);
+        DLogProof::hash_points_with_domain(&relation.domain(), sid, pid.into(), &points)
+    }
+}
+
+impl<G: DLogGroup> ConstantTimeEq for LinearRelationProof<G> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.t[..].ct_eq(&other.t[..]) & self.s[..].ct_eq(&other.s[..])
+    }
+}
+
+impl<G: DLogGroup> PartialEq for LinearRelationProof<G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<G: DLogGroup> Eq for LinearRelationProof<G> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::Scalar;
+
+    #[test]
+    fn shares_prove_their_aggregate() {
+        let g = ProjectivePoint::GENERATOR;
+        let mut xs: Vec<Scalar> = (0..3).map(|_| Scalar::random(&mut OsRng)).collect();
+        xs.push(xs.iter().sum());
+        let ys: Vec<ProjectivePoint> = xs.iter().map(|x| g * x).collect();
+        let relation = LinearRelation::sum(3);
+
+        let proof = LinearRelationProof::prove("sid", 1, &xs, &relation, &ys, g).unwrap();
+        assert!(proof.verify("sid", 1, &relation, &ys, g).unwrap());
+        assert!(!proof.verify("sid", 2, &relation, &ys, g).unwrap());
+        let other = LinearRelation::new(vec![Scalar::ONE; 4], Scalar::ZERO);
+        assert!(!proof.verify("sid", 1, &other, &ys, g).unwrap());
+
+        let json = serde_json::to_string(&proof).unwrap();
+        let decoded: LinearRelationProof = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(decoded.verify("sid", 1, &relation, &ys, g).unwrap());
+    }
+
+    #[test]
+    fn false_relations_are_rejected() {
+        let g = ProjectivePoint::GENERATOR;
+        let xs = [Scalar::random(&mut OsRng), Scalar::random(&mut OsRng)];
+        let ys = [g * xs[0], g * xs[1]];
+        let a = [Scalar::from(3u64), Scalar::from(5u64)];
+        let b = a[0] * xs[0] + a[1] * xs[1];
+
+        let relation = LinearRelation::new(a.to_vec(), b);
+        let proof = LinearRelationProof::prove("sid", 1, &xs, &relation, &ys, g).unwrap();
+        assert!(proof.verify("sid", 1, &relation, &ys, g).unwrap());
+
+        let shifted = LinearRelation::new(a.to_vec(), b + Scalar::ONE);
+        let proof = LinearRelationProof::prove("sid", 1, &xs, &shifted, &ys, g).unwrap();
+        assert!(!proof.verify("sid", 1, &shifted, &ys, g).unwrap());
+
+        // With only zero coefficients the relation holds exactly for b = 0
+        let zero = LinearRelation::new(vec![Scalar::ZERO; 2], Scalar::ZERO);
+        let proof = LinearRelationProof::prove("sid", 1, &xs, &zero, &ys, g).unwrap();
+        assert!(proof.verify("sid", 1, &zero, &ys, g).unwrap());
+
+        assert_eq!(
+            LinearRelationProof::prove("sid", 1, &xs[..1], &relation, &ys, g),
+            Err(DLogProofError::StatementCount)
+        );
+        assert_eq!(
+            proof.verify("sid", 1, &relation, &ys[..1], g),
+            Err(DLogProofError::StatementCount)
+        );
+    }
+}
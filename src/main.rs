@@ -1,151 +1,1010 @@
-mod dlog_proof;
-mod serialization;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use crate::dlog_proof::DLogProof;
-use elliptic_curve::sec1::ToEncodedPoint;
-use elliptic_curve::Field;
-use k256::{ProjectivePoint, Scalar};
+use clap::{Parser, Subcommand, ValueEnum};
+use elliptic_curve::PrimeField;
 use rand_core::OsRng;
-use serde::Serialize;
-use std::time::Instant;
+use schnorr_dlog_zkp::canonical_json::to_canonical_json;
+use schnorr_dlog_zkp::k256::ProjectivePoint;
+use schnorr_dlog_zkp::keystore::ScryptParams;
+use schnorr_dlog_zkp::mnemonic::DEFAULT_DERIVATION_PATH;
+use schnorr_dlog_zkp::signature::SigningKey;
+use schnorr_dlog_zkp::{
+    DLogGroup, DLogProof, DLogProofError, KeyPair, Keystore, ProofBundle, Prover, ProverContext,
+    UnknownFieldPolicy, Verifier, VerifierContext,
+};
+use serde::{Deserialize, Serialize};
+use signature::Signer;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::EnvFilter;
+use zeroize::{Zeroize, Zeroizing};
 
-/// Discrete Logarithm Zero-Knowledge Proof System
+/// Schnorr zero-knowledge proofs of knowledge of a secp256k1 secret key
 ///
-/// It implements a non-interactive Schnorr zero-knowledge proof system
-/// for discrete logarithms on the secp256k1 curve
+/// Every file argument accepts `-` for standard input or output, and outputs go to standard
+/// output when no file is given. Only keys and proofs are written to standard output;
+/// verdicts and errors go to standard error, diagnostics as `tracing` logs filtered by
+/// `RUST_LOG` (`info` by default; `RUST_LOG=schnorr_dlog_zkp=debug` adds the spans of proving,
+/// verification and decoding with their timings). The exit status is 0 on success (a valid proof
+/// for `verify`), 1 for an invalid proof and 2 for malformed input or other errors.
 ///
-/// The system proves knowledge of a secret value x (the discrete logarithm)
-/// satisfying y = x * G, where:
-/// - g is the generator point (base point)
-/// - y is the public point
-/// - x is the secret scalar
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Base point of secp256k1 curve
-    let base_point = ProjectivePoint::GENERATOR;
-
-    // Session identifier for domain separation in the proof (any string value)
-    let sid = "sid";
-
-    // Participant ID, make proofs distinct when using the same session ID
-    // Just an additional input to the hash function
-    let pid = 1;
-
-    // Generate a random secret scalar (private key) using OS random number generator
-    let x = Scalar::random(&mut OsRng);
-    println!("Random secret: 0X{}", hex::encode_upper(x.to_bytes()));
-
-    // Calculate the public point y = x * G where G is the base point
-    let y = base_point * x;
-
-    let proof_start_time = Instant::now();
-
-    // Generate the zero-knowledge proof that we know x such that y = x * G
-    let proof = DLogProof::prove(sid, pid, &x, y, base_point)
-        .map_err(|e| format!("Proof generation failed: {:?}", e))?;
-    println!(
-        "Proof computation time: {} ms",
-        proof_start_time.elapsed().as_millis()
-    );
+/// With `--audit-log`, every `keygen`, `prove`, `verify` and `verify-batch` appends a JSON line
+/// with its time, parameters, public key, proof ID and outcome to the file; secrets and
+/// passphrases are never logged. A failure to write it fails the command.
+#[derive(Parser)]
+#[command(name = "dlog", version)]
+struct Cli {
+    /// Encoding of the key and proof files
+    #[arg(long, value_enum, global = true, default_value_t = Format::Json)]
+    format: Format,
+
+    /// Format of the logs on standard error
+    #[arg(long, value_enum, global = true, default_value_t = LogFormat::Human)]
+    log_format: LogFormat,
+
+    /// Application domain separation tag bound into every challenge; proofs made with a tag
+    /// only verify with the same tag
+    #[arg(long, global = true)]
+    domain: Option<String>,
+
+    /// Append-only JSON Lines file recording every operation
+    #[arg(long, global = true, env = "DLOG_AUDIT_LOG")]
+    audit_log: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// File encodings
+///
+/// Keys are passphrase-encrypted `Keystore` JSON, or the plaintext hex secret or its 32 raw
+/// bytes (`prove` still reads the older plaintext `{"secret": "<hex>", "public": "<hex>"}`);
+/// proofs are the JSON `{"t": "<hex>", "s": "<hex>"}`, or the 65-byte encoding of
+/// `DLogProof::to_bytes` as hex or raw bytes.
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Json,
+    Hex,
+    Binary,
+}
+
+/// Log formats
+#[derive(Clone, Copy, ValueEnum)]
+enum LogFormat {
+    /// One line per event, with the fields of its spans
+    Human,
+    /// One JSON object per event
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generates a key pair, or derives it from a BIP-39 mnemonic, printing its public key as
+    /// hex when the key goes to a file
+    Keygen {
+        /// Key file to write, readable only by the owner
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Also write the hex public key to this file
+        #[arg(long)]
+        public_out: Option<PathBuf>,
+        /// Passphrase encrypting the JSON keystore
+        #[arg(long, env = "DLOG_PASSPHRASE", hide_env_values = true)]
+        passphrase: Option<String>,
+        /// File holding a BIP-39 mnemonic to derive the key from, `-` for standard input
+        #[arg(long)]
+        mnemonic: Option<PathBuf>,
+        /// BIP-32 derivation path of the key
+        #[arg(long, requires = "mnemonic", default_value = DEFAULT_DERIVATION_PATH)]
+        derivation_path: String,
+        /// BIP-39 passphrase of the mnemonic
+        #[arg(
+            long,
+            requires = "mnemonic",
+            env = "DLOG_MNEMONIC_PASSPHRASE",
+            hide_env_values = true
+        )]
+        mnemonic_passphrase: Option<String>,
+    },
+    /// Proves knowledge of the secret key of a key file
+    Prove {
+        /// Key file written by `keygen`, `-` for standard input
+        #[arg(long)]
+        key: PathBuf,
+        /// Session identifier
+        #[arg(long)]
+        sid: String,
+        /// Participant ID
+        #[arg(long)]
+        pid: u32,
+        /// Proof file to write, standard output if omitted
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Passphrase unlocking a JSON keystore
+        #[arg(long, env = "DLOG_PASSPHRASE", hide_env_values = true)]
+        passphrase: Option<String>,
+    },
+    /// Verifies newline-delimited JSON records `{"sid", "pid", "public", "proof"}` as one batch,
+    /// reporting each failing line; the status is 2 if a line is malformed, else 1 if a proof
+    /// is invalid
+    VerifyBatch {
+        /// JSON Lines file, `-` for standard input
+        #[arg(long)]
+        input: PathBuf,
+    },
+    /// Measures proving, verification and batch verification, printing a JSON report
+    Bench {
+        /// Timed runs of each operation
+        #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u32).range(1..))]
+        iterations: u32,
+        /// Proofs per batch for batch verification
+        #[arg(long, default_value_t = 64, value_parser = clap::value_parser!(u32).range(1..))]
+        batch_size: u32,
+        /// Untimed runs of each operation before the timed ones
+        #[arg(long, default_value_t = 100)]
+        warmup: u32,
+    },
+    /// Runs a proof-of-possession ceremony: collects one proof per participant of a roster
+    /// from files or HTTP submissions, verifies them under the shared sid and writes a summary
+    /// of who passed, signed by the coordinator; the status is 1 unless every participant
+    /// passed
+    Ceremony {
+        /// JSON roster of the declared keys, `[{"pid": 1, "public": "<hex>"}, ...]`
+        #[arg(long)]
+        roster: PathBuf,
+        /// Session identifier shared by all proofs
+        #[arg(long)]
+        sid: String,
+        /// Submission files `{"pid": 1, "proof": {...}}`, or directories of `.json` ones
+        #[arg(long, num_args = 1..)]
+        submissions: Vec<PathBuf>,
+        /// Also accept submissions POSTed to this address until every participant submitted
+        #[arg(long)]
+        listen: Option<SocketAddr>,
+        /// Seconds to wait for HTTP submissions
+        #[arg(long, default_value_t = 600, requires = "listen")]
+        deadline: u64,
+        /// Key file of the coordinator signing the summary (BIP-340 over its canonical JSON)
+        #[arg(long)]
+        key: PathBuf,
+        /// Passphrase unlocking a JSON keystore
+        #[arg(long, env = "DLOG_PASSPHRASE", hide_env_values = true)]
+        passphrase: Option<String>,
+        /// Summary file to write, standard output if omitted
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Verifies a proof, exiting with status 0 if it is valid and 1 if not
+    Verify {
+        /// Proof file written by `prove`, `-` for standard input
+        #[arg(long)]
+        proof: PathBuf,
+        /// File holding the hex SEC1 public key, `-` for standard input
+        #[arg(long)]
+        public: PathBuf,
+        /// Session identifier
+        #[arg(long)]
+        sid: String,
+        /// Participant ID
+        #[arg(long)]
+        pid: u32,
+    },
+}
+
+/// A line of the `verify-batch` input
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BatchRecord {
+    sid: String,
+    pid: u32,
+    /// Hex SEC1 public key
+    public: String,
+    proof: DLogProof,
+}
+
+/// A participant of a `ceremony` roster
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RosterEntry {
+    pid: u32,
+    /// Hex SEC1 public key
+    public: String,
+}
+
+/// A participant's proof, from a file or an HTTP submission
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Submission {
+    pid: u32,
+    proof: DLogProof,
+}
+
+/// The outcome of a `ceremony`
+#[derive(Serialize)]
+struct CeremonySummary {
+    sid: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    domain: Option<String>,
+    participants: usize,
+    /// Participant IDs with a valid proof
+    passed: Vec<u32>,
+    /// Participant IDs whose proof doesn't verify
+    failed: Vec<u32>,
+    /// Participant IDs without a submission
+    missing: Vec<u32>,
+    /// Hex `ProofBundle::merkle_root` of the submitted proofs, in participant order
+    bundle_root: String,
+    /// Hex x-only BIP-340 key of the coordinator
+    signer: String,
+}
+
+/// A `CeremonySummary` with the coordinator's BIP-340 signature over its canonical JSON
+#[derive(Serialize)]
+struct SignedSummary {
+    #[serde(flatten)]
+    summary: CeremonySummary,
+    signature: String,
+}
+
+/// Largest HTTP submission `ceremony --listen` reads
+const MAX_SUBMISSION_LEN: usize = 64 * 1024;
+
+/// A line of the `--audit-log`, holding only public data
+#[derive(Default, Serialize)]
+struct AuditRecord {
+    /// Seconds since the Unix epoch
+    timestamp: u64,
+    operation: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    domain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pid: Option<u32>,
+    /// Hex SEC1 public key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    public: Option<String>,
+    /// `DLogProof::id` of the proof made or verified
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proof_id: Option<String>,
+    /// Records read by `verify-batch`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proofs: Option<usize>,
+    /// Invalid and malformed records of `verify-batch`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    invalid: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    malformed: Option<usize>,
+    /// `ok`, `valid`, `invalid` or `error`
+    result: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl AuditRecord {
+    /// Appends the record as one line, in a single write so concurrent runs don't interleave
+    fn append(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut line = serde_json::to_vec(self)?;
+        line.push(b'\n');
+        fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)?
+            .write_all(&line)?;
+        Ok(())
+    }
+}
+
+/// The `bench` report
+#[derive(Serialize)]
+struct BenchReport {
+    iterations: u32,
+    batch_size: u32,
+    warmup: u32,
+    prove: Timings,
+    /// Timings of proving with the precomputed base point table of a `ProverContext`
+    prove_precomputed: Timings,
+    verify: Timings,
+    /// Timings of verifying with the precomputed tables of a `VerifierContext`
+    verify_precomputed: Timings,
+    /// Timings of verifying a whole batch
+    batch_verify: Timings,
+}
+
+/// Timings of one operation, in microseconds
+#[derive(Serialize)]
+struct Timings {
+    mean_us: f64,
+    median_us: f64,
+    p99_us: f64,
+    /// Proofs made or verified per second at the mean time
+    proofs_per_second: f64,
+}
+
+impl Timings {
+    fn new(mut samples: Vec<Duration>, proofs_per_run: u32) -> Self {
+        samples.sort_unstable();
+        let micros = |duration: Duration| duration.as_secs_f64() * 1e6;
+        let n = samples.len();
+        let mean_us = samples.iter().copied().map(micros).sum::<f64>() / n as f64;
+        let median_us = (micros(samples[(n - 1) / 2]) + micros(samples[n / 2])) / 2.0;
+        let p99_us = micros(samples[(n * 99).div_ceil(100) - 1]);
+        Timings {
+            mean_us,
+            median_us,
+            p99_us,
+            proofs_per_second: f64::from(proofs_per_run) * 1e6 / mean_us,
+        }
+    }
+}
+
+/// The plaintext JSON key file of earlier versions
+#[derive(Deserialize)]
+struct KeyFile {
+    secret: String,
+    public: String,
+}
+
+impl Drop for KeyFile {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    init_logging(cli.log_format);
+    let audit_log = cli.audit_log.clone();
+    let mut audit = AuditRecord {
+        domain: cli.domain.clone(),
+        result: "ok",
+        ..AuditRecord::default()
+    };
+    let code = match run(cli, &mut audit) {
+        Ok(code) => code,
+        Err(e) => {
+            tracing::error!("{e}");
+            audit.result = "error";
+            audit.error = Some(e.to_string());
+            ExitCode::from(2)
+        }
+    };
+    if let Some(path) = audit_log.filter(|_| !audit.operation.is_empty()) {
+        audit.timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        if let Err(e) = audit.append(&path) {
+            tracing::error!("failed to write the audit log: {e}");
+            return ExitCode::from(2);
+        }
+    }
+    code
+}
+
+/// Logs to standard error, reporting spans when they close so that their timings are known
+fn init_logging(format: LogFormat) {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let logs = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(io::stderr);
+    match format {
+        LogFormat::Human => logs.with_target(false).init(),
+        LogFormat::Json => logs.json().init(),
+    }
+}
+
+/// Runs the command, filling in `audit` as its parameters and results become known
+fn run(cli: Cli, audit: &mut AuditRecord) -> Result<ExitCode, Box<dyn Error>> {
+    match cli.command {
+        Command::Keygen {
+            out,
+            public_out,
+            passphrase,
+            mnemonic,
+            derivation_path,
+            mnemonic_passphrase,
+        } => {
+            audit.operation = "keygen";
+            let passphrase = passphrase.map(Zeroizing::new);
+            let key_pair = match mnemonic {
+                Some(path) => {
+                    let phrase = Zeroizing::new(String::from_utf8(read_input(&path)?)?);
+                    let mnemonic_passphrase =
+                        Zeroizing::new(mnemonic_passphrase.unwrap_or_default());
+                    KeyPair::from_mnemonic(phrase.trim(), &mnemonic_passphrase, &derivation_path)?
+                }
+                None => KeyPair::<ProjectivePoint>::generate(&mut OsRng),
+            };
+            let key = encode_key(&key_pair, cli.format, passphrase.as_deref())?;
+            let public = hex::encode(key_pair.public().encode_point());
+            audit.public = Some(public.clone());
+            if let Some(path) = public_out {
+                write_output(Some(&path), format!("{public}\n").as_bytes())?;
+            }
+            match out.filter(|path| !is_stdio(path)) {
+                Some(path) => {
+                    write_secret_file(&path, &key)?;
+                    println!("{public}");
+                }
+                None => write_output(None, &key)?,
+            }
+        }
+        Command::Prove {
+            key,
+            sid,
+            pid,
+            out,
+            passphrase,
+        } => {
+            audit.operation = "prove";
+            audit.sid = Some(sid.clone());
+            audit.pid = Some(pid);
+            let passphrase = passphrase.map(Zeroizing::new);
+            let key = Zeroizing::new(read_input(&key)?);
+            let key_pair = decode_key(&key, cli.format, passphrase.as_deref())?;
+            audit.public = Some(hex::encode(key_pair.public().encode_point()));
+            let proof = prover(cli.domain.as_deref()).prove(
+                &sid,
+                pid,
+                key_pair.secret(),
+                key_pair.public(),
+                ProjectivePoint::GENERATOR,
+            )?;
+            audit.proof_id = Some(proof.id().to_string());
+            let proof = encode_proof(&proof, cli.format)?;
+            write_output(out.as_deref(), &proof)?;
+        }
+        Command::Verify {
+            proof,
+            public,
+            sid,
+            pid,
+        } => {
+            audit.operation = "verify";
+            audit.sid = Some(sid.clone());
+            audit.pid = Some(pid);
+            if is_stdio(&proof) && is_stdio(&public) {
+                return Err(
+                    "only one of --proof and --public can be read from standard input".into(),
+                );
+            }
+            let proof = decode_proof(&read_input(&proof)?, cli.format)?;
+            audit.proof_id = Some(proof.id().to_string());
+            let public = hex::decode(std::str::from_utf8(&read_input(&public)?)?.trim())?;
+            let y = ProjectivePoint::decode_point(&public).ok_or("invalid public key")?;
+            audit.public = Some(hex::encode(y.encode_point()));
+            let verifier = verifier(cli.domain.as_deref());
+            if !verifier.verify(&proof, &sid, pid, y, ProjectivePoint::GENERATOR)? {
+                eprintln!("invalid");
+                audit.result = "invalid";
+                return Ok(ExitCode::FAILURE);
+            }
+            eprintln!("valid");
+            audit.result = "valid";
+        }
+        Command::VerifyBatch { input } => {
+            audit.operation = "verify-batch";
+            let verifier = verifier(cli.domain.as_deref());
+            return verify_batch(&verifier, &read_input(&input)?, audit);
+        }
+        Command::Ceremony {
+            roster,
+            sid,
+            submissions,
+            listen,
+            deadline,
+            key,
+            passphrase,
+            out,
+        } => {
+            audit.operation = "ceremony";
+            audit.sid = Some(sid.clone());
+            let passphrase = passphrase.map(Zeroizing::new);
+            let key = Zeroizing::new(read_input(&key)?);
+            let coordinator = decode_key(&key, cli.format, passphrase.as_deref())?;
+            let signing_key = SigningKey::new(coordinator.secret())?;
+            let roster = read_roster(&read_input(&roster)?)?;
+
+            let mut proofs = BTreeMap::new();
+            for path in submission_files(&submissions)? {
+                let submission = serde_json::from_slice(&fs::read(&path)?)
+                    .map_err(|e| format!("{}: {e}", path.display()))?;
+                if let Err(e) = admit(&roster, &mut proofs, submission) {
+                    tracing::warn!(file = %path.display(), "{e}");
+                }
+            }
+            if let Some(listen) = listen {
+                receive_submissions(listen, Duration::from_secs(deadline), &roster, &mut proofs)?;
+            }
+
+            let verifier = verifier(cli.domain.as_deref());
+            let summary = ceremony_summary(&verifier, &sid, &roster, proofs)?;
+            let signer = hex::encode(signing_key.verifying_key().to_bytes());
+            let summary = CeremonySummary {
+                domain: cli.domain.clone(),
+                signer,
+                ..summary
+            };
+            let signature = signing_key.try_sign(to_canonical_json(&summary)?.as_bytes())?;
+            tracing::info!(
+                participants = summary.participants,
+                passed = summary.passed.len(),
+                failed = summary.failed.len(),
+                missing = summary.missing.len(),
+                "ceremony finished"
+            );
+            audit.proofs = Some(summary.participants);
+            audit.invalid = Some(summary.failed.len() + summary.missing.len());
+            let passed = summary.passed.len() == summary.participants;
+            audit.result = if passed { "valid" } else { "invalid" };
+            let signed = SignedSummary {
+                summary,
+                signature: hex::encode(signature.to_bytes()),
+            };
+            write_output(
+                out.as_deref(),
+                format!("{}\n", serde_json::to_string_pretty(&signed)?).as_bytes(),
+            )?;
+            if !passed {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Bench {
+            iterations,
+            batch_size,
+            warmup,
+        } => {
+            let report = bench(iterations, batch_size, warmup)?;
+            write_output(
+                None,
+                format!("{}\n", serde_json::to_string_pretty(&report)?).as_bytes(),
+            )?;
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// The prover for `--domain`
+fn prover(domain: Option<&str>) -> Prover {
+    match domain {
+        Some(domain) => Prover::new().domain(domain.as_bytes()),
+        None => Prover::new(),
+    }
+}
 
-    let verify_start_time = Instant::now();
-    // Verify the proof without knowing the secret x
-    let result = proof
-        .verify(sid, pid, y, base_point)
-        .map_err(|e| format!("Verification failed: {:?}", e))?;
-    println!(
-        "Verify computation time: {} ms",
-        verify_start_time.elapsed().as_millis()
+/// The verifier for `--domain`
+fn verifier(domain: Option<&str>) -> Verifier {
+    match domain {
+        Some(domain) => Verifier::new().domain(domain.as_bytes()),
+        None => Verifier::new(),
+    }
+}
+
+fn verify_batch(
+    verifier: &Verifier,
+    input: &[u8],
+    audit: &mut AuditRecord,
+) -> Result<ExitCode, Box<dyn Error>> {
+    let mut records = Vec::new();
+    let mut malformed = 0;
+    for (index, line) in std::str::from_utf8(input)?.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match parse_batch_record(line) {
+            Ok((record, y)) => records.push((index + 1, record, y)),
+            Err(e) => {
+                tracing::warn!(line = index + 1, "{e}");
+                malformed += 1;
+            }
+        }
+    }
+
+    let items: Vec<_> = records
+        .iter()
+        .map(|(_, record, y)| {
+            (
+                record.sid.as_str().into(),
+                record.pid.into(),
+                *y,
+                &record.proof,
+            )
+        })
+        .collect();
+    let invalid = verifier.find_invalid(&items, ProjectivePoint::GENERATOR)?;
+    for &index in &invalid {
+        tracing::warn!(line = records[index].0, "invalid proof");
+    }
+    tracing::info!(
+        proofs = records.len() + malformed,
+        valid = records.len() - invalid.len(),
+        invalid = invalid.len(),
+        malformed,
+        "verified batch"
     );
-    if result {
-        println!("✅ DLOG proof is correct");
+    audit.proofs = Some(records.len() + malformed);
+    audit.invalid = Some(invalid.len());
+    audit.malformed = Some(malformed);
+    audit.result = if malformed == 0 && invalid.is_empty() {
+        "valid"
     } else {
-        println!("❌ DLOG proof is not correct");
+        "invalid"
+    };
+
+    Ok(if malformed > 0 {
+        ExitCode::from(2)
+    } else if !invalid.is_empty() {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+/// Parses a roster, rejecting invalid keys and repeated participant IDs
+fn read_roster(bytes: &[u8]) -> Result<BTreeMap<u32, ProjectivePoint>, Box<dyn Error>> {
+    let entries: Vec<RosterEntry> = serde_json::from_slice(bytes)?;
+    let mut roster = BTreeMap::new();
+    for entry in entries {
+        let y = ProjectivePoint::decode_point(&hex::decode(&entry.public)?)
+            .filter(|y| *y != ProjectivePoint::IDENTITY)
+            .ok_or_else(|| format!("invalid public key for pid {}", entry.pid))?;
+        if roster.insert(entry.pid, y).is_some() {
+            return Err(format!("pid {} is on the roster twice", entry.pid).into());
+        }
     }
+    Ok(roster)
+}
+
+/// The submission files given, with directories expanded to their `.json` files
+fn submission_files(paths: &[PathBuf]) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if !path.is_dir() {
+            files.push(path.clone());
+            continue;
+        }
+        let mut entries = fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<io::Result<Vec<_>>>()?;
+        entries.retain(|path| path.extension().is_some_and(|ext| ext == "json"));
+        entries.sort();
+        files.extend(entries);
+    }
+    Ok(files)
+}
 
-    print_proof_in_multiple_formats(&proof);
+/// Records a submission of a participant on the roster that hasn't submitted yet
+fn admit(
+    roster: &BTreeMap<u32, ProjectivePoint>,
+    proofs: &mut BTreeMap<u32, DLogProof>,
+    submission: Submission,
+) -> Result<(), String> {
+    if !roster.contains_key(&submission.pid) {
+        return Err(format!("pid {} is not on the roster", submission.pid));
+    }
+    if proofs.contains_key(&submission.pid) {
+        return Err(format!("pid {} already submitted", submission.pid));
+    }
+    proofs.insert(submission.pid, submission.proof);
+    Ok(())
+}
+
+/// Accepts submissions as `POST` bodies on `listen` until every participant has submitted or
+/// `deadline` has passed
+///
+/// Each connection carries one request, answered with 202 when the submission is accepted and
+/// 400 with `{"error": "<reason>"}` otherwise. Proofs are only verified once all are in.
+fn receive_submissions(
+    listen: SocketAddr,
+    deadline: Duration,
+    roster: &BTreeMap<u32, ProjectivePoint>,
+    proofs: &mut BTreeMap<u32, DLogProof>,
+) -> io::Result<()> {
+    let listener = TcpListener::bind(listen)?;
+    listener.set_nonblocking(true)?;
+    tracing::info!(address = %listener.local_addr()?, "waiting for submissions");
+    let end = Instant::now() + deadline;
+    while proofs.len() < roster.len() && Instant::now() < end {
+        let mut stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(Duration::from_millis(50));
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+        let outcome = read_http_body(&stream)
+            .map_err(|e| e.to_string())
+            .and_then(|body| serde_json::from_slice(&body).map_err(|e| e.to_string()))
+            .and_then(|submission| admit(roster, proofs, submission));
+        let (status, body) = match outcome {
+            Ok(()) => ("202 Accepted", r#"{"accepted":true}"#.to_string()),
+            Err(error) => {
+                tracing::warn!("rejected submission: {error}");
+                (
+                    "400 Bad Request",
+                    serde_json::json!({ "error": error }).to_string(),
+                )
+            }
+        };
+        let response = format!(
+            "HTTP/1.1 {status}\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\
+             connection: close\r\n\r\n{body}",
+            body.len()
+        );
+        if let Err(e) = stream.write_all(response.as_bytes()) {
+            tracing::warn!("failed to answer a submission: {e}");
+        }
+    }
     Ok(())
 }
 
-fn print_proof_in_multiple_formats(proof: &DLogProof) {
-    println!("Printing proof...");
-
-    println!("-----Original-----");
-    // t:  ProjectivePoint where each x, y & z coordinate (some values on curve) is in `Montgomery Form`
-    // (some_value × 2^256 mod p), split in 5 chunks of 52 bits, (where p is a large prime used in the curve)
-    // Used internally for efficient calculations (working with big number directly vs small chunks)
-    // Why 52 bits - Working with 52 bits leaves room for calculations without overflow for 64-bit numbers
-    // Why 5? Since we need to handle 256-bit numbers (52 bits × 5 = 260 bits, still give us enough space (260 > 256))
-    // `magnitude`: tells how big the number has grown (e.g., magnitude: 5 means 5 times)
-    // `normalized` indicates if the number is in final expected format
-    // s: A regular big number
-    println!("{:?}", proof);
-
-    println!("-----Affine-----");
-    // Standard (x,y) coordinates after normalizing the z-coordinate (computing x/z, y/z)
-    // Easier to read, but less efficient for calculations
-    let affine = proof.t.to_affine();
-    println!("t: {:?}", affine);
-
-    println!("-----HEX-----");
-    // Normalized (uncompressed) coordinates in hexadecimal format
-    // `false` gives both x,y values
-    let encoded = affine.to_encoded_point(false);
-    println!("t.x: 0x{}", hex::encode(encoded.x().unwrap()));
-    println!("t.y: 0x{}", hex::encode(encoded.y().unwrap()));
-    let s_bytes = proof.s.to_bytes();
-    println!("s: 0x{}", hex::encode(s_bytes));
-
-    in_json(proof);
-}
-
-fn in_json(proof: &DLogProof) {
-    println!("-----JSON-----");
-    // Compressed format - Uses prefix (02=even y, 03=odd y) + x-coordinate
-    let json = serde_json::to_string(&proof).expect("JSON serialization failed");
-    println!("Compressed JSON (standard): {}", json);
-
-    // Create a struct for uncompressed format
-    #[derive(Serialize)]
-    struct UncompressedProof<'a> {
-        t: UncompressedPoint<'a>,
-        s: &'a str,
-    }
-    #[derive(Serialize)]
-    struct UncompressedPoint<'a> {
-        x: &'a str,
-        y: &'a str,
-    }
-
-    // Create uncompressed JSON representation
-    let affine = proof.t.to_affine();
-    let encoded = affine.to_encoded_point(false);
-    let uncompressed_proof = UncompressedProof {
-        t: UncompressedPoint {
-            x: &format!("0x{}", hex::encode(encoded.x().unwrap())),
-            y: &format!("0x{}", hex::encode(encoded.y().unwrap())),
-        },
-        s: &format!("0x{}", hex::encode(proof.s.to_bytes())),
+/// Reads the body of an HTTP request with a `Content-Length` of at most `MAX_SUBMISSION_LEN`
+fn read_http_body(stream: &TcpStream) -> io::Result<Vec<u8>> {
+    let invalid = |reason: &str| io::Error::new(io::ErrorKind::InvalidData, reason);
+    let mut reader = BufReader::new(stream).take(MAX_SUBMISSION_LEN as u64 + 8 * 1024);
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        if line == "\r\n" {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = Some(value.trim().parse().map_err(|_| invalid("bad length"))?);
+            }
+        }
+    }
+    let length: usize = length.ok_or_else(|| invalid("missing content-length"))?;
+    if length > MAX_SUBMISSION_LEN {
+        return Err(invalid("submission too large"));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    Ok(body)
+}
+
+/// Verifies the submitted proofs in one batch and sorts the roster into passed, failed and
+/// missing participants; `domain` and `signer` are left to the caller
+fn ceremony_summary(
+    verifier: &Verifier,
+    sid: &str,
+    roster: &BTreeMap<u32, ProjectivePoint>,
+    mut proofs: BTreeMap<u32, DLogProof>,
+) -> Result<CeremonySummary, Box<dyn Error>> {
+    let mut bundle = ProofBundle::new(sid);
+    let mut missing = Vec::new();
+    for (&pid, &y) in roster {
+        match proofs.remove(&pid) {
+            Some(proof) => bundle.push(pid, y, proof),
+            None => missing.push(pid),
+        }
+    }
+    let items: Vec<_> = bundle
+        .entries
+        .iter()
+        .map(|entry| (sid.into(), entry.pid.into(), entry.public, &entry.proof))
+        .collect();
+    let invalid = verifier.find_invalid(&items, ProjectivePoint::GENERATOR)?;
+    let failed: Vec<u32> = invalid.iter().map(|&i| bundle.entries[i].pid).collect();
+    let passed = bundle
+        .entries
+        .iter()
+        .map(|entry| entry.pid)
+        .filter(|pid| !failed.contains(pid))
+        .collect();
+    Ok(CeremonySummary {
+        sid: sid.to_string(),
+        domain: None,
+        participants: roster.len(),
+        passed,
+        failed,
+        missing,
+        bundle_root: hex::encode(bundle.merkle_root()),
+        signer: String::new(),
+    })
+}
+
+fn bench(iterations: u32, batch_size: u32, warmup: u32) -> Result<BenchReport, Box<dyn Error>> {
+    let g = ProjectivePoint::GENERATOR;
+    let key_pairs: Vec<_> = (0..batch_size)
+        .map(|_| KeyPair::<ProjectivePoint>::generate(&mut OsRng))
+        .collect();
+    let proofs = (0..batch_size)
+        .map(|pid| key_pairs[pid as usize].prove("bench", pid))
+        .collect::<Result<Vec<_>, _>>()?;
+    let items: Vec<_> = proofs
+        .iter()
+        .zip(&key_pairs)
+        .zip(0..)
+        .map(|((proof, key_pair), pid)| ("bench".into(), pid.into(), key_pair.public(), proof))
+        .collect();
+    let (key_pair, proof) = (&key_pairs[0], &proofs[0]);
+
+    let prove = time(iterations, warmup, || {
+        key_pair.prove("bench", 0).map(|_| true)
+    })?;
+    let context = ProverContext::new(g);
+    let prove_precomputed = time(iterations, warmup, || {
+        context
+            .prove("bench", 0, key_pair.secret(), key_pair.public())
+            .map(|_| true)
+    })?;
+    let verify = time(iterations, warmup, || {
+        proof.verify("bench", 0, key_pair.public(), g)
+    })?;
+    let verifier_context = VerifierContext::new(key_pair.public(), g);
+    let verify_precomputed = time(iterations, warmup, || {
+        verifier_context.verify(proof, "bench", 0)
+    })?;
+    let batch_verify = time(iterations, warmup, || DLogProof::verify_batch(&items, g))?;
+    Ok(BenchReport {
+        iterations,
+        batch_size,
+        warmup,
+        prove: Timings::new(prove, 1),
+        prove_precomputed: Timings::new(prove_precomputed, 1),
+        verify: Timings::new(verify, 1),
+        verify_precomputed: Timings::new(verify_precomputed, 1),
+        batch_verify: Timings::new(batch_verify, batch_size),
+    })
+}
+
+/// Runs `operation` `warmup` times, then returns the durations of `iterations` more runs
+///
+/// A run returning `false` is an error, as the benchmark only uses valid proofs.
+fn time(
+    iterations: u32,
+    warmup: u32,
+    mut operation: impl FnMut() -> Result<bool, DLogProofError>,
+) -> Result<Vec<Duration>, Box<dyn Error>> {
+    for _ in 0..warmup {
+        operation()?;
+    }
+    let mut samples = Vec::with_capacity(iterations as usize);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let passed = std::hint::black_box(operation()?);
+        samples.push(start.elapsed());
+        if !passed {
+            return Err("a benchmarked proof failed to verify".into());
+        }
+    }
+    Ok(samples)
+}
+
+/// Parses a batch record and its public key, which must not be the identity
+fn parse_batch_record(line: &str) -> Result<(BatchRecord, ProjectivePoint), Box<dyn Error>> {
+    let record: BatchRecord = serde_json::from_str(line)?;
+    let y = ProjectivePoint::decode_point(&hex::decode(&record.public)?)
+        .filter(|y| *y != ProjectivePoint::IDENTITY)
+        .ok_or("invalid public key")?;
+    Ok((record, y))
+}
+
+const MISSING_PASSPHRASE: &str =
+    "a JSON keystore needs a passphrase (--passphrase or DLOG_PASSPHRASE)";
+
+fn encode_key(
+    key_pair: &KeyPair,
+    format: Format,
+    passphrase: Option<&String>,
+) -> Result<Zeroizing<Vec<u8>>, Box<dyn Error>> {
+    let secret = Zeroizing::new(key_pair.secret().expose_secret().to_repr());
+    let encoded = match format {
+        Format::Json => {
+            let passphrase = passphrase.ok_or(MISSING_PASSPHRASE)?;
+            let keystore = Keystore::encrypt(
+                key_pair,
+                passphrase.as_bytes(),
+                ScryptParams::default(),
+                &mut OsRng,
+            )?;
+            let mut json = serde_json::to_vec_pretty(&keystore)?;
+            json.push(b'\n');
+            json
+        }
+        Format::Hex => format!("{}\n", hex::encode(*secret)).into_bytes(),
+        Format::Binary => secret.to_vec(),
     };
+    Ok(Zeroizing::new(encoded))
+}
 
-    println!("Uncompressed JSON (with both coordinates):");
+fn decode_key(
+    bytes: &[u8],
+    format: Format,
+    passphrase: Option<&String>,
+) -> Result<KeyPair, Box<dyn Error>> {
+    let secret = Zeroizing::new(match format {
+        Format::Json if is_keystore(bytes) => {
+            let keystore: Keystore = serde_json::from_slice(bytes)?;
+            let passphrase = passphrase.ok_or(MISSING_PASSPHRASE)?;
+            return Ok(keystore.decrypt(passphrase.as_bytes())?);
+        }
+        Format::Json => {
+            let key_file: KeyFile = serde_json::from_slice(bytes)?;
+            let secret = hex::decode(&key_file.secret)?;
+            let key_pair = KeyPair::<ProjectivePoint>::from_secret_bytes(&secret)?;
+            if hex::decode(&key_file.public)? != key_pair.public().encode_point() {
+                return Err("the key file's public key doesn't match its secret key".into());
+            }
+            secret
+        }
+        Format::Hex => hex::decode(std::str::from_utf8(bytes)?.trim())?,
+        Format::Binary => bytes.to_vec(),
+    });
+    Ok(KeyPair::from_secret_bytes(&secret)?)
+}
 
-    // Will utilise Serde `serialize_with` attributes (if any)
-    println!(
-        "serde_json::to_string: {}",
-        serde_json::to_string(&uncompressed_proof).expect("JSON serialization failed")
-    );
-    println!(
-        "serde_json::to_string_pretty: {}",
-        serde_json::to_string_pretty(&uncompressed_proof).expect("JSON serialization failed")
-    );
+/// Whether a JSON key file is a `Keystore` rather than a plaintext key
+fn is_keystore(bytes: &[u8]) -> bool {
+    serde_json::from_slice::<serde_json::Map<String, serde_json::Value>>(bytes)
+        .is_ok_and(|fields| fields.contains_key("ciphertext"))
+}
 
-    // Will utilise Serde `deserialize_with` attributes (if any)
-    let parsed_proof: DLogProof = serde_json::from_str(&json).expect("JSON deserialization failed");
-    println!("Parsed proof from JSON: \n{:?}", parsed_proof);
+fn encode_proof(proof: &DLogProof, format: Format) -> Result<Vec<u8>, Box<dyn Error>> {
+    Ok(match format {
+        Format::Json => format!("{}\n", serde_json::to_string(proof)?).into_bytes(),
+        Format::Hex => format!("{}\n", hex::encode(proof.to_bytes())).into_bytes(),
+        Format::Binary => proof.to_bytes().to_vec(),
+    })
+}
 
-    assert_eq!(
-        parsed_proof, *proof,
-        "❌ Parsed proof doesn't match original"
-    );
-    println!("✅ DLog proof recovered successfully!");
+fn decode_proof(bytes: &[u8], format: Format) -> Result<DLogProof, Box<dyn Error>> {
+    Ok(match format {
+        Format::Json => {
+            DLogProof::from_json(std::str::from_utf8(bytes)?, UnknownFieldPolicy::Strict)?.0
+        }
+        Format::Hex => DLogProof::from_bytes(&hex::decode(std::str::from_utf8(bytes)?.trim())?)?,
+        Format::Binary => DLogProof::from_bytes(bytes)?,
+    })
+}
+
+/// Whether a file argument stands for standard input or output
+fn is_stdio(path: &Path) -> bool {
+    path.as_os_str() == "-"
+}
+
+/// Reads a file, or standard input for `-`
+fn read_input(path: &Path) -> io::Result<Vec<u8>> {
+    if !is_stdio(path) {
+        return fs::read(path);
+    }
+    let mut bytes = Vec::new();
+    io::stdin().read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Writes a file, or standard output if there is none or it is `-`
+fn write_output(path: Option<&Path>, contents: &[u8]) -> io::Result<()> {
+    match path.filter(|path| !is_stdio(path)) {
+        Some(path) => fs::write(path, contents),
+        None => {
+            let mut stdout = io::stdout().lock();
+            stdout.write_all(contents)?;
+            stdout.flush()
+        }
+    }
+}
+
+/// Writes a file only its owner can read (on Unix), replacing any existing one
+fn write_secret_file(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(contents)
 }
@@ -0,0 +1,241 @@
+use elliptic_curve::subtle::{Choice, ConstantTimeEq};
+use elliptic_curve::Field;
+use k256::ProjectivePoint;
+use rand_core::{CryptoRngCore, OsRng};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::dlog_proof::{DLogProof, TranscriptVersion};
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::secret::SecretScalar;
+use crate::serialization::ScalarHex;
+use crate::session::SessionId;
+
+/// Proof of knowledge of the discrete log of one key in a ring of N public keys
+///
+/// The ring-style (Abe-Ohkubo-Suzuki) variant of `OrProof`: the challenges are chained around
+/// the ring, c_{i+1} = H(ring, i, s_i * G - c_i * y_i), so only the first challenge needs to
+/// be sent. The prover starts the chain at its own key with t_k = r * G, simulates every other
+/// link with a random s_i, and closes the ring with s_k = r + c_k * x. The proof is N + 1
+/// scalars instead of the 3N elements of an `OrProof`:
+/// * `c` - The challenge c_0 of the first key
+/// * `s` - One response per key, in ring order
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct MembershipProof<G: DLogGroup = ProjectivePoint> {
+    #[serde_as(as = "ScalarHex")]
+    c: G::Scalar,
+    #[serde_as(as = "Vec<ScalarHex>")]
+    s: Vec<G::Scalar>,
+}
+
+impl<G: DLogGroup> MembershipProof<G> {
+    const DOMAIN_SEPARATOR: &'static [u8] = b"SCHNORR_MEMBERSHIP_PROOF";
+
+    /// The challenge of the first key in the ring
+    pub fn c(&self) -> G::Scalar {
+        self.c
+    }
+
+    /// The responses, one per key in ring order
+    pub fn s(&self) -> &[G::Scalar] {
+        &self.s
+    }
+
+    /// Proves knowledge of `x` with ring[index] = x * G, hiding `index` within `ring`
+    ///
+    /// # Arguments
+    /// * `sid` - Session identifier used for domain separation, a string or bytes (see `SessionId`)
+    /// * `pid` - Participant ID for uniqueness to distinguish different proofs
+    /// * `x` - The prover's secret scalar
+    /// * `index` - Position of the prover's key in `ring`
+    /// * `ring` - The public keys, the prover's among them
+    /// * `base_point` - Base point shared by all keys
+    ///
+    /// # Returns
+    /// * `Ok(MembershipProof)` - The proof
+    /// * `Err(DLogProofError)` - `StatementCount` if `ring` is empty or `index` is out of
+    ///   range, `IdentityPoint` for a degenerate key, or if a challenge can't be derived
+    pub fn prove<'s>(
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        index: usize,
+        ring: &[G],
+        base_point: G,
+    ) -> Result<Self, DLogProofError> {
+        Self::prove_with_rng(&mut OsRng, sid, pid, x, index, ring, base_point)
+    }
+
+    /// Proves like `prove`, drawing the nonce and the simulated responses from `rng`
+    pub fn prove_with_rng<'s>(
+        rng: &mut impl CryptoRngCore,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        index: usize,
+        ring: &[G],
+        base_point: G,
+    ) -> Result<Self, DLogProofError> {
+        let transcript = Self::transcript(sid.into(), pid, ring, base_point)?;
+        if index >= ring.len() {
+            return Err(DLogProofError::StatementCount);
+        }
+        let x = x.into();
+        let n = ring.len();
+
+        let r = Zeroizing::new(G::Scalar::random(&mut *rng));
+        let mut s = vec![G::Scalar::ZERO; n];
+        let mut c = Self::link(&transcript, (index + 1) % n, base_point * *r)?;
+        let mut c_0 = c;
+        for i in (index + 1..n).chain(0..index) {
+            if i == 0 {
+                c_0 = c;
+            }
+            s[i] = G::Scalar::random(&mut *rng);
+            c = Self::link(&transcript, (i + 1) % n, base_point * s[i] - ring[i] * c)?;
+        }
+        if index == 0 {
+            c_0 = c;
+        }
+        s[index] = *r + c * x.expose_secret();
+        Ok(MembershipProof { c: c_0, s })
+    }
+
+    /// Verifies that the prover knows the discrete log of one of the keys in `ring`
+    ///
+    /// A proof for a ring of a different size is invalid, not an error.
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Validity of the proof
+    /// * `Err(DLogProofError)` - `StatementCount` if `ring` is empty, `IdentityPoint` for a
+    ///   degenerate key, or if a challenge can't be derived
+    pub fn verify<'s>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        ring: &[G],
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        let transcript = Self::transcript(sid.into(), pid, ring, base_point)?;
+        if self.s.len() != ring.len() {
+            return Ok(false);
+        }
+        let mut c = self.c;
+        for (i, (s, &y)) in self.s.iter().zip(ring).enumerate() {
+            c = Self::link(&transcript, (i + 1) % ring.len(), base_point * s - y * c)?;
+        }
+        Ok(c.ct_eq(&self.c).into())
+    }
+
+    /// Hashes the statement once; every link of the ring continues from this state
+    fn transcript(
+        sid: SessionId<'_>,
+        pid: u32,
+        ring: &[G],
+        base_point: G,
+    ) -> Result<Sha256, DLogProofError> {
+        if ring.is_empty() {
+            return Err(DLogProofError::StatementCount);
+        }
+        let mut points = vec![base_point];
+        for &y in ring {
+            DLogProof::check_statement(y, base_point)?;
+            points.push(y);
+        }
+        let mut hasher = Sha256::new();
+        DLogProof::absorb_transcript(
+            &mut hasher,
+            TranscriptVersion::V1,
+            Self::DOMAIN_SEPARATOR,
+            sid,
+            pid.into(),
+            &points,
+        );
+        Ok(hasher)
+    }
+
+    /// Challenge c_i of key `i`, derived from the commitment of the key before it
+    fn link(transcript: &Sha256, i: usize, t: G) -> Result<G::Scalar, DLogProofError> {
+        let hash = transcript
+            .clone()
+            .chain_update((i as u32).to_be_bytes())
+            .chain_update(t.encode_point())
+            .finalize();
+        G::challenge_from_hash(&hash)
+    }
+}
+
+impl<G: DLogGroup> ConstantTimeEq for MembershipProof<G> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.c.ct_eq(&other.c) & self.s[..].ct_eq(&other.s[..])
+    }
+}
+
+impl<G: DLogGroup> PartialEq for MembershipProof<G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<G: DLogGroup> Eq for MembershipProof<G> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::Scalar;
+
+    fn ring(x: &Scalar, index: usize, n: usize) -> Vec<ProjectivePoint> {
+        let g = ProjectivePoint::GENERATOR;
+        (0..n)
+            .map(|i| {
+                if i == index {
+                    g * x
+                } else {
+                    g * Scalar::random(&mut OsRng)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn membership_proofs_verify_at_every_position() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        for (index, n) in [(0, 1), (0, 4), (2, 4), (3, 4)] {
+            let ring = ring(&x, index, n);
+            let proof = MembershipProof::prove("sid", 1, &x, index, &ring, g).unwrap();
+            assert!(proof.verify("sid", 1, &ring, g).unwrap());
+            assert!(!proof.verify("sid", 2, &ring, g).unwrap());
+
+            let json = serde_json::to_string(&proof).unwrap();
+            let decoded: MembershipProof = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, proof);
+            assert_eq!(decoded.s().len(), n);
+        }
+    }
+
+    #[test]
+    fn membership_proofs_need_a_key_in_the_ring() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let ring = ring(&x, 1, 3);
+
+        let proof = MembershipProof::prove("sid", 1, &x, 0, &ring, g).unwrap();
+        assert!(!proof.verify("sid", 1, &ring, g).unwrap());
+
+        let proof = MembershipProof::prove("sid", 1, &x, 1, &ring, g).unwrap();
+        let mut rotated = ring.clone();
+        rotated.rotate_left(1);
+        assert!(!proof.verify("sid", 1, &rotated, g).unwrap());
+        assert!(!proof.verify("sid", 1, &ring[..2], g).unwrap());
+        assert_eq!(
+            MembershipProof::prove("sid", 1, &x, 3, &ring, g),
+            Err(DLogProofError::StatementCount)
+        );
+    }
+}
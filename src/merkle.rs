@@ -0,0 +1,261 @@
+//! Merkle trees over proof bundles
+//!
+//! A ceremony can publish the 32-byte `ProofBundle::merkle_root` of its bundle (e.g. on-chain
+//! or in a transparency log) instead of the whole bundle, and later hand a single participant
+//! its entry together with an `InclusionProof`. Anyone holding the root then checks that
+//! entry alone: that it is leaf #i of the committed bundle and that its proof verifies.
+//!
+//! The tree is the Merkle Tree Hash of RFC 9162 over SHA-256: leaves are hashed as
+//! `SHA-256(0x00 || leaf)`, inner nodes as `SHA-256(0x01 || left || right)`, and a tree of
+//! n > 1 leaves splits after the largest power of two below n, so no leaf is duplicated. The
+//! leaf of an entry is its session ID, participant ID, public key, commitment and response,
+//! each prefixed with its length as 8 big-endian bytes.
+
+use elliptic_curve::PrimeField;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sha2::{Digest, Sha256};
+
+use crate::bundle::{BundleEntry, ProofBundle};
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::serialization::DigestHex;
+
+/// Proof that an entry is leaf `index` of a bundle with a given Merkle root
+///
+/// Serialized as `{"index": 2, "tree_size": 5, "path": ["<hex>", ...]}` with the sibling
+/// hashes from the leaf up, so it grows with the logarithm of the bundle size.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InclusionProof {
+    /// Position of the entry in the bundle
+    pub index: u64,
+    /// Number of entries in the bundle
+    pub tree_size: u64,
+    /// Sibling hashes from the leaf up to the root
+    #[serde_as(as = "Vec<DigestHex>")]
+    pub path: Vec<[u8; 32]>,
+}
+
+impl<G: DLogGroup> ProofBundle<G> {
+    /// Returns the Merkle root over all entries, in bundle order
+    ///
+    /// The root of an empty bundle is SHA-256 of the empty string, as in RFC 9162.
+    pub fn merkle_root(&self) -> [u8; 32] {
+        tree_hash(&self.leaf_hashes())
+    }
+
+    /// Returns the inclusion proof for the entry at `index`, `None` if it is out of range
+    pub fn inclusion_proof(&self, index: usize) -> Option<InclusionProof> {
+        let leaves = self.leaf_hashes();
+        if index >= leaves.len() {
+            return None;
+        }
+        let mut path = Vec::new();
+        audit_path(index, &leaves, &mut path);
+        Some(InclusionProof {
+            index: index as u64,
+            tree_size: leaves.len() as u64,
+            path,
+        })
+    }
+
+    fn leaf_hashes(&self) -> Vec<[u8; 32]> {
+        self.entries
+            .iter()
+            .map(|entry| leaf_hash(&self.sid, entry))
+            .collect()
+    }
+}
+
+impl InclusionProof {
+    /// Checks that `entry` of session `sid` is the leaf at `index` under `root`
+    ///
+    /// Only the tree is checked, not the entry's proof; see `verify` for both.
+    pub fn verify_inclusion<G: DLogGroup>(
+        &self,
+        root: &[u8; 32],
+        sid: &str,
+        entry: &BundleEntry<G>,
+    ) -> bool {
+        if self.index >= self.tree_size {
+            return false;
+        }
+        // RFC 9162, section 2.1.3.2
+        let (mut fnode, mut snode) = (self.index, self.tree_size - 1);
+        let mut hash = leaf_hash(sid, entry);
+        for sibling in &self.path {
+            if snode == 0 {
+                return false;
+            }
+            if fnode & 1 == 1 || fnode == snode {
+                hash = node_hash(sibling, &hash);
+                while fnode & 1 == 0 && fnode != 0 {
+                    fnode >>= 1;
+                    snode >>= 1;
+                }
+            } else {
+                hash = node_hash(&hash, sibling);
+            }
+            fnode >>= 1;
+            snode >>= 1;
+        }
+        snode == 0 && hash == *root
+    }
+
+    /// Verifies a single bundle member given only the bundle's Merkle root
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If `entry` is included under `root` and its proof is valid for `sid`
+    /// * `Ok(false)` - If the path doesn't lead to `root` or the proof is invalid
+    /// * `Err(DLogProofError)` - Any error of `DLogProof::verify`
+    pub fn verify<G: DLogGroup>(
+        &self,
+        root: &[u8; 32],
+        sid: &str,
+        entry: &BundleEntry<G>,
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        if !self.verify_inclusion(root, sid, entry) {
+            return Ok(false);
+        }
+        entry.proof.verify(sid, entry.pid, entry.public, base_point)
+    }
+}
+
+fn leaf_hash<G: DLogGroup>(sid: &str, entry: &BundleEntry<G>) -> [u8; 32] {
+    let mut hasher = Sha256::new_with_prefix([0x00]);
+    for field in [
+        sid.as_bytes(),
+        &entry.pid.to_be_bytes(),
+        &entry.public.encode_point(),
+        &entry.proof.t().encode_point(),
+        entry.proof.s().to_repr().as_ref(),
+    ] {
+        hasher.update((field.len() as u64).to_be_bytes());
+        hasher.update(field);
+    }
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    Sha256::new_with_prefix([0x01])
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+/// The largest power of two below `n`, for n > 1
+fn split(n: usize) -> usize {
+    1 << (usize::BITS - 1 - (n - 1).leading_zeros())
+}
+
+fn tree_hash(leaves: &[[u8; 32]]) -> [u8; 32] {
+    match leaves {
+        [] => Sha256::digest(b"").into(),
+        [leaf] => *leaf,
+        _ => {
+            let (left, right) = leaves.split_at(split(leaves.len()));
+            node_hash(&tree_hash(left), &tree_hash(right))
+        }
+    }
+}
+
+fn audit_path(index: usize, leaves: &[[u8; 32]], path: &mut Vec<[u8; 32]>) {
+    if leaves.len() <= 1 {
+        return;
+    }
+    let k = split(leaves.len());
+    let (left, right) = leaves.split_at(k);
+    if index < k {
+        audit_path(index, left, path);
+        path.push(tree_hash(right));
+    } else {
+        audit_path(index - k, right, path);
+        path.push(tree_hash(left));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlog_proof::DLogProof;
+    use elliptic_curve::Field;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_core::OsRng;
+
+    fn bundle(size: u32) -> ProofBundle {
+        let g = ProjectivePoint::GENERATOR;
+        let mut bundle = ProofBundle::new("ceremony-7");
+        for pid in 1..=size {
+            let x = Scalar::random(&mut OsRng);
+            let y = g * x;
+            bundle.push(
+                pid,
+                y,
+                DLogProof::prove("ceremony-7", pid, &x, y, g).unwrap(),
+            );
+        }
+        bundle
+    }
+
+    #[test]
+    fn every_member_verifies_against_the_root() {
+        let g = ProjectivePoint::GENERATOR;
+        for size in [1, 2, 3, 5, 8] {
+            let bundle = bundle(size);
+            let root = bundle.merkle_root();
+            for (index, entry) in bundle.entries.iter().enumerate() {
+                let proof = bundle.inclusion_proof(index).unwrap();
+                assert!(proof.path.len() <= 3);
+                assert!(proof.verify(&root, "ceremony-7", entry, g).unwrap());
+
+                let json = serde_json::to_string(&proof).unwrap();
+                assert_eq!(
+                    serde_json::from_str::<InclusionProof>(&json).unwrap(),
+                    proof
+                );
+            }
+            assert!(bundle.inclusion_proof(size as usize).is_none());
+        }
+        assert_eq!(
+            ProofBundle::<ProjectivePoint>::new("empty").merkle_root(),
+            <[u8; 32]>::from(Sha256::digest(b""))
+        );
+    }
+
+    #[test]
+    fn other_members_and_roots_are_rejected() {
+        let g = ProjectivePoint::GENERATOR;
+        let bundle = bundle(5);
+        let root = bundle.merkle_root();
+        let proof = bundle.inclusion_proof(2).unwrap();
+        let entry = &bundle.entries[2];
+
+        assert!(!proof.verify_inclusion(&root, "ceremony-7", &bundle.entries[3]));
+        assert!(!proof.verify_inclusion(&root, "ceremony-8", entry));
+        assert!(!proof.verify_inclusion(&self::bundle(5).merkle_root(), "ceremony-7", entry));
+
+        let mut moved = proof.clone();
+        moved.index = 3;
+        assert!(!moved.verify_inclusion(&root, "ceremony-7", entry));
+        let mut resized = proof.clone();
+        resized.tree_size = 4;
+        assert!(!resized.verify_inclusion(&root, "ceremony-7", entry));
+        let mut truncated = proof.clone();
+        truncated.path.pop();
+        assert!(!truncated.verify_inclusion(&root, "ceremony-7", entry));
+
+        // An included entry whose proof doesn't verify is rejected as well
+        let mut forged = self::bundle(3);
+        forged.entries[1].pid = 7;
+        let root = forged.merkle_root();
+        let proof = forged.inclusion_proof(1).unwrap();
+        assert!(proof.verify_inclusion(&root, "ceremony-7", &forged.entries[1]));
+        assert!(!proof
+            .verify(&root, "ceremony-7", &forged.entries[1], g)
+            .unwrap());
+    }
+}
@@ -0,0 +1,76 @@
+use bip32::{DerivationPath, XPrv};
+use bip39::Mnemonic;
+use k256::ProjectivePoint;
+use zeroize::Zeroizing;
+
+use crate::error::DLogProofError;
+use crate::keypair::KeyPair;
+
+/// Derivation path used by the `dlog` CLI when none is given (BIP-44, account 0, first key)
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/0'/0'/0/0";
+
+impl KeyPair<ProjectivePoint> {
+    /// Derives a key pair from a BIP-39 mnemonic and optional passphrase along a BIP-32 path
+    /// (`mnemonic` feature)
+    ///
+    /// The same words, passphrase and path give the same key pair on every machine, as in
+    /// BIP-32 wallets, so a proving key can be backed up as its mnemonic. The seed and the
+    /// derived extended keys are wiped after use.
+    ///
+    /// # Arguments
+    /// * `phrase` - English BIP-39 mnemonic of 12 to 24 words
+    /// * `passphrase` - BIP-39 passphrase, empty for none
+    /// * `path` - BIP-32 derivation path such as `m/44'/0'/0'/0/0`
+    ///
+    /// # Returns
+    /// * `Ok(KeyPair)` - The key pair of the derived secret key
+    /// * `Err(DLogProofError)` - `Serialization` for an invalid mnemonic (unknown word, wrong
+    ///   word count or checksum) or derivation path
+    pub fn from_mnemonic(
+        phrase: &str,
+        passphrase: &str,
+        path: &str,
+    ) -> Result<Self, DLogProofError> {
+        let mnemonic = Mnemonic::parse(phrase)
+            .map_err(|e| DLogProofError::Serialization(format!("Invalid mnemonic: {e}")))?;
+        let path: DerivationPath = path.parse().map_err(|e| {
+            DLogProofError::Serialization(format!("Invalid derivation path '{path}': {e}"))
+        })?;
+        let seed = Zeroizing::new(mnemonic.to_seed(passphrase));
+        let xprv = XPrv::derive_from_path(seed.as_ref(), &path)
+            .map_err(|e| DLogProofError::Serialization(format!("BIP-32 derivation: {e}")))?;
+        let secret = Zeroizing::new(xprv.private_key().to_bytes());
+        Self::from_secret_bytes(&secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::group::DLogGroup;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon \
+                          abandon abandon abandon about";
+
+    #[test]
+    fn mnemonics_derive_reproducible_keys() {
+        let key_pair = KeyPair::from_mnemonic(PHRASE, "", DEFAULT_DERIVATION_PATH).unwrap();
+        // First BIP-44 key of the all-`abandon` test mnemonic
+        assert_eq!(
+            hex::encode(key_pair.public().encode_point()),
+            "03aaeb52dd7494c361049de67cc680e83ebcbbbdbeb13637d92cd845f70308af5e"
+        );
+        let again = KeyPair::from_mnemonic(PHRASE, "", DEFAULT_DERIVATION_PATH).unwrap();
+        assert_eq!(again.public(), key_pair.public());
+
+        let other_path = KeyPair::from_mnemonic(PHRASE, "", "m/44'/0'/0'/0/1").unwrap();
+        assert_ne!(other_path.public(), key_pair.public());
+        let other_passphrase =
+            KeyPair::from_mnemonic(PHRASE, "TREZOR", DEFAULT_DERIVATION_PATH).unwrap();
+        assert_ne!(other_passphrase.public(), key_pair.public());
+
+        let bad_checksum = PHRASE.replace("about", "abandon");
+        assert!(KeyPair::from_mnemonic(&bad_checksum, "", DEFAULT_DERIVATION_PATH).is_err());
+        assert!(KeyPair::from_mnemonic(PHRASE, "", "44'/0'").is_err());
+    }
+}
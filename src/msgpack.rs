@@ -0,0 +1,88 @@
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+
+impl<G: DLogGroup> DLogProof<G> {
+    /// Encodes the proof as MessagePack (`msgpack` feature)
+    ///
+    /// The proof is a map with the string keys `"t"` and `"s"`, each holding a `bin` value
+    /// with the raw point and scalar encodings (compressed SEC1 and big-endian on secp256k1),
+    /// so any MessagePack library reads it as a dictionary of two byte strings:
+    ///
+    /// ```text
+    /// 82                      map of 2
+    /// a1 74  c4 21 <33 bytes> "t": bin 8
+    /// a1 73  c4 20 <32 bytes> "s": bin 8
+    /// ```
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, DLogProofError> {
+        rmp_serde::to_vec_named(self)
+            .map_err(|e| DLogProofError::Serialization(format!("MessagePack encoding: {e}")))
+    }
+
+    /// Decodes a proof from MessagePack, validating the point and scalar like the JSON format
+    ///
+    /// Besides the map written by `to_msgpack`, the positional form `[t, s]` of
+    /// `rmp_serde::to_vec` is accepted.
+    pub fn from_msgpack(bytes: &[u8]) -> Result<Self, DLogProofError> {
+        rmp_serde::from_slice(bytes)
+            .map_err(|e| DLogProofError::Serialization(format!("Invalid MessagePack proof: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::Field;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_core::OsRng;
+
+    /// MessagePack of the proof (t = G, s = 1), assembled by hand from the format spec
+    const FIXTURE: &str = concat!(
+        "82",
+        "a174",
+        "c421",
+        "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        "a173",
+        "c420",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+    );
+
+    #[test]
+    fn msgpack_matches_the_fixture() {
+        let proof = DLogProof::new(ProjectivePoint::GENERATOR, Scalar::ONE);
+        assert_eq!(hex::encode(proof.to_msgpack().unwrap()), FIXTURE);
+        assert_eq!(
+            DLogProof::from_msgpack(&hex::decode(FIXTURE).unwrap()).unwrap(),
+            proof
+        );
+
+        // Positional form: array of 2 (0x92) instead of the map
+        let positional = format!("92{}{}", &FIXTURE[6..76], &FIXTURE[80..]);
+        assert_eq!(
+            DLogProof::from_msgpack(&hex::decode(positional).unwrap()).unwrap(),
+            proof
+        );
+    }
+
+    #[test]
+    fn msgpack_roundtrips_and_validates() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let proof = DLogProof::prove("sid", 1, &x, g * x, g).unwrap();
+
+        let encoded = proof.to_msgpack().unwrap();
+        assert_eq!(encoded.len(), 1 + 2 + 2 + 33 + 2 + 2 + 32);
+        let decoded: DLogProof = DLogProof::from_msgpack(&encoded).unwrap();
+        assert!(decoded.verify("sid", 1, g * x, g).unwrap());
+
+        // Scalar above the group order
+        let mut tampered = encoded.clone();
+        let len = tampered.len();
+        tampered[len - 32..].fill(0xff);
+        assert!(matches!(
+            DLogProof::<ProjectivePoint>::from_msgpack(&tampered),
+            Err(DLogProofError::Serialization(_))
+        ));
+        assert!(DLogProof::<ProjectivePoint>::from_msgpack(&encoded[..40]).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+use elliptic_curve::group::GroupEncoding;
+use k256::ProjectivePoint;
+
+use crate::dlog_proof::{DLogProof, ProofId};
+use crate::error::DLogProofError;
+use crate::group::decode_canonical;
+
+/// Multicodec code of a compressed secp256k1 public key (`secp256k1-pub`), varint-encoded
+const SECP256K1_PUB_CODEC: [u8; 2] = [0xe7, 0x01];
+/// Multihash code of SHA2-256
+const SHA2_256_CODE: u8 = 0x12;
+
+/// Multibase encodings supported for proofs, keys and digests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Multibase {
+    /// Lowercase hexadecimal, prefix `f`
+    Base16,
+    /// Bitcoin base58 alphabet, prefix `z`
+    Base58Btc,
+}
+
+impl Multibase {
+    fn prefix(self) -> char {
+        match self {
+            Multibase::Base16 => 'f',
+            Multibase::Base58Btc => 'z',
+        }
+    }
+
+    /// Encodes bytes and prepends the multibase prefix character
+    pub fn encode(self, bytes: &[u8]) -> String {
+        let body = match self {
+            Multibase::Base16 => hex::encode(bytes),
+            Multibase::Base58Btc => bs58::encode(bytes).into_string(),
+        };
+        format!("{}{}", self.prefix(), body)
+    }
+
+    /// Decodes a multibase string, detecting the encoding from its prefix
+    ///
+    /// # Returns
+    /// * `Ok((Multibase, Vec<u8>))` - The detected encoding and the decoded bytes
+    /// * `Err(DLogProofError)` - `Serialization` if the prefix is unsupported or the body is
+    ///   malformed
+    pub fn decode(encoded: &str) -> Result<(Multibase, Vec<u8>), DLogProofError> {
+        let mut chars = encoded.chars();
+        let base = match chars.next() {
+            Some('f') | Some('F') => Multibase::Base16,
+            Some('z') => Multibase::Base58Btc,
+            Some(other) => {
+                return Err(DLogProofError::Serialization(format!(
+                    "Unsupported multibase prefix '{other}'"
+                )))
+            }
+            None => return Err(invalid("Empty multibase string")),
+        };
+        let body = chars.as_str();
+        let bytes = match base {
+            Multibase::Base16 => hex::decode(body).map_err(|_| invalid("Invalid hex encoding"))?,
+            Multibase::Base58Btc => bs58::decode(body)
+                .into_vec()
+                .map_err(|_| invalid("Invalid base58 encoding"))?,
+        };
+        Ok((base, bytes))
+    }
+}
+
+/// Encodes a public point as a multibase string over `secp256k1-pub` multicodec bytes
+/// (the same layout `did:key` uses)
+pub fn public_key_to_multibase(point: &ProjectivePoint, base: Multibase) -> String {
+    let mut bytes = SECP256K1_PUB_CODEC.to_vec();
+    bytes.extend_from_slice(&point.to_bytes());
+    base.encode(&bytes)
+}
+
+/// Decodes a public point from its multibase `secp256k1-pub` form
+pub fn public_key_from_multibase(encoded: &str) -> Result<ProjectivePoint, DLogProofError> {
+    let (_, bytes) = Multibase::decode(encoded)?;
+    let key = bytes
+        .strip_prefix(&SECP256K1_PUB_CODEC)
+        .ok_or_else(|| invalid("Not a secp256k1-pub multicodec key"))?;
+    let key: [u8; 33] = key.try_into().map_err(|_| DLogProofError::InvalidPoint)?;
+    decode_canonical::<ProjectivePoint>(&key.into()).ok_or(DLogProofError::InvalidPoint)
+}
+
+impl DLogProof {
+    /// Encodes the proof's fixed binary form (`t || s`) as a multibase string
+    pub fn to_multibase(&self, base: Multibase) -> String {
+        base.encode(&self.to_bytes())
+    }
+
+    /// Decodes a proof from a multibase string produced by `to_multibase`
+    pub fn from_multibase(encoded: &str) -> Result<Self, DLogProofError> {
+        let (_, bytes) = Multibase::decode(encoded)?;
+        DLogProof::from_bytes(&bytes)
+    }
+}
+
+impl ProofId {
+    /// Wraps the identifier as a SHA2-256 multihash (`0x12 || 0x20 || digest`)
+    pub fn to_multihash(self) -> Vec<u8> {
+        let mut bytes = vec![SHA2_256_CODE, self.0.len() as u8];
+        bytes.extend_from_slice(&self.0);
+        bytes
+    }
+
+    /// Parses a SHA2-256 multihash back into a proof identifier
+    pub fn from_multihash(bytes: &[u8]) -> Result<Self, DLogProofError> {
+        match bytes {
+            [SHA2_256_CODE, 32, digest @ ..] => digest
+                .try_into()
+                .map(ProofId)
+                .map_err(|_| invalid("Invalid SHA2-256 multihash length")),
+            [SHA2_256_CODE, ..] => Err(invalid("Invalid SHA2-256 multihash length")),
+            _ => Err(invalid("Unsupported multihash function")),
+        }
+    }
+}
+
+fn invalid(reason: &str) -> DLogProofError {
+    DLogProofError::Serialization(reason.to_string())
+}
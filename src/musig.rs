@@ -0,0 +1,336 @@
+use elliptic_curve::Field;
+use rand_core::{CryptoRngCore, OsRng};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use zeroize::Zeroizing;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::secret::SecretScalar;
+use crate::serialization::{PointHex, ScalarHex};
+use crate::session::SessionId;
+use crate::sigma::{DLogStatement, SchnorrDLog, SigmaProof};
+
+const KEY_AGG_DOMAIN: &[u8] = b"SCHNORR_MUSIG/key-aggregation";
+const NONCE_DOMAIN: &[u8] = b"SCHNORR_MUSIG/nonce";
+
+/// Aggregated public key Y = Σ a_i * y_i of a MuSig2 signer group
+///
+/// The coefficients a_i = H(L, y_i), with L the whole key list, keep a participant from
+/// choosing its key as a function of the others' keys (a rogue-key attack) to control Y.
+/// Keys are used in the given order; all participants must agree on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyAggregation<G: DLogGroup> {
+    keys: Vec<G>,
+    coefficients: Vec<G::Scalar>,
+    aggregate: G,
+}
+
+impl<G: DLogGroup> KeyAggregation<G> {
+    /// Aggregates the participants' public keys y_i = x_i * G
+    ///
+    /// # Returns
+    /// * `Ok(KeyAggregation)` - The aggregation
+    /// * `Err(DLogProofError)` - `StatementCount` if `keys` is empty, `IdentityPoint` if a key
+    ///   or the aggregate is the identity, or if a coefficient can't be derived
+    pub fn new(keys: &[G]) -> Result<Self, DLogProofError> {
+        if keys.is_empty() {
+            return Err(DLogProofError::StatementCount);
+        }
+        let mut points = keys.to_vec();
+        let coefficients = keys
+            .iter()
+            .map(|&y| {
+                points.push(y);
+                let a = DLogProof::hash_points_with_domain(
+                    KEY_AGG_DOMAIN,
+                    SessionId::new(b""),
+                    0.into(),
+                    &points,
+                );
+                points.pop();
+                a
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let aggregate = keys
+            .iter()
+            .zip(&coefficients)
+            .fold(G::identity(), |sum, (&y, a)| sum + y * a);
+        if keys.iter().any(|y| bool::from(y.is_identity())) || bool::from(aggregate.is_identity()) {
+            return Err(DLogProofError::IdentityPoint);
+        }
+        Ok(KeyAggregation {
+            keys: keys.to_vec(),
+            coefficients,
+            aggregate,
+        })
+    }
+
+    /// The aggregated key Y the joint proof is for
+    pub fn aggregate_key(&self) -> G {
+        self.aggregate
+    }
+
+    /// The participants' keys, in aggregation order
+    pub fn keys(&self) -> &[G] {
+        &self.keys
+    }
+
+    /// The coefficient a_i of participant `index`
+    pub fn coefficient(&self, index: usize) -> Option<G::Scalar> {
+        self.coefficients.get(index).copied()
+    }
+}
+
+/// First-round message of a participant, its nonce commitments R_i1 = k_i1 * G, R_i2 = k_i2 * G
+#[serde_as]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct NonceCommitment<G: DLogGroup> {
+    #[serde_as(as = "PointHex")]
+    r1: G,
+    #[serde_as(as = "PointHex")]
+    r2: G,
+}
+
+impl<G: DLogGroup> NonceCommitment<G> {
+    pub fn new(r1: G, r2: G) -> Self {
+        NonceCommitment { r1, r2 }
+    }
+
+    pub fn r1(&self) -> G {
+        self.r1
+    }
+
+    pub fn r2(&self) -> G {
+        self.r2
+    }
+}
+
+/// Second-round message of a participant, its partial response s_i
+#[serde_as]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct PartialResponse<G: DLogGroup> {
+    #[serde_as(as = "ScalarHex")]
+    s: G::Scalar,
+}
+
+impl<G: DLogGroup> PartialResponse<G> {
+    pub fn new(s: G::Scalar) -> Self {
+        PartialResponse { s }
+    }
+
+    pub fn s(&self) -> G::Scalar {
+        self.s
+    }
+}
+
+/// A participant's secret nonces between the two rounds
+///
+/// Wiped on drop and consumed by `respond`: reusing nonces across sessions reveals the
+/// participant's share. Deliberately neither `Clone` nor serializable.
+pub struct SecretNonces<G: DLogGroup> {
+    k1: Zeroizing<G::Scalar>,
+    k2: Zeroizing<G::Scalar>,
+}
+
+impl<G: DLogGroup> SecretNonces<G> {
+    /// First round: draws two nonces and commits to them
+    pub fn commit(base_point: G) -> (NonceCommitment<G>, Self) {
+        Self::commit_with_rng(&mut OsRng, base_point)
+    }
+
+    /// Draws the nonces like `commit`, from `rng`
+    pub fn commit_with_rng(
+        rng: &mut impl CryptoRngCore,
+        base_point: G,
+    ) -> (NonceCommitment<G>, Self) {
+        let k1 = Zeroizing::new(G::Scalar::random(&mut *rng));
+        let k2 = Zeroizing::new(G::Scalar::random(&mut *rng));
+        let commitment = NonceCommitment {
+            r1: base_point * *k1,
+            r2: base_point * *k2,
+        };
+        (commitment, SecretNonces { k1, k2 })
+    }
+
+    /// Second round: the partial response s_i = k_i1 + b * k_i2 + c * a_i * x_i of
+    /// participant `index`, holding the share `x` of its key
+    ///
+    /// # Returns
+    /// * `Ok(PartialResponse)` - The partial response
+    /// * `Err(DLogProofError)` - `StatementCount` if `index` is not a participant
+    pub fn respond(
+        self,
+        session: &MusigSession<G>,
+        index: usize,
+        x: impl Into<SecretScalar<G::Scalar>>,
+    ) -> Result<PartialResponse<G>, DLogProofError> {
+        let a = session
+            .key_agg
+            .coefficient(index)
+            .ok_or(DLogProofError::StatementCount)?;
+        let x = x.into();
+        Ok(PartialResponse {
+            s: *self.k1 + session.b * *self.k2 + session.c * a * x.expose_secret(),
+        })
+    }
+}
+
+/// Public state of one joint proof, computed by every participant and the aggregator
+///
+/// With all nonce commitments collected, the aggregate nonce is R = R_1 + b * R_2, where
+/// R_j = Σ R_ij and b = H(Y, R_1, R_2) binds the second nonce to the session. The proof's
+/// challenge is the ordinary `DLogProof` challenge c = H(G, Y, R), so the combined proof
+/// (R, Σ s_i) verifies with `DLogProof::verify` for Y.
+#[derive(Debug, Clone)]
+pub struct MusigSession<G: DLogGroup> {
+    key_agg: KeyAggregation<G>,
+    commitments: Vec<NonceCommitment<G>>,
+    base_point: G,
+    r: G,
+    b: G::Scalar,
+    c: G::Scalar,
+}
+
+impl<G: DLogGroup> MusigSession<G> {
+    /// Sets up the session for a proof bound to `sid` and `pid`
+    ///
+    /// # Arguments
+    /// * `sid` - Session identifier used for domain separation, a string or bytes (see `SessionId`)
+    /// * `pid` - Participant ID for uniqueness to distinguish different proofs
+    /// * `key_agg` - The aggregated key of the participants
+    /// * `commitments` - Every participant's nonce commitment, in key order
+    /// * `base_point` - Base point of the group
+    ///
+    /// # Returns
+    /// * `Ok(MusigSession)` - The session
+    /// * `Err(DLogProofError)` - `StatementCount` if there isn't one commitment per key, or if
+    ///   a challenge can't be derived
+    pub fn new<'s>(
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        key_agg: &KeyAggregation<G>,
+        commitments: &[NonceCommitment<G>],
+        base_point: G,
+    ) -> Result<Self, DLogProofError> {
+        if commitments.len() != key_agg.keys.len() {
+            return Err(DLogProofError::StatementCount);
+        }
+        DLogProof::check_statement(key_agg.aggregate, base_point)?;
+        let sid = sid.into();
+        let (r1, r2) = commitments
+            .iter()
+            .fold((G::identity(), G::identity()), |(r1, r2), commitment| {
+                (r1 + commitment.r1, r2 + commitment.r2)
+            });
+
+        let y = key_agg.aggregate;
+        let b = DLogProof::hash_points_with_domain(NONCE_DOMAIN, sid, pid.into(), &[y, r1, r2])?;
+        let r = r1 + r2 * b;
+        let statement = DLogStatement { base_point, y };
+        let c = SigmaProof::<SchnorrDLog<G>>::challenge(sid, pid.into(), &statement, &r)?;
+        Ok(MusigSession {
+            key_agg: key_agg.clone(),
+            commitments: commitments.to_vec(),
+            base_point,
+            r,
+            b,
+            c,
+        })
+    }
+
+    /// Checks participant `index`'s partial response, to identify who broke a failed proof
+    ///
+    /// Verifies s_i * G = R_i1 + b * R_i2 + c * a_i * y_i.
+    pub fn verify_partial(&self, index: usize, response: &PartialResponse<G>) -> bool {
+        let (Some(commitment), Some(&y), Some(a)) = (
+            self.commitments.get(index),
+            self.key_agg.keys.get(index),
+            self.key_agg.coefficient(index),
+        ) else {
+            return false;
+        };
+        let expected = commitment.r1 + commitment.r2 * self.b + y * (self.c * a);
+        (self.base_point * response.s).ct_eq(&expected).into()
+    }
+
+    /// Combines the partial responses, in key order, into the proof (R, Σ s_i) for Y
+    ///
+    /// # Returns
+    /// * `Ok(DLogProof)` - The joint proof; check it with `DLogProof::verify`, or the partial
+    ///   responses with `verify_partial`, before relying on it
+    /// * `Err(DLogProofError)` - `StatementCount` if there isn't one response per participant
+    pub fn aggregate(
+        &self,
+        responses: &[PartialResponse<G>],
+    ) -> Result<DLogProof<G>, DLogProofError> {
+        if responses.len() != self.key_agg.keys.len() {
+            return Err(DLogProofError::StatementCount);
+        }
+        let s = responses
+            .iter()
+            .fold(G::Scalar::ZERO, |sum, response| sum + response.s);
+        Ok(DLogProof::new(self.r, s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::{ProjectivePoint, Scalar};
+
+    #[test]
+    fn joint_proofs_verify_for_the_aggregated_key() {
+        let g = ProjectivePoint::GENERATOR;
+        let shares: Vec<Scalar> = (0..3).map(|_| Scalar::random(&mut OsRng)).collect();
+        let keys: Vec<ProjectivePoint> = shares.iter().map(|x| g * x).collect();
+        let key_agg = KeyAggregation::new(&keys).unwrap();
+        // The coefficients make Y differ from the plain sum of the keys
+        assert_ne!(key_agg.aggregate_key(), keys[0] + keys[1] + keys[2]);
+
+        let (commitments, nonces): (Vec<_>, Vec<_>) =
+            shares.iter().map(|_| SecretNonces::commit(g)).unzip();
+        let json = serde_json::to_string(&commitments).unwrap();
+        let commitments: Vec<NonceCommitment<_>> = serde_json::from_str(&json).unwrap();
+        let session = MusigSession::new("sid", 1, &key_agg, &commitments, g).unwrap();
+
+        let responses: Vec<_> = nonces
+            .into_iter()
+            .zip(&shares)
+            .enumerate()
+            .map(|(i, (nonces, x))| nonces.respond(&session, i, x).unwrap())
+            .collect();
+        assert!((0..3).all(|i| session.verify_partial(i, &responses[i])));
+        assert!(!session.verify_partial(0, &responses[1]));
+
+        let proof = session.aggregate(&responses).unwrap();
+        let y = key_agg.aggregate_key();
+        assert!(proof.verify("sid", 1, y, g).unwrap());
+        assert!(!proof.verify("sid", 2, y, g).unwrap());
+    }
+
+    #[test]
+    fn a_wrong_share_breaks_the_proof() {
+        let g = ProjectivePoint::GENERATOR;
+        let shares = [Scalar::random(&mut OsRng), Scalar::random(&mut OsRng)];
+        let key_agg = KeyAggregation::new(&[g * shares[0], g * shares[1]]).unwrap();
+
+        let (c0, n0) = SecretNonces::commit(g);
+        let (c1, n1) = SecretNonces::commit(g);
+        let session = MusigSession::new("sid", 1, &key_agg, &[c0, c1], g).unwrap();
+        let responses = [
+            n0.respond(&session, 0, &shares[0]).unwrap(),
+            n1.respond(&session, 1, &Scalar::ONE).unwrap(),
+        ];
+        assert!(!session.verify_partial(1, &responses[1]));
+        let proof = session.aggregate(&responses).unwrap();
+        assert!(!proof.verify("sid", 1, key_agg.aggregate_key(), g).unwrap());
+        assert_eq!(
+            MusigSession::new("sid", 1, &key_agg, &[c0], g).unwrap_err(),
+            DLogProofError::StatementCount
+        );
+    }
+}
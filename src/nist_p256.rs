@@ -0,0 +1,72 @@
+use alloc::vec::Vec;
+use p256::{NistP256, ProjectivePoint};
+
+use crate::group::{decode_sec1, encode_sec1, DLogGroup};
+
+/// NIST P-256 (`p256` feature)
+///
+/// Points use compressed SEC1 hex and scalars 32-byte big-endian hex, exactly like the
+/// secp256k1 path. The P-256 order is only about 2^-32 below 2^256, so a SHA-256 challenge
+/// reduced modulo the order is measurably biased; `ChallengeHash::Sha512` gives uniform
+/// challenges.
+impl DLogGroup for ProjectivePoint {
+    fn encode_point(&self) -> Vec<u8> {
+        encode_sec1::<NistP256>(self)
+    }
+
+    fn decode_point(bytes: &[u8]) -> Option<Self> {
+        decode_sec1::<NistP256>(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dlog_proof::DLogProof;
+    use crate::group::DLogGroup;
+    use elliptic_curve::{Field, PrimeField};
+    use rand_core::OsRng;
+
+    /// Proves over G's generator and checks the proof survives a JSON round trip
+    fn prove_and_roundtrip<G: DLogGroup>(x: &G::Scalar) -> (String, G) {
+        let g = G::generator();
+        let y = g * x;
+        let proof = DLogProof::prove("sid", 1, x, y, g).unwrap();
+        assert!(proof.verify("sid", 1, y, g).unwrap());
+
+        let json = serde_json::to_string(&proof).unwrap();
+        let parsed: DLogProof<G> = serde_json::from_str(&json).unwrap();
+        assert!(parsed.verify("sid", 1, y, g).unwrap());
+        (json, y)
+    }
+
+    #[test]
+    fn p256_proofs_match_the_secp256k1_format() {
+        let x = p256::Scalar::random(&mut OsRng);
+        let (json, _) = prove_and_roundtrip::<p256::ProjectivePoint>(&x);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let t = value["t"].as_str().unwrap();
+        assert_eq!(t.len(), 66);
+        assert!(t.starts_with("02") || t.starts_with("03"));
+        assert_eq!(value["s"].as_str().unwrap().len(), 64);
+    }
+
+    #[test]
+    fn proofs_do_not_transfer_across_curves() {
+        // The same secret bytes, used as a key on both curves
+        let bytes = k256::Scalar::random(&mut OsRng).to_repr();
+        let x_k256 = k256::Scalar::from_repr(bytes).unwrap();
+        let x_p256 = p256::Scalar::from_repr(bytes).unwrap();
+
+        let (k256_json, k256_y) = prove_and_roundtrip::<k256::ProjectivePoint>(&x_k256);
+        let (p256_json, p256_y) = prove_and_roundtrip::<p256::ProjectivePoint>(&x_p256);
+
+        let g = k256::ProjectivePoint::GENERATOR;
+        if let Ok(proof) = serde_json::from_str::<DLogProof>(&p256_json) {
+            assert!(!proof.verify("sid", 1, k256_y, g).unwrap());
+        }
+        let g = p256::ProjectivePoint::GENERATOR;
+        if let Ok(proof) = serde_json::from_str::<DLogProof<p256::ProjectivePoint>>(&k256_json) {
+            assert!(!proof.verify("sid", 1, p256_y, g).unwrap());
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Node.js native bindings through napi-rs (`node` feature)
+//!
+//! The same secp256k1 API as the `wasm` bindings, for servers: proofs over the standard
+//! generator as 65-byte `Buffer`s in the encoding of `DLogProof::to_bytes`, 32-byte
+//! big-endian secret keys and SEC1 public keys. `verifyBatchAsync` runs the batch check on
+//! the libuv thread pool and resolves a `Promise`, so verifying many proofs doesn't block
+//! the event loop. Errors are thrown as JavaScript `Error`s carrying the `DLogProofError`
+//! message followed by its stable code, as in `Invalid point encoding (code 1)`.
+//!
+//! Build the addon from a crate of `crate-type = ["cdylib"]` that depends on this one with
+//! the feature, as for the `wasm` bindings:
+//!
+//! ```js
+//! const { prove, publicKey, verifyBatchAsync } = require("./schnorr-dlog-zkp.node");
+//!
+//! const proof = prove("sid", 1, secretKey);
+//! await verifyBatchAsync([{ sid: "sid", pid: 1, publicKey: publicKey(secretKey), proof }]);
+//! ```
+
+use k256::ProjectivePoint;
+use napi::bindgen_prelude::{AsyncTask, Buffer};
+use napi::{Env, Error, Result, Task};
+use napi_derive::napi;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::keypair::KeyPair;
+
+impl From<DLogProofError> for Error {
+    fn from(e: DLogProofError) -> Self {
+        Error::from_reason(format!("{e} (code {})", e.code()))
+    }
+}
+
+/// One statement of a batch: `{ sid, pid, publicKey, proof }`
+#[napi(object)]
+pub struct BatchEntry {
+    pub sid: String,
+    pub pid: u32,
+    pub public_key: Buffer,
+    pub proof: Buffer,
+}
+
+/// Batch decoded on the JavaScript thread, verified on the libuv thread pool
+pub struct VerifyBatchTask {
+    statements: Vec<(String, u32, ProjectivePoint, DLogProof)>,
+}
+
+impl Task for VerifyBatchTask {
+    type Output = bool;
+    type JsValue = bool;
+
+    fn compute(&mut self) -> Result<bool> {
+        Ok(verify_statements(&self.statements)?)
+    }
+
+    fn resolve(&mut self, _env: Env, output: bool) -> Result<bool> {
+        Ok(output)
+    }
+}
+
+/// Proves knowledge of `secretKey` for the session and participant, returning the 65-byte proof
+#[napi]
+pub fn prove(sid: String, pid: u32, secret_key: Buffer) -> Result<Buffer> {
+    let key_pair = KeyPair::<ProjectivePoint>::from_secret_bytes(&secret_key)?;
+    Ok(key_pair
+        .prove(sid.as_str(), pid)?
+        .to_bytes()
+        .to_vec()
+        .into())
+}
+
+/// Verifies a 65-byte proof of knowledge of the secret key of `publicKey`
+#[napi]
+pub fn verify(sid: String, pid: u32, public_key: Buffer, proof: Buffer) -> Result<bool> {
+    let y = decode_public_key(&public_key)?;
+    let proof = DLogProof::from_bytes(&proof)?;
+    Ok(proof.verify(sid.as_str(), pid, y, ProjectivePoint::GENERATOR)?)
+}
+
+/// The compressed SEC1 public key of a 32-byte big-endian secret key
+#[napi]
+pub fn public_key(secret_key: Buffer) -> Result<Buffer> {
+    let key_pair = KeyPair::<ProjectivePoint>::from_secret_bytes(&secret_key)?;
+    Ok(key_pair.public().encode_point().into())
+}
+
+/// Verifies a batch of proofs at once, true only if all of them are valid
+#[napi]
+pub fn verify_batch(entries: Vec<BatchEntry>) -> Result<bool> {
+    Ok(verify_statements(&decode_batch(&entries)?)?)
+}
+
+/// Like `verifyBatch`, on the libuv thread pool, resolving to whether all proofs are valid
+#[napi]
+pub fn verify_batch_async(entries: Vec<BatchEntry>) -> Result<AsyncTask<VerifyBatchTask>> {
+    Ok(AsyncTask::new(VerifyBatchTask {
+        statements: decode_batch(&entries)?,
+    }))
+}
+
+fn decode_public_key(bytes: &[u8]) -> std::result::Result<ProjectivePoint, DLogProofError> {
+    ProjectivePoint::decode_point(bytes).ok_or(DLogProofError::InvalidPoint)
+}
+
+fn decode_batch(
+    entries: &[BatchEntry],
+) -> std::result::Result<Vec<(String, u32, ProjectivePoint, DLogProof)>, DLogProofError> {
+    entries
+        .iter()
+        .map(|entry| {
+            Ok((
+                entry.sid.clone(),
+                entry.pid,
+                decode_public_key(&entry.public_key)?,
+                DLogProof::from_bytes(&entry.proof)?,
+            ))
+        })
+        .collect()
+}
+
+fn verify_statements(
+    statements: &[(String, u32, ProjectivePoint, DLogProof)],
+) -> std::result::Result<bool, DLogProofError> {
+    let items: Vec<_> = statements
+        .iter()
+        .map(|(sid, pid, y, proof)| (sid.into(), (*pid).into(), *y, proof))
+        .collect();
+    DLogProof::verify_batch(&items, ProjectivePoint::GENERATOR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::{Field, PrimeField};
+    use k256::Scalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn bindings_prove_and_verify_single_and_batched() {
+        let entries: Vec<_> = (1..=3)
+            .map(|pid| {
+                let secret_key = Buffer::from(Scalar::random(&mut OsRng).to_repr().to_vec());
+                BatchEntry {
+                    sid: "sid".into(),
+                    pid,
+                    public_key: public_key(secret_key.clone()).unwrap(),
+                    proof: prove("sid".into(), pid, secret_key).unwrap(),
+                }
+            })
+            .collect();
+        let first = &entries[0];
+        assert!(verify(
+            "sid".into(),
+            1,
+            first.public_key.clone(),
+            first.proof.clone()
+        )
+        .unwrap());
+        assert!(!verify(
+            "sid".into(),
+            2,
+            first.public_key.clone(),
+            first.proof.clone()
+        )
+        .unwrap());
+
+        let mut task = VerifyBatchTask {
+            statements: decode_batch(&entries).unwrap(),
+        };
+        assert!(task.compute().unwrap());
+        assert!(verify_batch(entries).unwrap());
+
+        let Err(error) = prove("sid".into(), 1, Buffer::from(vec![0; 32])) else {
+            panic!("a zero secret key must be rejected");
+        };
+        assert_eq!(error.reason, "Invalid Scalar value (code 2)");
+    }
+}
@@ -0,0 +1,257 @@
+use elliptic_curve::subtle::{Choice, ConstantTimeEq};
+use elliptic_curve::Field;
+use k256::ProjectivePoint;
+use rand_core::{CryptoRngCore, OsRng};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use zeroize::Zeroizing;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::secret::SecretScalar;
+use crate::serialization::{PointHex, ScalarHex};
+use crate::session::SessionId;
+
+/// One branch (t_i, c_i, s_i) of an `OrProof`, real or simulated
+#[serde_as]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(bound = "")]
+struct Branch<G: DLogGroup> {
+    #[serde_as(as = "PointHex")]
+    t: G,
+    #[serde_as(as = "ScalarHex")]
+    c: G::Scalar,
+    #[serde_as(as = "ScalarHex")]
+    s: G::Scalar,
+}
+
+/// Proof of knowledge of the discrete log of one of y_1, ..., y_n, without revealing which
+///
+/// The Cramer-Damgård-Schoenmakers OR-composition of Schnorr proofs. For every branch the
+/// prover doesn't know the secret of, it picks c_i and s_i first and simulates the commitment
+/// t_i = s_i * G - c_i * y_i. The Fiat-Shamir challenge c hashes all the commitments, and the
+/// real branch gets c_k = c - Σ c_i, the only challenge it can't choose. The verifier checks
+/// Σ c_i = c and s_i * G = t_i + c_i * y_i for every branch; real and simulated branches are
+/// distributed identically.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct OrProof<G: DLogGroup = ProjectivePoint> {
+    branches: Vec<Branch<G>>,
+}
+
+impl<G: DLogGroup> OrProof<G> {
+    const DOMAIN_SEPARATOR: &'static [u8] = b"SCHNORR_OR_PROOF";
+
+    /// Proves knowledge of `x` with ys[index] = x * G, hiding `index` among `ys`
+    ///
+    /// # Arguments
+    /// * `sid` - Session identifier used for domain separation, a string or bytes (see `SessionId`)
+    /// * `pid` - Participant ID for uniqueness to distinguish different proofs
+    /// * `x` - The secret scalar of the known branch
+    /// * `index` - Position of the known branch in `ys`
+    /// * `ys` - The public points of all branches
+    /// * `base_point` - Base point shared by all branches
+    ///
+    /// # Returns
+    /// * `Ok(OrProof)` - The proof
+    /// * `Err(DLogProofError)` - `StatementCount` if `ys` is empty or `index` is out of range,
+    ///   `IdentityPoint` for a degenerate branch, or if the challenge can't be derived
+    pub fn prove<'s>(
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        index: usize,
+        ys: &[G],
+        base_point: G,
+    ) -> Result<Self, DLogProofError> {
+        Self::prove_with_rng(&mut OsRng, sid, pid, x, index, ys, base_point)
+    }
+
+    /// Proves like `prove`, drawing the nonce and the simulated branches from `rng`
+    pub fn prove_with_rng<'s>(
+        rng: &mut impl CryptoRngCore,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        index: usize,
+        ys: &[G],
+        base_point: G,
+    ) -> Result<Self, DLogProofError> {
+        Self::check_statement(ys, base_point)?;
+        if index >= ys.len() {
+            return Err(DLogProofError::StatementCount);
+        }
+        let x = x.into();
+
+        let r = Zeroizing::new(G::Scalar::random(&mut *rng));
+        let mut branches: Vec<Branch<G>> = ys
+            .iter()
+            .enumerate()
+            .map(|(i, &y)| {
+                if i == index {
+                    Branch {
+                        t: base_point * *r,
+                        c: G::Scalar::ZERO,
+                        s: G::Scalar::ZERO,
+                    }
+                } else {
+                    let c = G::Scalar::random(&mut *rng);
+                    let s = G::Scalar::random(&mut *rng);
+                    Branch {
+                        t: base_point * s - y * c,
+                        c,
+                        s,
+                    }
+                }
+            })
+            .collect();
+
+        let c = Self::challenge(sid.into(), pid, ys, base_point, &branches)?;
+        let simulated = branches.iter().fold(G::Scalar::ZERO, |sum, b| sum + b.c);
+        let real = &mut branches[index];
+        real.c = c - simulated;
+        real.s = *r + real.c * x.expose_secret();
+        Ok(OrProof { branches })
+    }
+
+    /// Verifies that the prover knows the discrete log of at least one of `ys`
+    ///
+    /// A proof for a different number of branches is invalid, not an error.
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Validity of the proof
+    /// * `Err(DLogProofError)` - `StatementCount` if `ys` is empty, `IdentityPoint` for a
+    ///   degenerate branch, or if the challenge can't be derived
+    pub fn verify<'s>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        ys: &[G],
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        Self::check_statement(ys, base_point)?;
+        if self.branches.len() != ys.len() {
+            return Ok(false);
+        }
+        let c = Self::challenge(sid.into(), pid, ys, base_point, &self.branches)?;
+
+        let sum = self
+            .branches
+            .iter()
+            .fold(G::Scalar::ZERO, |sum, b| sum + b.c);
+        let valid = self
+            .branches
+            .iter()
+            .zip(ys)
+            .fold(sum.ct_eq(&c), |valid, (b, &y)| {
+                valid & (base_point * b.s).ct_eq(&(b.t + y * b.c))
+            });
+        Ok(valid.into())
+    }
+
+    /// Number of branches the proof hides the known one among
+    pub fn len(&self) -> usize {
+        self.branches.len()
+    }
+
+    /// Whether the proof has no branches, which no valid proof has
+    pub fn is_empty(&self) -> bool {
+        self.branches.is_empty()
+    }
+
+    /// Rejects empty disjunctions and identity points like `DLogProof`
+    fn check_statement(ys: &[G], base_point: G) -> Result<(), DLogProofError> {
+        if ys.is_empty() {
+            return Err(DLogProofError::StatementCount);
+        }
+        ys.iter()
+            .try_for_each(|&y| DLogProof::check_statement(y, base_point))
+    }
+
+    /// Fiat-Shamir challenge over the base point, every y_i and then every t_i
+    fn challenge(
+        sid: SessionId<'_>,
+        pid: u32,
+        ys: &[G],
+        base_point: G,
+        branches: &[Branch<G>],
+    ) -> Result<G::Scalar, DLogProofError> {
+        let mut points = vec![base_point];
+        points.extend_from_slice(ys);
+        points.extend(branches.iter().map(|b| b.t));
+        DLogProof::hash_points_with_domain(Self::DOMAIN_SEPARATOR, sid, pid.into(), &points)
+    }
+}
+
+impl<G: DLogGroup> ConstantTimeEq for OrProof<G> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        if self.branches.len() != other.branches.len() {
+            return Choice::from(0);
+        }
+        self.branches
+            .iter()
+            .zip(&other.branches)
+            .fold(Choice::from(1), |eq, (a, b)| {
+                eq & a.t.ct_eq(&b.t) & a.c.ct_eq(&b.c) & a.s.ct_eq(&b.s)
+            })
+    }
+}
+
+impl<G: DLogGroup> PartialEq for OrProof<G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<G: DLogGroup> Eq for OrProof<G> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::Scalar;
+
+    #[test]
+    fn or_proofs_verify_for_any_known_branch() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let others = [
+            g * Scalar::random(&mut OsRng),
+            g * Scalar::random(&mut OsRng),
+        ];
+
+        for index in 0..3 {
+            let mut ys = others.to_vec();
+            ys.insert(index, g * x);
+            let proof = OrProof::prove("sid", 1, &x, index, &ys, g).unwrap();
+            assert!(proof.verify("sid", 1, &ys, g).unwrap());
+            assert!(!proof.verify("sid", 2, &ys, g).unwrap());
+            assert!(!proof.verify("sid", 1, &ys[..2], g).unwrap());
+
+            let json = serde_json::to_string(&proof).unwrap();
+            let decoded: OrProof = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, proof);
+        }
+    }
+
+    #[test]
+    fn or_proofs_need_one_known_secret() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let ys = [
+            g * Scalar::random(&mut OsRng),
+            g * Scalar::random(&mut OsRng),
+        ];
+
+        let proof = OrProof::prove("sid", 1, &x, 0, &ys, g).unwrap();
+        assert!(!proof.verify("sid", 1, &ys, g).unwrap());
+        assert_eq!(
+            OrProof::prove("sid", 1, &x, 2, &ys, g),
+            Err(DLogProofError::StatementCount)
+        );
+        assert_eq!(
+            OrProof::prove("sid", 1, &x, 0, &[g * x, ProjectivePoint::IDENTITY], g),
+            Err(DLogProofError::IdentityPoint)
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Participant identifier bound into every challenge, a `u32` index or arbitrary bytes
+///
+/// Proving and verification functions take `impl Into<ParticipantId>`, so a `u32` works as
+/// before and gives the same challenges, while identifiers such as UUIDs or public-key
+/// fingerprints are passed as bytes or strings:
+/// `DLogProof::prove(sid, &uuid, &x, y, g)` with `uuid: [u8; 16]`.
+///
+/// Byte identifiers enter the transcript with their length: in transcript v1 as an 8-byte
+/// big-endian length followed by the bytes, in v2 as a `pidb` field. An index never hashes
+/// like a byte identifier, not even like its own four bytes.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParticipantId<'a>(Repr<'a>);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Repr<'a> {
+    Index(u32),
+    Bytes(&'a [u8]),
+}
+
+impl<'a> ParticipantId<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        ParticipantId(Repr::Bytes(bytes))
+    }
+
+    /// The index, for identifiers made from a `u32`
+    pub fn index(&self) -> Option<u32> {
+        match self.0 {
+            Repr::Index(index) => Some(index),
+            Repr::Bytes(_) => None,
+        }
+    }
+
+    /// The bytes, for identifiers made from bytes or a string
+    pub fn as_bytes(&self) -> Option<&'a [u8]> {
+        match self.0 {
+            Repr::Index(_) => None,
+            Repr::Bytes(bytes) => Some(bytes),
+        }
+    }
+
+    /// Appends the identifier to a v1 transcript
+    pub(crate) fn absorb_v1(&self, hasher: &mut impl hmac::digest::Update) {
+        match self.0 {
+            Repr::Index(index) => hasher.update(&index.to_le_bytes()),
+            Repr::Bytes(bytes) => {
+                // One update, so the identifier stays a single transcript entry
+                let mut entry = Vec::with_capacity(8 + bytes.len());
+                entry.extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+                entry.extend_from_slice(bytes);
+                hasher.update(&entry);
+            }
+        }
+    }
+
+    /// The label and value of the identifier's field in a v2 transcript
+    pub(crate) fn v2_field(&self) -> (&'static [u8], Vec<u8>) {
+        match self.0 {
+            Repr::Index(index) => (b"pid", index.to_be_bytes().to_vec()),
+            Repr::Bytes(bytes) => (b"pidb", bytes.to_vec()),
+        }
+    }
+}
+
+impl From<u32> for ParticipantId<'_> {
+    fn from(pid: u32) -> Self {
+        ParticipantId(Repr::Index(pid))
+    }
+}
+
+impl<'a> From<&'a str> for ParticipantId<'a> {
+    fn from(pid: &'a str) -> Self {
+        ParticipantId::new(pid.as_bytes())
+    }
+}
+
+impl<'a> From<&'a String> for ParticipantId<'a> {
+    fn from(pid: &'a String) -> Self {
+        ParticipantId::new(pid.as_bytes())
+    }
+}
+
+impl<'a> From<&'a [u8]> for ParticipantId<'a> {
+    fn from(pid: &'a [u8]) -> Self {
+        ParticipantId::new(pid)
+    }
+}
+
+impl<'a, const N: usize> From<&'a [u8; N]> for ParticipantId<'a> {
+    fn from(pid: &'a [u8; N]) -> Self {
+        ParticipantId::new(pid)
+    }
+}
+
+impl<'a> From<&'a Vec<u8>> for ParticipantId<'a> {
+    fn from(pid: &'a Vec<u8>) -> Self {
+        ParticipantId::new(pid)
+    }
+}
+
+/// Shows indices as numbers, UTF-8 identifiers as strings and binary ones as hex
+impl fmt::Display for ParticipantId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Repr::Index(index) => write!(f, "{index}"),
+            Repr::Bytes(bytes) => match core::str::from_utf8(bytes) {
+                Ok(pid) => write!(f, "{pid:?}"),
+                Err(_) => write!(f, "0x{}", hex::encode(bytes)),
+            },
+        }
+    }
+}
+
+impl fmt::Debug for ParticipantId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ParticipantId({self})")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlog_proof::{DLogProof, TranscriptVersion};
+    use crate::prover::Prover;
+    use crate::verifier::Verifier;
+    use elliptic_curve::Field;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_core::OsRng;
+
+    #[test]
+    fn byte_participant_ids_prove_and_stay_apart_from_indices() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+
+        let uuid = [0x5au8; 16];
+        let proof = DLogProof::prove("sid", &uuid, &x, y, g).unwrap();
+        assert!(proof.verify("sid", &uuid, y, g).unwrap());
+        assert!(!proof.verify("sid", &[0x5au8; 15], y, g).unwrap());
+
+        // An index is not its own bytes, in either transcript version
+        for version in [TranscriptVersion::V1, TranscriptVersion::V2] {
+            let prover = Prover::new().transcript_version(version);
+            let verifier = Verifier::new().transcript_version(version);
+            let proof = prover.prove("sid", 7, &x, y, g).unwrap();
+            assert!(verifier.verify(&proof, "sid", 7, y, g).unwrap());
+            for bytes in [7u32.to_le_bytes(), 7u32.to_be_bytes()] {
+                assert!(!verifier.verify(&proof, "sid", &bytes, y, g).unwrap());
+            }
+            let proof = prover.prove("sid", "alice", &x, y, g).unwrap();
+            assert!(verifier.verify(&proof, "sid", b"alice", y, g).unwrap());
+        }
+
+        assert_eq!(format!("{:?}", ParticipantId::from(7)), "ParticipantId(7)");
+        assert_eq!(ParticipantId::from("ab").to_string(), r#""ab""#);
+        assert_eq!(ParticipantId::from(&[0xffu8, 0]).to_string(), "0xff00");
+        assert_eq!(ParticipantId::from(7).index(), Some(7));
+        assert_eq!(ParticipantId::from("ab").as_bytes(), Some(&b"ab"[..]));
+    }
+}
@@ -0,0 +1,481 @@
+use alloc::vec::Vec;
+#[cfg(feature = "prove")]
+use elliptic_curve::group::Group;
+use elliptic_curve::subtle::{ConditionallySelectable, ConstantTimeEq};
+use elliptic_curve::PrimeField;
+use k256::ProjectivePoint;
+#[cfg(feature = "prove")]
+use rand_core::CryptoRngCore;
+#[cfg(feature = "std")]
+use rand_core::OsRng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use zeroize::Zeroize;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::participant::ParticipantId;
+#[cfg(feature = "prove")]
+use crate::prover::Prover;
+#[cfg(feature = "prove")]
+use crate::secret::SecretScalar;
+use crate::session::SessionId;
+#[cfg(feature = "verify")]
+use crate::verifier::{Timing, Verifier};
+
+/// Multiples j * 16^i * P of a fixed point P, for computing k * P a 4-bit window at a time
+///
+/// A multiplication is one table lookup and one addition per window (64 of each for a
+/// 256-bit scalar) instead of a double-and-add over every bit. Lookups scan the whole window
+/// and pick the entry with `ConditionallySelectable`, so neither the memory access pattern nor
+/// the timing depends on the scalar. The table of a secp256k1 point holds 1024 points (about
+/// 120 KiB).
+#[derive(Debug, Clone)]
+pub struct FixedBaseTable<G: DLogGroup + ConditionallySelectable> {
+    point: G,
+    windows: Vec<[G; 16]>,
+}
+
+impl<G: DLogGroup + ConditionallySelectable> FixedBaseTable<G> {
+    /// Precomputes the table of `point`
+    pub fn new(point: G) -> Self {
+        let window_count = 2 * <G::Scalar as PrimeField>::Repr::default().as_ref().len();
+        let mut windows = Vec::with_capacity(window_count);
+        let mut base = point;
+        for _ in 0..window_count {
+            let mut window = [G::identity(); 16];
+            for j in 1..16 {
+                window[j] = window[j - 1] + base;
+            }
+            base = window[15] + base;
+            windows.push(window);
+        }
+        FixedBaseTable { point, windows }
+    }
+
+    /// The point P the table is for
+    pub fn point(&self) -> G {
+        self.point
+    }
+
+    /// Computes k * P in constant time
+    pub fn mul(&self, k: &G::Scalar) -> G {
+        let mut repr = k.to_repr();
+        let mut sum = G::identity();
+        for (index, window) in self.windows.iter().enumerate() {
+            let digit = digit::<G::Scalar>(repr.as_ref(), index);
+            let mut entry = G::identity();
+            for (j, candidate) in window.iter().enumerate() {
+                entry.conditional_assign(candidate, (j as u8).ct_eq(&digit));
+            }
+            sum += entry;
+        }
+        repr.as_mut().zeroize();
+        sum
+    }
+
+    /// Computes k * P, with a memory access pattern and timing depending on k
+    ///
+    /// Only for public scalars, such as those of a proof being verified.
+    #[cfg(feature = "verify")]
+    pub fn mul_vartime(&self, k: &G::Scalar) -> G {
+        let repr = k.to_repr();
+        let mut sum = G::identity();
+        for (index, window) in self.windows.iter().enumerate() {
+            let digit = digit::<G::Scalar>(repr.as_ref(), index);
+            if digit != 0 {
+                sum += window[digit as usize];
+            }
+        }
+        sum
+    }
+}
+
+/// The `index`-th 4-bit digit, least significant first, of the encoding of a scalar
+fn digit<F: PrimeField>(repr: &[u8], index: usize) -> u8 {
+    // Scalar encodings are big-endian (RustCrypto curves) or little-endian (dalek,
+    // bls12_381); the encoding of one tells which
+    let big_endian = F::ONE.to_repr().as_ref()[0] == 0;
+    let byte = repr[if big_endian {
+        repr.len() - 1 - index / 2
+    } else {
+        index / 2
+    }];
+    if index.is_multiple_of(2) {
+        byte & 0x0f
+    } else {
+        byte >> 4
+    }
+}
+
+/// Prover for a fixed base point, with its multiples precomputed
+///
+/// Every proof computes the commitment t = r * G for a fresh nonce r. A context builds a
+/// `FixedBaseTable` for G once, making that multiplication several times faster, and then
+/// proves any number of statements y = x * G over the same base point with a `Prover`
+/// configuration. The proofs are exactly those of `Prover::prove`, so they verify as usual;
+/// `dlog bench` reports the speed-up as `prove_precomputed`.
+///
+/// ```
+/// # use elliptic_curve::Field;
+/// # use schnorr_dlog_zkp::k256::{ProjectivePoint, Scalar};
+/// # use schnorr_dlog_zkp::precomputed::ProverContext;
+/// let context = ProverContext::new(ProjectivePoint::GENERATOR);
+/// let x = Scalar::random(&mut rand_core::OsRng);
+/// let y = ProjectivePoint::GENERATOR * x;
+/// let proof = context.prove("sid", 1, &x, y)?;
+/// assert!(proof.verify("sid", 1, y, ProjectivePoint::GENERATOR)?);
+/// # Ok::<(), schnorr_dlog_zkp::DLogProofError>(())
+/// ```
+#[cfg(feature = "prove")]
+#[derive(Debug, Clone)]
+pub struct ProverContext<G: DLogGroup + ConditionallySelectable = ProjectivePoint> {
+    prover: Prover,
+    table: FixedBaseTable<G>,
+}
+
+#[cfg(feature = "prove")]
+impl<G: DLogGroup + ConditionallySelectable> ProverContext<G> {
+    /// Precomputes the table of `base_point` for proofs with the default `Prover`
+    pub fn new(base_point: G) -> Self {
+        ProverContext {
+            prover: Prover::default(),
+            table: FixedBaseTable::new(base_point),
+        }
+    }
+
+    /// Sets the prover configuration (blinding, challenge options, ...)
+    pub fn prover(mut self, prover: Prover) -> Self {
+        self.prover = prover;
+        self
+    }
+
+    /// The base point G
+    pub fn base_point(&self) -> G {
+        self.table.point()
+    }
+
+    /// Creates a proof of knowledge of `x` for y = x * G, see `DLogProof::prove`
+    #[cfg(feature = "std")]
+    pub fn prove<'s, 'p>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: G,
+    ) -> Result<DLogProof<G>, DLogProofError> {
+        self.prove_with_rng(&mut OsRng, sid, pid, x, y)
+    }
+
+    /// Creates a proof like `prove`, drawing the nonce and blinding masks from `rng`
+    ///
+    /// See `DLogProof::prove_with_rng` for the requirements on `rng`.
+    pub fn prove_with_rng<'s, 'p>(
+        &self,
+        rng: &mut impl CryptoRngCore,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: G,
+    ) -> Result<DLogProof<G>, DLogProofError> {
+        DLogProof::prove_with_base_mul(
+            &self.prover,
+            rng,
+            sid.into(),
+            pid.into(),
+            &x.into(),
+            y,
+            self.base_point(),
+            false,
+            |k| self.table.mul(k),
+        )
+        .map(|(proof, _)| proof)
+    }
+}
+
+/// One statement to prove in a batch: session ID, participant ID, secret x and y = x * G
+#[cfg(feature = "prove")]
+pub type ProveItem<'a, G> = (
+    SessionId<'a>,
+    ParticipantId<'a>,
+    &'a SecretScalar<<G as Group>::Scalar>,
+    G,
+);
+
+#[cfg(feature = "std")]
+impl<G: DLogGroup + ConditionallySelectable> ProverContext<G> {
+    /// Proves every statement of `items` over the context's base point, in order
+    ///
+    /// Each item gets its own result, so a bad statement (e.g. y = 0) fails alone without
+    /// aborting the rest of the batch.
+    pub fn prove_batch(&self, items: &[ProveItem<G>]) -> Vec<Result<DLogProof<G>, DLogProofError>> {
+        items
+            .iter()
+            .map(|&(sid, pid, x, y)| self.prove(sid, pid, x, y))
+            .collect()
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<G: DLogGroup + ConditionallySelectable + Send + Sync> ProverContext<G> {
+    /// Proves a batch like `prove_batch`, spreading the items over the rayon thread pool
+    /// (`parallel` feature)
+    ///
+    /// The table is shared by all threads and every proof draws its nonce from `OsRng`; the
+    /// results keep the order of `items`.
+    pub fn prove_batch_parallel(
+        &self,
+        items: &[ProveItem<G>],
+    ) -> Vec<Result<DLogProof<G>, DLogProofError>> {
+        items
+            .par_iter()
+            .map(|&(sid, pid, x, y)| self.prove(sid, pid, x, y))
+            .collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<G: DLogGroup + ConditionallySelectable> DLogProof<G> {
+    /// Proves many statements over the same base point with the default `Prover`
+    ///
+    /// The base point table of a `ProverContext` is built once for the whole batch, which pays
+    /// off from a handful of proofs on. The results are in the order of `items`, one per item;
+    /// use a `ProverContext` directly for other prover options or to reuse the table across
+    /// batches.
+    ///
+    /// ```
+    /// # use elliptic_curve::Field;
+    /// # use schnorr_dlog_zkp::k256::{ProjectivePoint, Scalar};
+    /// # use schnorr_dlog_zkp::{DLogProof, SecretScalar};
+    /// let g = ProjectivePoint::GENERATOR;
+    /// let keys: Vec<_> = (0..3)
+    ///     .map(|_| SecretScalar::new(Scalar::random(&mut rand_core::OsRng)))
+    ///     .collect();
+    /// let items: Vec<_> = (1u32..).zip(&keys).map(|(pid, x)| ("sid".into(), pid.into(), x, g * x.expose_secret())).collect();
+    /// for (result, &(_, pid, _, y)) in DLogProof::prove_batch(&items, g).into_iter().zip(&items) {
+    ///     assert!(result?.verify("sid", pid, y, g)?);
+    /// }
+    /// # Ok::<(), schnorr_dlog_zkp::DLogProofError>(())
+    /// ```
+    pub fn prove_batch(
+        items: &[ProveItem<G>],
+        base_point: G,
+    ) -> Vec<Result<DLogProof<G>, DLogProofError>> {
+        ProverContext::new(base_point).prove_batch(items)
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<G: DLogGroup + ConditionallySelectable + Send + Sync> DLogProof<G> {
+    /// Proves a batch like `prove_batch` on the rayon thread pool (`parallel` feature)
+    pub fn prove_batch_parallel(
+        items: &[ProveItem<G>],
+        base_point: G,
+    ) -> Vec<Result<DLogProof<G>, DLogProofError>> {
+        ProverContext::new(base_point).prove_batch_parallel(items)
+    }
+}
+
+/// Verifier for the proofs of one public key y, with the multiples of y and of the base point
+/// precomputed
+///
+/// Verification spends most of its time on s * G and c * y. When many proofs of the same key
+/// are checked, such as the per-request proofs of a long-lived session, a context computes
+/// both from tables built once for the key and verifies about twice as fast. The tables are
+/// read with `FixedBaseTable::mul`, or with `mul_vartime` if the verifier selects
+/// `Timing::Variable`; `dlog bench` reports the speed-up as `verify_precomputed`.
+///
+/// ```
+/// # use elliptic_curve::Field;
+/// # use schnorr_dlog_zkp::k256::{ProjectivePoint, Scalar};
+/// # use schnorr_dlog_zkp::precomputed::VerifierContext;
+/// # use schnorr_dlog_zkp::DLogProof;
+/// # let g = ProjectivePoint::GENERATOR;
+/// # let x = Scalar::random(&mut rand_core::OsRng);
+/// # let y = g * x;
+/// let context = VerifierContext::new(y, g);
+/// for pid in 1..=3 {
+///     let proof = DLogProof::prove("session", pid, &x, y, g)?;
+///     assert!(context.verify(&proof, "session", pid)?);
+/// }
+/// # Ok::<(), schnorr_dlog_zkp::DLogProofError>(())
+/// ```
+#[cfg(feature = "verify")]
+#[derive(Debug, Clone)]
+pub struct VerifierContext<G: DLogGroup + ConditionallySelectable = ProjectivePoint> {
+    verifier: Verifier,
+    base_table: FixedBaseTable<G>,
+    key_table: FixedBaseTable<G>,
+}
+
+#[cfg(feature = "verify")]
+impl<G: DLogGroup + ConditionallySelectable> VerifierContext<G> {
+    /// Precomputes the tables of `y` and `base_point` for verification with the default
+    /// `Verifier`
+    pub fn new(y: G, base_point: G) -> Self {
+        VerifierContext {
+            verifier: Verifier::default(),
+            base_table: FixedBaseTable::new(base_point),
+            key_table: FixedBaseTable::new(y),
+        }
+    }
+
+    /// Sets the verifier configuration the proofs are checked with
+    pub fn verifier(mut self, verifier: Verifier) -> Self {
+        self.verifier = verifier;
+        self
+    }
+
+    /// The public key y
+    pub fn public_key(&self) -> G {
+        self.key_table.point()
+    }
+
+    /// The base point G
+    pub fn base_point(&self) -> G {
+        self.base_table.point()
+    }
+
+    /// Verifies `proof` for y = x * G, with the same result as `Verifier::verify`
+    pub fn verify<'s, 'p>(
+        &self,
+        proof: &DLogProof<G>,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+    ) -> Result<bool, DLogProofError> {
+        proof
+            .verify_with_equation(
+                self.verifier.params(),
+                sid.into(),
+                pid.into(),
+                self.public_key(),
+                self.base_point(),
+                false,
+                |s, c| {
+                    let lhs = match self.verifier.params().timing {
+                        Timing::Constant => self.base_table.mul(s) - self.key_table.mul(c),
+                        Timing::Variable => {
+                            self.base_table.mul_vartime(s) - self.key_table.mul_vartime(c)
+                        }
+                    };
+                    (lhs, 2)
+                },
+            )
+            .map(|report| report.is_valid())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlog_proof::ChallengeMode;
+    use elliptic_curve::Field;
+    use k256::Scalar;
+    use rand_chacha::rand_core::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::OsRng;
+
+    #[test]
+    fn tables_multiply_like_the_group() {
+        let point = ProjectivePoint::GENERATOR * Scalar::random(&mut OsRng);
+        let table = FixedBaseTable::new(point);
+        for k in [
+            Scalar::ZERO,
+            Scalar::ONE,
+            -Scalar::ONE,
+            Scalar::random(&mut OsRng),
+        ] {
+            assert_eq!(table.mul(&k), point * k);
+        }
+    }
+
+    #[test]
+    fn contexts_make_the_proofs_of_the_prover() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        for prover in [Prover::new(), Prover::new().blinded(true)] {
+            let context = ProverContext::new(g).prover(prover.clone());
+            let proof = context
+                .prove_with_rng(&mut ChaCha20Rng::seed_from_u64(7), "sid", 1, &x, y)
+                .unwrap();
+            let expected = prover
+                .prove_with_rng(&mut ChaCha20Rng::seed_from_u64(7), "sid", 1, &x, y, g)
+                .unwrap();
+            assert_eq!(proof, expected);
+            assert!(proof.verify("sid", 1, y, g).unwrap());
+        }
+    }
+
+    #[test]
+    fn batches_are_proven_in_order_with_per_item_errors() {
+        let g = ProjectivePoint::GENERATOR;
+        let keys: Vec<SecretScalar<Scalar>> = (0..5)
+            .map(|_| SecretScalar::new(Scalar::random(&mut OsRng)))
+            .collect();
+        let mut items: Vec<ProveItem<ProjectivePoint>> = (1u32..)
+            .zip(&keys)
+            .map(|(pid, x)| ("batch".into(), pid.into(), x, g * x.expose_secret()))
+            .collect();
+        items[2].3 = ProjectivePoint::IDENTITY;
+
+        let check = |results: Vec<Result<DLogProof, DLogProofError>>| {
+            assert_eq!(results.len(), items.len());
+            for (result, &(sid, pid, _, y)) in results.into_iter().zip(&items) {
+                if pid.index() == Some(3) {
+                    assert_eq!(result, Err(DLogProofError::IdentityPoint));
+                } else {
+                    assert!(result.unwrap().verify(sid, pid, y, g).unwrap());
+                }
+            }
+        };
+        check(DLogProof::prove_batch(&items, g));
+        #[cfg(feature = "parallel")]
+        check(DLogProof::prove_batch_parallel(&items, g));
+        assert!(DLogProof::prove_batch(&[], g).is_empty());
+    }
+
+    #[test]
+    fn verifier_contexts_agree_with_the_verifier() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        // y and -y have opposite parities, which BIP-340 proofs treat differently
+        for x in [x, -x] {
+            let y = g * x;
+            for mode in [ChallengeMode::Standard, ChallengeMode::Bip340Tagged] {
+                let proof = Prover::new()
+                    .challenge_mode(mode)
+                    .prove("sid", 1, &x, y, g)
+                    .unwrap();
+                let verifier = Verifier::new().challenge_mode(mode);
+                let context = VerifierContext::new(y, g).verifier(verifier.clone());
+                for pid in [1, 2] {
+                    assert_eq!(
+                        context.verify(&proof, "sid", pid),
+                        verifier.verify(&proof, "sid", pid, y, g)
+                    );
+                }
+                assert!(context.verify(&proof, "sid", 1).unwrap());
+            }
+        }
+        let context = VerifierContext::new(ProjectivePoint::IDENTITY, g);
+        let proof = DLogProof::prove("sid", 1, &x, g * x, g).unwrap();
+        assert_eq!(
+            context.verify(&proof, "sid", 1),
+            Err(DLogProofError::IdentityPoint)
+        );
+    }
+
+    #[cfg(feature = "backend-ristretto")]
+    #[test]
+    fn little_endian_scalars_are_windowed_correctly() {
+        use curve25519_dalek::RistrettoPoint;
+
+        let point = RistrettoPoint::random(&mut OsRng);
+        let k = curve25519_dalek::Scalar::random(&mut OsRng);
+        let table = FixedBaseTable::new(point);
+        assert_eq!(table.mul(&k), point * k);
+        assert_eq!(table.mul_vartime(&k), point * k);
+    }
+}
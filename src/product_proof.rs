@@ -0,0 +1,212 @@
+use elliptic_curve::subtle::{Choice, ConstantTimeEq};
+use elliptic_curve::Field;
+use k256::{ProjectivePoint, Scalar};
+use rand_core::OsRng;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::serialization::{PointHex, ScalarHex};
+use crate::session::SessionId;
+
+/// Opening (value, blinding) of a Pedersen commitment C = value * G + blinding * H
+#[derive(Clone)]
+pub struct PedersenOpening {
+    pub value: Scalar,
+    pub blinding: Scalar,
+}
+
+impl PedersenOpening {
+    pub fn new(value: Scalar, blinding: Scalar) -> Self {
+        PedersenOpening { value, blinding }
+    }
+
+    /// Computes the commitment C = value * G + blinding * H
+    pub fn commit(&self, base_point: ProjectivePoint, h: ProjectivePoint) -> ProjectivePoint {
+        base_point * self.value + h * self.blinding
+    }
+}
+
+/// Proof that three Pedersen commitments satisfy a multiplicative relation:
+/// C1 commits to a, C2 commits to b and C3 commits to a * b
+///
+/// C3 is rewritten as C3 = a * C2 + (r3 - a * r2) * H, so the statement becomes three linked
+/// Schnorr-style relations sharing the witness a:
+/// * `t1 = α * G + ρ1 * H` - commitment for C1 = a * G + r1 * H
+/// * `t2 = β * G + ρ2 * H` - commitment for C2 = b * G + r2 * H
+/// * `t3 = α * C2 + ρ3 * H` - commitment for C3 = a * C2 + (r3 - a * r2) * H
+///
+/// and one response per witness, z = nonce + c * witness.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ProductProof {
+    #[serde_as(as = "PointHex")]
+    pub(crate) t1: ProjectivePoint,
+    #[serde_as(as = "PointHex")]
+    pub(crate) t2: ProjectivePoint,
+    #[serde_as(as = "PointHex")]
+    pub(crate) t3: ProjectivePoint,
+    #[serde_as(as = "ScalarHex")]
+    pub(crate) z_a: Scalar,
+    #[serde_as(as = "ScalarHex")]
+    pub(crate) z_r1: Scalar,
+    #[serde_as(as = "ScalarHex")]
+    pub(crate) z_b: Scalar,
+    #[serde_as(as = "ScalarHex")]
+    pub(crate) z_r2: Scalar,
+    #[serde_as(as = "ScalarHex")]
+    pub(crate) z_r3: Scalar,
+}
+
+impl ProductProof {
+    const DOMAIN_SEPARATOR: &'static [u8] = b"SCHNORR_PRODUCT_PROOF";
+
+    /// Proves that the commitments to `a`, `b` and `ab` satisfy ab.value = a.value * b.value
+    ///
+    /// # Arguments
+    /// * `sid` - Session identifier used for domain separation, a string or bytes (see `SessionId`)
+    /// * `pid` - Participant ID for uniqueness to distinguish different proofs
+    /// * `a`, `b`, `ab` - Openings of the three commitments
+    /// * `base_point` - Base point G of secp256k1 curve
+    /// * `h` - Second Pedersen generator H, whose discrete log relative to G must be unknown
+    ///
+    /// # Returns
+    /// * `Ok(ProductProof)` - The proof
+    /// * `Err(DLogProofError)` - `InvalidWitness` if ab.value != a.value * b.value, or any
+    ///   error of proof generation
+    pub fn prove<'s>(
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        a: &PedersenOpening,
+        b: &PedersenOpening,
+        ab: &PedersenOpening,
+        base_point: ProjectivePoint,
+        h: ProjectivePoint,
+    ) -> Result<Self, DLogProofError> {
+        if ab.value != a.value * b.value {
+            return Err(DLogProofError::InvalidWitness);
+        }
+        let c2 = b.commit(base_point, h);
+        let commitments = [a.commit(base_point, h), c2, ab.commit(base_point, h)];
+
+        // Nonces for each witness: a, r1, b, r2 and r3 - a * r2
+        let alpha = Scalar::random(&mut OsRng);
+        let rho1 = Scalar::random(&mut OsRng);
+        let beta = Scalar::random(&mut OsRng);
+        let rho2 = Scalar::random(&mut OsRng);
+        let rho3 = Scalar::random(&mut OsRng);
+
+        let t1 = base_point * alpha + h * rho1;
+        let t2 = base_point * beta + h * rho2;
+        let t3 = c2 * alpha + h * rho3;
+
+        let c = Self::challenge(sid.into(), pid, &commitments, base_point, h, [t1, t2, t3])?;
+
+        Ok(ProductProof {
+            t1,
+            t2,
+            t3,
+            z_a: alpha + c * a.value,
+            z_r1: rho1 + c * a.blinding,
+            z_b: beta + c * b.value,
+            z_r2: rho2 + c * b.blinding,
+            z_r3: rho3 + c * (ab.blinding - a.value * b.blinding),
+        })
+    }
+
+    /// Verifies that `commitments` = [C1, C2, C3] satisfy the product relation
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Validity of the proof
+    /// * `Err(DLogProofError)` - Any error during verification
+    pub fn verify<'s>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        commitments: &[ProjectivePoint; 3],
+        base_point: ProjectivePoint,
+        h: ProjectivePoint,
+    ) -> Result<bool, DLogProofError> {
+        let [c1, c2, c3] = *commitments;
+        let c = Self::challenge(
+            sid.into(),
+            pid,
+            commitments,
+            base_point,
+            h,
+            [self.t1, self.t2, self.t3],
+        )?;
+
+        let first: Choice = (base_point * self.z_a + h * self.z_r1).ct_eq(&(self.t1 + c1 * c));
+        let second: Choice = (base_point * self.z_b + h * self.z_r2).ct_eq(&(self.t2 + c2 * c));
+        let third: Choice = (c2 * self.z_a + h * self.z_r3).ct_eq(&(self.t3 + c3 * c));
+
+        Ok((first & second & third).into())
+    }
+
+    fn challenge(
+        sid: SessionId<'_>,
+        pid: u32,
+        commitments: &[ProjectivePoint; 3],
+        base_point: ProjectivePoint,
+        h: ProjectivePoint,
+        t: [ProjectivePoint; 3],
+    ) -> Result<Scalar, DLogProofError> {
+        let [c1, c2, c3] = *commitments;
+        let [t1, t2, t3] = t;
+        DLogProof::hash_points_with_domain(
+            Self::DOMAIN_SEPARATOR,
+            sid,
+            pid.into(),
+            &[base_point, h, c1, c2, c3, t1, t2, t3],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_opening() -> PedersenOpening {
+        PedersenOpening::new(Scalar::random(&mut OsRng), Scalar::random(&mut OsRng))
+    }
+
+    fn setup() -> (
+        ProjectivePoint,
+        ProjectivePoint,
+        PedersenOpening,
+        PedersenOpening,
+    ) {
+        let g = ProjectivePoint::GENERATOR;
+        let h = g * Scalar::random(&mut OsRng);
+        (g, h, random_opening(), random_opening())
+    }
+
+    #[test]
+    fn valid_product_verifies() {
+        let (g, h, a, b) = setup();
+        let ab = PedersenOpening::new(a.value * b.value, Scalar::random(&mut OsRng));
+        let proof = ProductProof::prove("sid", 1, &a, &b, &ab, g, h).unwrap();
+
+        let commitments = [a.commit(g, h), b.commit(g, h), ab.commit(g, h)];
+        assert!(proof.verify("sid", 1, &commitments, g, h).unwrap());
+        assert!(!proof.verify("sid", 2, &commitments, g, h).unwrap());
+    }
+
+    #[test]
+    fn wrong_product_is_rejected() {
+        let (g, h, a, b) = setup();
+        let wrong = PedersenOpening::new(a.value + b.value, Scalar::random(&mut OsRng));
+        assert_eq!(
+            ProductProof::prove("sid", 1, &a, &b, &wrong, g, h),
+            Err(DLogProofError::InvalidWitness)
+        );
+
+        // A proof for one C3 must not verify against a commitment to a different value
+        let ab = PedersenOpening::new(a.value * b.value, Scalar::random(&mut OsRng));
+        let proof = ProductProof::prove("sid", 1, &a, &b, &ab, g, h).unwrap();
+        let commitments = [a.commit(g, h), b.commit(g, h), wrong.commit(g, h)];
+        assert!(!proof.verify("sid", 1, &commitments, g, h).unwrap());
+    }
+}
@@ -0,0 +1,300 @@
+use std::path::Path;
+use std::{fs, io};
+
+use crate::dlog_proof::UnknownFieldPolicy;
+use crate::envelope::{hash_from_id, hash_id, Curve, ProofEnvelope};
+use crate::error::DLogProofError;
+use crate::session::SessionId;
+
+/// First bytes of every `.dlogproof` file
+pub const MAGIC: [u8; 8] = *b"DLOGPROF";
+
+/// Current version of the file format, the only one `ProofFile::from_bytes` accepts
+///
+/// The version only changes for layouts older readers can't parse; new information is added
+/// as extension fields, so files written today stay readable.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// File name extension of proof files, without the dot
+pub const FILE_EXTENSION: &str = "dlogproof";
+
+/// Extension tags with this bit set are critical: a reader that doesn't know the tag must
+/// reject the file instead of skipping the field
+pub const CRITICAL_FIELD: u16 = 0x8000;
+
+/// A proof stored with its statement metadata in the binary `.dlogproof` format
+///
+/// The layout, with integers big-endian, is
+///
+/// ```text
+/// magic "DLOGPROF" || version (1) || curve id (1) || hash id (1) || pid (4)
+///     || sid length (4) || sid || proof length (4) || proof
+///     || { tag (2) || length (4) || value }*
+/// ```
+///
+/// The curve and hash identifiers are those of `ProofEnvelope`, and the proof is the
+/// envelope's `t || s` encoding (the 65 bytes of `DLogProof::to_bytes` on secp256k1). The
+/// extension fields after the proof carry anything later versions add. Fields this version
+/// doesn't know are kept and written back unchanged, unless their tag has the
+/// `CRITICAL_FIELD` bit set, in which case the file is rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofFile {
+    envelope: ProofEnvelope,
+    sid: Vec<u8>,
+    pid: u32,
+    unknown_fields: Vec<(u16, Vec<u8>)>,
+}
+
+impl ProofFile {
+    /// Stores an enveloped proof with the session and participant it was made for
+    pub fn new<'s>(envelope: ProofEnvelope, sid: impl Into<SessionId<'s>>, pid: u32) -> Self {
+        ProofFile {
+            envelope,
+            sid: sid.into().as_bytes().to_vec(),
+            pid,
+            unknown_fields: Vec::new(),
+        }
+    }
+
+    /// The enveloped proof
+    pub fn envelope(&self) -> &ProofEnvelope {
+        &self.envelope
+    }
+
+    /// The session identifier the proof was made for
+    pub fn sid(&self) -> SessionId<'_> {
+        SessionId::new(&self.sid)
+    }
+
+    /// The participant ID the proof was made for
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// Tags of the extension fields this version doesn't know, in file order
+    pub fn unknown_fields(&self) -> impl Iterator<Item = u16> + '_ {
+        self.unknown_fields.iter().map(|(tag, _)| *tag)
+    }
+
+    /// Verifies the proof for the stored session and participant, see `ProofEnvelope::verify`
+    pub fn verify(&self, y: &[u8]) -> Result<bool, DLogProofError> {
+        self.envelope.verify(self.sid(), self.pid, y)
+    }
+
+    /// Encodes the file
+    ///
+    /// Fails with `Serialization` if the session id, the proof or an extension field is
+    /// 4 GiB or longer, as lengths are encoded in 32 bits.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, DLogProofError> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&[
+            FORMAT_VERSION,
+            self.envelope.curve.id(),
+            hash_id(self.envelope.hash),
+        ]);
+        bytes.extend_from_slice(&self.pid.to_be_bytes());
+        put_length_prefixed(&mut bytes, &self.sid)?;
+        put_length_prefixed(&mut bytes, &self.envelope.proof)?;
+        for (tag, value) in &self.unknown_fields {
+            bytes.extend_from_slice(&tag.to_be_bytes());
+            put_length_prefixed(&mut bytes, value)?;
+        }
+        Ok(bytes)
+    }
+
+    /// Decodes a file
+    ///
+    /// # Arguments
+    /// * `bytes` - The file contents
+    /// * `policy` - Whether unknown non-critical extension fields are kept or rejected
+    ///
+    /// # Returns
+    /// * `Ok(ProofFile)` - The file; the proof bytes are only checked when it is verified
+    /// * `Err(DLogProofError)` - `Serialization` for a wrong magic, an unknown version, curve
+    ///   or hash, a truncated file, an unknown critical field, or an unknown field under
+    ///   `UnknownFieldPolicy::Strict`
+    pub fn from_bytes(bytes: &[u8], policy: UnknownFieldPolicy) -> Result<Self, DLogProofError> {
+        let mut reader = Reader(bytes);
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(DLogProofError::Serialization(
+                "Not a .dlogproof file".to_string(),
+            ));
+        }
+        let version = reader.u8()?;
+        if version != FORMAT_VERSION {
+            return Err(DLogProofError::Serialization(format!(
+                "Unsupported proof file version {version}, expected {FORMAT_VERSION}"
+            )));
+        }
+        let curve = reader.u8()?;
+        let curve = Curve::from_id(curve).ok_or_else(|| {
+            DLogProofError::Serialization(format!("Unknown curve identifier {curve}"))
+        })?;
+        let hash = reader.u8()?;
+        let hash = hash_from_id(hash).ok_or_else(|| {
+            DLogProofError::Serialization(format!("Unknown hash identifier {hash}"))
+        })?;
+        let pid = u32::from_be_bytes(reader.array()?);
+        let sid = reader.length_prefixed()?.to_vec();
+        let proof = reader.length_prefixed()?.to_vec();
+
+        let mut unknown_fields = Vec::new();
+        while !reader.0.is_empty() {
+            let tag = u16::from_be_bytes(reader.array()?);
+            let value = reader.length_prefixed()?;
+            if tag & CRITICAL_FIELD != 0 || policy == UnknownFieldPolicy::Strict {
+                return Err(DLogProofError::Serialization(format!(
+                    "Unknown proof file field {tag:#06x}"
+                )));
+            }
+            unknown_fields.push((tag, value.to_vec()));
+        }
+        Ok(ProofFile {
+            envelope: ProofEnvelope { curve, hash, proof },
+            sid,
+            pid,
+            unknown_fields,
+        })
+    }
+
+    /// Writes the file to `path`
+    ///
+    /// Encoding errors are returned as `io::ErrorKind::InvalidData` wrapping the
+    /// `DLogProofError`.
+    pub fn write_to(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let bytes = self
+            .to_bytes()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, bytes)
+    }
+
+    /// Reads a file from `path`, keeping unknown non-critical fields
+    ///
+    /// Decoding errors are returned as `io::ErrorKind::InvalidData` wrapping the
+    /// `DLogProofError`.
+    pub fn read_from(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_bytes(&fs::read(path)?, UnknownFieldPolicy::Tolerant)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+fn put_length_prefixed(bytes: &mut Vec<u8>, value: &[u8]) -> Result<(), DLogProofError> {
+    let len = u32::try_from(value.len()).map_err(|_| {
+        DLogProofError::Serialization("Proof file field of 4 GiB or more".to_string())
+    })?;
+    bytes.extend_from_slice(&len.to_be_bytes());
+    bytes.extend_from_slice(value);
+    Ok(())
+}
+
+/// The unread rest of a file
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], DLogProofError> {
+        if self.0.len() < n {
+            return Err(DLogProofError::Serialization(
+                "Truncated proof file".to_string(),
+            ));
+        }
+        let (head, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], DLogProofError> {
+        let (head, rest) = self
+            .0
+            .split_first_chunk::<N>()
+            .ok_or_else(|| DLogProofError::Serialization("Truncated proof file".to_string()))?;
+        self.0 = rest;
+        Ok(*head)
+    }
+
+    fn u8(&mut self) -> Result<u8, DLogProofError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn length_prefixed(&mut self) -> Result<&'a [u8], DLogProofError> {
+        let len = u32::from_be_bytes(self.array()?);
+        self.take(len as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlog_proof::{ChallengeHash, DLogProof};
+    use elliptic_curve::group::GroupEncoding;
+    use elliptic_curve::Field;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_core::OsRng;
+
+    #[test]
+    fn proof_files_round_trip_and_keep_unknown_fields() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let proof = DLogProof::prove("sid", 7, &x, y, g).unwrap();
+        let file = ProofFile::new(ProofEnvelope::seal(&proof, ChallengeHash::Sha256), "sid", 7);
+
+        let bytes = file.to_bytes().unwrap();
+        assert_eq!(bytes[..8], *b"DLOGPROF");
+        assert_eq!(bytes[8..15], [FORMAT_VERSION, 1, 1, 0, 0, 0, 7]);
+        assert_eq!(bytes[bytes.len() - 65..], proof.to_bytes());
+
+        let path =
+            std::env::temp_dir().join(format!("test-{}.{FILE_EXTENSION}", std::process::id()));
+        file.write_to(&path).unwrap();
+        let read = ProofFile::read_from(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(read, file);
+        assert_eq!(read.sid(), SessionId::from("sid"));
+        assert!(read.verify(&y.to_bytes()).unwrap());
+
+        // A field added by a later version is kept and written back
+        let mut extended = bytes.clone();
+        extended.extend_from_slice(&[0x00, 0x01, 0, 0, 0, 2, 0xab, 0xcd]);
+        let parsed = ProofFile::from_bytes(&extended, UnknownFieldPolicy::Tolerant).unwrap();
+        assert_eq!(parsed.unknown_fields().collect::<Vec<_>>(), [1]);
+        assert_eq!(parsed.to_bytes().unwrap(), extended);
+        assert!(parsed.verify(&y.to_bytes()).unwrap());
+        assert!(ProofFile::from_bytes(&extended, UnknownFieldPolicy::Strict).is_err());
+
+        // unless it is critical
+        extended[bytes.len()] = 0x80;
+        assert!(ProofFile::from_bytes(&extended, UnknownFieldPolicy::Tolerant).is_err());
+    }
+
+    #[test]
+    fn malformed_proof_files_are_rejected() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let proof = DLogProof::prove("sid", 1, &x, g * x, g).unwrap();
+        let bytes = ProofFile::new(ProofEnvelope::seal(&proof, ChallengeHash::Sha256), "sid", 1)
+            .to_bytes()
+            .unwrap();
+
+        for (index, value, message) in [
+            (0, b'X', "Not a .dlogproof file"),
+            (8, 2, "Unsupported proof file version 2, expected 1"),
+            (9, 9, "Unknown curve identifier 9"),
+            (10, 0, "Unknown hash identifier 0"),
+        ] {
+            let mut tampered = bytes.clone();
+            tampered[index] = value;
+            assert_eq!(
+                ProofFile::from_bytes(&tampered, UnknownFieldPolicy::Tolerant),
+                Err(DLogProofError::Serialization(message.to_string()))
+            );
+        }
+        for len in [4, 20, bytes.len() - 1] {
+            assert_eq!(
+                ProofFile::from_bytes(&bytes[..len], UnknownFieldPolicy::Tolerant),
+                Err(DLogProofError::Serialization(
+                    "Truncated proof file".to_string()
+                ))
+            );
+        }
+    }
+}
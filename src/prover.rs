@@ -0,0 +1,369 @@
+use elliptic_curve::group::Group;
+use elliptic_curve::Field;
+use rand_core::CryptoRngCore;
+#[cfg(feature = "std")]
+use rand_core::OsRng;
+use zeroize::Zeroize;
+
+#[cfg(feature = "std")]
+use crate::audit::TranscriptLog;
+use crate::dlog_proof::{
+    ChallengeHash, ChallengeMode, ChallengeParams, DLogProof, Sha256Hook, TranscriptVersion,
+};
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::participant::ParticipantId;
+use crate::secret::SecretScalar;
+use crate::session::SessionId;
+#[cfg(feature = "std")]
+use crate::stats::ProveStats;
+#[cfg(feature = "std")]
+use crate::validity::TimedProof;
+
+/// Prover configuration for `DLogProof`
+///
+/// `DLogProof::prove` uses the default configuration. Build a `Prover` to opt into
+/// extra behaviour, e.g. `Prover::new().blinded(true).prove(sid, pid, &x, y, base_point)`
+#[derive(Debug, Clone)]
+pub struct Prover {
+    params: ChallengeParams,
+    blinded: bool,
+    synthetic_nonces: bool,
+    scrub_stack: bool,
+    #[cfg(feature = "verify")]
+    verify_after_prove: bool,
+}
+
+/// The `hardened` profile turns the fault-attack countermeasure on for every proof
+#[cfg(feature = "verify")]
+const VERIFY_AFTER_PROVE_DEFAULT: bool = cfg!(feature = "hardened");
+
+// Derivable without the `verify_after_prove` field
+#[cfg_attr(not(feature = "verify"), allow(clippy::derivable_impls))]
+impl Default for Prover {
+    fn default() -> Self {
+        Prover {
+            params: ChallengeParams::default(),
+            blinded: false,
+            synthetic_nonces: false,
+            scrub_stack: false,
+            #[cfg(feature = "verify")]
+            verify_after_prove: VERIFY_AFTER_PROVE_DEFAULT,
+        }
+    }
+}
+
+impl Prover {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The side-channel hardened profile: blinding, synthetic nonces, stack scrubbing and
+    /// verification after proving all enabled
+    ///
+    /// For provers an attacker can probe or glitch, e.g. on embedded devices. The scalar
+    /// arithmetic of the proof is constant time either way, and the prover branches only on
+    /// public values; the profile adds the countermeasures above at the cost of about two
+    /// extra scalar multiplications and one verification per proof. The proofs verify like
+    /// any other. Needs the `verify` feature, for the verification.
+    #[cfg(feature = "verify")]
+    pub fn hardened() -> Self {
+        Self::default()
+            .blinded(true)
+            .synthetic_nonces(true)
+            .scrub_stack(true)
+            .verify_after_prove(true)
+    }
+
+    /// Sets an application domain separation tag, bound into every challenge
+    ///
+    /// The tag is combined with (not substituted for) the crate's base tag, so proofs made for
+    /// one application don't verify for another using the same keys, nor without a tag for
+    /// any sid. Under `TranscriptVersion::V1` the length-prefixed tag is absorbed before the
+    /// base tag, as `0x00 || len (4 bytes big-endian) || tag`; under V2 it is appended to the
+    /// base tag. The `Verifier` must be configured with the same tag.
+    pub fn domain(mut self, app_domain: &[u8]) -> Self {
+        self.params.app_domain = Some(app_domain.to_vec());
+        self
+    }
+
+    /// Derives the challenge with HMAC-SHA256 under a verifier-provided key
+    ///
+    /// The resulting proofs are scoped to the verifier holding the key: nobody without the
+    /// key can check them, and they don't transfer to other verifiers. The `Verifier` must be
+    /// configured with the same key.
+    pub fn hmac_key(mut self, key: &[u8]) -> Self {
+        self.params.hmac_key = Some(key.to_vec());
+        self
+    }
+
+    /// Selects the hash function deriving the challenge (SHA-256 by default)
+    ///
+    /// Also applies to the HMAC set with `hmac_key`. The `Verifier` must be configured with
+    /// the same hash.
+    pub fn hash(mut self, hash: ChallengeHash) -> Self {
+        self.params.hash = hash;
+        self
+    }
+
+    /// Selects how the challenge is derived (see `ChallengeMode`)
+    ///
+    /// The `Verifier` must be configured with the same mode.
+    pub fn challenge_mode(mut self, mode: ChallengeMode) -> Self {
+        self.params.mode = mode;
+        self
+    }
+
+    /// Sets the domain separation tag of `ChallengeMode::ExpandMessageXmd`
+    ///
+    /// Required in that mode, and ignored in the others. The `Verifier` must be configured
+    /// with the same tag.
+    pub fn xmd_dst(mut self, dst: &[u8]) -> Self {
+        self.params.xmd_dst = Some(dst.to_vec());
+        self
+    }
+
+    /// Selects the transcript layout (see `TranscriptVersion`)
+    ///
+    /// `TranscriptVersion::V3` is recommended for new deployments, as its challenges are
+    /// uniform with every hash; the default `V1` keeps proofs compatible with earlier
+    /// versions. The `Verifier` must use the same version.
+    pub fn transcript_version(mut self, version: TranscriptVersion) -> Self {
+        self.params.version = version;
+        self
+    }
+
+    /// Computes the SHA-256 challenge hash with `hook` instead of the `sha2` crate
+    ///
+    /// The proofs are the same either way (see `Verifier::sha256`).
+    pub fn sha256(mut self, hook: Sha256Hook) -> Self {
+        self.params.sha256 = Some(hook);
+        self
+    }
+
+    /// Binds additional authenticated data into the challenge (see `ProofBuilder::aad`)
+    #[cfg(feature = "std")]
+    pub(crate) fn aad(mut self, aad: &[u8]) -> Self {
+        self.params.aad = Some(aad.to_vec());
+        self
+    }
+
+    pub(crate) fn params(&self) -> &ChallengeParams {
+        &self.params
+    }
+
+    /// Enables blinding of the secret-dependent computations
+    ///
+    /// The nonce multiplication r * G is split into (r - b) * G + b * G with a fresh random b,
+    /// and the response r + c * x is computed over a randomly masked copy of x. Neither the
+    /// nonce nor the secret is then used directly in an operation an EM/power probe can
+    /// correlate across proofs. Costs one extra scalar multiplication per proof; the proofs
+    /// themselves are identical in distribution to unblinded ones.
+    pub fn blinded(mut self, enabled: bool) -> Self {
+        self.blinded = enabled;
+        self
+    }
+
+    pub fn is_blinded(&self) -> bool {
+        self.blinded
+    }
+
+    /// Derives every nonce from the secret mixed with fresh randomness (synthetic nonces)
+    ///
+    /// The nonce is drawn from an HMAC-DRBG seeded with x and 32 bytes from the RNG, with the
+    /// statement hashed in as for `DLogProof::prove_deterministic`. A weak or repeating RNG
+    /// then can't repeat a nonce for different statements, and a fault in the RNG alone
+    /// doesn't give a predictable one, while proofs of the same statement still differ.
+    pub fn synthetic_nonces(mut self, enabled: bool) -> Self {
+        self.synthetic_nonces = enabled;
+        self
+    }
+
+    pub fn uses_synthetic_nonces(&self) -> bool {
+        self.synthetic_nonces
+    }
+
+    /// Overwrites the stack below the prover with zeros once the proof is made
+    ///
+    /// Secret copies and nonces the prover holds are wiped on drop regardless; this also
+    /// clears the temporaries the scalar multiplication and arithmetic left in callee
+    /// frames. Best effort, over `STACK_SCRUB_LEN` bytes, which must fit on the stack.
+    pub fn scrub_stack(mut self, enabled: bool) -> Self {
+        self.scrub_stack = enabled;
+        self
+    }
+
+    pub fn scrubs_stack(&self) -> bool {
+        self.scrub_stack
+    }
+
+    /// Verifies every freshly produced proof before returning it
+    ///
+    /// A fault injected while computing t or s (voltage/clock glitching on embedded provers)
+    /// yields a malformed (t, s) pair, and a handful of those can be enough to recover x.
+    /// With this check a faulty proof is never released; `prove` returns an error instead.
+    /// On by default when the crate is built with the `hardened` feature.
+    #[cfg(feature = "verify")]
+    pub fn verify_after_prove(mut self, enabled: bool) -> Self {
+        self.verify_after_prove = enabled;
+        self
+    }
+
+    #[cfg(feature = "verify")]
+    pub fn verifies_after_prove(&self) -> bool {
+        self.verify_after_prove
+    }
+
+    /// Creates a proof of knowledge of `x` for y = x * G with this configuration
+    ///
+    /// See `DLogProof::prove` for the meaning of the arguments.
+    #[cfg(feature = "std")]
+    pub fn prove<'s, 'p, G: DLogGroup>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: impl Into<G>,
+        base_point: G,
+    ) -> Result<DLogProof<G>, DLogProofError> {
+        DLogProof::prove_with(
+            self,
+            &mut OsRng,
+            sid.into(),
+            pid.into(),
+            &x.into(),
+            y.into(),
+            base_point,
+        )
+    }
+
+    /// Creates a proof like `prove`, drawing the nonce and blinding masks from `rng`
+    ///
+    /// See `DLogProof::prove_with_rng` for the requirements on `rng`.
+    pub fn prove_with_rng<'s, 'p, G: DLogGroup>(
+        &self,
+        rng: &mut impl CryptoRngCore,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: impl Into<G>,
+        base_point: G,
+    ) -> Result<DLogProof<G>, DLogProofError> {
+        DLogProof::prove_with(
+            self,
+            rng,
+            sid.into(),
+            pid.into(),
+            &x.into(),
+            y.into(),
+            base_point,
+        )
+    }
+
+    /// Creates a proof like `prove` that is only valid from `not_before` to `not_after`
+    ///
+    /// The window is bound into the challenge, so the proof verifies only with
+    /// `Verifier::verify_at` (or `TimedProof::verify_at`) for a timestamp inside it. This is
+    /// the way to make short-lived proofs, e.g. for a login; encoding an expiry in the
+    /// session ID leaves it to every verifier to parse and check it.
+    ///
+    /// # Returns
+    /// * `Ok(TimedProof)` - The proof with its window
+    /// * `Err(DLogProofError)` - `OutsideValidityWindow` if `not_before` is after
+    ///   `not_after`, or any error of `prove`
+    #[cfg(feature = "std")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_with_validity<'s, 'p, G: DLogGroup>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        not_before: u64,
+        not_after: u64,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: impl Into<G>,
+        base_point: G,
+    ) -> Result<TimedProof<G>, DLogProofError> {
+        if not_before > not_after {
+            return Err(DLogProofError::OutsideValidityWindow);
+        }
+        let mut prover = self.clone();
+        prover.params.validity = Some((not_before, not_after));
+        Ok(TimedProof {
+            not_before,
+            not_after,
+            proof: prover.prove(sid, pid, x, y, base_point)?,
+        })
+    }
+
+    /// Creates a proof like `prove`, also returning counters and per-phase timings
+    #[cfg(feature = "std")]
+    pub fn prove_with_stats<'s, 'p, G: DLogGroup>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: G,
+        base_point: G,
+    ) -> Result<(DLogProof<G>, ProveStats), DLogProofError> {
+        DLogProof::prove_with_stats(
+            self,
+            &mut OsRng,
+            sid.into(),
+            pid.into(),
+            &x.into(),
+            y,
+            base_point,
+        )
+    }
+
+    /// Creates a proof like `prove`, also returning the record of its challenge transcript
+    ///
+    /// # Returns
+    /// * `Ok((DLogProof, TranscriptLog))` - The proof and what went into its challenge
+    /// * `Err(DLogProofError)` - Any error of `prove`, or `UnsupportedChallengeMode` if this
+    ///   configuration can't be recorded (see `TranscriptLog`)
+    #[cfg(feature = "std")]
+    pub fn prove_with_transcript<'s, 'p, G: DLogGroup>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: G,
+        base_point: G,
+    ) -> Result<(DLogProof<G>, TranscriptLog), DLogProofError> {
+        let sid = sid.into();
+        let pid = pid.into();
+        let proof = self.prove(sid, pid, x, y, base_point)?;
+        let log = TranscriptLog::record(&self.params, sid, pid, &[base_point, y, proof.t])?;
+        Ok((proof, log))
+    }
+}
+
+/// Bytes of stack `Prover::scrub_stack` overwrites
+pub const STACK_SCRUB_LEN: usize = 8 * 1024;
+
+/// Zeros `STACK_SCRUB_LEN` bytes of stack, where the frames of the functions the caller
+/// returned from were
+#[inline(never)]
+pub(crate) fn scrub_stack() {
+    let mut scratch = [0u8; STACK_SCRUB_LEN];
+    scratch.zeroize();
+    core::hint::black_box(&scratch);
+}
+
+/// Computes k * P by additive scalar splitting: (k - b) * P + b * P for a fresh random b,
+/// with `mul` computing multiples of P
+pub(crate) fn blinded_mul<G: Group>(
+    mul: impl Fn(&G::Scalar) -> G,
+    k: &G::Scalar,
+    rng: &mut impl CryptoRngCore,
+) -> G {
+    let b = G::Scalar::random(rng);
+    mul(&(*k - b)) + mul(&b)
+}
+
+/// Computes r + c * x as r + c * (x + m) - c * m for a fresh random mask m
+pub(crate) fn blinded_response<F: Field>(r: &F, c: &F, x: &F, rng: &mut impl CryptoRngCore) -> F {
+    let m = F::random(rng);
+    *r + *c * (*x + m) - *c * m
+}
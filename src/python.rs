@@ -0,0 +1,156 @@
+//! Python bindings through PyO3 (`python` feature)
+//!
+//! A `schnorr_dlog_zkp` extension module with the secp256k1 API of the `wasm` and `node`
+//! bindings: proofs over the standard generator as 65-byte `bytes` in the encoding of
+//! `DLogProof::to_bytes`, 32-byte big-endian secret keys and SEC1 public keys, plus the
+//! JSON format of `DLogProof`'s serde implementation. Errors raise `ValueError` with the
+//! `DLogProofError` message and its stable code in the `code` attribute. `verify_batch` releases the GIL while it verifies, so other
+//! Python threads keep running.
+//!
+//! Build the wheel with maturin from a crate of `crate-type = ["cdylib"]` depending on this
+//! one with the feature and `pyo3/extension-module`:
+//!
+//! ```python
+//! import schnorr_dlog_zkp as dlog
+//!
+//! proof = dlog.prove("sid", 1, secret_bytes)
+//! assert dlog.verify("sid", 1, dlog.public_key(secret_bytes), proof)
+//! assert dlog.proof_from_json(dlog.proof_to_json(proof)) == proof
+//! ```
+
+use k256::ProjectivePoint;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::dlog_proof::{DLogProof, UnknownFieldPolicy};
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::keypair::KeyPair;
+
+impl From<DLogProofError> for PyErr {
+    fn from(e: DLogProofError) -> Self {
+        let error = PyValueError::new_err(e.to_string());
+        Python::with_gil(|py| {
+            // Setting an attribute on a fresh exception instance doesn't fail
+            let _ = error.value(py).setattr("code", e.code());
+        });
+        error
+    }
+}
+
+/// Proves knowledge of `secret_bytes` for the session and participant, returning the
+/// 65-byte proof
+#[pyfunction]
+fn prove<'py>(
+    py: Python<'py>,
+    sid: &str,
+    pid: u32,
+    secret_bytes: &[u8],
+) -> PyResult<Bound<'py, PyBytes>> {
+    let key_pair = KeyPair::<ProjectivePoint>::from_secret_bytes(secret_bytes)?;
+    Ok(PyBytes::new(py, &key_pair.prove(sid, pid)?.to_bytes()))
+}
+
+/// Verifies a 65-byte proof of knowledge of the secret key of `public_bytes`
+#[pyfunction]
+fn verify(sid: &str, pid: u32, public_bytes: &[u8], proof_bytes: &[u8]) -> PyResult<bool> {
+    let y = decode_public_key(public_bytes)?;
+    let proof = DLogProof::from_bytes(proof_bytes)?;
+    Ok(proof.verify(sid, pid, y, ProjectivePoint::GENERATOR)?)
+}
+
+/// Verifies a list of `(sid, pid, public_bytes, proof_bytes)` at once, without holding the
+/// GIL; true only if all proofs are valid
+#[pyfunction]
+fn verify_batch(py: Python<'_>, items: Vec<(String, u32, Vec<u8>, Vec<u8>)>) -> PyResult<bool> {
+    let statements = items
+        .iter()
+        .map(|(sid, pid, public_bytes, proof_bytes)| {
+            Ok((
+                sid.as_str(),
+                *pid,
+                decode_public_key(public_bytes)?,
+                DLogProof::from_bytes(proof_bytes)?,
+            ))
+        })
+        .collect::<Result<Vec<_>, DLogProofError>>()?;
+    py.allow_threads(|| {
+        let items: Vec<_> = statements
+            .iter()
+            .map(|(sid, pid, y, proof)| ((*sid).into(), (*pid).into(), *y, proof))
+            .collect();
+        Ok(DLogProof::verify_batch(&items, ProjectivePoint::GENERATOR)?)
+    })
+}
+
+/// The compressed SEC1 public key of a 32-byte big-endian secret key
+#[pyfunction]
+fn public_key<'py>(py: Python<'py>, secret_bytes: &[u8]) -> PyResult<Bound<'py, PyBytes>> {
+    let key_pair = KeyPair::<ProjectivePoint>::from_secret_bytes(secret_bytes)?;
+    Ok(PyBytes::new(py, &key_pair.public().encode_point()))
+}
+
+/// JSON export of a 65-byte proof, `{"t": "<hex>", "s": "<hex>"}`
+#[pyfunction]
+fn proof_to_json(proof_bytes: &[u8]) -> PyResult<String> {
+    let proof = DLogProof::from_bytes(proof_bytes)?;
+    serde_json::to_string(&proof)
+        .map_err(|e| DLogProofError::Serialization(format!("Proof JSON encoding: {e}")).into())
+}
+
+/// JSON import of a proof into its 65 bytes, rejecting unknown fields
+#[pyfunction]
+fn proof_from_json<'py>(py: Python<'py>, json: &str) -> PyResult<Bound<'py, PyBytes>> {
+    let (proof, _) = DLogProof::from_json(json, UnknownFieldPolicy::Strict)?;
+    Ok(PyBytes::new(py, &proof.to_bytes()))
+}
+
+fn decode_public_key(bytes: &[u8]) -> Result<ProjectivePoint, DLogProofError> {
+    ProjectivePoint::decode_point(bytes).ok_or(DLogProofError::InvalidPoint)
+}
+
+/// The `schnorr_dlog_zkp` Python module
+#[pymodule]
+fn schnorr_dlog_zkp(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(prove, m)?)?;
+    m.add_function(wrap_pyfunction!(verify, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(public_key, m)?)?;
+    m.add_function(wrap_pyfunction!(proof_to_json, m)?)?;
+    m.add_function(wrap_pyfunction!(proof_from_json, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::{Field, PrimeField};
+    use k256::Scalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn bindings_prove_verify_and_round_trip_json() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let items: Vec<_> = (1..=3)
+                .map(|pid| {
+                    let secret = Scalar::random(&mut OsRng).to_repr();
+                    let y = public_key(py, &secret).unwrap().as_bytes().to_vec();
+                    let proof = prove(py, "sid", pid, &secret).unwrap().as_bytes().to_vec();
+                    ("sid".to_string(), pid, y, proof)
+                })
+                .collect();
+            let (_, _, y, proof) = &items[0];
+            assert!(verify("sid", 1, y, proof).unwrap());
+            assert!(!verify("sid", 2, y, proof).unwrap());
+            assert!(verify_batch(py, items.clone()).unwrap());
+
+            let json = proof_to_json(proof).unwrap();
+            assert_eq!(proof_from_json(py, &json).unwrap().as_bytes(), &proof[..]);
+
+            let error = verify("sid", 1, &[4; 33][..], proof).unwrap_err();
+            assert!(error.is_instance_of::<PyValueError>(py));
+        });
+    }
+}
@@ -0,0 +1,234 @@
+use elliptic_curve::Field;
+use k256::ProjectivePoint;
+use rand_core::{CryptoRngCore, OsRng};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use zeroize::Zeroizing;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::or_proof::OrProof;
+use crate::serialization::PointHex;
+use crate::session::SessionId;
+
+/// Largest number of bits a `RangeProof` covers, the width of its `u64` values
+pub const MAX_RANGE_BITS: usize = 64;
+
+/// Proof that a Pedersen commitment C = v * G + r * H opens to a value v in [0, 2^n)
+///
+/// The value is split into bits b_j, each committed to as C_j = b_j * G + r_j * H with
+/// blindings adding up to Σ 2^j * r_j = r, so that Σ 2^j * C_j = C. An `OrProof` over the
+/// base point H shows for every bit that C_j or C_j - G is a multiple of H, i.e. that b_j is
+/// 0 or 1, without revealing which. The proof grows linearly with n (n points and n OR
+/// proofs of two branches), so it suits small ranges such as ages or limits; it is not a
+/// Bulletproof.
+///
+/// G and H must be independent generators, e.g. H = `generators::nums_generator(PEDERSEN_H)`,
+/// or the commitment isn't binding.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct RangeProof<G: DLogGroup = ProjectivePoint> {
+    /// The bit commitments C_j, least significant first
+    #[serde_as(as = "Vec<PointHex>")]
+    commitments: Vec<G>,
+    /// One OR proof per bit commitment
+    bits: Vec<OrProof<G>>,
+}
+
+impl<G: DLogGroup> RangeProof<G> {
+    /// The bit commitments C_j, least significant first
+    pub fn bit_commitments(&self) -> &[G] {
+        &self.commitments
+    }
+
+    /// The number of bits n of the range [0, 2^n)
+    pub fn bits(&self) -> usize {
+        self.commitments.len()
+    }
+
+    /// Proves that C = value * G + blinding * H commits to a value below 2^bits
+    ///
+    /// # Arguments
+    /// * `sid` - Session identifier used for domain separation, a string or bytes (see `SessionId`)
+    /// * `pid` - Participant ID for uniqueness to distinguish different proofs
+    /// * `value` - The committed value v
+    /// * `blinding` - The blinding factor r of the commitment
+    /// * `bits` - The bit length n of the range, 1 to `MAX_RANGE_BITS`
+    /// * `base_point` - The base point G the value is committed with
+    /// * `h` - The base point H the blinding is committed with
+    ///
+    /// # Returns
+    /// * `Ok(RangeProof)` - The proof
+    /// * `Err(DLogProofError)` - `OutOfRange` if `bits` is unsupported or `value` doesn't fit,
+    ///   `IdentityPoint` for a degenerate statement, or if a challenge can't be derived
+    pub fn prove<'s>(
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        value: u64,
+        blinding: &G::Scalar,
+        bits: usize,
+        base_point: G,
+        h: G,
+    ) -> Result<Self, DLogProofError> {
+        Self::prove_with_rng(&mut OsRng, sid, pid, value, blinding, bits, base_point, h)
+    }
+
+    /// Proves like `prove`, drawing the bit blindings and the OR proofs' randomness from `rng`
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove_with_rng<'s>(
+        rng: &mut impl CryptoRngCore,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        value: u64,
+        blinding: &G::Scalar,
+        bits: usize,
+        base_point: G,
+        h: G,
+    ) -> Result<Self, DLogProofError> {
+        Self::check_bits(bits)?;
+        if bits < MAX_RANGE_BITS && value >> bits != 0 {
+            return Err(DLogProofError::OutOfRange);
+        }
+        DLogProof::check_statement(h, base_point)?;
+        let sid = sid.into();
+
+        // Random blindings for the high bits, and the lowest one chosen so that
+        // Σ 2^j * r_j = r
+        let mut blindings: Zeroizing<Vec<G::Scalar>> =
+            Zeroizing::new((0..bits).map(|_| G::Scalar::random(&mut *rng)).collect());
+        let high = (1..bits).fold(G::Scalar::ZERO, |sum, j| {
+            sum + blindings[j] * G::Scalar::from(1u64 << j)
+        });
+        blindings[0] = *blinding - high;
+
+        let mut commitments = Vec::with_capacity(bits);
+        let mut proofs = Vec::with_capacity(bits);
+        for (j, r) in blindings.iter().enumerate() {
+            let bit = ((value >> j) & 1) as usize;
+            let commitment = h * r + base_point * G::Scalar::from(bit as u64);
+            let branches = [commitment, commitment - base_point];
+            let proof = OrProof::prove_with_rng(rng, sid, pid, r, bit, &branches, h)?;
+            proofs.push(proof);
+            commitments.push(commitment);
+        }
+        Ok(RangeProof {
+            commitments,
+            bits: proofs,
+        })
+    }
+
+    /// Verifies that `commitment` opens to a value in [0, 2^bits)
+    ///
+    /// A proof for a different number of bits, or with bit commitments of 0 or G (which no
+    /// honest prover produces), is invalid, not an error.
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Validity of the proof
+    /// * `Err(DLogProofError)` - `OutOfRange` if `bits` is unsupported, `IdentityPoint` for a
+    ///   degenerate statement, or if a challenge can't be derived
+    pub fn verify<'s>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        commitment: G,
+        bits: usize,
+        base_point: G,
+        h: G,
+    ) -> Result<bool, DLogProofError> {
+        Self::check_bits(bits)?;
+        DLogProof::check_statement(h, base_point)?;
+        if self.commitments.len() != bits || self.bits.len() != bits {
+            return Ok(false);
+        }
+        let sid = sid.into();
+
+        let sum = self
+            .commitments
+            .iter()
+            .enumerate()
+            .fold(G::identity(), |sum, (j, c)| {
+                sum + *c * G::Scalar::from(1u64 << j)
+            });
+        if !bool::from(sum.ct_eq(&commitment)) {
+            return Ok(false);
+        }
+        for (&c, proof) in self.commitments.iter().zip(&self.bits) {
+            let branches = [c, c - base_point];
+            if branches.iter().any(|b| bool::from(b.is_identity())) {
+                return Ok(false);
+            }
+            if !proof.verify(sid, pid, &branches, h)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn check_bits(bits: usize) -> Result<(), DLogProofError> {
+        if bits == 0 || bits > MAX_RANGE_BITS {
+            return Err(DLogProofError::OutOfRange);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::{nums_generator, PEDERSEN_H};
+    use k256::Scalar;
+
+    fn commit(value: u64, blinding: &Scalar) -> ProjectivePoint {
+        let h = nums_generator(PEDERSEN_H).unwrap();
+        ProjectivePoint::GENERATOR * Scalar::from(value) + h * blinding
+    }
+
+    #[test]
+    fn values_in_range_are_provable() {
+        let g = ProjectivePoint::GENERATOR;
+        let h = nums_generator(PEDERSEN_H).unwrap();
+        for (value, bits) in [(0, 1), (1, 1), (18, 8), (255, 8), (u64::MAX, 64)] {
+            let r = Scalar::random(&mut OsRng);
+            let c = commit(value, &r);
+            let proof = RangeProof::prove("sid", 1, value, &r, bits, g, h).unwrap();
+            assert_eq!(proof.bits(), bits);
+            assert!(proof.verify("sid", 1, c, bits, g, h).unwrap());
+            assert!(!proof.verify("sid", 2, c, bits, g, h).unwrap());
+            assert!(!proof.verify("sid", 1, c + g, bits, g, h).unwrap());
+            if bits < 64 {
+                assert!(!proof.verify("sid", 1, c, bits + 1, g, h).unwrap());
+            }
+        }
+
+        let r = Scalar::random(&mut OsRng);
+        let proof = RangeProof::prove("sid", 1, 42, &r, 8, g, h).unwrap();
+        let json = serde_json::to_string(&proof).unwrap();
+        let decoded: RangeProof = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(decoded.verify("sid", 1, commit(42, &r), 8, g, h).unwrap());
+    }
+
+    #[test]
+    fn values_out_of_range_are_rejected() {
+        let g = ProjectivePoint::GENERATOR;
+        let h = nums_generator(PEDERSEN_H).unwrap();
+        let r = Scalar::random(&mut OsRng);
+        assert_eq!(
+            RangeProof::prove("sid", 1, 256, &r, 8, g, h),
+            Err(DLogProofError::OutOfRange)
+        );
+        assert_eq!(
+            RangeProof::prove("sid", 1, 0, &r, 65, g, h),
+            Err(DLogProofError::OutOfRange)
+        );
+
+        // A bit commitment to 2 can't be proven to hold 0 or 1
+        let mut proof = RangeProof::prove("sid", 1, 3, &r, 2, g, h).unwrap();
+        let c = commit(3, &r);
+        proof.commitments[0] += g + g;
+        proof.commitments[1] -= g;
+        assert!(!proof.verify("sid", 1, c, 2, g, h).unwrap());
+    }
+}
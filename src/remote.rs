@@ -0,0 +1,382 @@
+//! Client for the `dlog-server` verification service (`remote` feature)
+//!
+//! `RemoteVerifier` submits proofs to `POST /verify` and `/verify-batch` and turns the answers
+//! into the crate's types. Requests time out after `timeout`; connection failures, timeouts,
+//! rate limiting (429) and server errors (5xx) are retried up to `retries` times with doubling
+//! delays, while a rejected request (400) fails at once with the server's reason.
+
+use std::time::Duration;
+
+use k256::ProjectivePoint;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::batch::BatchItem;
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::serialization::PointHex;
+
+/// A proof with its statement, in the server's request format
+#[serde_as]
+#[derive(Serialize)]
+struct RemoteRequest<'a> {
+    sid: &'a str,
+    pid: u32,
+    #[serde_as(as = "PointHex")]
+    public: ProjectivePoint,
+    proof: &'a DLogProof,
+}
+
+impl<'a> TryFrom<&BatchItem<'a, ProjectivePoint>> for RemoteRequest<'a> {
+    type Error = DLogProofError;
+
+    fn try_from(
+        &(sid, pid, public, proof): &BatchItem<'a, ProjectivePoint>,
+    ) -> Result<Self, DLogProofError> {
+        let sid = core::str::from_utf8(sid.as_bytes()).map_err(|_| {
+            DLogProofError::Serialization("the server only takes UTF-8 session IDs".into())
+        })?;
+        let pid = pid.index().ok_or_else(|| {
+            DLogProofError::Serialization("the server only takes u32 participant IDs".into())
+        })?;
+        Ok(RemoteRequest {
+            sid,
+            pid,
+            public,
+            proof,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct RemoteVerdict {
+    valid: bool,
+}
+
+#[derive(Deserialize)]
+struct RemoteError {
+    error: String,
+}
+
+/// The server's verdict on a batch
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct BatchVerdict {
+    /// `true` only if every entry was well-formed and verified
+    pub valid: bool,
+    /// Number of entries submitted
+    pub total: usize,
+    /// Indices of the well-formed entries whose proof doesn't verify
+    pub invalid: Vec<usize>,
+    /// Entries the server couldn't parse or doesn't accept
+    pub malformed: Vec<MalformedEntry>,
+}
+
+/// A batch entry the server rejected, with its reason
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct MalformedEntry {
+    pub index: usize,
+    pub error: String,
+}
+
+/// Client for a `dlog-server`
+///
+/// ```no_run
+/// # use schnorr_dlog_zkp::{k256::ProjectivePoint, DLogProof, DLogProofError};
+/// use schnorr_dlog_zkp::remote::RemoteVerifier;
+///
+/// # async fn run(proof: DLogProof, y: ProjectivePoint) -> Result<(), DLogProofError> {
+/// let server = RemoteVerifier::new("http://127.0.0.1:8080")?.tenant("product-a");
+/// let valid = server.verify("session-1", 1, y, &proof).await?;
+/// # let _ = valid;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RemoteVerifier {
+    client: reqwest::Client,
+    base_url: String,
+    timeout: Duration,
+    retries: u32,
+    backoff: Duration,
+}
+
+impl RemoteVerifier {
+    /// Requests time out after 10 seconds and are retried 3 times by default
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+    pub const DEFAULT_RETRIES: u32 = 3;
+
+    /// Creates a client for the server at `base_url`, e.g. `http://127.0.0.1:8080`
+    ///
+    /// # Returns
+    /// * `Ok(RemoteVerifier)` - The client
+    /// * `Err(DLogProofError)` - `Io` if the HTTP client can't be set up (e.g. no TLS roots)
+    pub fn new(base_url: impl Into<String>) -> Result<Self, DLogProofError> {
+        let client = reqwest::Client::builder()
+            .build()
+            .map_err(|e| DLogProofError::Io(format!("Failed to create HTTP client: {e}")))?;
+        Ok(RemoteVerifier {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            timeout: Self::DEFAULT_TIMEOUT,
+            retries: Self::DEFAULT_RETRIES,
+            backoff: Duration::from_millis(100),
+        })
+    }
+
+    /// Sends the requests to the endpoints of `tenant` on a server run with `--tenants`
+    pub fn tenant(mut self, tenant: &str) -> Self {
+        self.base_url = format!("{}/tenants/{tenant}", self.base_url);
+        self
+    }
+
+    /// Sets the time a single attempt may take
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets how often a failed attempt is retried
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the delay before the first retry, doubled for every further one
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Has the server verify a proof for y = x * G with the secp256k1 generator
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - The server's verdict
+    /// * `Err(DLogProofError)` - `Serialization` with the server's reason if it rejected the
+    ///   request, `Io` if it couldn't be reached or kept failing
+    pub async fn verify(
+        &self,
+        sid: &str,
+        pid: u32,
+        y: impl Into<ProjectivePoint>,
+        proof: &DLogProof,
+    ) -> Result<bool, DLogProofError> {
+        let request = RemoteRequest {
+            sid,
+            pid,
+            public: y.into(),
+            proof,
+        };
+        let verdict: RemoteVerdict = self.post("verify", &request).await?;
+        Ok(verdict.valid)
+    }
+
+    /// Has the server verify a batch, with the errors of `verify`, and `Serialization` for a
+    /// session ID that isn't UTF-8 or a participant ID that isn't an index, which the
+    /// server's JSON requests can't carry
+    pub async fn verify_batch(
+        &self,
+        items: &[BatchItem<'_, ProjectivePoint>],
+    ) -> Result<BatchVerdict, DLogProofError> {
+        let requests = items
+            .iter()
+            .map(RemoteRequest::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        self.post("verify-batch", &requests).await
+    }
+
+    async fn post<T, R>(&self, endpoint: &str, body: &T) -> Result<R, DLogProofError>
+    where
+        T: Serialize + ?Sized,
+        R: for<'de> Deserialize<'de>,
+    {
+        let url = format!("{}/{endpoint}", self.base_url);
+        let mut delay = self.backoff;
+        let mut attempt = 0;
+        loop {
+            let error = match self.attempt(&url, body).await {
+                Ok(response) => return Ok(response),
+                Err(Attempt::Failed(error)) => return Err(error),
+                Err(Attempt::Retryable(error)) => error,
+            };
+            if attempt == self.retries {
+                return Err(DLogProofError::Io(format!(
+                    "{url} failed after {} attempts: {error}",
+                    attempt + 1
+                )));
+            }
+            tokio::time::sleep(delay).await;
+            delay = delay.saturating_mul(2);
+            attempt += 1;
+        }
+    }
+
+    async fn attempt<T, R>(&self, url: &str, body: &T) -> Result<R, Attempt>
+    where
+        T: Serialize + ?Sized,
+        R: for<'de> Deserialize<'de>,
+    {
+        let response = self
+            .client
+            .post(url)
+            .timeout(self.timeout)
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| Attempt::Retryable(e.to_string()))?;
+        let status = response.status();
+        if status.is_success() {
+            return response.json().await.map_err(|e| {
+                Attempt::Failed(DLogProofError::Serialization(format!(
+                    "Unexpected response from {url}: {e}"
+                )))
+            });
+        }
+        let reason = response
+            .json::<RemoteError>()
+            .await
+            .map_or_else(|_| status.to_string(), |body| body.error);
+        if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+            Err(Attempt::Retryable(reason))
+        } else if status == StatusCode::BAD_REQUEST {
+            Err(Attempt::Failed(DLogProofError::Serialization(format!(
+                "Server rejected the request: {reason}"
+            ))))
+        } else {
+            Err(Attempt::Failed(DLogProofError::Io(format!(
+                "{url} answered {status}: {reason}"
+            ))))
+        }
+    }
+}
+
+/// Why an attempt failed: worth retrying or final
+enum Attempt {
+    Retryable(String),
+    Failed(DLogProofError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::group::DLogGroup;
+    use elliptic_curve::Field;
+    use k256::Scalar;
+    use rand_core::OsRng;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serves one canned response per connection, in order, and returns the request bodies
+    fn serve(responses: &[(u16, &'static str)]) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let responses = responses.to_vec();
+        let server = thread::spawn(move || {
+            let mut bodies = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut request = vec![0; length];
+                reader.read_exact(&mut request).unwrap();
+                bodies.push(String::from_utf8(request).unwrap());
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 {status} X\r\ncontent-type: application/json\r\n\
+                     content-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            bodies
+        });
+        (url, server)
+    }
+
+    fn proof() -> (ProjectivePoint, DLogProof) {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        (y, DLogProof::prove("s", 1, &x, y, g).unwrap())
+    }
+
+    #[tokio::test]
+    async fn verdicts_are_retried_until_they_arrive() {
+        let (y, proof) = proof();
+        let (url, server) = serve(&[
+            (503, ""),
+            (429, r#"{"error":"rate limit exceeded"}"#),
+            (200, r#"{"valid":true}"#),
+        ]);
+        let client = RemoteVerifier::new(url)
+            .unwrap()
+            .backoff(Duration::from_millis(1));
+        assert_eq!(client.verify("s", 1, y, &proof).await, Ok(true));
+
+        let bodies = server.join().unwrap();
+        let sent: serde_json::Value = serde_json::from_str(&bodies[2]).unwrap();
+        assert_eq!(sent["sid"], "s");
+        assert_eq!(sent["public"], hex::encode(y.encode_point()));
+        assert_eq!(
+            serde_json::from_value::<DLogProof>(sent["proof"].clone()).unwrap(),
+            proof
+        );
+    }
+
+    #[tokio::test]
+    async fn rejections_and_exhausted_retries_fail() {
+        let (y, proof) = proof();
+        let (url, server) = serve(&[
+            (400, r#"{"error":"invalid public key"}"#),
+            (500, ""),
+            (500, ""),
+        ]);
+        let client = RemoteVerifier::new(url)
+            .unwrap()
+            .retries(1)
+            .backoff(Duration::from_millis(1));
+        assert_eq!(
+            client.verify("s", 1, y, &proof).await,
+            Err(DLogProofError::Serialization(
+                "Server rejected the request: invalid public key".into()
+            ))
+        );
+        assert!(matches!(
+            client.verify("s", 1, y, &proof).await,
+            Err(DLogProofError::Io(_))
+        ));
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn batches_get_structured_verdicts() {
+        let (y, proof) = proof();
+        let (url, server) = serve(&[(
+            200,
+            r#"{"valid":false,"total":2,"invalid":[1],"malformed":[]}"#,
+        )]);
+        let client = RemoteVerifier::new(format!("{url}/")).unwrap();
+        let verdict = client
+            .verify_batch(&[
+                ("s".into(), 1.into(), y, &proof),
+                ("s".into(), 2.into(), y, &proof),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(verdict.invalid, [1]);
+        assert!(!verdict.valid);
+        let bodies = server.join().unwrap();
+        assert!(bodies[0].starts_with(r#"[{"sid":"s","pid":1,"#));
+    }
+}
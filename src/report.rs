@@ -0,0 +1,66 @@
+use crate::error::DLogProofError;
+use crate::stats::VerifyStats;
+
+/// Why a proof failed verification (see `VerificationReport`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationFailure {
+    /// The challenge recomputed from the transcript differs from the one the proof carries
+    /// (proofs in (c, s) form, e.g. `CompactDLogProof`)
+    ChallengeMismatch,
+    /// s * G differs from t + c * y for the challenge c recomputed from the transcript, e.g.
+    /// because the proof was made for another statement, session or transcript configuration
+    EquationFailure,
+    /// The base point or the public key y is the identity (`DLogProofError::IdentityPoint` of
+    /// `verify`)
+    IdentityStatement,
+    /// The proof or the statement is malformed, with the decoding error; this includes a
+    /// BIP-340 proof whose t has an odd y-coordinate
+    Encoding(DLogProofError),
+}
+
+/// Outcome of a verification, from `DLogProof::verify_detailed` and friends
+///
+/// Unlike the `bool` of `verify`, a failed verification says why it failed. Only the
+/// categories are distinguished; the final comparison of the verification equation is still
+/// constant-time, so the report reveals nothing about how close a forgery came.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationReport {
+    /// `None` for a valid proof, otherwise why it was rejected
+    pub failure: Option<VerificationFailure>,
+    /// What the verification cost; zero where it stopped before the challenge, and for
+    /// `CompactDLogProof`, which doesn't measure it
+    pub stats: VerifyStats,
+}
+
+impl VerificationReport {
+    /// Whether the proof is valid
+    pub fn is_valid(&self) -> bool {
+        self.failure.is_none()
+    }
+
+    /// The report of a verification rejected before any computation
+    pub(crate) fn rejected(failure: VerificationFailure) -> Self {
+        VerificationReport {
+            failure: Some(failure),
+            stats: VerifyStats::default(),
+        }
+    }
+
+    /// Turns the errors `verify` returns for a degenerate or malformed statement into
+    /// failures, passing through the others (e.g. `UnsupportedChallengeMode`)
+    pub(crate) fn from_result(
+        result: Result<Self, DLogProofError>,
+    ) -> Result<Self, DLogProofError> {
+        match result {
+            Err(DLogProofError::IdentityPoint) => {
+                Ok(Self::rejected(VerificationFailure::IdentityStatement))
+            }
+            Err(
+                error @ (DLogProofError::InvalidPoint
+                | DLogProofError::InvalidScalar
+                | DLogProofError::Serialization(_)),
+            ) => Ok(Self::rejected(VerificationFailure::Encoding(error))),
+            result => result,
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use elliptic_curve::subtle::{Choice, ConstantTimeEq};
+use elliptic_curve::Field;
+use k256::ProjectivePoint;
+use rand_core::CryptoRngCore;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use std::marker::PhantomData;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::secret::SecretScalar;
+use crate::serialization::{PointHex, ScalarHex};
+use crate::session::SessionId;
+use crate::sigma::{SigmaProof, SigmaProtocol};
+
+/// Statement of `Okamoto`: y = x_1 * G_1 + ... + x_n * G_n
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepresentationStatement<G> {
+    pub bases: Vec<G>,
+    pub y: G,
+}
+
+/// Okamoto's proof of knowledge of a representation, the protocol of `RepresentationProof`
+///
+/// Commitment t = Σ r_i * G_i, responses s_i = r_i + c * x_i, check Σ s_i * G_i = t + c * y.
+/// With a single base it is `SchnorrDLog`.
+pub struct Okamoto<G>(PhantomData<G>);
+
+impl<G: DLogGroup> SigmaProtocol for Okamoto<G> {
+    type Group = G;
+    type Statement = RepresentationStatement<G>;
+    type Witness = Vec<SecretScalar<G::Scalar>>;
+    type Commitment = G;
+    type Response = Vec<G::Scalar>;
+    type Nonce = Vec<G::Scalar>;
+
+    const DOMAIN: &'static [u8] = b"SCHNORR_REPRESENTATION_PROOF";
+
+    fn statement_points(statement: &RepresentationStatement<G>) -> Vec<G> {
+        let mut points = statement.bases.clone();
+        points.push(statement.y);
+        points
+    }
+
+    fn commitment_points(t: &G) -> Vec<G> {
+        vec![*t]
+    }
+
+    fn commit(
+        statement: &RepresentationStatement<G>,
+        _xs: &Vec<SecretScalar<G::Scalar>>,
+        rng: &mut impl CryptoRngCore,
+    ) -> (G, Vec<G::Scalar>) {
+        let rs: Vec<G::Scalar> = statement
+            .bases
+            .iter()
+            .map(|_| G::Scalar::random(&mut *rng))
+            .collect();
+        (Self::combine(&statement.bases, &rs), rs)
+    }
+
+    fn respond(
+        xs: &Vec<SecretScalar<G::Scalar>>,
+        rs: &Vec<G::Scalar>,
+        c: &G::Scalar,
+    ) -> Vec<G::Scalar> {
+        xs.iter()
+            .zip(rs)
+            .map(|(x, r)| *r + *c * x.expose_secret())
+            .collect()
+    }
+
+    fn check(
+        statement: &RepresentationStatement<G>,
+        t: &G,
+        c: &G::Scalar,
+        s: &Vec<G::Scalar>,
+    ) -> bool {
+        if s.len() != statement.bases.len() {
+            return false;
+        }
+        let lhs = Self::combine(&statement.bases, s);
+        lhs.ct_eq(&(*t + statement.y * c)).into()
+    }
+}
+
+impl<G: DLogGroup> Okamoto<G> {
+    /// Computes Σ scalars_i * bases_i
+    fn combine(bases: &[G], scalars: &[G::Scalar]) -> G {
+        bases
+            .iter()
+            .zip(scalars)
+            .fold(G::identity(), |sum, (&base, s)| sum + base * s)
+    }
+}
+
+/// Proof of knowledge of x_1, ..., x_n with y = x_1 * G_1 + ... + x_n * G_n
+///
+/// The multi-base generalization of `DLogProof` (see `Okamoto`), e.g. for opening a Pedersen
+/// commitment C = v * G + b * H without revealing v or b. One commitment for the whole
+/// statement and one response per witness:
+/// * `t` - Commitment t = Σ r_i * G_i
+/// * `s` - Responses s_i = r_i + c * x_i, in base order
+///
+/// The bases must be independent generators (e.g. from `generators::nums_generators`), or a
+/// prover knowing their relation could prove representations it doesn't know.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct RepresentationProof<G: DLogGroup = ProjectivePoint> {
+    #[serde_as(as = "PointHex")]
+    t: G,
+    #[serde_as(as = "Vec<ScalarHex>")]
+    s: Vec<G::Scalar>,
+}
+
+impl<G: DLogGroup> RepresentationProof<G> {
+    /// The commitment t = Σ r_i * G_i
+    pub fn t(&self) -> G {
+        self.t
+    }
+
+    /// The responses, one per base
+    pub fn s(&self) -> &[G::Scalar] {
+        &self.s
+    }
+
+    /// Proves knowledge of `xs` with y = Σ xs[i] * bases[i]
+    ///
+    /// # Arguments
+    /// * `sid` - Session identifier used for domain separation, a string or bytes (see `SessionId`)
+    /// * `pid` - Participant ID for uniqueness to distinguish different proofs
+    /// * `xs` - The secret scalars, one per base
+    /// * `bases` - The base points G_1, ..., G_n
+    /// * `y` - The public point
+    ///
+    /// # Returns
+    /// * `Ok(RepresentationProof)` - The proof
+    /// * `Err(DLogProofError)` - `StatementCount` if there are no bases or `xs` and `bases`
+    ///   differ in length, `IdentityPoint` for a degenerate statement, or if the challenge
+    ///   can't be derived
+    pub fn prove<'s>(
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        xs: &[G::Scalar],
+        bases: &[G],
+        y: G,
+    ) -> Result<Self, DLogProofError> {
+        if xs.len() != bases.len() {
+            return Err(DLogProofError::StatementCount);
+        }
+        let statement = Self::statement(bases, y)?;
+        let xs = xs.iter().map(SecretScalar::from).collect();
+        let proof = SigmaProof::<Okamoto<G>>::prove(sid, pid, &statement, &xs)?;
+        Ok(RepresentationProof {
+            t: proof.commitment,
+            s: proof.response,
+        })
+    }
+
+    /// Verifies the proof for y over `bases`
+    ///
+    /// A proof with a different number of responses than bases is invalid, not an error.
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Validity of the proof
+    /// * `Err(DLogProofError)` - `StatementCount` if `bases` is empty, `IdentityPoint` for a
+    ///   degenerate statement, or if the challenge can't be derived
+    pub fn verify<'s>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: u32,
+        bases: &[G],
+        y: G,
+    ) -> Result<bool, DLogProofError> {
+        let statement = Self::statement(bases, y)?;
+        let proof = SigmaProof::<Okamoto<G>> {
+            commitment: self.t,
+            response: self.s.clone(),
+        };
+        proof.verify(sid, pid, &statement)
+    }
+
+    /// Checks the statement like `DLogProof` does and collects it
+    fn statement(bases: &[G], y: G) -> Result<RepresentationStatement<G>, DLogProofError> {
+        if bases.is_empty() {
+            return Err(DLogProofError::StatementCount);
+        }
+        for &base in bases {
+            DLogProof::check_statement(y, base)?;
+        }
+        Ok(RepresentationStatement {
+            bases: bases.to_vec(),
+            y,
+        })
+    }
+}
+
+impl<G: DLogGroup> ConstantTimeEq for RepresentationProof<G> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.t.ct_eq(&other.t) & self.s[..].ct_eq(&other.s[..])
+    }
+}
+
+impl<G: DLogGroup> PartialEq for RepresentationProof<G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<G: DLogGroup> Eq for RepresentationProof<G> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::nums_generator;
+    use k256::Scalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn pedersen_openings_are_provable() {
+        let bases = [
+            ProjectivePoint::GENERATOR,
+            nums_generator("representation-test").unwrap(),
+        ];
+        let xs = [Scalar::random(&mut OsRng), Scalar::random(&mut OsRng)];
+        let y = bases[0] * xs[0] + bases[1] * xs[1];
+
+        let proof = RepresentationProof::prove("sid", 1, &xs, &bases, y).unwrap();
+        assert!(proof.verify("sid", 1, &bases, y).unwrap());
+        assert!(!proof.verify("sid", 2, &bases, y).unwrap());
+        assert!(!proof.verify("sid", 1, &[bases[1], bases[0]], y).unwrap());
+        assert!(!proof.verify("sid", 1, &bases[..1], y).unwrap());
+
+        let json = serde_json::to_string(&proof).unwrap();
+        let decoded: RepresentationProof = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(decoded.verify("sid", 1, &bases, y).unwrap());
+    }
+
+    #[test]
+    fn wrong_representations_are_rejected() {
+        let bases = [
+            ProjectivePoint::GENERATOR,
+            nums_generator("representation-test").unwrap(),
+        ];
+        let xs = [Scalar::random(&mut OsRng), Scalar::random(&mut OsRng)];
+        let y = bases[0] * xs[0] + bases[1] * (xs[1] + Scalar::ONE);
+
+        let proof = RepresentationProof::prove("sid", 1, &xs, &bases, y).unwrap();
+        assert!(!proof.verify("sid", 1, &bases, y).unwrap());
+        assert_eq!(
+            RepresentationProof::prove("sid", 1, &xs[..1], &bases, y),
+            Err(DLogProofError::StatementCount)
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use curve25519_dalek::traits::{MultiscalarMul, VartimeMultiscalarMul};
+use curve25519_dalek::{RistrettoPoint, Scalar};
+
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+
+/// Ristretto255, the prime-order group built on Curve25519 (`backend-ristretto` feature)
+///
+/// Points and scalars use their canonical 32-byte encodings, scalars being little-endian, so
+/// a `DLogProof<RistrettoPoint>` serializes to JSON like a secp256k1 proof with shorter hex
+/// strings. The group order is close to 2^252, so the challenge hash is reduced modulo the
+/// order, little-endian like the scalars.
+impl DLogGroup for RistrettoPoint {
+    fn lincomb(terms: &[(Self, Scalar)]) -> Self {
+        RistrettoPoint::multiscalar_mul(
+            terms.iter().map(|(_, scalar)| scalar),
+            terms.iter().map(|(point, _)| point),
+        )
+    }
+
+    fn lincomb_vartime(terms: &[(Self, Scalar)]) -> Self {
+        RistrettoPoint::vartime_multiscalar_mul(
+            terms.iter().map(|(_, scalar)| scalar),
+            terms.iter().map(|(point, _)| point),
+        )
+    }
+
+    fn challenge_from_hash(hash: &[u8]) -> Result<Scalar, DLogProofError> {
+        // Little-endian, so zero-extending a 32-byte hash keeps its value
+        let mut wide = [0u8; 64];
+        wide.get_mut(..hash.len())
+            .ok_or(DLogProofError::ChallengeDerivation)?
+            .copy_from_slice(hash);
+        Ok(Scalar::from_bytes_mod_order_wide(&wide))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dlog_proof::DLogProof;
+    use crate::prover::Prover;
+    use crate::verifier::{Timing, Verifier};
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+    use curve25519_dalek::{RistrettoPoint, Scalar};
+    use rand_core::OsRng;
+
+    #[test]
+    fn ristretto_proofs_verify() {
+        let g = RISTRETTO_BASEPOINT_POINT;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+
+        let proof = DLogProof::prove("sid", 1, &x, y, g).unwrap();
+        assert!(proof.verify("sid", 1, y, g).unwrap());
+        assert!(!proof.verify("sid", 2, y, g).unwrap());
+        assert!(!proof
+            .verify("sid", 1, g * Scalar::random(&mut OsRng), g)
+            .unwrap());
+
+        let proof = Prover::new()
+            .domain(b"app")
+            .blinded(true)
+            .prove("sid", 1, &x, y, g)
+            .unwrap();
+        assert!(Verifier::new()
+            .domain(b"app")
+            .verify(&proof, "sid", 1, y, g)
+            .unwrap());
+        let vartime = Verifier::new().domain(b"app").timing(Timing::Variable);
+        assert!(vartime.verify(&proof, "sid", 1, y, g).unwrap());
+        assert!(!vartime.verify(&proof, "sid", 2, y, g).unwrap());
+    }
+
+    #[test]
+    fn ristretto_proofs_roundtrip_through_json() {
+        let g = RISTRETTO_BASEPOINT_POINT;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let proof = DLogProof::prove("sid", 1, &x, y, g).unwrap();
+
+        let json = serde_json::to_string(&proof).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["t"].as_str().unwrap().len(), 64);
+        assert_eq!(value["s"].as_str().unwrap().len(), 64);
+
+        let parsed: DLogProof<RistrettoPoint> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, proof);
+        assert!(parsed.verify("sid", 1, y, g).unwrap());
+
+        // A non-canonical scalar (the group order plus one is above it) is rejected
+        let order_plus_one = "eed3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010";
+        let tampered = json.replace(value["s"].as_str().unwrap(), order_plus_one);
+        assert!(serde_json::from_str::<DLogProof<RistrettoPoint>>(&tampered).is_err());
+    }
+}
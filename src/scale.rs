@@ -0,0 +1,70 @@
+use parity_scale_codec::{Decode, Encode, EncodeLike, Error, Input, MaxEncodedLen, Output};
+
+use crate::dlog_proof::DLogProof;
+
+/// SCALE encoding of a proof: the fixed 65-byte layout `t (compressed SEC1) || s (big-endian)`
+///
+/// Both parts have a fixed size, so no length prefix is written, which keeps the encoding
+/// usable as a `MaxEncodedLen` storage value in Substrate pallets.
+impl Encode for DLogProof {
+    fn size_hint(&self) -> usize {
+        Self::ENCODED_LEN
+    }
+
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        dest.write(&self.to_bytes());
+    }
+}
+
+impl EncodeLike for DLogProof {}
+
+impl MaxEncodedLen for DLogProof {
+    fn max_encoded_len() -> usize {
+        Self::ENCODED_LEN
+    }
+}
+
+impl Decode for DLogProof {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        let mut bytes = [0u8; Self::ENCODED_LEN];
+        input.read(&mut bytes)?;
+        // Rejects invalid points and scalars >= curve order, so every proof has exactly one encoding
+        DLogProof::from_bytes(&bytes).map_err(|_| Error::from("Invalid proof encoding"))
+    }
+
+    fn encoded_fixed_size() -> Option<usize> {
+        Some(Self::ENCODED_LEN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::Field;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_core::OsRng;
+
+    #[test]
+    fn round_trips_with_fixed_size() {
+        let x = Scalar::random(&mut OsRng);
+        let g = ProjectivePoint::GENERATOR;
+        let proof = DLogProof::prove("sid", 1, &x, g * x, g).unwrap();
+
+        let encoded = proof.encode();
+        assert_eq!(encoded.len(), DLogProof::max_encoded_len());
+        assert_eq!(DLogProof::decode(&mut &encoded[..]).unwrap(), proof);
+    }
+
+    #[test]
+    fn rejects_truncated_and_non_canonical_input() {
+        let x = Scalar::random(&mut OsRng);
+        let g = ProjectivePoint::GENERATOR;
+        let mut encoded = DLogProof::prove("sid", 1, &x, g * x, g).unwrap().encode();
+
+        assert!(DLogProof::decode(&mut &encoded[..64]).is_err());
+
+        // s = 2^256 - 1 is above the group order
+        encoded[33..].fill(0xff);
+        assert!(DLogProof::decode(&mut &encoded[..]).is_err());
+    }
+}
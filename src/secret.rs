@@ -0,0 +1,75 @@
+use core::fmt;
+
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Secret scalar (the discrete logarithm x) that is wiped from memory when dropped
+///
+/// `DLogProof::prove` and the other proving entry points take anything convertible into a
+/// `SecretScalar`. Passing one by value hands the only copy of the secret to the prover, which
+/// wipes it once the proof is made; passing a plain `&Scalar` still works, and then only the
+/// prover's internal copy is wiped. `Debug` never prints the value.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct SecretScalar<F: Zeroize>(F);
+
+impl<F: Zeroize> SecretScalar<F> {
+    /// Takes ownership of `x`
+    pub fn new(x: F) -> Self {
+        SecretScalar(x)
+    }
+
+    /// The secret value, for computations that need it directly (e.g. deriving y = x * G)
+    pub fn expose_secret(&self) -> &F {
+        &self.0
+    }
+}
+
+impl<F: Zeroize> From<F> for SecretScalar<F> {
+    fn from(x: F) -> Self {
+        SecretScalar(x)
+    }
+}
+
+impl<F: Zeroize + Copy> From<&F> for SecretScalar<F> {
+    fn from(x: &F) -> Self {
+        SecretScalar(*x)
+    }
+}
+
+impl<F: Zeroize + Copy> From<&SecretScalar<F>> for SecretScalar<F> {
+    fn from(x: &SecretScalar<F>) -> Self {
+        SecretScalar(x.0)
+    }
+}
+
+impl<F: Zeroize> fmt::Debug for SecretScalar<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretScalar(<redacted>)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlog_proof::DLogProof;
+    use elliptic_curve::Field;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_core::OsRng;
+
+    #[test]
+    fn secret_scalars_prove_and_are_wiped() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+
+        let secret = SecretScalar::new(x);
+        assert_eq!(format!("{secret:?}"), "SecretScalar(<redacted>)");
+        let proof = DLogProof::prove("sid", 1, &secret, y, g).unwrap();
+        assert!(proof.verify("sid", 1, y, g).unwrap());
+        let proof = DLogProof::prove("sid", 1, secret, y, g).unwrap();
+        assert!(proof.verify("sid", 1, y, g).unwrap());
+
+        let mut secret = SecretScalar::new(x);
+        secret.zeroize();
+        assert_eq!(*secret.expose_secret(), Scalar::ZERO);
+    }
+}
@@ -0,0 +1,198 @@
+//! Serde helpers for your own types holding points and scalars
+//!
+//! The crate's wire formats, gathered for reuse: the `serde_with` adapters of `serialization`
+//! (`PointHex`, `ScalarHex`, `Uncompressed`, `Prefixed`), the `AsBytes` adapter, and
+//! modules for `#[serde(with = "...")]` where `serde_with` isn't wanted:
+//!
+//! ```
+//! use schnorr_dlog_zkp::k256::{ProjectivePoint, Scalar};
+//! use schnorr_dlog_zkp::serde_utils::{self, PointHex};
+//! use serde::{Deserialize, Serialize};
+//! use serde_with::serde_as;
+//!
+//! #[serde_as]
+//! #[derive(Serialize, Deserialize)]
+//! struct Registration {
+//!     #[serde(with = "serde_utils::point_hex")]
+//!     key: ProjectivePoint,
+//!     #[serde_as(as = "Vec<PointHex>")]
+//!     delegates: Vec<ProjectivePoint>,
+//!     #[serde(with = "serde_utils::scalar_bytes")]
+//!     tweak: Scalar,
+//! }
+//! ```
+//!
+//! The hex formats write hex strings for human-readable serializers such as JSON and the
+//! bytes themselves for binary ones such as bincode; reading accepts any letter case and an
+//! optional `0x` prefix. The byte formats write bytes for every serializer, which JSON shows
+//! as an array of numbers. Points and scalars are validated when read, like the fields of a
+//! `DLogProof`.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use serde_with::{Bytes, DeserializeAs, SerializeAs};
+
+pub use crate::serialization::{
+    deserialize_point_hex, deserialize_scalar_hex, serialize_point_hex, serialize_scalar_hex,
+    HexEncoding, PointHex, Prefixed, ScalarHex, Uncompressed,
+};
+
+/// `serde_with` adapter writing the encoding of another adapter as bytes in every format
+///
+/// E.g. `#[serde_as(as = "AsBytes<PointHex>")] y: ProjectivePoint` for a format that should
+/// carry the 33 bytes rather than their hex even when it is human-readable.
+pub struct AsBytes<A>(PhantomData<A>);
+
+impl<T, A: HexEncoding<T>> SerializeAs<T> for AsBytes<A> {
+    fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(&A::to_encoding(source))
+    }
+}
+
+impl<'de, T, A: HexEncoding<T>> DeserializeAs<'de, T> for AsBytes<A> {
+    fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes: Vec<u8> = Bytes::deserialize_as(deserializer)?;
+        A::from_encoding(&bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+/// `#[serde(with = "point_hex")]` for a point of any `DLogGroup`, see `PointHex`
+pub mod point_hex {
+    use super::*;
+    use crate::group::DLogGroup;
+
+    pub fn serialize<G: DLogGroup, S: serde::Serializer>(
+        point: &G,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        PointHex::serialize_as(point, serializer)
+    }
+
+    pub fn deserialize<'de, G: DLogGroup, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<G, D::Error> {
+        PointHex::deserialize_as(deserializer)
+    }
+}
+
+/// `#[serde(with = "scalar_hex")]` for a scalar of any prime field, see `ScalarHex`
+pub mod scalar_hex {
+    use super::*;
+    use elliptic_curve::PrimeField;
+
+    pub fn serialize<F: PrimeField, S: serde::Serializer>(
+        scalar: &F,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        ScalarHex::serialize_as(scalar, serializer)
+    }
+
+    pub fn deserialize<'de, F: PrimeField, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<F, D::Error> {
+        ScalarHex::deserialize_as(deserializer)
+    }
+}
+
+/// `#[serde(with = "point_bytes")]`, the point encoding of `PointHex` as bytes in every format
+pub mod point_bytes {
+    use super::*;
+    use crate::group::DLogGroup;
+
+    pub fn serialize<G: DLogGroup, S: serde::Serializer>(
+        point: &G,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        AsBytes::<PointHex>::serialize_as(point, serializer)
+    }
+
+    pub fn deserialize<'de, G: DLogGroup, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<G, D::Error> {
+        AsBytes::<PointHex>::deserialize_as(deserializer)
+    }
+}
+
+/// `#[serde(with = "scalar_bytes")]`, the scalar encoding of `ScalarHex` as bytes in every
+/// format
+pub mod scalar_bytes {
+    use super::*;
+    use elliptic_curve::PrimeField;
+
+    pub fn serialize<F: PrimeField, S: serde::Serializer>(
+        scalar: &F,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        AsBytes::<ScalarHex>::serialize_as(scalar, serializer)
+    }
+
+    pub fn deserialize<'de, F: PrimeField, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<F, D::Error> {
+        AsBytes::<ScalarHex>::deserialize_as(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::{Field, PrimeField};
+    use k256::elliptic_curve::sec1::ToEncodedPoint;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_core::OsRng;
+    use serde::{Deserialize, Serialize};
+    use serde_with::serde_as;
+
+    #[serde_as]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        #[serde(with = "point_hex")]
+        key: ProjectivePoint,
+        #[serde(with = "scalar_hex")]
+        tweak: Scalar,
+        #[serde(with = "point_bytes")]
+        raw_key: ProjectivePoint,
+        #[serde(with = "scalar_bytes")]
+        raw_tweak: Scalar,
+        #[serde_as(as = "Vec<AsBytes<PointHex>>")]
+        raw_keys: Vec<ProjectivePoint>,
+    }
+
+    #[test]
+    fn helpers_round_trip_in_text_and_binary_formats() {
+        let g = ProjectivePoint::GENERATOR;
+        let tweak = Scalar::random(&mut OsRng);
+        let key = g * tweak;
+        let record = Record {
+            key,
+            tweak,
+            raw_key: key,
+            raw_tweak: tweak,
+            raw_keys: vec![key, g],
+        };
+
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["key"], hex::encode(key.to_encoded_point(true)));
+        assert_eq!(json["tweak"], hex::encode(tweak.to_repr()));
+        let raw_key: Vec<u8> = serde_json::from_value(json["raw_key"].clone()).unwrap();
+        assert_eq!(raw_key, key.to_encoded_point(true).as_bytes());
+        assert_eq!(json["raw_tweak"].as_array().unwrap().len(), 32);
+        assert_eq!(serde_json::from_value::<Record>(json).unwrap(), record);
+
+        let bytes = bincode::serialize(&record).unwrap();
+        assert_eq!(bincode::deserialize::<Record>(&bytes).unwrap(), record);
+        let bytes = postcard::to_allocvec(&record).unwrap();
+        assert_eq!(postcard::from_bytes::<Record>(&bytes).unwrap(), record);
+
+        // Bytes are validated like hex
+        let mut json = serde_json::to_value(&record).unwrap();
+        json["raw_tweak"] = serde_json::json!([0xffu8; 32].to_vec());
+        assert!(serde_json::from_value::<Record>(json).is_err());
+    }
+}
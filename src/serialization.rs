@@ -1,7 +1,19 @@
-use elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
 use hex::{decode, encode};
-use k256::{elliptic_curve::PrimeField, AffinePoint, FieldBytes, ProjectivePoint, Scalar};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::{elliptic_curve::PrimeField, ProjectivePoint, Scalar};
+use serde::ser::SerializeStruct;
 use serde::Deserialize;
+use serde_with::de::DeserializeAsWrap;
+use serde_with::ser::SerializeAsWrap;
+use serde_with::{Bytes, DeserializeAs, SerializeAs};
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
 
 /// Serializes a ProjectivePoint to a hex string in compressed SEC1 (Standards for Efficient Cryptography 1) format
 /// SEC1 format is a standard for representing elliptic curve points.
@@ -9,67 +21,560 @@ use serde::Deserialize;
 ///  - Uncompressed: 0x04 + x_coordinate + y_coordinate (65 bytes total)
 ///  - Compressed:   (0x02 or 0x03) + x_coordinate (33 bytes total)
 ///    02 (if y is even), 03 (if y is odd)
-pub(crate) fn serialize_point_hex<S>(point: &ProjectivePoint, serializer: S) -> Result<S::Ok, S::Error>
+pub fn serialize_point_hex<S>(point: &ProjectivePoint, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: serde::Serializer,
 {
-    // Convert to (x,y) coordinates
-    let affine = point.to_affine();
-    // `true` means use compressed format
-    let encoded_point = affine.to_encoded_point(true);
-    let bytes = encoded_point.as_bytes();
-    let hex = encode(bytes);
-
-    serializer.serialize_str(&hex)
+    PointHex::serialize_as(point, serializer)
 }
 
 /// Deserializes a hex string in SEC1 format back to ProjectivePoint
-pub(crate) fn deserialize_point_hex<'de, D>(deserializer: D) -> Result<ProjectivePoint, D::Error>
+///
+/// Accepts compressed and uncompressed SEC1, an optional `0x` prefix and any letter case; the
+/// point is re-serialized compressed and in lowercase.
+pub fn deserialize_point_hex<'de, D>(deserializer: D) -> Result<ProjectivePoint, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
-    let hex_str: String = String::deserialize(deserializer)?;
-    let bytes = decode(&hex_str)
-        .map_err(|_| serde::de::Error::custom("Invalid hex encoding"))?;
+    PointHex::deserialize_as(deserializer)
+}
 
-    // Parse bytes as SEC1 encoded point, then convert to AffinePoint
-    let affine = AffinePoint::from_encoded_point(&k256::EncodedPoint::from_bytes(&bytes)
-        .map_err(|_| serde::de::Error::custom("Invalid point bytes"))?);
+/// Serializes a Scalar (field element) to hex string
+pub fn serialize_scalar_hex<S>(scalar: &Scalar, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    ScalarHex::serialize_as(scalar, serializer)
+}
 
-    // Convert to ProjectivePoint if valid
-    if affine.is_some().into() {
-        Ok(ProjectivePoint::from(affine.unwrap()))
+/// Deserializes a hex string back to a Scalar, with an optional `0x` prefix and in any case
+pub fn deserialize_scalar_hex<'de, D>(deserializer: D) -> Result<Scalar, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    ScalarHex::deserialize_as(deserializer)
+}
+
+/// Writes an encoding as a hex string for human-readable formats and as raw bytes otherwise
+///
+/// JSON and other text formats keep the crate's hex strings, while binary formats such as
+/// bincode or postcard store the bytes as they are instead of twice as many hex characters.
+fn serialize_encoded<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&encode(bytes))
     } else {
-        Err(serde::de::Error::custom("Invalid point encoding"))
+        serializer.serialize_bytes(bytes)
     }
 }
 
-/// Serializes a Scalar (field element) to hex string
-pub(crate) fn serialize_scalar_hex<S>(scalar: &Scalar, serializer: S) -> Result<S::Ok, S::Error>
+/// Reads an encoding written by `serialize_encoded`, failing with `invalid` on malformed hex
+///
+/// Hex strings are accepted in any case and with an optional `0x` prefix, as produced by
+/// other tools (and by the crate's own uncompressed JSON output).
+fn deserialize_encoded<'de, D>(
+    deserializer: D,
+    invalid: DLogProofError,
+) -> Result<Vec<u8>, D::Error>
 where
-    S: serde::Serializer,
+    D: serde::Deserializer<'de>,
 {
-    let repr = scalar.to_repr();
-    let hex = encode::<&[u8]>(repr.as_ref());
+    if deserializer.is_human_readable() {
+        let hex_str: String = String::deserialize(deserializer)?;
+        let digits = hex_str
+            .strip_prefix("0x")
+            .or_else(|| hex_str.strip_prefix("0X"))
+            .unwrap_or(&hex_str);
+        decode(digits).map_err(|_| serde::de::Error::custom(invalid))
+    } else {
+        Bytes::deserialize_as(deserializer)
+    }
+}
+
+/// The bytes a hex adapter of this module writes for a `T`
+///
+/// `Prefixed` builds on it to write the same bytes with a `0x` prefix.
+pub trait HexEncoding<T> {
+    /// Encodes `source` into the bytes written as hex (or as-is for binary serializers)
+    fn to_encoding(source: &T) -> Vec<u8>;
+
+    /// Decodes the bytes read back
+    fn from_encoding(bytes: &[u8]) -> Result<T, DLogProofError>;
 
-    // Serialize as string
-    serializer.serialize_str(&hex)
+    /// The error for a string that isn't hex
+    fn invalid_hex() -> DLogProofError {
+        DLogProofError::Serialization("Invalid hex".into())
+    }
 }
 
-/// Deserializes a hex string back to a Scalar
-pub(crate) fn deserialize_scalar_hex<'de, D>(deserializer: D) -> Result<Scalar, D::Error>
+fn serialize_as_hex<T, A, S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
 where
+    A: HexEncoding<T>,
+    S: serde::Serializer,
+{
+    serialize_encoded(&A::to_encoding(source), serializer)
+}
+
+fn deserialize_as_hex<'de, T, A, D>(deserializer: D) -> Result<T, D::Error>
+where
+    A: HexEncoding<T>,
     D: serde::Deserializer<'de>,
 {
-    let hex_str: String = String::deserialize(deserializer)?;
-    let bytes = decode(&hex_str)
-        .map_err(|_| serde::de::Error::custom("Invalid hex encoding"))?;
+    let bytes = deserialize_encoded(deserializer, A::invalid_hex())?;
+    A::from_encoding(&bytes).map_err(serde::de::Error::custom)
+}
+
+/// `serde_with` adapter for points in the crate's hex format
+///
+/// Points are hex encoded with `DLogGroup::encode_point`, i.e. compressed SEC1 on secp256k1.
+/// Binary serializers (`is_human_readable() == false`) get the encoded bytes unchanged.
+/// Lets other structs reuse the format, including inside containers:
+/// `#[serde_as(as = "PointHex")] y: ProjectivePoint` or
+/// `#[serde_as(as = "Vec<PointHex>")] keys: Vec<ProjectivePoint>`
+pub struct PointHex;
+
+impl<G: DLogGroup> HexEncoding<G> for PointHex {
+    fn to_encoding(source: &G) -> Vec<u8> {
+        source.encode_point()
+    }
+
+    fn from_encoding(bytes: &[u8]) -> Result<G, DLogProofError> {
+        G::decode_point(bytes).ok_or(DLogProofError::InvalidPoint)
+    }
+
+    fn invalid_hex() -> DLogProofError {
+        DLogProofError::InvalidPoint
+    }
+}
+
+impl<G: DLogGroup> SerializeAs<G> for PointHex {
+    fn serialize_as<S>(source: &G, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_as_hex::<G, Self, S>(source, serializer)
+    }
+}
+
+impl<'de, G: DLogGroup> DeserializeAs<'de, G> for PointHex {
+    fn deserialize_as<D>(deserializer: D) -> Result<G, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_as_hex::<G, Self, D>(deserializer)
+    }
+}
+
+/// `serde_with` adapter for secp256k1 points as uncompressed SEC1 hex, 0x04 || x || y
+///
+/// For consumers that expect both coordinates, such as EVM contracts and many JavaScript
+/// libraries. Like `PointHex` it reads back any SEC1 encoding.
+pub struct Uncompressed;
+
+impl HexEncoding<ProjectivePoint> for Uncompressed {
+    fn to_encoding(source: &ProjectivePoint) -> Vec<u8> {
+        source.to_affine().to_encoded_point(false).as_bytes().to_vec()
+    }
+
+    fn from_encoding(bytes: &[u8]) -> Result<ProjectivePoint, DLogProofError> {
+        PointHex::from_encoding(bytes)
+    }
+
+    fn invalid_hex() -> DLogProofError {
+        DLogProofError::InvalidPoint
+    }
+}
+
+impl SerializeAs<ProjectivePoint> for Uncompressed {
+    fn serialize_as<S>(source: &ProjectivePoint, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_as_hex::<ProjectivePoint, Self, S>(source, serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, ProjectivePoint> for Uncompressed {
+    fn deserialize_as<D>(deserializer: D) -> Result<ProjectivePoint, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_as_hex::<ProjectivePoint, Self, D>(deserializer)
+    }
+}
+
+/// `serde_with` adapter writing the hex of another adapter with a `0x` prefix
+///
+/// E.g. `#[serde_as(as = "Prefixed<Uncompressed>")] y: ProjectivePoint`. Binary serializers
+/// still get the bytes alone, and reading accepts the hex with or without the prefix.
+pub struct Prefixed<A>(PhantomData<A>);
+
+impl<T, A: HexEncoding<T>> SerializeAs<T> for Prefixed<A> {
+    fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let bytes = A::to_encoding(source);
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&format!("0x{}", encode(bytes)))
+        } else {
+            serializer.serialize_bytes(&bytes)
+        }
+    }
+}
 
-    // Ensure bytes are exactly 32 bytes (256 bits)
-    let bytes_array: [u8; 32] = bytes.try_into()
-        .map_err(|_| serde::de::Error::custom("Invalid length for Scalar"))?;
+impl<'de, T, A: HexEncoding<T>> DeserializeAs<'de, T> for Prefixed<A> {
+    fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_as_hex::<T, A, D>(deserializer)
+    }
+}
 
-    // Convert bytes to Scalar
-    Option::from(Scalar::from_repr(FieldBytes::from(bytes_array)))
-        .ok_or_else(|| serde::de::Error::custom("Invalid Scalar value"))
+/// `serde_with` adapter for scalars in the crate's hex format
+///
+/// Scalars are hex encoded in their canonical representation, 32 bytes big-endian on secp256k1,
+/// or written as raw bytes for binary serializers.
+pub struct ScalarHex;
+
+impl<F: PrimeField> HexEncoding<F> for ScalarHex {
+    fn to_encoding(source: &F) -> Vec<u8> {
+        source.to_repr().as_ref().to_vec()
+    }
+
+    fn from_encoding(bytes: &[u8]) -> Result<F, DLogProofError> {
+        // Ensure bytes have the exact length of a scalar (32 bytes on secp256k1)
+        let mut repr = F::Repr::default();
+        if repr.as_ref().len() != bytes.len() {
+            return Err(DLogProofError::InvalidScalar);
+        }
+        repr.as_mut().copy_from_slice(bytes);
+
+        // Convert bytes to Scalar
+        Option::from(F::from_repr(repr)).ok_or(DLogProofError::InvalidScalar)
+    }
+
+    fn invalid_hex() -> DLogProofError {
+        DLogProofError::InvalidScalar
+    }
+}
+
+impl<F: PrimeField> SerializeAs<F> for ScalarHex {
+    fn serialize_as<S>(source: &F, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_as_hex::<F, Self, S>(source, serializer)
+    }
+}
+
+impl<'de, F: PrimeField> DeserializeAs<'de, F> for ScalarHex {
+    fn deserialize_as<D>(deserializer: D) -> Result<F, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_as_hex::<F, Self, D>(deserializer)
+    }
+}
+
+/// `serde_with` adapter for whole secp256k1 proofs as the hex of `DLogProof::to_bytes`
+///
+/// A single 130-character string instead of the `{"t", "s"}` object (the 65 bytes themselves
+/// for binary serializers), decoded with the strict validation of `DLogProof::from_bytes`:
+/// `#[serde_as(as = "ProofBytes")] proof: DLogProof`
+pub struct ProofBytes;
+
+impl HexEncoding<DLogProof> for ProofBytes {
+    fn to_encoding(source: &DLogProof) -> Vec<u8> {
+        source.to_bytes().to_vec()
+    }
+
+    fn from_encoding(bytes: &[u8]) -> Result<DLogProof, DLogProofError> {
+        DLogProof::from_bytes(bytes)
+    }
+}
+
+impl SerializeAs<DLogProof> for ProofBytes {
+    fn serialize_as<S>(source: &DLogProof, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_as_hex::<DLogProof, Self, S>(source, serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, DLogProof> for ProofBytes {
+    fn deserialize_as<D>(deserializer: D) -> Result<DLogProof, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_as_hex::<DLogProof, Self, D>(deserializer)
+    }
+}
+
+/// `serde_with` adapter for 32-byte digests such as Merkle roots, as 64 hex characters
+///
+/// `#[serde_as(as = "Vec<DigestHex>")] path: Vec<[u8; 32]>`
+pub struct DigestHex;
+
+impl HexEncoding<[u8; 32]> for DigestHex {
+    fn to_encoding(source: &[u8; 32]) -> Vec<u8> {
+        source.to_vec()
+    }
+
+    fn from_encoding(bytes: &[u8]) -> Result<[u8; 32], DLogProofError> {
+        bytes
+            .try_into()
+            .map_err(|_| DLogProofError::Serialization("Expected a 32-byte digest".into()))
+    }
+}
+
+impl SerializeAs<[u8; 32]> for DigestHex {
+    fn serialize_as<S>(source: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_as_hex::<[u8; 32], Self, S>(source, serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, [u8; 32]> for DigestHex {
+    fn deserialize_as<D>(deserializer: D) -> Result<[u8; 32], D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_as_hex::<[u8; 32], Self, D>(deserializer)
+    }
+}
+
+/// `serde_with` adapter for secp256k1 proofs as the `{"t", "s"}` object, with `T` and `S`
+/// choosing how the commitment and the response are written
+///
+/// `ProofEncoding` alone is the format of `DLogProof`'s own serde implementation; a field
+/// declares another wire encoding instead of converting by hand, e.g.
+/// `#[serde_as(as = "ProofEncoding<Prefixed<Uncompressed>, Prefixed<ScalarHex>>")]`
+/// for `{"t": "0x04…", "s": "0x…"}`.
+pub struct ProofEncoding<T = PointHex, S = ScalarHex>(PhantomData<(T, S)>);
+
+impl<T, S> SerializeAs<DLogProof> for ProofEncoding<T, S>
+where
+    T: SerializeAs<ProjectivePoint>,
+    S: SerializeAs<Scalar>,
+{
+    fn serialize_as<Ser>(source: &DLogProof, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: serde::Serializer,
+    {
+        let mut proof = serializer.serialize_struct("DLogProof", 2)?;
+        proof.serialize_field("t", &SerializeAsWrap::<ProjectivePoint, T>::new(&source.t))?;
+        proof.serialize_field("s", &SerializeAsWrap::<Scalar, S>::new(&source.s))?;
+        proof.end()
+    }
+}
+
+/// The fields of a proof read through the adapters of a `ProofEncoding`
+#[derive(Deserialize)]
+#[serde(bound = "T: DeserializeAs<'de, ProjectivePoint>, S: DeserializeAs<'de, Scalar>")]
+struct ProofFields<T, S> {
+    t: DeserializeAsWrap<ProjectivePoint, T>,
+    s: DeserializeAsWrap<Scalar, S>,
+}
+
+impl<'de, T, S> DeserializeAs<'de, DLogProof> for ProofEncoding<T, S>
+where
+    T: DeserializeAs<'de, ProjectivePoint>,
+    S: DeserializeAs<'de, Scalar>,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<DLogProof, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let fields = ProofFields::<T, S>::deserialize(deserializer)?;
+        Ok(DLogProof::new(fields.t.into_inner(), fields.s.into_inner()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+    use serde_with::serde_as;
+
+    #[serde_as]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Registry {
+        #[serde_as(as = "Vec<PointHex>")]
+        keys: Vec<ProjectivePoint>,
+        #[serde_as(as = "Option<ScalarHex>")]
+        tweak: Option<Scalar>,
+    }
+
+    #[test]
+    fn adapters_work_inside_containers() {
+        let registry = Registry {
+            keys: vec![ProjectivePoint::GENERATOR, ProjectivePoint::GENERATOR.double()],
+            tweak: Some(Scalar::ONE),
+        };
+        let json = serde_json::to_string(&registry).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"keys":["{}","{}"],"tweak":"{}"}}"#,
+                "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+                "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+                "0000000000000000000000000000000000000000000000000000000000000001"
+            )
+        );
+        assert_eq!(serde_json::from_str::<Registry>(&json).unwrap(), registry);
+    }
+
+    #[serde_as]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Envelope {
+        #[serde_as(as = "ProofBytes")]
+        proof: DLogProof,
+    }
+
+    #[test]
+    fn proof_bytes_reuse_the_fixed_encoding() {
+        use elliptic_curve::Field;
+
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut rand_core::OsRng);
+        let proof = DLogProof::prove("sid", 1, &x, g * x, g).unwrap();
+        let expected = encode(proof.to_bytes());
+
+        let envelope = Envelope { proof };
+        let json = serde_json::to_string(&envelope).unwrap();
+        assert_eq!(json, format!(r#"{{"proof":"{expected}"}}"#));
+        assert_eq!(serde_json::from_str::<Envelope>(&json).unwrap(), envelope);
+
+        // Same strict validation as from_bytes: a truncated proof and a scalar above the order
+        let truncated = format!(r#"{{"proof":"{}"}}"#, &expected[..128]);
+        assert!(serde_json::from_str::<Envelope>(&truncated).is_err());
+        let above_order = format!(r#"{{"proof":"{}{}"}}"#, &expected[..66], "ff".repeat(32));
+        let error = serde_json::from_str::<Envelope>(&above_order).unwrap_err();
+        assert!(error.to_string().contains("Invalid Scalar value"));
+    }
+
+    #[test]
+    fn foreign_hex_conventions_are_normalized() {
+        use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::from(7u64);
+        let proof = DLogProof::new(g * x, x);
+        let json = serde_json::to_string(&proof).unwrap();
+
+        // Uncompressed, 0x-prefixed and uppercase, as other tools print them
+        let t = (g * x).to_affine().to_encoded_point(false);
+        let foreign = format!(
+            r#"{{"t":"0x{}","s":"0X{}"}}"#,
+            hex::encode_upper(t.as_bytes()),
+            hex::encode_upper(x.to_bytes())
+        );
+        let parsed: DLogProof = serde_json::from_str(&foreign).unwrap();
+        assert_eq!(parsed, proof);
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+
+        // Mixed case without prefix
+        let mixed = json.replacen("a", "A", 3);
+        assert_eq!(serde_json::from_str::<DLogProof>(&mixed).unwrap(), proof);
+
+        // The prefix is only stripped once
+        let s_hex = hex::encode(x.to_bytes());
+        let doubled = json.replace(&s_hex, &format!("0x0x{s_hex}"));
+        assert!(serde_json::from_str::<DLogProof>(&doubled).is_err());
+    }
+
+    #[serde_as]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct EvmRecord {
+        #[serde_as(as = "Prefixed<Uncompressed>")]
+        y: ProjectivePoint,
+        #[serde_as(as = "ProofEncoding<Prefixed<Uncompressed>, Prefixed<ScalarHex>>")]
+        proof: DLogProof,
+    }
+
+    #[serde_as]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct PlainRecord {
+        #[serde_as(as = "ProofEncoding")]
+        proof: DLogProof,
+    }
+
+    #[test]
+    fn wire_encodings_are_declared_per_field() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::from(7u64);
+        let proof = DLogProof::new(g * x, x);
+        let uncompressed = |point: ProjectivePoint| {
+            format!("0x{}", encode(point.to_affine().to_encoded_point(false)))
+        };
+
+        let record = EvmRecord { y: g, proof };
+        let json = serde_json::to_string(&record).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"y":"{}","proof":{{"t":"{}","s":"0x{}"}}}}"#,
+                uncompressed(g),
+                uncompressed(g * x),
+                encode(x.to_repr())
+            )
+        );
+        assert_eq!(serde_json::from_str::<EvmRecord>(&json).unwrap(), record);
+
+        // Binary serializers get the bytes without the prefix: 8 + 65 bytes for y
+        let bincoded = bincode::serialize(&record).unwrap();
+        assert_eq!(&bincoded[8..73], &Uncompressed::to_encoding(&g)[..]);
+        assert_eq!(bincode::deserialize::<EvmRecord>(&bincoded).unwrap(), record);
+
+        // The default parameters are the proof's own serde format
+        let default = PlainRecord {
+            proof: record.proof,
+        };
+        let json = serde_json::to_string(&default).unwrap();
+        let own = serde_json::to_string(&default.proof).unwrap();
+        assert_eq!(json, format!(r#"{{"proof":{own}}}"#));
+        assert_eq!(serde_json::from_str::<PlainRecord>(&json).unwrap(), default);
+    }
+
+    #[test]
+    fn binary_formats_get_raw_bytes() {
+        use elliptic_curve::Field;
+
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut rand_core::OsRng);
+        let proof = DLogProof::prove("sid", 1, &x, g * x, g).unwrap();
+
+        // Two length-prefixed byte strings: 8 + 33 and 8 + 32 bytes
+        let bincoded = bincode::serialize(&proof).unwrap();
+        assert_eq!(bincoded.len(), 81);
+        assert_eq!(&bincoded[8..41], &proof.to_bytes()[..33]);
+        assert_eq!(bincode::deserialize::<DLogProof>(&bincoded).unwrap(), proof);
+
+        // Varint lengths: 1 + 33 and 1 + 32 bytes
+        let postcarded = postcard::to_allocvec(&proof).unwrap();
+        assert_eq!(postcarded.len(), 67);
+        assert_eq!(postcard::from_bytes::<DLogProof>(&postcarded).unwrap(), proof);
+
+        // JSON keeps the hex strings
+        let json = serde_json::to_string(&proof).unwrap();
+        assert_eq!(json.len(), r#"{"t":"","s":""}"#.len() + 130);
+        assert_eq!(serde_json::from_str::<DLogProof>(&json).unwrap(), proof);
+
+        let envelope = Envelope { proof };
+        let bincoded = bincode::serialize(&envelope).unwrap();
+        assert_eq!(bincoded.len(), 8 + DLogProof::ENCODED_LEN);
+        assert_eq!(bincode::deserialize::<Envelope>(&bincoded).unwrap(), envelope);
+
+        // Binary input is validated like hex input
+        let mut bincoded = bincode::serialize(&envelope.proof).unwrap();
+        bincoded[49..].fill(0xff);
+        assert!(bincode::deserialize::<DLogProof>(&bincoded).is_err());
+    }
 }
@@ -0,0 +1,95 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Session identifier bound into every challenge, as arbitrary bytes
+///
+/// Proving and verification functions take `impl Into<SessionId>`, so a `&str` works as
+/// before (and gives the same challenges, since its UTF-8 bytes are hashed), while binary
+/// identifiers such as 32-byte session hashes are passed directly:
+/// `DLogProof::prove(&session_hash, pid, &x, y, g)` with `session_hash: [u8; 32]`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SessionId<'a>(&'a [u8]);
+
+impl<'a> SessionId<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        SessionId(bytes)
+    }
+
+    /// The bytes hashed into the transcript
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+}
+
+impl<'a> From<&'a str> for SessionId<'a> {
+    fn from(sid: &'a str) -> Self {
+        SessionId(sid.as_bytes())
+    }
+}
+
+impl<'a> From<&'a String> for SessionId<'a> {
+    fn from(sid: &'a String) -> Self {
+        SessionId(sid.as_bytes())
+    }
+}
+
+impl<'a> From<&'a [u8]> for SessionId<'a> {
+    fn from(sid: &'a [u8]) -> Self {
+        SessionId(sid)
+    }
+}
+
+impl<'a, const N: usize> From<&'a [u8; N]> for SessionId<'a> {
+    fn from(sid: &'a [u8; N]) -> Self {
+        SessionId(sid)
+    }
+}
+
+impl<'a> From<&'a Vec<u8>> for SessionId<'a> {
+    fn from(sid: &'a Vec<u8>) -> Self {
+        SessionId(sid)
+    }
+}
+
+/// Shows UTF-8 identifiers as strings and binary ones as hex
+impl fmt::Debug for SessionId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match core::str::from_utf8(self.0) {
+            Ok(sid) => write!(f, "SessionId({sid:?})"),
+            Err(_) => write!(f, "SessionId(0x{})", hex::encode(self.0)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlog_proof::DLogProof;
+    use elliptic_curve::Field;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_core::OsRng;
+
+    #[test]
+    fn binary_session_ids_prove_and_match_strings() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+
+        let sid = [0xc3u8; 32];
+        let proof = DLogProof::prove(&sid, 1, &x, y, g).unwrap();
+        assert!(proof.verify(&sid, 1, y, g).unwrap());
+        assert!(!proof.verify(&[0xc3u8; 31], 1, y, g).unwrap());
+
+        // A string and its UTF-8 bytes are the same session
+        let proof = DLogProof::prove("session", 1, &x, y, g).unwrap();
+        assert!(proof.verify(b"session", 1, y, g).unwrap());
+        assert!(proof.verify(SessionId::new(b"session"), 1, y, g).unwrap());
+
+        assert_eq!(format!("{:?}", SessionId::from("ab")), r#"SessionId("ab")"#);
+        assert_eq!(
+            format!("{:?}", SessionId::from(&[0xffu8, 0])),
+            "SessionId(0xff00)"
+        );
+    }
+}
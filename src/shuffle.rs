@@ -0,0 +1,409 @@
+use elliptic_curve::subtle::{Choice, ConstantTimeEq};
+use elliptic_curve::{Field, PrimeField};
+use k256::ProjectivePoint;
+use rand_core::{CryptoRngCore, OsRng};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::dlog_proof::DLogProof;
+use crate::elgamal::ElGamalCiphertext;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::participant::ParticipantId;
+use crate::serialization::ScalarHex;
+use crate::session::SessionId;
+
+/// One cut-and-choose round of a `ShuffleProof`
+///
+/// An intermediate shuffle of the inputs, and the permutation and re-encryption randomness
+/// linking it to the inputs (challenge bit 0) or the outputs to it (challenge bit 1).
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+struct Round<G: DLogGroup> {
+    shuffled: Vec<ElGamalCiphertext<G>>,
+    permutation: Vec<u32>,
+    #[serde_as(as = "Vec<ScalarHex>")]
+    randomness: Vec<G::Scalar>,
+}
+
+/// Proof that ElGamal ciphertexts are a re-encrypted permutation of others, one mixnet step
+///
+/// The shuffle outputs e'_i = e_π(i) + (r_i * G, r_i * P) for a secret permutation π and
+/// fresh randomness r_i, so they decrypt to the same messages as the inputs e_i under the
+/// key P but can't be linked to them. The proof is the Sako-Kilian cut-and-choose argument
+/// made non-interactive with Fiat-Shamir: in each of `ROUNDS` parallel rounds the prover
+/// shuffles the inputs again with its own permutation σ, and a challenge bit asks it to
+/// open either the inputs → intermediate step (σ) or the intermediate → outputs step
+/// (σ⁻¹ ∘ π). Either opening alone reveals nothing about π, and a prover who didn't shuffle
+/// honestly can answer at most one of the two per round, so it cheats with probability
+/// 2^-ROUNDS.
+///
+/// The proof grows with ROUNDS * n ciphertexts and costs about 2 * ROUNDS * n scalar
+/// multiplications to make and to check, which suits the tens to hundreds of ballots of a
+/// small election rather than large mixes.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ShuffleProof<G: DLogGroup = ProjectivePoint> {
+    rounds: Vec<Round<G>>,
+}
+
+impl<G: DLogGroup> ShuffleProof<G> {
+    const DOMAIN_SEPARATOR: &'static [u8] = b"SCHNORR_SHUFFLE_PROOF";
+
+    /// Number of parallel rounds, each halving a cheating prover's chance
+    pub const ROUNDS: usize = 128;
+
+    /// Shuffles and re-encrypts `inputs` under `public_key`, proving the outputs are a shuffle
+    ///
+    /// # Arguments
+    /// * `sid` - Session identifier used for domain separation, a string or bytes (see `SessionId`)
+    /// * `pid` - Participant ID for uniqueness to distinguish different proofs
+    /// * `inputs` - The ciphertexts to shuffle
+    /// * `public_key` - The ElGamal public key P the inputs are encrypted under
+    /// * `base_point` - Base point G of the group
+    ///
+    /// # Returns
+    /// * `Ok((Vec<ElGamalCiphertext>, ShuffleProof))` - The shuffled ciphertexts and the proof
+    /// * `Err(DLogProofError)` - `StatementCount` if `inputs` is empty, `IdentityPoint` for an
+    ///   identity key or base point, or if the challenge can't be derived
+    pub fn shuffle<'s, 'p>(
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        inputs: &[ElGamalCiphertext<G>],
+        public_key: G,
+        base_point: G,
+    ) -> Result<(Vec<ElGamalCiphertext<G>>, Self), DLogProofError> {
+        Self::shuffle_with_rng(&mut OsRng, sid, pid, inputs, public_key, base_point)
+    }
+
+    /// Shuffles like `shuffle`, drawing the permutations and the randomness from `rng`
+    pub fn shuffle_with_rng<'s, 'p>(
+        rng: &mut impl CryptoRngCore,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        inputs: &[ElGamalCiphertext<G>],
+        public_key: G,
+        base_point: G,
+    ) -> Result<(Vec<ElGamalCiphertext<G>>, Self), DLogProofError> {
+        Self::check_statement(inputs, public_key, base_point)?;
+        let n = inputs.len();
+
+        let pi = Zeroizing::new(random_permutation(rng, n));
+        let r = Zeroizing::new(random_scalars::<G>(rng, n));
+        let outputs = reencrypt(inputs, &pi, &r, public_key, base_point);
+
+        let mut sigmas = Vec::with_capacity(Self::ROUNDS);
+        let mut rounds = Vec::with_capacity(Self::ROUNDS);
+        for _ in 0..Self::ROUNDS {
+            let sigma = Zeroizing::new(random_permutation(rng, n));
+            let s = random_scalars::<G>(rng, n);
+            rounds.push(Round {
+                shuffled: reencrypt(inputs, &sigma, &s, public_key, base_point),
+                permutation: Vec::new(),
+                randomness: s,
+            });
+            sigmas.push(sigma);
+        }
+
+        let bits = Self::challenge_bits(
+            sid.into(),
+            pid.into(),
+            inputs,
+            &outputs,
+            &rounds,
+            public_key,
+            base_point,
+        )?;
+        for ((round, sigma), open_outputs) in rounds.iter_mut().zip(sigmas).zip(bits) {
+            if open_outputs {
+                // outputs[i] = shuffled[τ(i)] + Enc(0; r_i - s_τ(i)) with τ = σ⁻¹ ∘ π
+                let mut inverse = Zeroizing::new(vec![0; n]);
+                for (i, &j) in sigma.iter().enumerate() {
+                    inverse[j as usize] = i as u32;
+                }
+                let tau: Vec<u32> = pi.iter().map(|&j| inverse[j as usize]).collect();
+                round.randomness = tau
+                    .iter()
+                    .zip(r.iter())
+                    .map(|(&j, r)| *r - round.randomness[j as usize])
+                    .collect();
+                round.permutation = tau;
+            } else {
+                round.permutation = sigma.to_vec();
+            }
+        }
+        Ok((outputs, ShuffleProof { rounds }))
+    }
+
+    /// Verifies that `outputs` are a re-encrypted permutation of `inputs` under `public_key`
+    ///
+    /// A proof with another number of rounds or ciphertexts is invalid, not an error.
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Validity of the proof
+    /// * `Err(DLogProofError)` - `StatementCount` if `inputs` is empty or `outputs` has another
+    ///   length, `IdentityPoint` for an identity key or base point, or if the challenge can't
+    ///   be derived
+    pub fn verify<'s, 'p>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        inputs: &[ElGamalCiphertext<G>],
+        outputs: &[ElGamalCiphertext<G>],
+        public_key: G,
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        Self::check_statement(inputs, public_key, base_point)?;
+        if outputs.len() != inputs.len() {
+            return Err(DLogProofError::StatementCount);
+        }
+        let n = inputs.len();
+        if self.rounds.len() != Self::ROUNDS
+            || self.rounds.iter().any(|round| {
+                round.shuffled.len() != n
+                    || round.permutation.len() != n
+                    || round.randomness.len() != n
+                    || !is_permutation(&round.permutation)
+            })
+        {
+            return Ok(false);
+        }
+
+        let bits = Self::challenge_bits(
+            sid.into(),
+            pid.into(),
+            inputs,
+            outputs,
+            &self.rounds,
+            public_key,
+            base_point,
+        )?;
+        let valid =
+            self.rounds
+                .iter()
+                .zip(bits)
+                .fold(Choice::from(1), |valid, (round, open_outputs)| {
+                    let (from, to) = if open_outputs {
+                        (&round.shuffled[..], outputs)
+                    } else {
+                        (inputs, &round.shuffled[..])
+                    };
+                    let expected = reencrypt(
+                        from,
+                        &round.permutation,
+                        &round.randomness,
+                        public_key,
+                        base_point,
+                    );
+                    expected
+                        .iter()
+                        .zip(to)
+                        .fold(valid, |valid, (expected, to)| valid & expected.ct_eq(to))
+                });
+        Ok(valid.into())
+    }
+
+    /// Rejects empty shuffles and degenerate keys, under which nothing is hidden
+    fn check_statement(
+        inputs: &[ElGamalCiphertext<G>],
+        public_key: G,
+        base_point: G,
+    ) -> Result<(), DLogProofError> {
+        if inputs.is_empty() {
+            return Err(DLogProofError::StatementCount);
+        }
+        DLogProof::check_statement(public_key, base_point)
+    }
+
+    /// Derives one challenge bit per round from a transcript over the statement and every
+    /// intermediate shuffle
+    fn challenge_bits(
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        inputs: &[ElGamalCiphertext<G>],
+        outputs: &[ElGamalCiphertext<G>],
+        rounds: &[Round<G>],
+        public_key: G,
+        base_point: G,
+    ) -> Result<Vec<bool>, DLogProofError> {
+        // Round count and shuffle size extend the tag at fixed offsets
+        let mut domain = Self::DOMAIN_SEPARATOR.to_vec();
+        domain.extend_from_slice(&(rounds.len() as u32).to_be_bytes());
+        domain.extend_from_slice(&(inputs.len() as u32).to_be_bytes());
+        let mut points = vec![base_point, public_key];
+        let shuffled = rounds.iter().flat_map(|round| &round.shuffled);
+        for ciphertext in inputs.iter().chain(outputs).chain(shuffled) {
+            points.extend([ciphertext.c1(), ciphertext.c2()]);
+        }
+        let seed = DLogProof::hash_points_with_domain(&domain, sid, pid, &points)?;
+
+        let bits = (0u32..)
+            .flat_map(|block| {
+                let bytes: [u8; 32] = Sha256::new()
+                    .chain_update(seed.to_repr())
+                    .chain_update(block.to_be_bytes())
+                    .finalize()
+                    .into();
+                bytes
+            })
+            .flat_map(|byte| (0..8).map(move |bit| byte >> bit & 1 == 1))
+            .take(rounds.len())
+            .collect();
+        Ok(bits)
+    }
+}
+
+/// Re-encrypts `from` into to[i] = from[permutation[i]] + (r_i * G, r_i * P)
+fn reencrypt<G: DLogGroup>(
+    from: &[ElGamalCiphertext<G>],
+    permutation: &[u32],
+    randomness: &[G::Scalar],
+    public_key: G,
+    base_point: G,
+) -> Vec<ElGamalCiphertext<G>> {
+    permutation
+        .iter()
+        .zip(randomness)
+        .map(|(&j, r)| {
+            let source = from[j as usize];
+            ElGamalCiphertext::new(source.c1() + base_point * r, source.c2() + public_key * r)
+        })
+        .collect()
+}
+
+/// Whether `permutation` holds each of 0..len exactly once
+fn is_permutation(permutation: &[u32]) -> bool {
+    let mut seen = vec![false; permutation.len()];
+    permutation.iter().all(|&j| {
+        let fresh = seen.get(j as usize) == Some(&false);
+        if fresh {
+            seen[j as usize] = true;
+        }
+        fresh
+    })
+}
+
+/// A uniformly random permutation of 0..n (Fisher-Yates with unbiased indices)
+fn random_permutation(rng: &mut impl CryptoRngCore, n: usize) -> Vec<u32> {
+    let mut permutation: Vec<u32> = (0..n as u32).collect();
+    for i in (1..n).rev() {
+        let bound = i as u64 + 1;
+        // Rejects the top partial range of u64 so every index is equally likely
+        let zone = u64::MAX - u64::MAX % bound;
+        let j = loop {
+            let candidate = rng.next_u64();
+            if candidate < zone {
+                break candidate % bound;
+            }
+        };
+        permutation.swap(i, j as usize);
+    }
+    permutation
+}
+
+fn random_scalars<G: DLogGroup>(rng: &mut impl CryptoRngCore, n: usize) -> Vec<G::Scalar> {
+    (0..n).map(|_| G::Scalar::random(&mut *rng)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::group::GroupEncoding;
+    use k256::Scalar;
+
+    fn ballots(
+        n: u64,
+    ) -> (
+        Scalar,
+        ProjectivePoint,
+        Vec<ElGamalCiphertext>,
+        Vec<ProjectivePoint>,
+    ) {
+        let g = ProjectivePoint::GENERATOR;
+        let secret_key = Scalar::random(&mut OsRng);
+        let public_key = g * secret_key;
+        let messages: Vec<ProjectivePoint> = (1..=n).map(|m| g * Scalar::from(m)).collect();
+        let inputs = messages
+            .iter()
+            .map(|&m| ElGamalCiphertext::encrypt(&mut OsRng, m, public_key, g).0)
+            .collect();
+        (secret_key, public_key, inputs, messages)
+    }
+
+    #[test]
+    fn shuffles_verify_and_decrypt_to_the_same_messages() {
+        let g = ProjectivePoint::GENERATOR;
+        let (secret_key, public_key, inputs, messages) = ballots(3);
+        let (outputs, proof) = ShuffleProof::shuffle("sid", 1, &inputs, public_key, g).unwrap();
+        assert!(proof
+            .verify("sid", 1, &inputs, &outputs, public_key, g)
+            .unwrap());
+        assert!(!proof
+            .verify("sid", 2, &inputs, &outputs, public_key, g)
+            .unwrap());
+
+        let mut decrypted: Vec<_> = outputs
+            .iter()
+            .map(|c| c.decrypt(&secret_key).to_bytes())
+            .collect();
+        decrypted.sort();
+        let mut expected: Vec<_> = messages.iter().map(|m| m.to_bytes()).collect();
+        expected.sort();
+        assert_eq!(decrypted, expected);
+
+        let json = serde_json::to_string(&proof).unwrap();
+        let decoded: ShuffleProof = serde_json::from_str(&json).unwrap();
+        assert!(decoded
+            .verify("sid", 1, &inputs, &outputs, public_key, g)
+            .unwrap());
+    }
+
+    #[test]
+    fn tampered_shuffles_are_rejected() {
+        let g = ProjectivePoint::GENERATOR;
+        let (_, public_key, inputs, _) = ballots(3);
+        let (outputs, proof) = ShuffleProof::shuffle("sid", 1, &inputs, public_key, g).unwrap();
+
+        // Openings must be whole permutations, or they would skip ciphertexts
+        let mut repeated = proof.clone();
+        repeated.rounds[0].permutation = vec![0; 3];
+        let mut truncated = proof.clone();
+        for round in &mut truncated.rounds {
+            round.permutation.pop();
+            round.randomness.pop();
+        }
+        for malformed in [repeated, truncated] {
+            assert!(!malformed
+                .verify("sid", 1, &inputs, &outputs, public_key, g)
+                .unwrap());
+        }
+
+        // Replacing a ballot breaks every round that opens the outputs
+        let mut replaced = outputs.clone();
+        replaced[0] = ElGamalCiphertext::encrypt(&mut OsRng, g, public_key, g).0;
+        assert!(!proof
+            .verify("sid", 1, &inputs, &replaced, public_key, g)
+            .unwrap());
+        assert_eq!(
+            proof.verify("sid", 1, &inputs, &outputs[1..], public_key, g),
+            Err(DLogProofError::StatementCount)
+        );
+        assert_eq!(
+            ShuffleProof::shuffle("sid", 1, &[], public_key, g).map(|_| ()),
+            Err(DLogProofError::StatementCount)
+        );
+    }
+
+    #[test]
+    fn permutations_are_valid() {
+        for n in [0, 1, 2, 7] {
+            assert!(is_permutation(&random_permutation(&mut OsRng, n)));
+        }
+        assert!(!is_permutation(&[0, 2]));
+        assert!(!is_permutation(&[1, 1]));
+    }
+}
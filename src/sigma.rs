@@ -0,0 +1,260 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use elliptic_curve::Field;
+use rand_core::CryptoRngCore;
+#[cfg(feature = "std")]
+use rand_core::OsRng;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::participant::ParticipantId;
+use crate::secret::SecretScalar;
+use crate::session::SessionId;
+
+/// A sigma protocol: commitment, challenge and response over a prime-order group
+///
+/// Implementations describe one interactive proof of knowledge; `SigmaProof` turns any of
+/// them into a non-interactive proof with the crate's Fiat-Shamir transcript. The challenge
+/// hashes `DOMAIN`, the session and participant IDs, the statement points and then the
+/// commitment points, so a protocol only defines the algebra:
+/// * `commit` draws the nonces and computes the first message
+/// * `respond` answers a challenge with the witness and the nonces
+/// * `check` is the verifier's equation on the statement, commitment, challenge and response
+///
+/// `SchnorrDLog` is the protocol behind `DLogProof`.
+pub trait SigmaProtocol {
+    /// Group the statement lives in
+    type Group: DLogGroup;
+    /// Public statement, e.g. the base point and y = x * G
+    type Statement;
+    /// Secret witness, e.g. x
+    type Witness;
+    /// First message of the prover, computed from the nonces
+    type Commitment;
+    /// Answer to the challenge
+    type Response;
+    /// Prover-side nonces behind the commitment, wiped after responding
+    type Nonce: Zeroize;
+
+    /// Domain separation tag of the challenge; must not be a prefix of another protocol's tag
+    const DOMAIN: &'static [u8];
+
+    /// Points of the statement, absorbed into the challenge before the commitment
+    fn statement_points(statement: &Self::Statement) -> Vec<Self::Group>;
+
+    /// Points of the commitment, absorbed into the challenge after the statement
+    fn commitment_points(commitment: &Self::Commitment) -> Vec<Self::Group>;
+
+    /// Draws fresh nonces from `rng` and computes the commitment
+    fn commit(
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        rng: &mut impl CryptoRngCore,
+    ) -> (Self::Commitment, Self::Nonce);
+
+    /// Computes the response to `challenge`
+    fn respond(
+        witness: &Self::Witness,
+        nonce: &Self::Nonce,
+        challenge: &<Self::Group as elliptic_curve::Group>::Scalar,
+    ) -> Self::Response;
+
+    /// Whether the verification equations hold, compared in constant time
+    fn check(
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &<Self::Group as elliptic_curve::Group>::Scalar,
+        response: &Self::Response,
+    ) -> bool;
+}
+
+/// Non-interactive proof for a `SigmaProtocol`, made with the Fiat-Shamir transform
+pub struct SigmaProof<P: SigmaProtocol> {
+    pub commitment: P::Commitment,
+    pub response: P::Response,
+}
+
+impl<P: SigmaProtocol> SigmaProof<P> {
+    /// Proves knowledge of `witness` for `statement`, with nonces from `OsRng`
+    #[cfg(feature = "std")]
+    pub fn prove<'s, 'p>(
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        statement: &P::Statement,
+        witness: &P::Witness,
+    ) -> Result<Self, DLogProofError> {
+        Self::prove_with_rng(&mut OsRng, sid, pid, statement, witness)
+    }
+
+    /// Proves like `prove`, drawing the nonces from `rng` (see `DLogProof::prove_with_rng`)
+    pub fn prove_with_rng<'s, 'p>(
+        rng: &mut impl CryptoRngCore,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        statement: &P::Statement,
+        witness: &P::Witness,
+    ) -> Result<Self, DLogProofError> {
+        let (commitment, nonce) = P::commit(statement, witness, rng);
+        let nonce = Zeroizing::new(nonce);
+        let c = Self::challenge(sid.into(), pid.into(), statement, &commitment)?;
+        let response = P::respond(witness, &nonce, &c);
+        Ok(SigmaProof {
+            commitment,
+            response,
+        })
+    }
+
+    /// Verifies the proof for `statement`
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Whether the verification equations hold for the derived challenge
+    /// * `Err(DLogProofError)` - If the challenge can't be derived
+    pub fn verify<'s, 'p>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        statement: &P::Statement,
+    ) -> Result<bool, DLogProofError> {
+        let c = Self::challenge(sid.into(), pid.into(), statement, &self.commitment)?;
+        Ok(P::check(statement, &self.commitment, &c, &self.response))
+    }
+
+    /// The Fiat-Shamir challenge of a protocol run
+    pub fn challenge(
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        statement: &P::Statement,
+        commitment: &P::Commitment,
+    ) -> Result<<P::Group as elliptic_curve::Group>::Scalar, DLogProofError> {
+        let mut points = P::statement_points(statement);
+        points.extend(P::commitment_points(commitment));
+        DLogProof::hash_points_with_domain(P::DOMAIN, sid, pid, &points)
+    }
+}
+
+impl<P: SigmaProtocol> Clone for SigmaProof<P>
+where
+    P::Commitment: Clone,
+    P::Response: Clone,
+{
+    fn clone(&self) -> Self {
+        SigmaProof {
+            commitment: self.commitment.clone(),
+            response: self.response.clone(),
+        }
+    }
+}
+
+impl<P: SigmaProtocol> core::fmt::Debug for SigmaProof<P>
+where
+    P::Commitment: core::fmt::Debug,
+    P::Response: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SigmaProof")
+            .field("commitment", &self.commitment)
+            .field("response", &self.response)
+            .finish()
+    }
+}
+
+/// Statement of `SchnorrDLog`: y = x * G for the base point G
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DLogStatement<G> {
+    pub base_point: G,
+    pub y: G,
+}
+
+/// Schnorr's proof of knowledge of a discrete logarithm, the protocol of `DLogProof`
+///
+/// Commitment t = r * G, response s = r + c * x, check s * G = t + c * y. Its `SigmaProof`
+/// has the same transcript as `DLogProof::prove` with the default configuration, and the two
+/// convert into each other.
+pub struct SchnorrDLog<G>(PhantomData<G>);
+
+impl<G: DLogGroup> SigmaProtocol for SchnorrDLog<G> {
+    type Group = G;
+    type Statement = DLogStatement<G>;
+    type Witness = SecretScalar<G::Scalar>;
+    type Commitment = G;
+    type Response = G::Scalar;
+    type Nonce = G::Scalar;
+
+    const DOMAIN: &'static [u8] = b"SCHNORR_PROOF";
+
+    fn statement_points(statement: &DLogStatement<G>) -> Vec<G> {
+        vec![statement.base_point, statement.y]
+    }
+
+    fn commitment_points(t: &G) -> Vec<G> {
+        vec![*t]
+    }
+
+    fn commit(
+        statement: &DLogStatement<G>,
+        _x: &SecretScalar<G::Scalar>,
+        rng: &mut impl CryptoRngCore,
+    ) -> (G, G::Scalar) {
+        let r = G::Scalar::random(rng);
+        (statement.base_point * r, r)
+    }
+
+    fn respond(x: &SecretScalar<G::Scalar>, r: &G::Scalar, c: &G::Scalar) -> G::Scalar {
+        *r + *c * x.expose_secret()
+    }
+
+    fn check(statement: &DLogStatement<G>, t: &G, c: &G::Scalar, s: &G::Scalar) -> bool {
+        // s * G - c * y = t, as one double-scalar multiplication (see `DLogGroup::lincomb`)
+        let lhs = G::lincomb(&[(statement.base_point, *s), (statement.y, -*c)]);
+        lhs.ct_eq(t).into()
+    }
+}
+
+impl<G: DLogGroup> From<SigmaProof<SchnorrDLog<G>>> for DLogProof<G> {
+    fn from(proof: SigmaProof<SchnorrDLog<G>>) -> Self {
+        DLogProof::new(proof.commitment, proof.response)
+    }
+}
+
+impl<G: DLogGroup> From<DLogProof<G>> for SigmaProof<SchnorrDLog<G>> {
+    fn from(proof: DLogProof<G>) -> Self {
+        SigmaProof {
+            commitment: proof.t(),
+            response: proof.s(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::{ProjectivePoint, Scalar};
+
+    #[test]
+    fn schnorr_protocol_matches_dlog_proof() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let statement = DLogStatement {
+            base_point: g,
+            y: g * x,
+        };
+
+        let proof = SigmaProof::<SchnorrDLog<_>>::prove("sid", 1, &statement, &x.into()).unwrap();
+        assert!(proof.verify("sid", 1, &statement).unwrap());
+        assert!(!proof.verify("sid", 2, &statement).unwrap());
+
+        // Same transcript as DLogProof in both directions
+        let dlog: DLogProof = proof.into();
+        assert!(dlog.verify("sid", 1, statement.y, g).unwrap());
+        let dlog = DLogProof::prove("sid", 1, &x, statement.y, g).unwrap();
+        let proof = SigmaProof::<SchnorrDLog<_>>::from(dlog);
+        assert!(proof.verify("sid", 1, &statement).unwrap());
+        let dlog = DLogProof::prove("sid", "alice", &x, statement.y, g).unwrap();
+        let proof = SigmaProof::<SchnorrDLog<_>>::from(dlog);
+        assert!(proof.verify("sid", "alice", &statement).unwrap());
+        assert!(!proof.verify("sid", "bob", &statement).unwrap());
+    }
+}
@@ -0,0 +1,95 @@
+//! Verification inside Solana programs (`solana` feature)
+//!
+//! A program depends on the crate with `default-features = false, features = ["verify-only",
+//! "solana"]`: no_std with alloc, and nothing on the verify path draws randomness. `verifier`
+//! returns a `Verifier` whose SHA-256 challenge hash is computed by the runtime's
+//! `sol_sha256` syscall, which costs far fewer compute units than hashing in the program:
+//!
+//! ```
+//! # use elliptic_curve::Field;
+//! # use schnorr_dlog_zkp::k256::{ProjectivePoint, Scalar};
+//! # use schnorr_dlog_zkp::DLogProof;
+//! # let g = ProjectivePoint::GENERATOR;
+//! # let x = Scalar::random(&mut rand_core::OsRng);
+//! # let y = g * x;
+//! # let proof = DLogProof::prove("sid", 1, &x, y, g).unwrap();
+//! let valid = schnorr_dlog_zkp::solana::verifier().verify(&proof, "sid", 1, y, g);
+//! # assert!(valid.unwrap());
+//! ```
+//!
+//! On every other target `sha256` hashes with the `sha2` crate instead, so the same code
+//! runs in off-chain clients and tests. The proofs are the ones `DLogProof::prove` makes.
+
+use crate::verifier::Verifier;
+
+#[cfg(target_os = "solana")]
+extern "C" {
+    fn sol_sha256(vals: *const u8, val_len: u64, hash_result: *mut u8) -> u64;
+}
+
+/// SHA-256 over the concatenation of `parts`, by the `sol_sha256` syscall on Solana
+#[cfg(target_os = "solana")]
+pub fn sha256(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hash = [0; 32];
+    // SAFETY: the syscall reads `parts.len()` (pointer, length) pairs, the layout of a slice
+    // of byte slices, and writes the 32-byte digest to `hash`
+    unsafe {
+        sol_sha256(
+            parts.as_ptr() as *const u8,
+            parts.len() as u64,
+            hash.as_mut_ptr(),
+        );
+    }
+    hash
+}
+
+/// SHA-256 over the concatenation of `parts`, by the `sha2` crate off Solana
+#[cfg(not(target_os = "solana"))]
+pub fn sha256(parts: &[&[u8]]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+/// A default `Verifier` hashing its challenges with `sha256`
+pub fn verifier() -> Verifier {
+    Verifier::new().sha256(sha256)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlog_proof::DLogProof;
+    use elliptic_curve::Field;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_core::OsRng;
+    use sha2::{Digest, Sha256};
+
+    #[test]
+    fn hooked_challenges_match_the_default_ones() {
+        assert_eq!(
+            sha256(&[b"ab", b"", b"c"]),
+            <[u8; 32]>::from(Sha256::digest(b"abc"))
+        );
+
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let proof = DLogProof::prove("sid", 1, &x, y, g).unwrap();
+        assert!(verifier().verify(&proof, "sid", 1, y, g).unwrap());
+        assert!(!verifier().verify(&proof, "sid", 2, y, g).unwrap());
+
+        // The hook really computes the challenge
+        let broken = Verifier::new().sha256(|_| [0; 32]);
+        assert!(!broken.verify(&proof, "sid", 1, y, g).unwrap());
+        let proof = crate::Prover::new()
+            .sha256(|_| [0; 32])
+            .prove("sid", 1, &x, y, g)
+            .unwrap();
+        assert!(broken.verify(&proof, "sid", 1, y, g).unwrap());
+    }
+}
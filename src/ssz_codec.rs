@@ -0,0 +1,244 @@
+//! SimpleSerialize (SSZ) encoding and hash-tree-roots of proofs (`ssz` feature)
+//!
+//! The types follow the SSZ schemas
+//!
+//! ```text
+//! class DLogProof(Container):
+//!     t: ByteVector[33]   # compressed SEC1
+//!     s: Bytes32          # big-endian
+//!
+//! class ProofEnvelope(Container):
+//!     version: uint8
+//!     curve: uint8
+//!     hash: uint8
+//!     proof: ByteList[128]
+//! ```
+//!
+//! so a proof can be a field of an Ethereum consensus-layer container and is Merkleized like
+//! one. `DLogProof` is fixed-size and encodes to its 65 bytes `t || s`; the envelope holds
+//! the proof bytes of any curve (up to `SSZ_MAX_PROOF_LEN`) and encodes with an offset.
+
+use sha2::{Digest, Sha256};
+use ssz::{Decode, DecodeError, Encode, SszDecoderBuilder, SszEncoder, BYTES_PER_LENGTH_OFFSET};
+
+use crate::dlog_proof::DLogProof;
+use crate::envelope::{hash_id, ProofEnvelope, ENVELOPE_VERSION};
+
+/// Limit of the `proof` list of an SSZ `ProofEnvelope`, above the proof length of every curve
+pub const SSZ_MAX_PROOF_LEN: usize = 128;
+
+const CHUNK_LEN: usize = 32;
+
+impl Encode for DLogProof {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        Self::ENCODED_LEN
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.to_bytes());
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        Self::ENCODED_LEN
+    }
+}
+
+impl Decode for DLogProof {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        Self::ENCODED_LEN
+    }
+
+    /// Rejects invalid points and scalars >= curve order, like `DLogProof::from_bytes`
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        if bytes.len() != Self::ENCODED_LEN {
+            return Err(DecodeError::InvalidByteLength {
+                len: bytes.len(),
+                expected: Self::ENCODED_LEN,
+            });
+        }
+        DLogProof::from_bytes(bytes).map_err(|e| DecodeError::BytesInvalid(e.to_string()))
+    }
+}
+
+impl DLogProof {
+    /// SSZ hash-tree-root of the proof as the `DLogProof` container
+    pub fn hash_tree_root(&self) -> [u8; 32] {
+        let bytes = self.to_bytes();
+        let (t, s) = bytes.split_at(33);
+        merkleize(&[merkleize(&pack(t), 2), pack(s)[0]], 2)
+    }
+}
+
+impl Encode for ProofEnvelope {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        let mut encoder = SszEncoder::container(buf, 3 + BYTES_PER_LENGTH_OFFSET);
+        let [version, curve, hash] = self.header();
+        encoder.append(&version);
+        encoder.append(&curve);
+        encoder.append(&hash);
+        encoder.append(&self.proof);
+        encoder.finalize();
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        3 + BYTES_PER_LENGTH_OFFSET + self.proof.len()
+    }
+}
+
+impl Decode for ProofEnvelope {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    /// Rejects unknown versions, curves and hashes like `ProofEnvelope::from_bytes`, and proofs
+    /// longer than `SSZ_MAX_PROOF_LEN`
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut builder = SszDecoderBuilder::new(bytes);
+        builder.register_type::<u8>()?;
+        builder.register_type::<u8>()?;
+        builder.register_type::<u8>()?;
+        builder.register_type::<Vec<u8>>()?;
+        let mut decoder = builder.build()?;
+        let header: [u8; 3] = [
+            decoder.decode_next()?,
+            decoder.decode_next()?,
+            decoder.decode_next()?,
+        ];
+        let proof: Vec<u8> = decoder.decode_next()?;
+        if proof.len() > SSZ_MAX_PROOF_LEN {
+            return Err(DecodeError::BytesInvalid(format!(
+                "Proof of {} bytes exceeds the limit of {SSZ_MAX_PROOF_LEN}",
+                proof.len()
+            )));
+        }
+        ProofEnvelope::from_bytes(&[&header[..], &proof].concat())
+            .map_err(|e| DecodeError::BytesInvalid(e.to_string()))
+    }
+}
+
+impl ProofEnvelope {
+    /// SSZ hash-tree-root of the envelope as the `ProofEnvelope` container
+    pub fn hash_tree_root(&self) -> [u8; 32] {
+        let [version, curve, hash] = self.header().map(|field| pack(&[field])[0]);
+        let proof = merkleize(&pack(&self.proof), SSZ_MAX_PROOF_LEN / CHUNK_LEN);
+        let mut length = [0u8; CHUNK_LEN];
+        length[..8].copy_from_slice(&(self.proof.len() as u64).to_le_bytes());
+        merkleize(&[version, curve, hash, hash_pair(&proof, &length)], 4)
+    }
+
+    fn header(&self) -> [u8; 3] {
+        [ENVELOPE_VERSION, self.curve.id(), hash_id(self.hash)]
+    }
+}
+
+/// Splits bytes into 32-byte chunks, zero-padding the last one
+fn pack(bytes: &[u8]) -> Vec<[u8; CHUNK_LEN]> {
+    bytes
+        .chunks(CHUNK_LEN)
+        .map(|chunk| {
+            let mut padded = [0u8; CHUNK_LEN];
+            padded[..chunk.len()].copy_from_slice(chunk);
+            padded
+        })
+        .collect()
+}
+
+/// Merkle root of `chunks` padded with zero chunks to `limit`, a power of two
+fn merkleize(chunks: &[[u8; CHUNK_LEN]], limit: usize) -> [u8; CHUNK_LEN] {
+    let mut layer = chunks.to_vec();
+    layer.resize(limit, [0u8; CHUNK_LEN]);
+    while layer.len() > 1 {
+        layer = layer
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], &pair[1]))
+            .collect();
+    }
+    layer[0]
+}
+
+fn hash_pair(left: &[u8; CHUNK_LEN], right: &[u8; CHUNK_LEN]) -> [u8; CHUNK_LEN] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlog_proof::ChallengeHash;
+    use crate::prover::Prover;
+    use elliptic_curve::group::GroupEncoding;
+    use elliptic_curve::Field;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_core::OsRng;
+
+    fn sha256(parts: &[&[u8]]) -> [u8; 32] {
+        Sha256::digest(parts.concat()).into()
+    }
+
+    #[test]
+    fn proofs_and_envelopes_round_trip_and_merkleize() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let proof = Prover::new()
+            .hash(ChallengeHash::Sha512)
+            .prove("sid", 1, &x, y, g)
+            .unwrap();
+
+        let bytes = proof.as_ssz_bytes();
+        assert_eq!(bytes, proof.to_bytes());
+        assert_eq!(DLogProof::from_ssz_bytes(&bytes).unwrap(), proof);
+        assert!(DLogProof::from_ssz_bytes(&bytes[1..]).is_err());
+        let t_root = sha256(&[&bytes[..32], &bytes[32..33], &[0u8; 31]]);
+        assert_eq!(proof.hash_tree_root(), sha256(&[&t_root, &bytes[33..]]));
+
+        let envelope = ProofEnvelope::seal(&proof, ChallengeHash::Sha512);
+        let bytes = envelope.as_ssz_bytes();
+        assert_eq!(&bytes[..3], &envelope.to_bytes()[..3]);
+        assert_eq!(bytes[3..7], 7u32.to_le_bytes());
+        assert_eq!(&bytes[7..], &proof.to_bytes());
+        let decoded = ProofEnvelope::from_ssz_bytes(&bytes).unwrap();
+        assert_eq!(decoded, envelope);
+        assert!(decoded.verify("sid", 1, &y.to_bytes()).unwrap());
+
+        // Four fields, with the proof list of 4 chunks mixed in with its length
+        let chunk = |byte: u8| {
+            let mut chunk = [0u8; 32];
+            chunk[0] = byte;
+            chunk
+        };
+        let zero = [0u8; 32];
+        let data = proof.to_bytes();
+        let list = sha256(&[
+            &sha256(&[&data[..32], &data[32..64]]),
+            &sha256(&[&chunk(data[64]), &zero]),
+        ]);
+        let proof_root = sha256(&[&list, &chunk(65)]);
+        let expected = sha256(&[
+            &sha256(&[&chunk(1), &chunk(1)]),
+            &sha256(&[&chunk(2), &proof_root]),
+        ]);
+        assert_eq!(envelope.hash_tree_root(), expected);
+
+        let mut unknown = bytes.clone();
+        unknown[1] = 0xff;
+        assert!(ProofEnvelope::from_ssz_bytes(&unknown).is_err());
+        let mut long = bytes[..7].to_vec();
+        long.extend_from_slice(&[0u8; SSZ_MAX_PROOF_LEN + 1]);
+        assert!(ProofEnvelope::from_ssz_bytes(&long).is_err());
+    }
+}
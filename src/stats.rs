@@ -0,0 +1,202 @@
+use core::time::Duration;
+
+use crate::participant::ParticipantId;
+
+/// Telemetry of one proof generation, from `Prover::prove_with_stats`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProveStats {
+    /// Elliptic-curve scalar multiplications performed, including verify-after-prove; a
+    /// multi-scalar multiplication counts as one
+    pub scalar_muls: u32,
+    /// Bytes absorbed by the challenge hash (or MAC), including verify-after-prove
+    pub hash_bytes: usize,
+    /// Time spent drawing the nonce and computing the commitment t
+    pub commitment: Duration,
+    /// Time spent deriving the Fiat-Shamir challenge
+    pub challenge: Duration,
+    /// Time spent computing the response s
+    pub response: Duration,
+    /// Time spent on verify-after-prove, zero when it is disabled
+    pub verification: Duration,
+    /// Wall-clock time of the whole call
+    pub total: Duration,
+}
+
+/// Telemetry of one verification, from `Verifier::verify_with_stats`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VerifyStats {
+    /// Elliptic-curve scalar multiplications performed; a multi-scalar multiplication counts
+    /// as one
+    pub scalar_muls: u32,
+    /// Bytes absorbed by the challenge hash (or MAC)
+    pub hash_bytes: usize,
+    /// Time spent recomputing the Fiat-Shamir challenge
+    pub challenge: Duration,
+    /// Time spent evaluating s * G = t + c * y
+    pub equation: Duration,
+    /// Wall-clock time of the whole call
+    pub total: Duration,
+}
+
+/// Clock for the timings above, read only when they are asked for
+///
+/// Proving and verifying without stats never start it. There is no clock without the `std`
+/// feature, nor on wasm32, where `std::time::Instant::now` panics: every timing is zero
+/// there, while the counters keep working.
+#[derive(Clone, Copy)]
+pub(crate) struct Stopwatch {
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+    start: Option<std::time::Instant>,
+}
+
+impl Stopwatch {
+    /// Starts timing if `enabled`, else returns a stopwatch that always reads zero
+    #[allow(unused_variables)]
+    pub(crate) fn start(enabled: bool) -> Self {
+        Stopwatch {
+            #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+            start: enabled.then(std::time::Instant::now),
+        }
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+        if let Some(start) = self.start {
+            return start.elapsed();
+        }
+        Duration::ZERO
+    }
+}
+
+/// Span around a proof generation, verification or (de)serialization (`tracing` feature)
+///
+/// Entered on creation and closed on drop, which records the elapsed time as `elapsed_us`;
+/// the phases of `ProveStats` and `VerifyStats` are recorded as further fields. Without the
+/// feature it does nothing.
+pub(crate) struct Span {
+    #[cfg(feature = "tracing")]
+    span: tracing::span::EnteredSpan,
+    #[cfg(feature = "tracing")]
+    start: Stopwatch,
+}
+
+impl Span {
+    #[cfg(feature = "tracing")]
+    fn enter(span: tracing::Span) -> Self {
+        Span {
+            start: Stopwatch::start(!span.is_disabled()),
+            span: span.entered(),
+        }
+    }
+
+    /// Whether a subscriber records this span, so its timings are worth measuring
+    pub(crate) fn is_recording(&self) -> bool {
+        #[cfg(feature = "tracing")]
+        return !self.span.is_disabled();
+        #[cfg(not(feature = "tracing"))]
+        false
+    }
+
+    /// Span `prove` of participant `pid`
+    #[cfg(feature = "prove")]
+    #[allow(unused_variables)]
+    pub(crate) fn prove(pid: ParticipantId<'_>) -> Self {
+        #[cfg(feature = "tracing")]
+        return Span::enter(tracing::debug_span!(
+            target: "schnorr_dlog_zkp",
+            "prove",
+            pid = %pid,
+            scalar_muls = tracing::field::Empty,
+            hash_bytes = tracing::field::Empty,
+            commitment_us = tracing::field::Empty,
+            challenge_us = tracing::field::Empty,
+            response_us = tracing::field::Empty,
+            verification_us = tracing::field::Empty,
+            elapsed_us = tracing::field::Empty,
+        ));
+        #[cfg(not(feature = "tracing"))]
+        Span {}
+    }
+
+    /// Span `verify` of participant `pid`
+    #[cfg(feature = "verify")]
+    #[allow(unused_variables)]
+    pub(crate) fn verify(pid: ParticipantId<'_>) -> Self {
+        #[cfg(feature = "tracing")]
+        return Span::enter(tracing::debug_span!(
+            target: "schnorr_dlog_zkp",
+            "verify",
+            pid = %pid,
+            valid = tracing::field::Empty,
+            scalar_muls = tracing::field::Empty,
+            hash_bytes = tracing::field::Empty,
+            challenge_us = tracing::field::Empty,
+            equation_us = tracing::field::Empty,
+            elapsed_us = tracing::field::Empty,
+        ));
+        #[cfg(not(feature = "tracing"))]
+        Span {}
+    }
+
+    /// Span `encode` or `decode` (`operation`) of a proof in `format`, e.g. `bytes`
+    #[allow(unused_variables)]
+    pub(crate) fn serialization(operation: &'static str, format: &'static str) -> Self {
+        #[cfg(feature = "tracing")]
+        return Span::enter(tracing::debug_span!(
+            target: "schnorr_dlog_zkp",
+            "serialization",
+            operation,
+            format,
+            len = tracing::field::Empty,
+            elapsed_us = tracing::field::Empty,
+        ));
+        #[cfg(not(feature = "tracing"))]
+        Span {}
+    }
+
+    /// Records the telemetry of a proof generation
+    #[cfg(feature = "prove")]
+    #[allow(unused_variables)]
+    pub(crate) fn record_prove(&self, stats: &ProveStats) {
+        #[cfg(feature = "tracing")]
+        self.span
+            .record("scalar_muls", stats.scalar_muls)
+            .record("hash_bytes", stats.hash_bytes)
+            .record("commitment_us", micros(stats.commitment))
+            .record("challenge_us", micros(stats.challenge))
+            .record("response_us", micros(stats.response))
+            .record("verification_us", micros(stats.verification));
+    }
+
+    /// Records the outcome and telemetry of a verification
+    #[cfg(feature = "verify")]
+    #[allow(unused_variables)]
+    pub(crate) fn record_verify(&self, valid: bool, stats: &VerifyStats) {
+        #[cfg(feature = "tracing")]
+        self.span
+            .record("valid", valid)
+            .record("scalar_muls", stats.scalar_muls)
+            .record("hash_bytes", stats.hash_bytes)
+            .record("challenge_us", micros(stats.challenge))
+            .record("equation_us", micros(stats.equation));
+    }
+
+    /// Records the length of an encoded proof
+    #[allow(unused_variables)]
+    pub(crate) fn record_len(&self, len: usize) {
+        #[cfg(feature = "tracing")]
+        self.span.record("len", len);
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for Span {
+    fn drop(&mut self) {
+        self.span.record("elapsed_us", micros(self.start.elapsed()));
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn micros(duration: Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
+}
@@ -0,0 +1,267 @@
+use k256::{ProjectivePoint, Scalar};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use std::io::{BufRead, Write};
+use std::panic::resume_unwind;
+use std::thread;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::prover::Prover;
+use crate::serialization::{PointHex, ScalarHex};
+
+/// One line of prover input: `{"sid": "...", "pid": 1, "secret": "<scalar hex>"}`
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProveRequest {
+    sid: String,
+    pid: u32,
+    #[serde_as(as = "ScalarHex")]
+    secret: Scalar,
+}
+
+/// One line of prover output, tagged with the 1-based input line it answers
+///
+/// Never contains the secret; a failed line only reports why it failed.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum ProveRecord {
+    Proved(Box<ProvedLine>),
+    Failed { line: u64, code: u32, error: String },
+}
+
+#[serde_as]
+#[derive(Serialize)]
+struct ProvedLine {
+    line: u64,
+    sid: String,
+    pid: u32,
+    #[serde_as(as = "PointHex")]
+    y: ProjectivePoint,
+    proof: DLogProof,
+}
+
+/// Counts reported by `StreamingProver::run`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamSummary {
+    pub proved: u64,
+    pub failed: u64,
+}
+
+/// Proves statements read from a JSONL stream and writes one JSONL result per input line
+///
+/// Input is consumed in batches of `batch_size` lines, each batch is proven by `workers`
+/// threads and written out (in input order) before the next one is read, so memory stays
+/// bounded by the batch size no matter how long the stream is. Malformed lines and invalid
+/// secrets produce an error record `{"line": 3, "code": 4, "error": "..."}`, with the
+/// `DLogProofError::code` of the failure, and don't stop the run; I/O errors do.
+#[derive(Debug, Clone)]
+pub struct StreamingProver {
+    prover: Prover,
+    workers: usize,
+    batch_size: usize,
+}
+
+impl StreamingProver {
+    const DEFAULT_BATCH_SIZE: usize = 4096;
+
+    /// Creates a pipeline issuing proofs with the given `Prover` configuration
+    pub fn new(prover: Prover) -> Self {
+        StreamingProver {
+            prover,
+            workers: thread::available_parallelism().map_or(1, |n| n.get()),
+            batch_size: Self::DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Sets the number of worker threads (defaults to the available parallelism)
+    pub fn workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// Sets how many input lines are held in memory at once
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Runs the pipeline until `input` is exhausted
+    ///
+    /// # Returns
+    /// * `Ok(StreamSummary)` - Number of proved and failed lines
+    /// * `Err(DLogProofError)` - `Io` if reading the input or writing the output fails
+    pub fn run<R: BufRead, W: Write>(
+        &self,
+        input: R,
+        mut output: W,
+    ) -> Result<StreamSummary, DLogProofError> {
+        let mut summary = StreamSummary::default();
+        let mut lines = input.lines();
+        let mut line_number = 0u64;
+
+        loop {
+            let mut batch = Vec::with_capacity(self.batch_size);
+            for line in lines.by_ref().take(self.batch_size) {
+                line_number += 1;
+                let line = line.map_err(|e| {
+                    DLogProofError::Io(format!("Failed to read line {}: {}", line_number, e))
+                })?;
+                if !line.trim().is_empty() {
+                    batch.push((line_number, line));
+                }
+            }
+            if batch.is_empty() {
+                break;
+            }
+
+            for record in self.prove_batch(&batch) {
+                match record {
+                    ProveRecord::Proved(_) => summary.proved += 1,
+                    ProveRecord::Failed { .. } => summary.failed += 1,
+                }
+                let json = serde_json::to_string(&record).map_err(|e| {
+                    DLogProofError::Serialization(format!("Failed to serialize result: {}", e))
+                })?;
+                writeln!(output, "{}", json)
+                    .map_err(|e| DLogProofError::Io(format!("Failed to write result: {}", e)))?;
+            }
+            output
+                .flush()
+                .map_err(|e| DLogProofError::Io(format!("Failed to flush output: {}", e)))?;
+        }
+        Ok(summary)
+    }
+
+    fn prove_batch(&self, batch: &[(u64, String)]) -> Vec<ProveRecord> {
+        let chunk_size = batch.len().div_ceil(self.workers);
+        thread::scope(|scope| {
+            let handles: Vec<_> = batch
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|(line, json)| self.prove_line(*line, json))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap_or_else(|panic| resume_unwind(panic)))
+                .collect()
+        })
+    }
+
+    fn prove_line(&self, line: u64, json: &str) -> ProveRecord {
+        let failed = |error: DLogProofError| ProveRecord::Failed {
+            line,
+            code: error.code(),
+            error: error.to_string(),
+        };
+        let request: ProveRequest = match serde_json::from_str(json) {
+            Ok(request) => request,
+            Err(e) => {
+                return failed(DLogProofError::Serialization(format!(
+                    "Invalid request: {}",
+                    e
+                )))
+            }
+        };
+        if bool::from(request.secret.is_zero()) {
+            return failed(DLogProofError::InvalidScalar);
+        }
+
+        let base_point = ProjectivePoint::GENERATOR;
+        let y = base_point * request.secret;
+        match self
+            .prover
+            .prove(&request.sid, request.pid, &request.secret, y, base_point)
+        {
+            Ok(proof) => ProveRecord::Proved(Box::new(ProvedLine {
+                line,
+                sid: request.sid,
+                pid: request.pid,
+                y,
+                proof,
+            })),
+            Err(error) => failed(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::Field;
+    use rand_core::OsRng;
+    use serde_json::Value;
+
+    fn request(pid: u32) -> String {
+        let secret = hex::encode(Scalar::random(&mut OsRng).to_bytes());
+        format!(
+            r#"{{"sid":"migration","pid":{},"secret":"{}"}}"#,
+            pid, secret
+        )
+    }
+
+    #[test]
+    fn results_follow_input_order_across_batches() {
+        let mut input: Vec<String> = (0..25).map(request).collect();
+        input.insert(10, "not json".to_string());
+        let input = input.join("\n");
+
+        let mut output = Vec::new();
+        let summary = StreamingProver::new(Prover::new())
+            .workers(3)
+            .batch_size(4)
+            .run(input.as_bytes(), &mut output)
+            .unwrap();
+        assert_eq!(
+            summary,
+            StreamSummary {
+                proved: 25,
+                failed: 1
+            }
+        );
+
+        let records: Vec<Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 26);
+        for (i, record) in records.iter().enumerate() {
+            assert_eq!(record["line"], i as u64 + 1);
+            assert!(record.get("secret").is_none());
+        }
+        assert!(records[10]["error"].is_string());
+        assert_eq!(
+            records[10]["code"],
+            DLogProofError::Serialization(String::new()).code()
+        );
+    }
+
+    #[test]
+    fn emitted_proofs_verify() {
+        let mut output = Vec::new();
+        StreamingProver::new(Prover::new())
+            .run(request(7).as_bytes(), &mut output)
+            .unwrap();
+
+        #[serde_as]
+        #[derive(Deserialize)]
+        struct Proved {
+            #[serde_as(as = "PointHex")]
+            y: ProjectivePoint,
+            proof: DLogProof,
+        }
+        let record: Proved = serde_json::from_slice(&output).unwrap();
+        assert!(record
+            .proof
+            .verify("migration", 7, record.y, ProjectivePoint::GENERATOR)
+            .unwrap());
+    }
+}
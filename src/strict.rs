@@ -0,0 +1,194 @@
+use elliptic_curve::PrimeField;
+use serde_json::{Map, Value};
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::participant::ParticipantId;
+use crate::session::SessionId;
+
+/// Decodes a point that must be given in its canonical encoding (`DLogGroup::encode_point`)
+///
+/// Rejects every other encoding the lenient decoders accept, such as uncompressed SEC1 on
+/// secp256k1, and the identity, which has no encoding in the strict profile.
+pub(crate) fn decode_point_strict<G: DLogGroup>(bytes: &[u8]) -> Result<G, DLogProofError> {
+    let point = G::decode_point(bytes).ok_or(DLogProofError::InvalidPoint)?;
+    if bool::from(point.is_identity()) {
+        return Err(DLogProofError::IdentityPoint);
+    }
+    if point.encode_point() != bytes {
+        return Err(DLogProofError::InvalidPoint);
+    }
+    Ok(point)
+}
+
+/// Decodes a scalar from exactly the bytes of its canonical representation
+pub(crate) fn decode_scalar_strict<F: PrimeField>(bytes: &[u8]) -> Result<F, DLogProofError> {
+    let mut repr = F::Repr::default();
+    if repr.as_ref().len() != bytes.len() {
+        return Err(DLogProofError::InvalidScalar);
+    }
+    repr.as_mut().copy_from_slice(bytes);
+    Option::from(F::from_repr(repr)).ok_or(DLogProofError::InvalidScalar)
+}
+
+/// Decodes lowercase hex without a prefix, the only form the strict JSON profile allows
+fn decode_hex_strict(value: &Value, invalid: DLogProofError) -> Result<Vec<u8>, DLogProofError> {
+    let hex_str = value.as_str().ok_or_else(|| invalid.clone())?;
+    let bytes = hex::decode(hex_str).map_err(|_| invalid.clone())?;
+    if hex::encode(&bytes) != hex_str {
+        return Err(invalid);
+    }
+    Ok(bytes)
+}
+
+impl<G: DLogGroup> DLogProof<G> {
+    /// Decodes a proof from the encodings of t and s, accepting only canonical ones
+    ///
+    /// Every valid proof has exactly one strict encoding: t in the group's canonical encoding
+    /// (compressed SEC1 on secp256k1) and not the identity, s as its canonical representation
+    /// (below the group order).
+    pub fn decode_strict(t: &[u8], s: &[u8]) -> Result<Self, DLogProofError> {
+        Ok(DLogProof::new(
+            decode_point_strict(t)?,
+            decode_scalar_strict(s)?,
+        ))
+    }
+
+    /// Parses the JSON written by `serde_json::to_string` under the strict profile
+    ///
+    /// Besides the checks of `decode_strict`, the object must hold exactly the fields `t` and
+    /// `s`, as lowercase hex strings without a `0x` prefix. Unlike serde deserialization, which
+    /// accepts uncompressed points and any letter case, this admits one JSON value per proof
+    /// (up to whitespace and member order, which `canonical_json` fixes).
+    pub fn from_json_strict(json: &str) -> Result<Self, DLogProofError> {
+        let fields: Map<String, Value> = serde_json::from_str(json)
+            .map_err(|e| DLogProofError::Serialization(format!("Invalid proof JSON: {e}")))?;
+        if fields.len() != 2 {
+            return Err(DLogProofError::Serialization(
+                "Strict proof JSON must have exactly the fields t and s".into(),
+            ));
+        }
+        let field = |name: &str| {
+            fields.get(name).ok_or_else(|| {
+                DLogProofError::Serialization(format!("Missing proof field: {name}"))
+            })
+        };
+        let t = decode_hex_strict(field("t")?, DLogProofError::InvalidPoint)?;
+        let s = decode_hex_strict(field("s")?, DLogProofError::InvalidScalar)?;
+        Self::decode_strict(&t, &s)
+    }
+
+    /// Verifies the proof like `verify`, additionally rejecting a commitment t at infinity
+    ///
+    /// Honest proofs never have t = 0 (it needs r = 0), and a proof decoded with
+    /// `decode_strict`, `from_json_strict` or `from_bytes_strict` can't have it, so this only
+    /// matters for proofs that came through the lenient decoders.
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Validity of the proof, `false` if t is the identity
+    /// * `Err(DLogProofError)` - Any error of `verify`
+    pub fn verify_strict<'s, 'p>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        y: G,
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        if bool::from(self.t().is_identity()) {
+            return Ok(false);
+        }
+        self.verify(sid, pid, y, base_point)
+    }
+}
+
+impl DLogProof {
+    /// Decodes the fixed 65-byte encoding under the strict profile (see `decode_strict`)
+    ///
+    /// `from_bytes` already requires compressed points and canonical scalars; this also
+    /// rejects the all-zero t, which `from_bytes` reads as the point at infinity.
+    pub fn from_bytes_strict(bytes: &[u8]) -> Result<Self, DLogProofError> {
+        if bytes.len() != Self::ENCODED_LEN {
+            return Err(DLogProofError::Serialization(format!(
+                "Invalid proof length: expected {} bytes, got {}",
+                Self::ENCODED_LEN,
+                bytes.len()
+            )));
+        }
+        let (t, s) = bytes.split_at(33);
+        Self::decode_strict(t, s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlog_proof::ChallengeParams;
+    use elliptic_curve::sec1::ToEncodedPoint;
+    use elliptic_curve::Field;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_core::OsRng;
+
+    #[test]
+    fn strict_decoding_admits_one_encoding() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let proof = DLogProof::prove("sid", 1, &x, y, g).unwrap();
+
+        let bytes = proof.to_bytes();
+        assert_eq!(DLogProof::from_bytes_strict(&bytes).unwrap(), proof);
+        let json = serde_json::to_string(&proof).unwrap();
+        let decoded = DLogProof::from_json_strict(&json).unwrap();
+        assert!(decoded.verify_strict("sid", 1, y, g).unwrap());
+
+        // Uncompressed t, upper case and 0x-prefixed hex are all accepted by serde
+        let uncompressed = hex::encode(proof.t().to_affine().to_encoded_point(false));
+        let s = hex::encode(proof.s().to_repr());
+        let t = hex::encode(proof.t().encode_point());
+        for (t, s) in [
+            (uncompressed, s.clone()),
+            (t.to_uppercase(), s.clone()),
+            (t.clone(), format!("0x{s}")),
+        ] {
+            let json = format!(r#"{{"t":"{t}","s":"{s}"}}"#);
+            assert_eq!(serde_json::from_str::<DLogProof>(&json).unwrap(), proof);
+            assert!(DLogProof::<ProjectivePoint>::from_json_strict(&json).is_err());
+        }
+        let json = format!(r#"{{"t":"{t}","s":"{s}","v":1}}"#);
+        assert!(DLogProof::<ProjectivePoint>::from_json_strict(&json).is_err());
+    }
+
+    #[test]
+    fn points_at_infinity_are_rejected() {
+        let g = ProjectivePoint::GENERATOR;
+        let mut bytes = [0u8; DLogProof::ENCODED_LEN];
+        bytes[64] = 1;
+        let lenient = DLogProof::from_bytes(&bytes).unwrap();
+        assert_eq!(lenient.t(), ProjectivePoint::IDENTITY);
+        assert_eq!(
+            DLogProof::from_bytes_strict(&bytes),
+            Err(DLogProofError::InvalidPoint)
+        );
+        // The SEC1 identity 0x00 decodes, but is refused as a commitment
+        assert_eq!(
+            DLogProof::<ProjectivePoint>::decode_strict(&[0u8], &bytes[33..]),
+            Err(DLogProofError::IdentityPoint)
+        );
+
+        // With t = 0 and s = c * x the equation holds, so only the strict verifier refuses it
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let identity = ProjectivePoint::IDENTITY;
+        let (c, _) = DLogProof::challenge(
+            &ChallengeParams::default(),
+            "sid".into(),
+            1.into(),
+            &[g, y, identity],
+        )
+        .unwrap();
+        let proof = DLogProof::new(identity, c * x);
+        assert!(proof.verify("sid", 1, y, g).unwrap());
+        assert!(!proof.verify_strict("sid", 1, y, g).unwrap());
+    }
+}
@@ -0,0 +1,319 @@
+//! Known-answer test vectors for other implementations (`test-vectors` feature)
+//!
+//! `test_vectors/dlog_proof.json` lists secp256k1 proofs made with fixed secrets, statements
+//! and transcript options, with the nonces drawn from a ChaCha20 RNG under a fixed seed. Each
+//! vector also gives the nonce r and the challenge c, so an implementation in another
+//! language can plug in r instead of reproducing this crate's RNG, derive c from the
+//! transcript and compare the 65-byte proof `t || s` (`DLogProof::to_bytes`) byte for byte.
+//! (r is the first 32 bytes of the ChaCha20 keystream read big-endian, which are below the
+//! group order for every seed in the file.)
+//!
+//! The file is the output of `generate`, and the tests check that it still is, so any change
+//! to challenge derivation or encodings shows up as a mismatch. After an intended change the
+//! file is rewritten with `cargo test --features test-vectors -- --ignored write_vectors`.
+
+use elliptic_curve::{Field, PrimeField};
+use k256::{ProjectivePoint, Scalar};
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::dlog_proof::{ChallengeHash, DLogProof, TranscriptVersion};
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::prover::Prover;
+use crate::verifier::Verifier;
+
+/// The vectors of `test_vectors/dlog_proof.json`
+pub const VECTORS_JSON: &str = include_str!("../test_vectors/dlog_proof.json");
+
+/// One proof with everything needed to reproduce it
+///
+/// Scalars and points are hex: scalars as 32 big-endian bytes, points compressed SEC1.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TestVector {
+    pub name: String,
+    /// `SHA-256`, `SHA-512`, `SHA3-256` or `BLAKE2b-512` (see `ChallengeHash`)
+    pub hash: String,
+    /// `1`, `2` or `3` (see `TranscriptVersion`)
+    pub transcript: u8,
+    /// Application domain (see `Prover::domain`), if any
+    pub domain: Option<String>,
+    /// Secret x
+    pub secret: String,
+    /// Public key y = x * G
+    pub public: String,
+    pub sid: String,
+    pub pid: u32,
+    /// 32-byte seed of the `ChaCha20Rng` the nonce is drawn from
+    pub rng_seed: String,
+    /// Nonce r, the first scalar drawn from the RNG
+    pub nonce: String,
+    /// Challenge c
+    pub challenge: String,
+    /// The proof `t || s`
+    pub proof: String,
+}
+
+/// Inputs of a vector
+struct Spec {
+    name: &'static str,
+    secret: &'static str,
+    sid: &'static str,
+    pid: u32,
+    hash: ChallengeHash,
+    version: TranscriptVersion,
+    domain: Option<&'static str>,
+}
+
+const SPECS: [Spec; 9] = [
+    Spec {
+        name: "secret-one",
+        secret: "0000000000000000000000000000000000000000000000000000000000000001",
+        sid: "sid",
+        pid: 1,
+        hash: ChallengeHash::Sha256,
+        version: TranscriptVersion::V1,
+        domain: None,
+    },
+    Spec {
+        name: "secret-order-minus-one",
+        secret: "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+        sid: "session-2024",
+        pid: 42,
+        hash: ChallengeHash::Sha256,
+        version: TranscriptVersion::V1,
+        domain: None,
+    },
+    Spec {
+        name: "sha512",
+        secret: "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+        sid: "sid",
+        pid: 7,
+        hash: ChallengeHash::Sha512,
+        version: TranscriptVersion::V1,
+        domain: None,
+    },
+    Spec {
+        name: "sha3-256",
+        secret: "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+        sid: "sid",
+        pid: 7,
+        hash: ChallengeHash::Sha3_256,
+        version: TranscriptVersion::V1,
+        domain: None,
+    },
+    Spec {
+        name: "blake2b-512",
+        secret: "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+        sid: "sid",
+        pid: 7,
+        hash: ChallengeHash::Blake2b512,
+        version: TranscriptVersion::V1,
+        domain: None,
+    },
+    Spec {
+        name: "transcript-v2",
+        secret: "2d8f6c1c5e13a7d4b9e0f3a6c2718b5d4e9f0a1b2c3d4e5f60718293a4b5c6d7",
+        sid: "sid",
+        pid: 3,
+        hash: ChallengeHash::Sha256,
+        version: TranscriptVersion::V2,
+        domain: None,
+    },
+    Spec {
+        name: "domain",
+        secret: "2d8f6c1c5e13a7d4b9e0f3a6c2718b5d4e9f0a1b2c3d4e5f60718293a4b5c6d7",
+        sid: "login",
+        pid: 3,
+        hash: ChallengeHash::Sha256,
+        version: TranscriptVersion::V2,
+        domain: Some("example.org"),
+    },
+    Spec {
+        name: "utf8-sid-max-pid",
+        secret: "0b9c2e7f5a3d1c8e6f4a2b0d9e7c5a3f1e8d6b4c2a0f9e7d5c3b1a0f8e6d4c2b",
+        sid: "s\u{e9}ance-\u{1f511}",
+        pid: u32::MAX,
+        hash: ChallengeHash::Sha256,
+        version: TranscriptVersion::V1,
+        domain: None,
+    },
+    Spec {
+        name: "transcript-v3",
+        secret: "2d8f6c1c5e13a7d4b9e0f3a6c2718b5d4e9f0a1b2c3d4e5f60718293a4b5c6d7",
+        sid: "sid",
+        pid: 3,
+        hash: ChallengeHash::Sha256,
+        version: TranscriptVersion::V3,
+        domain: None,
+    },
+];
+
+/// Computes the vectors of `test_vectors/dlog_proof.json`
+///
+/// The RNG seed of the i-th vector is 32 bytes of value i.
+pub fn generate() -> Result<Vec<TestVector>, DLogProofError> {
+    SPECS
+        .iter()
+        .zip(0u8..)
+        .map(|(spec, seed)| {
+            TestVector::generate(
+                spec.name,
+                spec.hash,
+                spec.version,
+                spec.domain,
+                &decode_hex(spec.secret)?,
+                spec.sid,
+                spec.pid,
+                [seed; 32],
+            )
+        })
+        .collect()
+}
+
+/// Parses `VECTORS_JSON`
+pub fn vectors() -> Result<Vec<TestVector>, DLogProofError> {
+    serde_json::from_str(VECTORS_JSON)
+        .map_err(|e| DLogProofError::Serialization(format!("Invalid test vector file: {e}")))
+}
+
+impl TestVector {
+    #[allow(clippy::too_many_arguments)]
+    fn generate(
+        name: &str,
+        hash: ChallengeHash,
+        version: TranscriptVersion,
+        domain: Option<&str>,
+        secret: &[u8],
+        sid: &str,
+        pid: u32,
+        rng_seed: [u8; 32],
+    ) -> Result<Self, DLogProofError> {
+        let g = ProjectivePoint::GENERATOR;
+        let x = decode_scalar(secret)?;
+        let y = g * x;
+        let rng = ChaCha20Rng::from_seed(rng_seed);
+        let r = Scalar::random(&mut rng.clone());
+        let proof =
+            prover(hash, version, domain).prove_with_rng(&mut rng.clone(), sid, pid, &x, y, g)?;
+        let bytes = proof.to_bytes();
+        // s = r + c * x
+        let x_inv: Option<Scalar> = x.invert().into();
+        let c = (decode_scalar(&bytes[33..])? - r) * x_inv.ok_or(DLogProofError::InvalidScalar)?;
+        Ok(TestVector {
+            name: name.to_string(),
+            hash: hash.name().to_string(),
+            transcript: match version {
+                TranscriptVersion::V1 => 1,
+                TranscriptVersion::V2 => 2,
+                TranscriptVersion::V3 => 3,
+            },
+            domain: domain.map(str::to_string),
+            secret: hex::encode(secret),
+            public: hex::encode(y.encode_point()),
+            sid: sid.to_string(),
+            pid,
+            rng_seed: hex::encode(rng_seed),
+            nonce: hex::encode(r.to_repr()),
+            challenge: hex::encode(c.to_repr()),
+            proof: hex::encode(bytes),
+        })
+    }
+
+    /// Recomputes the vector from its inputs (name, options, secret, statement and seed)
+    pub fn regenerate(&self) -> Result<Self, DLogProofError> {
+        let seed = hex::decode(&self.rng_seed)
+            .ok()
+            .and_then(|seed| <[u8; 32]>::try_from(seed).ok())
+            .ok_or_else(|| DLogProofError::Serialization("Invalid RNG seed".to_string()))?;
+        TestVector::generate(
+            &self.name,
+            self.challenge_hash()?,
+            self.transcript_version()?,
+            self.domain.as_deref(),
+            &decode_hex(&self.secret)?,
+            &self.sid,
+            self.pid,
+            seed,
+        )
+    }
+
+    /// Verifies the proof of the vector for its public key and options
+    pub fn verify(&self) -> Result<bool, DLogProofError> {
+        let mut verifier = Verifier::new()
+            .hash(self.challenge_hash()?)
+            .transcript_version(self.transcript_version()?);
+        if let Some(domain) = &self.domain {
+            verifier = verifier.domain(domain.as_bytes());
+        }
+        let y = ProjectivePoint::decode_point(&decode_hex(&self.public)?)
+            .ok_or(DLogProofError::InvalidPoint)?;
+        let proof = DLogProof::from_bytes(&decode_hex(&self.proof)?)?;
+        verifier.verify(
+            &proof,
+            self.sid.as_str(),
+            self.pid,
+            y,
+            ProjectivePoint::GENERATOR,
+        )
+    }
+
+    fn challenge_hash(&self) -> Result<ChallengeHash, DLogProofError> {
+        ChallengeHash::from_name(&self.hash)
+            .ok_or_else(|| DLogProofError::Serialization(format!("Unknown hash {}", self.hash)))
+    }
+
+    fn transcript_version(&self) -> Result<TranscriptVersion, DLogProofError> {
+        match self.transcript {
+            1 => Ok(TranscriptVersion::V1),
+            2 => Ok(TranscriptVersion::V2),
+            3 => Ok(TranscriptVersion::V3),
+            version => Err(DLogProofError::Serialization(format!(
+                "Unknown transcript version {version}"
+            ))),
+        }
+    }
+}
+
+fn prover(hash: ChallengeHash, version: TranscriptVersion, domain: Option<&str>) -> Prover {
+    let prover = Prover::new().hash(hash).transcript_version(version);
+    match domain {
+        Some(domain) => prover.domain(domain.as_bytes()),
+        None => prover,
+    }
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>, DLogProofError> {
+    hex::decode(value).map_err(|e| DLogProofError::Serialization(format!("Invalid hex: {e}")))
+}
+
+fn decode_scalar(bytes: &[u8]) -> Result<Scalar, DLogProofError> {
+    let repr = <[u8; 32]>::try_from(bytes)
+        .map_err(|_| DLogProofError::Serialization("Scalar is not 32 bytes".to_string()))?;
+    Option::from(Scalar::from_repr(repr.into())).ok_or(DLogProofError::InvalidScalar)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_vector_file_matches_the_implementation() {
+        let vectors = vectors().unwrap();
+        assert_eq!(vectors, generate().unwrap());
+        for vector in &vectors {
+            assert_eq!(vector.regenerate().unwrap(), *vector, "{}", vector.name);
+            assert!(vector.verify().unwrap(), "{}", vector.name);
+        }
+    }
+
+    #[test]
+    #[ignore = "rewrites test_vectors/dlog_proof.json"]
+    fn write_vectors() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_vectors/dlog_proof.json");
+        let json = serde_json::to_string_pretty(&generate().unwrap()).unwrap();
+        std::fs::write(path, json + "\n").unwrap();
+    }
+}
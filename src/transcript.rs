@@ -0,0 +1,144 @@
+use elliptic_curve::{Field, PrimeField};
+use merlin::Transcript;
+use rand_core::OsRng;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::participant::ParticipantId;
+use crate::secret::SecretScalar;
+use crate::session::SessionId;
+
+/// Domain separator appended to the caller's transcript before the proof's own messages
+const TRANSCRIPT_DOMAIN: &[u8] = b"SCHNORR_PROOF/merlin";
+
+impl<G: DLogGroup> DLogProof<G> {
+    /// Creates a proof with the challenge drawn from a merlin transcript (`merlin` feature)
+    ///
+    /// The proof appends labeled messages for the domain, `sid`, `pid`, the base point, `y`
+    /// and the commitment `t` to `transcript`, then draws the challenge from it. Whatever the
+    /// caller appended before (e.g. statements of an enclosing protocol) is bound into the
+    /// challenge too, and the transcript can be used further after the proof. The nonce is
+    /// derived from the transcript state, the secret and fresh randomness (merlin's
+    /// `TranscriptRng`), so it stays unpredictable even with a weak system RNG.
+    ///
+    /// The verifier must call `verify_with_transcript` on a transcript in the same state.
+    pub fn prove_with_transcript<'s, 'p>(
+        transcript: &mut Transcript,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: G,
+        base_point: G,
+    ) -> Result<Self, DLogProofError> {
+        let x = x.into();
+        Self::check_statement(y, base_point)?;
+        Self::append_statement(transcript, sid.into(), pid.into(), y, base_point);
+
+        let mut witness = x.expose_secret().to_repr();
+        let mut rng = transcript
+            .build_rng()
+            .rekey_with_witness_bytes(b"x", witness.as_ref())
+            .finalize(&mut OsRng);
+        witness.as_mut().zeroize();
+        let r = Zeroizing::new(G::Scalar::random(&mut rng));
+        let t = base_point * *r;
+
+        let c = Self::transcript_challenge(transcript, &t)?;
+        Ok(DLogProof {
+            t,
+            s: *r + c * x.expose_secret(),
+        })
+    }
+
+    /// Verifies a proof made with `prove_with_transcript`, advancing `transcript` the same way
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Validity of the proof
+    /// * `Err(DLogProofError)` - If the challenge can't be derived, or `IdentityPoint` for a
+    ///   degenerate statement
+    pub fn verify_with_transcript<'s, 'p>(
+        &self,
+        transcript: &mut Transcript,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        y: G,
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        Self::check_statement(y, base_point)?;
+        Self::append_statement(transcript, sid.into(), pid.into(), y, base_point);
+        let c = Self::transcript_challenge(transcript, &self.t)?;
+        Ok((base_point * self.s).ct_eq(&(self.t + y * c)).into())
+    }
+
+    fn append_statement(
+        transcript: &mut Transcript,
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        y: G,
+        base_point: G,
+    ) {
+        transcript.append_message(b"dom-sep", TRANSCRIPT_DOMAIN);
+        transcript.append_message(b"sid", sid.as_bytes());
+        match pid.index() {
+            Some(index) => transcript.append_u64(b"pid", u64::from(index)),
+            None => transcript.append_message(b"pidb", pid.as_bytes().unwrap_or_default()),
+        }
+        transcript.append_message(b"G", base_point.to_bytes().as_ref());
+        transcript.append_message(b"y", y.to_bytes().as_ref());
+    }
+
+    fn transcript_challenge(
+        transcript: &mut Transcript,
+        t: &G,
+    ) -> Result<G::Scalar, DLogProofError> {
+        transcript.append_message(b"t", t.to_bytes().as_ref());
+        // 64 bytes, so the reduction modulo the order is statistically uniform
+        let mut challenge = [0u8; 64];
+        transcript.challenge_bytes(b"c", &mut challenge);
+        G::challenge_from_hash(&challenge)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::{ProjectivePoint, Scalar};
+
+    #[test]
+    fn proofs_are_bound_to_the_enclosing_transcript() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let transcript = || {
+            let mut transcript = Transcript::new(b"outer protocol");
+            transcript.append_message(b"round", b"1");
+            transcript
+        };
+
+        let mut prover_transcript = transcript();
+        let proof =
+            DLogProof::prove_with_transcript(&mut prover_transcript, "sid", 1, &x, y, g).unwrap();
+
+        let mut verifier_transcript = transcript();
+        assert!(proof
+            .verify_with_transcript(&mut verifier_transcript, "sid", 1, y, g)
+            .unwrap());
+
+        // Both transcripts end in the same state, so the outer protocol can continue
+        let (mut a, mut b) = ([0u8; 32], [0u8; 32]);
+        prover_transcript.challenge_bytes(b"next", &mut a);
+        verifier_transcript.challenge_bytes(b"next", &mut b);
+        assert_eq!(a, b);
+
+        let mut other = Transcript::new(b"outer protocol");
+        other.append_message(b"round", b"2");
+        assert!(!proof
+            .verify_with_transcript(&mut other, "sid", 1, y, g)
+            .unwrap());
+        assert!(!proof
+            .verify_with_transcript(&mut transcript(), "sid", 2, y, g)
+            .unwrap());
+    }
+}
@@ -0,0 +1,400 @@
+//! Interactive proofs over an async transport (`transport` feature)
+//!
+//! `InteractiveSession` runs the three-move protocol of `Prover::commit` and
+//! `Verifier::challenge` over any `ProofTransport`, a connection carrying whole frames.
+//! `StreamTransport` frames a byte stream such as a `tokio::net::TcpStream` with a 4-byte
+//! big-endian length prefix. Each frame is one JSON message, in this order:
+//!
+//! ```text
+//! prover   -> verifier  {"type": "commitment", "sid": "s", "pid": 1, "commitment": {"t": "<hex>"}}
+//! verifier -> prover    {"type": "challenge", "sid": "s", "challenge": {"c": "<hex>"}}
+//! prover   -> verifier  {"type": "response", "sid": "s", "response": {"s": "<hex>"}}
+//! verifier -> prover    {"type": "verdict", "sid": "s", "accepted": true}
+//! ```
+//!
+//! Both sides are configured with the same session ID and participant ID, and a message for
+//! another session or participant ends the run with `SessionMismatch` or
+//! `ParticipantMismatch`. Each send and receive is limited by the session's timeout.
+//!
+//! The runs are cancellation safe in the sense that matters for the secret: dropping a run at
+//! any await point drops the prover's nonce with it, wiped and never reused, so a retry starts
+//! over with a fresh commitment. A transport whose run was cancelled or timed out may be in
+//! the middle of a frame and should be closed rather than reused.
+
+use std::future::Future;
+use std::io;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::interactive::{Challenge, Commitment, Response};
+use crate::prover::Prover;
+use crate::secret::SecretScalar;
+use crate::verifier::Verifier;
+
+/// Longest frame `StreamTransport` sends or accepts
+pub const MAX_FRAME_LEN: usize = 64 * 1024;
+
+/// A connection exchanging whole frames with the peer
+///
+/// The futures are `Send`, so runs can be spawned on a multi-threaded runtime.
+pub trait ProofTransport {
+    /// Sends one frame to the peer
+    fn send(&mut self, frame: &[u8]) -> impl Future<Output = Result<(), DLogProofError>> + Send;
+
+    /// Receives the next frame from the peer
+    fn receive(&mut self) -> impl Future<Output = Result<Vec<u8>, DLogProofError>> + Send;
+}
+
+/// Length-prefixed frames over a byte stream
+#[derive(Debug)]
+pub struct StreamTransport<S> {
+    stream: S,
+}
+
+impl<S> StreamTransport<S> {
+    pub fn new(stream: S) -> Self {
+        StreamTransport { stream }
+    }
+
+    /// Returns the underlying stream
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin + Send> ProofTransport for StreamTransport<S> {
+    async fn send(&mut self, frame: &[u8]) -> Result<(), DLogProofError> {
+        if frame.len() > MAX_FRAME_LEN {
+            return Err(DLogProofError::Io(format!(
+                "Frame of {} bytes exceeds {MAX_FRAME_LEN} bytes",
+                frame.len()
+            )));
+        }
+        // MAX_FRAME_LEN fits the prefix
+        let length = (frame.len() as u32).to_be_bytes();
+        self.stream.write_all(&length).await.map_err(io_error)?;
+        self.stream.write_all(frame).await.map_err(io_error)?;
+        self.stream.flush().await.map_err(io_error)
+    }
+
+    async fn receive(&mut self) -> Result<Vec<u8>, DLogProofError> {
+        let mut length = [0; 4];
+        self.stream
+            .read_exact(&mut length)
+            .await
+            .map_err(io_error)?;
+        let length = u32::from_be_bytes(length) as usize;
+        if length > MAX_FRAME_LEN {
+            return Err(DLogProofError::Io(format!(
+                "Frame of {length} bytes exceeds {MAX_FRAME_LEN} bytes"
+            )));
+        }
+        let mut frame = vec![0; length];
+        self.stream.read_exact(&mut frame).await.map_err(io_error)?;
+        Ok(frame)
+    }
+}
+
+fn io_error(e: io::Error) -> DLogProofError {
+    if e.kind() == io::ErrorKind::UnexpectedEof {
+        DLogProofError::Io("Connection closed by the peer".into())
+    } else {
+        DLogProofError::Io(e.to_string())
+    }
+}
+
+/// A message of the protocol, one per frame
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "", tag = "type", rename_all = "snake_case")]
+enum Message<G: DLogGroup> {
+    Commitment {
+        sid: String,
+        pid: u32,
+        commitment: Commitment<G>,
+    },
+    Challenge {
+        sid: String,
+        challenge: Challenge<G>,
+    },
+    Response {
+        sid: String,
+        response: Response<G>,
+    },
+    Verdict {
+        sid: String,
+        accepted: bool,
+    },
+}
+
+/// One run of the interactive protocol for a session and participant
+///
+/// ```no_run
+/// # use elliptic_curve::Field;
+/// # use schnorr_dlog_zkp::k256::{ProjectivePoint, Scalar};
+/// use schnorr_dlog_zkp::transport::{InteractiveSession, StreamTransport};
+/// use schnorr_dlog_zkp::Prover;
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// # let g = ProjectivePoint::GENERATOR;
+/// # let x = Scalar::random(&mut rand_core::OsRng);
+/// # let y = g * x;
+///
+/// let stream = tokio::net::TcpStream::connect("127.0.0.1:7000").await?;
+/// let mut transport = StreamTransport::new(stream);
+/// let session = InteractiveSession::new("provisioning-42", 1);
+/// let accepted = session.prove(&mut transport, &Prover::new(), &x, y, g).await?;
+/// # let _ = accepted;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct InteractiveSession {
+    sid: String,
+    pid: u32,
+    timeout: Duration,
+}
+
+impl InteractiveSession {
+    /// Each send and receive may take 30 seconds by default
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Creates a run for participant `pid` of session `sid`, agreed on by both sides
+    pub fn new(sid: impl Into<String>, pid: u32) -> Self {
+        InteractiveSession {
+            sid: sid.into(),
+            pid,
+            timeout: Self::DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Sets the time a single send or receive may take
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Proves knowledge of `x` for y = x * G to the verifier at the other end of `transport`
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - The verifier's verdict
+    /// * `Err(DLogProofError)` - `Io` if the transport fails or times out, `SessionMismatch`
+    ///   if the verifier answers for another session, `Serialization` for malformed or
+    ///   out-of-order messages, or any error of `Prover::commit`
+    pub async fn prove<G: DLogGroup, T: ProofTransport>(
+        &self,
+        transport: &mut T,
+        prover: &Prover,
+        x: impl Into<SecretScalar<G::Scalar>>,
+        y: G,
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        let (commitment, state) = prover.commit(x, y, base_point)?;
+        let commitment = Message::Commitment {
+            sid: self.sid.clone(),
+            pid: self.pid,
+            commitment,
+        };
+        self.send(transport, &commitment).await?;
+
+        let challenge = match self.receive::<G>(transport).await? {
+            Message::Challenge { sid, challenge } => {
+                self.check_sid(&sid)?;
+                challenge
+            }
+            _ => return Err(unexpected("a challenge")),
+        };
+        let response = Message::Response {
+            sid: self.sid.clone(),
+            response: state.respond(&challenge),
+        };
+        self.send(transport, &response).await?;
+
+        match self.receive::<G>(transport).await? {
+            Message::Verdict { sid, accepted } => {
+                self.check_sid(&sid)?;
+                Ok(accepted)
+            }
+            _ => Err(unexpected("a verdict")),
+        }
+    }
+
+    /// Checks the prover at the other end of `transport` knows the logarithm of y
+    ///
+    /// The prover learns the verdict as well.
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - If the prover's response is valid
+    /// * `Err(DLogProofError)` - The errors of `prove`, `ParticipantMismatch` if the prover
+    ///   commits as another participant, or any error of `Verifier::challenge`
+    pub async fn verify<G: DLogGroup, T: ProofTransport>(
+        &self,
+        transport: &mut T,
+        verifier: &Verifier,
+        y: G,
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        let commitment = match self.receive::<G>(transport).await? {
+            Message::Commitment {
+                sid,
+                pid,
+                commitment,
+            } => {
+                self.check_sid(&sid)?;
+                if pid != self.pid {
+                    return Err(DLogProofError::ParticipantMismatch);
+                }
+                commitment
+            }
+            _ => return Err(unexpected("a commitment")),
+        };
+        let state = verifier.challenge(commitment, y, base_point)?;
+        let challenge = Message::Challenge {
+            sid: self.sid.clone(),
+            challenge: state.challenge(),
+        };
+        self.send(transport, &challenge).await?;
+
+        let accepted = match self.receive::<G>(transport).await? {
+            Message::Response { sid, response } => {
+                self.check_sid(&sid)?;
+                state.check(&response)
+            }
+            _ => return Err(unexpected("a response")),
+        };
+        let verdict = Message::<G>::Verdict {
+            sid: self.sid.clone(),
+            accepted,
+        };
+        self.send(transport, &verdict).await?;
+        Ok(accepted)
+    }
+
+    fn check_sid(&self, sid: &str) -> Result<(), DLogProofError> {
+        if sid == self.sid {
+            Ok(())
+        } else {
+            Err(DLogProofError::SessionMismatch)
+        }
+    }
+
+    async fn send<G: DLogGroup>(
+        &self,
+        transport: &mut impl ProofTransport,
+        message: &Message<G>,
+    ) -> Result<(), DLogProofError> {
+        let frame = serde_json::to_vec(message)
+            .map_err(|e| DLogProofError::Serialization(e.to_string()))?;
+        self.within(transport.send(&frame)).await
+    }
+
+    async fn receive<G: DLogGroup>(
+        &self,
+        transport: &mut impl ProofTransport,
+    ) -> Result<Message<G>, DLogProofError> {
+        let frame = self.within(transport.receive()).await?;
+        serde_json::from_slice(&frame)
+            .map_err(|e| DLogProofError::Serialization(format!("Malformed message: {e}")))
+    }
+
+    async fn within<R>(
+        &self,
+        step: impl Future<Output = Result<R, DLogProofError>>,
+    ) -> Result<R, DLogProofError> {
+        tokio::time::timeout(self.timeout, step)
+            .await
+            .map_err(|_| DLogProofError::Io(format!("Peer timed out after {:?}", self.timeout)))?
+    }
+}
+
+fn unexpected(expected: &str) -> DLogProofError {
+    DLogProofError::Serialization(format!("Expected {expected} from the peer"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::Field;
+    use k256::{ProjectivePoint, Scalar};
+    use rand_core::OsRng;
+    use tokio::io::{duplex, DuplexStream};
+
+    fn connection() -> (StreamTransport<DuplexStream>, StreamTransport<DuplexStream>) {
+        let (a, b) = duplex(MAX_FRAME_LEN);
+        (StreamTransport::new(a), StreamTransport::new(b))
+    }
+
+    #[tokio::test]
+    async fn both_sides_agree_on_the_verdict() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let session = InteractiveSession::new("device-7", 3);
+        let verifier = Verifier::new();
+
+        for (prover, secret) in [
+            (Prover::new(), x),
+            (Prover::new().blinded(true), x),
+            (Prover::new(), Scalar::ONE),
+        ] {
+            let (mut a, mut b) = connection();
+            let (proved, verified) = tokio::join!(
+                session.prove(&mut a, &prover, &secret, y, g),
+                session.verify(&mut b, &verifier, y, g),
+            );
+            assert_eq!(proved.unwrap(), secret == x);
+            assert_eq!(verified.unwrap(), secret == x);
+        }
+    }
+
+    #[tokio::test]
+    async fn other_sessions_and_silent_peers_are_rejected() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let (prover, verifier) = (Prover::new(), Verifier::new());
+        let session = InteractiveSession::new("device-7", 3);
+        let impatient =
+            |sid, pid| InteractiveSession::new(sid, pid).timeout(Duration::from_millis(50));
+
+        for (other, error) in [
+            (impatient("device-8", 3), DLogProofError::SessionMismatch),
+            (
+                impatient("device-7", 4),
+                DLogProofError::ParticipantMismatch,
+            ),
+        ] {
+            let (mut a, mut b) = connection();
+            let (proved, verified) = tokio::join!(
+                other.prove(&mut a, &prover, &x, y, g),
+                session.verify(&mut b, &verifier, y, g),
+            );
+            // The prover waits for a challenge that never comes
+            assert!(matches!(proved, Err(DLogProofError::Io(_))));
+            assert_eq!(verified, Err(error));
+        }
+
+        // Out-of-order messages are refused
+        let (mut a, mut b) = connection();
+        let mut frame = serde_json::to_vec(&Message::<ProjectivePoint>::Verdict {
+            sid: "device-7".into(),
+            accepted: true,
+        })
+        .unwrap();
+        a.send(&frame).await.unwrap();
+        assert!(matches!(
+            session.verify(&mut b, &verifier, y, g).await,
+            Err(DLogProofError::Serialization(_))
+        ));
+
+        frame.resize(MAX_FRAME_LEN + 1, b' ');
+        assert!(matches!(a.send(&frame).await, Err(DLogProofError::Io(_))));
+        drop(a);
+        assert_eq!(
+            b.receive().await,
+            Err(DLogProofError::Io("Connection closed by the peer".into()))
+        );
+    }
+}
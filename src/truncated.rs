@@ -0,0 +1,221 @@
+use elliptic_curve::subtle::{Choice, ConstantTimeEq};
+use elliptic_curve::{Field, PrimeField};
+use k256::{FieldBytes, ProjectivePoint, Scalar};
+use rand_core::OsRng;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sha2::{Digest, Sha256};
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::participant::ParticipantId;
+use crate::serialization::{PointHex, ScalarHex};
+use crate::session::SessionId;
+
+/// One (t, s) pair of a `TruncatedProof`
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Round {
+    #[serde_as(as = "PointHex")]
+    t: ProjectivePoint,
+    #[serde_as(as = "ScalarHex")]
+    s: Scalar,
+}
+
+/// Schnorr proof of knowledge of x for y = x * G with `BITS`-bit challenges
+///
+/// Short challenges are cheaper to handle in constrained verifiers (circuits, scripts), but a
+/// cheating prover succeeds with probability 2^-BITS per round instead of ~2^-256. To make
+/// up for it the proof can run several rounds in parallel, all bound to one transcript, for
+/// a soundness of BITS * rounds bits. Both numbers are part of the challenge domain and of
+/// `ciphersuite()`, so a truncated proof can never pass as a full `DLogProof` or as a
+/// truncated proof with other parameters.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct TruncatedProof<const BITS: usize> {
+    rounds: Vec<Round>,
+}
+
+impl<const BITS: usize> TruncatedProof<BITS> {
+    const DOMAIN_SEPARATOR: &'static [u8] = b"SCHNORR_TRUNCATED_PROOF";
+
+    /// Challenges stay below 2^248, so they are valid scalars without reduction
+    const VALID_BITS: () = assert!(BITS > 0 && BITS <= 248, "BITS must be in 1..=248");
+
+    /// Proves knowledge of `x` for y = x * G in `rounds` parallel rounds
+    ///
+    /// # Arguments
+    /// * `sid` - Session identifier used for domain separation, a string or bytes (see `SessionId`)
+    /// * `pid` - Participant ID for uniqueness to distinguish different proofs
+    /// * `x` - The secret scalar
+    /// * `y` - The public point, must satisfy y = x * G
+    /// * `base_point` - Base point of secp256k1 curve
+    /// * `rounds` - Number of repetitions, at least 1
+    ///
+    /// # Returns
+    /// * `Ok(TruncatedProof)` - The proof
+    /// * `Err(DLogProofError)` - `OutOfRange` if `rounds` is zero, or any error of proof
+    ///   generation
+    pub fn prove<'s, 'p>(
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        x: &Scalar,
+        y: ProjectivePoint,
+        base_point: ProjectivePoint,
+        rounds: usize,
+    ) -> Result<Self, DLogProofError> {
+        if rounds == 0 {
+            return Err(DLogProofError::OutOfRange);
+        }
+
+        let nonces: Vec<Scalar> = (0..rounds).map(|_| Scalar::random(&mut OsRng)).collect();
+        let t: Vec<ProjectivePoint> = nonces.iter().map(|r| base_point * r).collect();
+        let challenges = Self::challenges(sid.into(), pid.into(), y, base_point, &t)?;
+
+        let rounds = nonces
+            .iter()
+            .zip(t)
+            .zip(challenges)
+            .map(|((r, t), c)| Round { t, s: r + c * x })
+            .collect();
+        Ok(TruncatedProof { rounds })
+    }
+
+    /// Verifies the proof, requiring exactly `rounds` repetitions
+    ///
+    /// The verifier fixes the number of rounds (and with it the soundness it accepts); a
+    /// proof with fewer rounds is rejected rather than trusted at lower soundness.
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Validity of the proof
+    /// * `Err(DLogProofError)` - Any error during verification
+    pub fn verify<'s, 'p>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        y: ProjectivePoint,
+        base_point: ProjectivePoint,
+        rounds: usize,
+    ) -> Result<bool, DLogProofError> {
+        if self.rounds.len() != rounds {
+            return Ok(false);
+        }
+        let t: Vec<ProjectivePoint> = self.rounds.iter().map(|round| round.t).collect();
+        let challenges = Self::challenges(sid.into(), pid.into(), y, base_point, &t)?;
+
+        let valid = self
+            .rounds
+            .iter()
+            .zip(challenges)
+            .fold(Choice::from(1), |valid, (round, c)| {
+                valid & (base_point * round.s).ct_eq(&(round.t + y * c))
+            });
+        Ok(valid.into())
+    }
+
+    /// Soundness of this proof in bits: BITS per round
+    pub fn soundness_bits(&self) -> usize {
+        BITS * self.rounds.len()
+    }
+
+    /// Ciphersuite identifier spelling out the challenge size and number of rounds
+    pub fn ciphersuite(&self) -> String {
+        format!(
+            "SCHNORR_PROOF/secp256k1/SHA-256-trunc{}x{}/sec1-compressed",
+            BITS,
+            self.rounds.len()
+        )
+    }
+
+    /// Derives one `BITS`-bit challenge per round from a single transcript over all rounds
+    fn challenges(
+        sid: SessionId<'_>,
+        pid: ParticipantId<'_>,
+        y: ProjectivePoint,
+        base_point: ProjectivePoint,
+        t: &[ProjectivePoint],
+    ) -> Result<Vec<Scalar>, DLogProofError> {
+        let () = Self::VALID_BITS;
+
+        // Challenge size and round count extend the tag at fixed offsets
+        let mut domain = Self::DOMAIN_SEPARATOR.to_vec();
+        domain.extend_from_slice(&(BITS as u16).to_be_bytes());
+        domain.extend_from_slice(&(t.len() as u32).to_be_bytes());
+        let mut points = vec![base_point, y];
+        points.extend_from_slice(t);
+        let seed = DLogProof::hash_points_with_domain(&domain, sid, pid, &points)?;
+
+        (0..t.len() as u32)
+            .map(|i| {
+                let mut bytes: [u8; 32] = Sha256::new()
+                    .chain_update(seed.to_repr())
+                    .chain_update(i.to_be_bytes())
+                    .finalize()
+                    .into();
+                Self::truncate(&mut bytes);
+                Option::from(Scalar::from_repr(FieldBytes::from(bytes)))
+                    .ok_or(DLogProofError::ChallengeDerivation)
+            })
+            .collect()
+    }
+
+    /// Keeps only the low `BITS` bits of a big-endian 256-bit value
+    fn truncate(bytes: &mut [u8; 32]) {
+        let zero_bits = 256 - BITS;
+        for byte in bytes.iter_mut().take(zero_bits / 8) {
+            *byte = 0;
+        }
+        let partial_bits = zero_bits % 8;
+        if partial_bits > 0 {
+            bytes[zero_bits / 8] &= 0xff >> partial_bits;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statement() -> (Scalar, ProjectivePoint, ProjectivePoint) {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        (x, g * x, g)
+    }
+
+    #[test]
+    fn truncated_proofs_verify_with_matching_rounds() {
+        let (x, y, g) = statement();
+        let proof = TruncatedProof::<128>::prove("sid", 1, &x, y, g, 2).unwrap();
+        assert!(proof.verify("sid", 1, y, g, 2).unwrap());
+        assert!(!proof.verify("sid", 1, y, g, 1).unwrap());
+        assert!(!proof.verify("sid", 2, y, g, 2).unwrap());
+        assert_eq!(proof.soundness_bits(), 256);
+        assert_eq!(
+            proof.ciphersuite(),
+            "SCHNORR_PROOF/secp256k1/SHA-256-trunc128x2/sec1-compressed"
+        );
+        assert_eq!(
+            TruncatedProof::<128>::prove("sid", 1, &x, y, g, 0),
+            Err(DLogProofError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn challenges_fit_in_the_configured_size() {
+        let (_, y, g) = statement();
+        let t = [g, y];
+        for c in TruncatedProof::<13>::challenges("sid".into(), 1.into(), y, g, &t).unwrap() {
+            let bytes = c.to_repr();
+            assert!(bytes[..30].iter().all(|&b| b == 0));
+            assert!(bytes[30] < 1 << 5);
+        }
+    }
+
+    #[test]
+    fn challenge_size_is_bound_into_the_transcript() {
+        let (x, y, g) = statement();
+        let proof = TruncatedProof::<64>::prove("sid", 1, &x, y, g, 1).unwrap();
+        let json = serde_json::to_string(&proof).unwrap();
+        let reparsed: TruncatedProof<128> = serde_json::from_str(&json).unwrap();
+        assert!(!reparsed.verify("sid", 1, y, g, 1).unwrap());
+    }
+}
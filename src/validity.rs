@@ -0,0 +1,174 @@
+use k256::ProjectivePoint;
+use serde::{Deserialize, Serialize};
+
+use crate::dlog_proof::DLogProof;
+#[cfg(feature = "verify")]
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+#[cfg(feature = "verify")]
+use crate::participant::ParticipantId;
+#[cfg(feature = "verify")]
+use crate::session::SessionId;
+#[cfg(feature = "verify")]
+use crate::verifier::Verifier;
+
+/// A proof that is only valid within a time window, for short-lived proofs such as login
+/// tokens
+///
+/// Made by `Prover::prove_with_validity`. The window is bound into the challenge after the
+/// additional authenticated data, so it can't be widened without invalidating the proof, and
+/// the inner proof doesn't verify without it. Timestamps are seconds since the Unix epoch by
+/// convention; the crate only compares them, so any unit works as long as prover and
+/// verifier agree on it. `Verifier::leeway` tolerates clock skew between them, and
+/// `Verifier::verify_now` takes the time from a `Clock`. Serialized as
+/// `{"not_before": ..., "not_after": ..., "proof": {"t": "<hex>", "s": "<hex>"}}`.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "", deny_unknown_fields)]
+pub struct TimedProof<G: DLogGroup = ProjectivePoint> {
+    /// First timestamp the proof is valid at
+    pub not_before: u64,
+    /// Last timestamp the proof is valid at
+    pub not_after: u64,
+    pub proof: DLogProof<G>,
+}
+
+/// Source of the current time for `Verifier::verify_now`, in the unit of the proofs' windows
+///
+/// `SystemClock` reads the system time; closures returning a timestamp are clocks as well,
+/// e.g. for a synchronized or mocked time source.
+pub trait Clock {
+    /// The current timestamp
+    fn now(&self) -> u64;
+}
+
+impl<F: Fn() -> u64> Clock for F {
+    fn now(&self) -> u64 {
+        self()
+    }
+}
+
+/// The system clock, in seconds since the Unix epoch (0 for a clock set before it)
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs())
+    }
+}
+
+impl<G: DLogGroup> TimedProof<G> {
+    /// Whether `timestamp` lies in the window, both ends included
+    pub fn covers(&self, timestamp: u64) -> bool {
+        self.covers_with_leeway(timestamp, 0)
+    }
+
+    /// Whether `timestamp` lies in the window widened by `leeway` on both ends, for clocks
+    /// that drift apart by up to `leeway`
+    pub fn covers_with_leeway(&self, timestamp: u64, leeway: u64) -> bool {
+        (self.not_before.saturating_sub(leeway)..=self.not_after.saturating_add(leeway))
+            .contains(&timestamp)
+    }
+
+    /// Verifies the proof at `timestamp` with the default `Verifier` (see
+    /// `Verifier::verify_at`)
+    #[cfg(feature = "verify")]
+    pub fn verify_at<'s, 'p>(
+        &self,
+        sid: impl Into<SessionId<'s>>,
+        pid: impl Into<ParticipantId<'p>>,
+        timestamp: u64,
+        y: impl Into<G>,
+        base_point: G,
+    ) -> Result<bool, DLogProofError> {
+        Verifier::new().verify_at(self, sid, pid, timestamp, y, base_point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prover::Prover;
+    use elliptic_curve::Field;
+    use k256::Scalar;
+    use rand_core::OsRng;
+
+    #[test]
+    fn proofs_verify_only_within_their_window() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let timed = Prover::new()
+            .prove_with_validity("login", 1, 1_000, 1_060, &x, y, g)
+            .unwrap();
+        assert!(timed.verify_at("login", 1, 1_000, y, g).unwrap());
+        assert!(timed.verify_at("login", 1, 1_060, y, g).unwrap());
+        assert!(!timed.verify_at("login", 2, 1_030, y, g).unwrap());
+        for timestamp in [999, 1_061] {
+            assert_eq!(
+                timed.verify_at("login", 1, timestamp, y, g),
+                Err(DLogProofError::OutsideValidityWindow)
+            );
+        }
+
+        // The window is bound into the challenge
+        let extended = TimedProof {
+            not_after: 2_000,
+            ..serde_json::from_value(serde_json::to_value(&timed).unwrap()).unwrap()
+        };
+        assert!(!extended.verify_at("login", 1, 1_500, y, g).unwrap());
+        assert!(!timed.proof.verify("login", 1, y, g).unwrap());
+
+        let verifier = Verifier::new().domain(b"app");
+        let timed = Prover::new()
+            .domain(b"app")
+            .prove_with_validity("login", 1, 0, u64::MAX, &x, y, g)
+            .unwrap();
+        assert!(verifier.verify_at(&timed, "login", 1, 7, y, g).unwrap());
+        assert!(!timed.verify_at("login", 1, 7, y, g).unwrap());
+        assert_eq!(
+            Prover::new().prove_with_validity("login", 1, 2, 1, &x, y, g),
+            Err(DLogProofError::OutsideValidityWindow)
+        );
+    }
+
+    #[test]
+    fn leeway_and_clocks_absorb_skew() {
+        let g = ProjectivePoint::GENERATOR;
+        let x = Scalar::random(&mut OsRng);
+        let y = g * x;
+        let timed = Prover::new()
+            .prove_with_validity("login", 1, 1_000, 1_060, &x, y, g)
+            .unwrap();
+        assert!(timed.covers_with_leeway(995, 5));
+        assert!(!timed.covers_with_leeway(994, 5));
+        assert!(timed.covers_with_leeway(u64::MAX, u64::MAX));
+
+        // A verifier whose clock runs 30 seconds ahead still accepts the proof
+        let verifier = Verifier::new().leeway(30);
+        assert!(verifier.verify_at(&timed, "login", 1, 1_090, y, g).unwrap());
+        assert!(verifier.verify_at(&timed, "login", 1, 970, y, g).unwrap());
+        assert_eq!(
+            verifier.verify_at(&timed, "login", 1, 1_091, y, g),
+            Err(DLogProofError::OutsideValidityWindow)
+        );
+
+        let clock = || 1_075;
+        assert!(verifier
+            .verify_now(&timed, "login", 1, &clock, y, g)
+            .unwrap());
+        assert_eq!(
+            Verifier::new().verify_now(&timed, "login", 1, &clock, y, g),
+            Err(DLogProofError::OutsideValidityWindow)
+        );
+        assert_eq!(
+            verifier.verify_now(&timed, "login", 1, &SystemClock, y, g),
+            Err(DLogProofError::OutsideValidityWindow)
+        );
+        assert!(SystemClock.now() > 1_700_000_000);
+    }
+}
@@ -0,0 +1,185 @@
+use elliptic_curve::bigint::ArrayEncoding;
+use elliptic_curve::hash2curve::{ExpandMsgXmd, GroupDigest};
+use elliptic_curve::ops::Reduce;
+use elliptic_curve::subtle::ConstantTimeEq;
+use elliptic_curve::{Curve, PrimeField};
+use k256::{FieldBytes, ProjectivePoint, Scalar, Secp256k1, U256};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::secret::SecretScalar;
+
+/// Suite string of ECVRF-SECP256K1-SHA256-SSWU; RFC 9381 registers no secp256k1 suite, so it
+/// takes a value outside the registered range
+const SUITE: u8 = 0xfe;
+
+/// RFC 9380 tag of encode_to_curve: "ECVRF_" || h2c suite ID || suite_string
+const H2C_DST: &[u8] = b"ECVRF_secp256k1_XMD:SHA-256_SSWU_NU_\xfe";
+
+/// Length of the truncated challenge in bytes
+const C_LEN: usize = 16;
+
+/// Length of an encoded proof: Gamma (33 bytes) || c (16 bytes) || s (32 bytes)
+pub const PROOF_LEN: usize = 33 + C_LEN + 32;
+
+/// ECVRF proof pi = (Gamma, c, s)
+///
+/// Gamma = x * H is the VRF value for the input point H = encode_to_curve(y || alpha), and
+/// (c, s) is a Chaum-Pedersen proof that log_G(y) = log_H(Gamma), following RFC 9381 with the
+/// secp256k1 analogue of its P-256 SSWU suite: 16-byte challenges, RFC 6979 nonces and
+/// compressed SEC1 points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VrfProof {
+    gamma: ProjectivePoint,
+    c: Scalar,
+    s: Scalar,
+}
+
+impl VrfProof {
+    /// Encodes the proof as Gamma || c || s
+    pub fn to_bytes(&self) -> [u8; PROOF_LEN] {
+        let mut bytes = [0u8; PROOF_LEN];
+        bytes[..33].copy_from_slice(&self.gamma.encode_point());
+        bytes[33..33 + C_LEN].copy_from_slice(&self.c.to_repr()[32 - C_LEN..]);
+        bytes[33 + C_LEN..].copy_from_slice(&self.s.to_repr());
+        bytes
+    }
+
+    /// Decodes a proof produced by `to_bytes`
+    ///
+    /// # Returns
+    /// * `Ok(VrfProof)` - The proof
+    /// * `Err(DLogProofError)` - `Serialization` for a wrong length, `InvalidPoint` or
+    ///   `InvalidScalar` for a malformed component
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DLogProofError> {
+        if bytes.len() != PROOF_LEN {
+            return Err(DLogProofError::Serialization(format!(
+                "VRF proof must be {PROOF_LEN} bytes, got {}",
+                bytes.len()
+            )));
+        }
+        let gamma =
+            ProjectivePoint::decode_point(&bytes[..33]).ok_or(DLogProofError::InvalidPoint)?;
+        let c = challenge_scalar(&bytes[33..33 + C_LEN]);
+        let mut repr = FieldBytes::default();
+        repr.copy_from_slice(&bytes[33 + C_LEN..]);
+        let s = Option::from(Scalar::from_repr(repr)).ok_or(DLogProofError::InvalidScalar)?;
+        Ok(VrfProof { gamma, c, s })
+    }
+
+    /// The VRF output beta = Hash(suite || 0x03 || Gamma || 0x00), without verifying the proof
+    pub fn output(&self) -> [u8; 32] {
+        Sha256::new()
+            .chain_update([SUITE, 0x03])
+            .chain_update(self.gamma.encode_point())
+            .chain_update([0x00])
+            .finalize()
+            .into()
+    }
+}
+
+/// Evaluates the VRF on `alpha` with the secret key `sk`
+///
+/// # Returns
+/// * `Ok(([u8; 32], VrfProof))` - The output beta and the proof anyone can check it with
+///   against the public key sk * G
+/// * `Err(DLogProofError)` - `InvalidScalar` for a zero key, or if hashing to the curve fails
+pub fn vrf_prove(
+    sk: impl Into<SecretScalar<Scalar>>,
+    alpha: &[u8],
+) -> Result<([u8; 32], VrfProof), DLogProofError> {
+    let sk = sk.into();
+    let x = sk.expose_secret();
+    if bool::from(x.is_zero()) {
+        return Err(DLogProofError::InvalidScalar);
+    }
+    let y = ProjectivePoint::GENERATOR * x;
+    let h = encode_to_curve(&y, alpha)?;
+    let gamma = h * x;
+
+    let k = SecretScalar::new(nonce(x, &h));
+    let k = k.expose_secret();
+    let c = challenge(&[y, h, gamma, ProjectivePoint::GENERATOR * k, h * k]);
+    let proof = VrfProof {
+        gamma,
+        c,
+        s: *k + c * x,
+    };
+    Ok((proof.output(), proof))
+}
+
+/// Verifies a VRF proof for `alpha` under the public key `pk`
+///
+/// # Returns
+/// * `Some([u8; 32])` - The VRF output beta, if the proof is valid
+/// * `None` - If the proof or the public key is invalid
+pub fn vrf_verify(pk: ProjectivePoint, alpha: &[u8], proof: &VrfProof) -> Option<[u8; 32]> {
+    if pk == ProjectivePoint::IDENTITY {
+        return None;
+    }
+    let h = encode_to_curve(&pk, alpha).ok()?;
+    let u = ProjectivePoint::GENERATOR * proof.s - pk * proof.c;
+    let v = h * proof.s - proof.gamma * proof.c;
+    let c = challenge(&[pk, h, proof.gamma, u, v]);
+    bool::from(c.ct_eq(&proof.c)).then(|| proof.output())
+}
+
+/// ECVRF_encode_to_curve: the RFC 9380 NU encoding of pk || alpha
+fn encode_to_curve(pk: &ProjectivePoint, alpha: &[u8]) -> Result<ProjectivePoint, DLogProofError> {
+    Secp256k1::encode_from_bytes::<ExpandMsgXmd<Sha256>>(&[&pk.encode_point(), alpha], &[H2C_DST])
+        .map_err(|_| DLogProofError::InvalidPoint)
+}
+
+/// ECVRF_nonce_generation per RFC 6979 over h1 = Hash(H)
+fn nonce(x: &Scalar, h: &ProjectivePoint) -> Scalar {
+    let h1 = Sha256::digest(h.encode_point());
+    let mut x_bytes = x.to_repr();
+    let order = Secp256k1::ORDER.to_be_byte_array();
+    let k = rfc6979::generate_k::<Sha256, _>(&x_bytes, &order, &h1, &[]);
+    x_bytes.zeroize();
+    // generate_k only returns values in [1, n), which reduction leaves unchanged
+    <Scalar as Reduce<U256>>::reduce_bytes(&k)
+}
+
+/// ECVRF_challenge_generation: Hash(suite || 0x02 || points || 0x00) truncated to 16 bytes
+fn challenge(points: &[ProjectivePoint; 5]) -> Scalar {
+    let mut hasher = Sha256::new().chain_update([SUITE, 0x02]);
+    for point in points {
+        hasher.update(point.encode_point());
+    }
+    let hash = hasher.chain_update([0x00]).finalize();
+    challenge_scalar(&hash[..C_LEN])
+}
+
+/// Reads a 16-byte big-endian challenge, always below the group order
+fn challenge_scalar(bytes: &[u8]) -> Scalar {
+    let mut repr = FieldBytes::default();
+    repr[32 - C_LEN..].copy_from_slice(bytes);
+    <Scalar as Reduce<U256>>::reduce_bytes(&repr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::Field;
+    use rand_core::OsRng;
+
+    #[test]
+    fn vrf_outputs_are_deterministic_and_verifiable() {
+        let sk = Scalar::random(&mut OsRng);
+        let pk = ProjectivePoint::GENERATOR * sk;
+
+        let (beta, proof) = vrf_prove(sk, b"sample").unwrap();
+        assert_eq!(vrf_prove(sk, b"sample").unwrap(), (beta, proof));
+        assert_eq!(vrf_verify(pk, b"sample", &proof), Some(beta));
+        assert_eq!(vrf_verify(pk, b"other", &proof), None);
+        assert_eq!(vrf_verify(pk + pk, b"sample", &proof), None);
+        assert_ne!(vrf_prove(sk, b"other").unwrap().0, beta);
+
+        let decoded = VrfProof::from_bytes(&proof.to_bytes()).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(VrfProof::from_bytes(&proof.to_bytes()[1..]).is_err());
+    }
+}
@@ -0,0 +1,124 @@
+//! JavaScript bindings through wasm-bindgen (`wasm` feature)
+//!
+//! Proofs on secp256k1 for the standard generator, passed as `Uint8Array`s in the fixed
+//! 65-byte encoding of `DLogProof::to_bytes` or as its hex string. Keys are 32-byte
+//! big-endian secrets and compressed or uncompressed SEC1 public keys. Errors are thrown
+//! as JavaScript `Error`s carrying the `DLogProofError` message, with the error's stable
+//! `DLogProofError::code` in their `code` property.
+//!
+//! Built for `wasm32-unknown-unknown`, the feature enables getrandom's `js` backend, so
+//! `prove` draws its nonces from `crypto.getRandomValues`. The crate stays an rlib, as a
+//! `cdylib` target would break its `no_std` builds, so package it with wasm-pack from a small crate of
+//! `crate-type = ["cdylib"]` that depends on this one with the feature and re-exports
+//! `schnorr_dlog_zkp::wasm::*`:
+//!
+//! ```js
+//! import { prove, publicKey, verify, proofToHex } from "schnorr-dlog-zkp";
+//!
+//! const proof = prove("sid", 1, secretKey);
+//! verify("sid", 1, publicKey(secretKey), proof); // true
+//! proofToHex(proof);
+//! ```
+
+use k256::ProjectivePoint;
+use wasm_bindgen::prelude::*;
+
+use crate::dlog_proof::DLogProof;
+use crate::error::DLogProofError;
+use crate::group::DLogGroup;
+use crate::keypair::KeyPair;
+
+impl From<DLogProofError> for JsValue {
+    fn from(e: DLogProofError) -> Self {
+        let error = js_sys::Error::new(&e.to_string());
+        // Setting a property on a fresh `Error` object can't fail
+        let _ = js_sys::Reflect::set(&error, &"code".into(), &e.code().into());
+        error.into()
+    }
+}
+
+/// Proves knowledge of `secretKey` for the session and participant, returning the 65-byte proof
+#[wasm_bindgen]
+pub fn prove(sid: &str, pid: u32, secret_key: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let key_pair = KeyPair::<ProjectivePoint>::from_secret_bytes(secret_key)?;
+    Ok(key_pair.prove(sid, pid)?.to_bytes().to_vec())
+}
+
+/// Verifies a 65-byte proof of knowledge of the secret key of `publicKey`
+#[wasm_bindgen]
+pub fn verify(sid: &str, pid: u32, public_key: &[u8], proof: &[u8]) -> Result<bool, JsValue> {
+    let y = ProjectivePoint::decode_point(public_key).ok_or(DLogProofError::InvalidPoint)?;
+    let proof = DLogProof::from_bytes(proof)?;
+    Ok(proof.verify(sid, pid, y, ProjectivePoint::GENERATOR)?)
+}
+
+/// The compressed SEC1 public key of a 32-byte big-endian secret key
+#[wasm_bindgen(js_name = publicKey)]
+pub fn public_key(secret_key: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let key_pair = KeyPair::<ProjectivePoint>::from_secret_bytes(secret_key)?;
+    Ok(key_pair.public().encode_point())
+}
+
+/// Hex encoding of a 65-byte proof, after checking that it decodes
+#[wasm_bindgen(js_name = proofToHex)]
+pub fn proof_to_hex(proof: &[u8]) -> Result<String, JsValue> {
+    Ok(hex::encode(DLogProof::from_bytes(proof)?.to_bytes()))
+}
+
+/// Decodes the hex encoding of a proof into its 65 bytes, validating the point and scalar
+#[wasm_bindgen(js_name = proofFromHex)]
+pub fn proof_from_hex(proof: &str) -> Result<Vec<u8>, JsValue> {
+    let bytes = hex::decode(proof)
+        .map_err(|e| DLogProofError::Serialization(format!("Invalid proof hex: {e}")))?;
+    Ok(DLogProof::from_bytes(&bytes)?.to_bytes().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::{Field, PrimeField};
+    use k256::Scalar;
+    use rand_core::OsRng;
+
+    // Only the success paths: a JavaScript `Error` can't be created outside a wasm runtime
+    #[test]
+    fn bindings_prove_verify_and_round_trip_hex() {
+        let secret_key = Scalar::random(&mut OsRng).to_repr();
+        let y = public_key(&secret_key).unwrap();
+        assert_eq!(y.len(), 33);
+
+        let proof = prove("sid", 1, &secret_key).unwrap();
+        assert_eq!(proof.len(), DLogProof::ENCODED_LEN);
+        assert!(verify("sid", 1, &y, &proof).unwrap());
+        assert!(!verify("sid", 2, &y, &proof).unwrap());
+
+        let encoded = proof_to_hex(&proof).unwrap();
+        assert_eq!(encoded, hex::encode(&proof));
+        assert_eq!(proof_from_hex(&encoded).unwrap(), proof);
+    }
+
+    // In a wasm runtime: `wasm-pack test --node --features wasm -- --lib wasm`
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn bindings_run_in_a_wasm_runtime() {
+        let secret_key = Scalar::random(&mut OsRng).to_repr();
+        let y = public_key(&secret_key).unwrap();
+        let proof = prove("sid", 1, &secret_key).unwrap();
+        assert!(verify("sid", 1, &y, &proof).unwrap());
+
+        // Stats calls work too, without a clock to time them
+        let x = Scalar::random(&mut OsRng);
+        let g = ProjectivePoint::GENERATOR;
+        let (_, stats) = crate::Prover::new()
+            .prove_with_stats("sid", 1, &x, g * x, g)
+            .unwrap();
+        assert_eq!(stats.total, core::time::Duration::ZERO);
+
+        let error = verify("sid", 1, &[0; 33], &proof).unwrap_err();
+        let code = js_sys::Reflect::get(&error, &"code".into()).unwrap();
+        assert_eq!(
+            code.as_f64(),
+            Some(f64::from(DLogProofError::InvalidPoint.code()))
+        );
+    }
+}